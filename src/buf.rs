@@ -0,0 +1,214 @@
+//! Buffer utilities.
+//!
+//! See [`BufPool`].
+
+use std::io::IoSliceMut;
+use std::ops::{Deref, DerefMut};
+use std::sync::{Arc, Mutex};
+use std::{fmt, mem};
+
+/// A pool of fixed-size buffers.
+///
+/// High-throughput servers often allocate a new buffer for each read, or
+/// datagram received, putting a lot of pressure on the allocator. `BufPool`
+/// allows the buffers to be reused instead. Buffers are retrieved using
+/// [`get`], which returns a [`PooledBuf`] that gives mutable access to a
+/// buffer of [`buf_size`] bytes. It can be passed to any API accepting a
+/// `&mut [u8]`, such as [`UdpSocket::recv_from`], or converted into an
+/// [`IoSliceMut`] for vectored I/O, such as [`TcpStream::read_vectored`].
+/// Once the `PooledBuf` is dropped the buffer is returned to the pool, ready
+/// to be used again.
+///
+/// The pool holds at most [`capacity`] buffers, which are allocated lazily.
+/// Once all buffers are in use `get` returns `None`, it never allocates more
+/// buffers than the capacity of the pool. Note that reused buffers are not
+/// cleared, they still contain the data of the previous use.
+///
+/// The pool can be cheaply cloned, all clones share the same buffers.
+///
+/// [`get`]: BufPool::get
+/// [`buf_size`]: BufPool::buf_size
+/// [`capacity`]: BufPool::capacity
+/// [`UdpSocket::recv_from`]: crate::net::UdpSocket::recv_from
+/// [`TcpStream::read_vectored`]: std::io::Read::read_vectored
+///
+/// # Examples
+///
+#[cfg_attr(feature = "os-poll", doc = "```")]
+#[cfg_attr(not(feature = "os-poll"), doc = "```ignore")]
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use std::io;
+///
+/// use mio::buf::BufPool;
+/// use mio::net::UdpSocket;
+///
+/// let pool = BufPool::new(16, 1500);
+/// let socket = UdpSocket::bind("127.0.0.1:0".parse()?)?;
+///
+/// let mut buf = pool.get().expect("all buffers in use");
+/// match socket.recv_from(&mut buf) {
+///     Ok((n, address)) => println!("received {:?} from {}", &buf[..n], address),
+///     // Wait for the next readable event, `buf` is returned to the pool.
+///     Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => {}
+///     Err(err) => return Err(err.into()),
+/// }
+/// #     Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct BufPool {
+    shared: Arc<Shared>,
+}
+
+struct Shared {
+    /// Buffers not in use.
+    free: Mutex<Free>,
+    capacity: usize,
+    buf_size: usize,
+}
+
+struct Free {
+    bufs: Vec<Box<[u8]>>,
+    /// Number of buffers allocated by the pool, including the ones in use.
+    allocated: usize,
+}
+
+impl BufPool {
+    /// Create a new pool of at most `capacity` buffers, each of `buf_size`
+    /// bytes.
+    ///
+    /// The buffers are allocated lazily, by [`BufPool::get`].
+    pub fn new(capacity: usize, buf_size: usize) -> BufPool {
+        BufPool {
+            shared: Arc::new(Shared {
+                free: Mutex::new(Free {
+                    bufs: Vec::with_capacity(capacity),
+                    allocated: 0,
+                }),
+                capacity,
+                buf_size,
+            }),
+        }
+    }
+
+    /// Get a buffer from the pool.
+    ///
+    /// The buffer is returned to the pool once the returned [`PooledBuf`] is
+    /// dropped. Returns `None` if all buffers are in use.
+    pub fn get(&self) -> Option<PooledBuf> {
+        let mut free = self.shared.free.lock().unwrap();
+        let buf = match free.bufs.pop() {
+            Some(buf) => buf,
+            None if free.allocated < self.shared.capacity => {
+                free.allocated += 1;
+                // Don't hold the lock while allocating.
+                drop(free);
+                vec![0; self.shared.buf_size].into_boxed_slice()
+            }
+            None => return None,
+        };
+        Some(PooledBuf {
+            buf,
+            pool: self.shared.clone(),
+        })
+    }
+
+    /// Returns the maximum number of buffers in the pool.
+    pub fn capacity(&self) -> usize {
+        self.shared.capacity
+    }
+
+    /// Returns the size of the buffers in the pool.
+    pub fn buf_size(&self) -> usize {
+        self.shared.buf_size
+    }
+
+    /// Returns the number of buffers allocated by the pool, including the
+    /// buffers in use.
+    ///
+    /// This never exceeds the [`capacity`] of the pool.
+    ///
+    /// [`capacity`]: BufPool::capacity
+    pub fn allocated(&self) -> usize {
+        self.shared.free.lock().unwrap().allocated
+    }
+
+    /// Returns the number of allocated buffers that are not in use.
+    pub fn available(&self) -> usize {
+        self.shared.free.lock().unwrap().bufs.len()
+    }
+}
+
+impl fmt::Debug for BufPool {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BufPool")
+            .field("capacity", &self.capacity())
+            .field("buf_size", &self.buf_size())
+            .field("allocated", &self.allocated())
+            .field("available", &self.available())
+            .finish()
+    }
+}
+
+/// A buffer retrieved from a [`BufPool`].
+///
+/// This dereferences to a `[u8]` of [`BufPool::buf_size`] bytes. The buffer is
+/// returned to the pool when dropped.
+pub struct PooledBuf {
+    buf: Box<[u8]>,
+    pool: Arc<Shared>,
+}
+
+impl PooledBuf {
+    /// Returns the buffer as `IoSliceMut`, to use in vectored I/O such as
+    /// [`read_vectored`].
+    ///
+    /// [`read_vectored`]: std::io::Read::read_vectored
+    pub fn as_io_slice_mut(&mut self) -> IoSliceMut<'_> {
+        IoSliceMut::new(&mut self.buf)
+    }
+}
+
+impl Deref for PooledBuf {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.buf
+    }
+}
+
+impl DerefMut for PooledBuf {
+    fn deref_mut(&mut self) -> &mut [u8] {
+        &mut self.buf
+    }
+}
+
+impl AsRef<[u8]> for PooledBuf {
+    fn as_ref(&self) -> &[u8] {
+        &self.buf
+    }
+}
+
+impl AsMut<[u8]> for PooledBuf {
+    fn as_mut(&mut self) -> &mut [u8] {
+        &mut self.buf
+    }
+}
+
+impl fmt::Debug for PooledBuf {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PooledBuf")
+            .field("len", &self.buf.len())
+            .finish()
+    }
+}
+
+impl Drop for PooledBuf {
+    fn drop(&mut self) {
+        let buf = mem::take(&mut self.buf);
+        if let Ok(mut free) = self.pool.free.lock() {
+            free.bufs.push(buf);
+        }
+    }
+}
@@ -0,0 +1,134 @@
+use std::{error, fmt, io};
+
+/// Errors that originate in Mio itself, rather than in the OS.
+///
+/// All Mio APIs return [`io::Result`], these errors are embedded in the
+/// returned [`io::Error`] (using [`io::Error::new`]). This allows them to be
+/// distinguished from errors returned by the OS, e.g. a source registered with
+/// a different [`Registry`] from an `EBADF` error, using [`Error::from_io`].
+///
+/// Only failures detected by Mio itself are returned as a Mio error. Failures
+/// reported by the OS are returned as is, keeping the OS error code (see
+/// [`io::Error::raw_os_error`]), e.g. `EEXIST` when registering a file
+/// descriptor twice with epoll in a build without debug assertions. Which
+/// checks Mio makes depends on the platform and on whether debug assertions
+/// are enabled.
+///
+/// [`Registry`]: crate::Registry
+///
+/// # Examples
+///
+#[cfg_attr(all(feature = "os-poll", feature = "net"), doc = "```")]
+#[cfg_attr(not(all(feature = "os-poll", feature = "net")), doc = "```ignore")]
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use mio::net::UdpSocket;
+/// use mio::{Interest, Poll, Token};
+///
+/// let poll = Poll::new()?;
+/// let mut socket = UdpSocket::bind("127.0.0.1:0".parse()?)?;
+/// poll.registry().register(&mut socket, Token(0), Interest::READABLE)?;
+///
+/// let err = poll.registry()
+///     .register(&mut socket, Token(1), Interest::READABLE)
+///     .unwrap_err();
+/// # // The check is only present when debug assertions are enabled.
+/// # #[cfg(debug_assertions)]
+/// assert!(matches!(mio::Error::from_io(&err), Some(mio::Error::AlreadyRegistered)));
+/// #     Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// An error returned by the OS.
+    Os(io::Error),
+    /// The event source is registered with a different `Registry`.
+    WrongRegistry,
+    /// The event source is already registered.
+    AlreadyRegistered,
+    /// The event source is not registered.
+    NotRegistered,
+    /// The event source can't be polled for readiness, e.g. a regular file
+    /// when using io_uring. Note that epoll reports this as an `EPERM` error
+    /// instead.
+    NotPollable,
+    /// The operation is not supported on this platform or by this event
+    /// source.
+    Unsupported,
+    /// The token is reserved by Mio, see [`Poll::cancel_notifier`].
+    ///
+    /// [`Poll::cancel_notifier`]: crate::Poll::cancel_notifier
+    ReservedToken,
+}
+
+impl Error {
+    /// Returns the Mio error embedded in `err`, if any.
+    ///
+    /// Returns `None` for errors returned by the OS.
+    pub fn from_io(err: &io::Error) -> Option<&Error> {
+        err.get_ref()?.downcast_ref::<Error>()
+    }
+
+    /// Returns the corresponding [`io::ErrorKind`] of this error.
+    pub fn kind(&self) -> io::ErrorKind {
+        match self {
+            Error::Os(err) => err.kind(),
+            Error::WrongRegistry | Error::AlreadyRegistered => io::ErrorKind::AlreadyExists,
+            Error::NotRegistered => io::ErrorKind::NotFound,
+            Error::NotPollable => io::ErrorKind::PermissionDenied,
+            Error::Unsupported => io::ErrorKind::Unsupported,
+            Error::ReservedToken => io::ErrorKind::InvalidInput,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    /// Converts an `io::Error` into an `Error`, unwrapping the Mio error if
+    /// one is embedded in `err`, otherwise returning [`Error::Os`].
+    fn from(err: io::Error) -> Error {
+        if Error::from_io(&err).is_some() {
+            // Can't fail, we just checked the type above.
+            let inner = err.into_inner().unwrap();
+            *inner.downcast::<Error>().unwrap()
+        } else {
+            Error::Os(err)
+        }
+    }
+}
+
+impl From<Error> for io::Error {
+    fn from(err: Error) -> io::Error {
+        match err {
+            Error::Os(err) => err,
+            err => io::Error::new(err.kind(), err),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Os(err) => err.fmt(f),
+            Error::WrongRegistry => {
+                f.write_str("I/O source already registered with a different `Registry`")
+            }
+            Error::AlreadyRegistered => {
+                f.write_str("I/O source already registered with a `Registry`")
+            }
+            Error::NotRegistered => f.write_str("I/O source not registered with `Registry`"),
+            Error::NotPollable => f.write_str("I/O source doesn't support polling"),
+            Error::Unsupported => f.write_str("operation not supported"),
+            Error::ReservedToken => f.write_str("token reserved by `Poll::cancel_notifier`"),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Error::Os(err) => Some(err),
+            _ => None,
+        }
+    }
+}
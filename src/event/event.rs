@@ -1,6 +1,10 @@
+use crate::event::Readiness;
 use crate::{sys, Token};
 
 use std::fmt;
+use std::mem;
+#[cfg(unix)]
+use std::os::fd::RawFd;
 
 /// A readiness event.
 ///
@@ -18,6 +22,11 @@ pub struct Event {
     inner: sys::Event,
 }
 
+// `Event` is cast from and to `sys::Event`, e.g. in `from_sys_event_ref` and
+// `Events::from_vec`, which requires the same layout.
+const _: () = assert!(mem::size_of::<Event>() == mem::size_of::<sys::Event>());
+const _: () = assert!(mem::align_of::<Event>() == mem::align_of::<sys::Event>());
+
 impl Event {
     /// Returns the event's token.
     pub fn token(&self) -> Token {
@@ -49,6 +58,17 @@ impl Event {
     /// the socket will also receive a readable or writable event. Reading or
     /// writing to the socket will result in an error.
     ///
+    /// The error state is always reported, even if the source was only
+    /// registered with [`Interest::WRITABLE`] (or [`Interest::READABLE`]). For
+    /// example a failed non-blocking connect on a [`TcpStream`] creates an
+    /// event with error readiness, the error can be retrieved using
+    /// [`TcpStream::take_error`].
+    ///
+    /// [`Interest::WRITABLE`]: crate::Interest::WRITABLE
+    /// [`Interest::READABLE`]: crate::Interest::READABLE
+    /// [`TcpStream`]: ../net/struct.TcpStream.html
+    /// [`TcpStream::take_error`]: ../net/struct.TcpStream.html#method.take_error
+    ///
     /// # Notes
     ///
     /// Method is available on all platforms, but not all platforms trigger the
@@ -183,6 +203,66 @@ impl Event {
         sys::event::is_lio(&self.inner)
     }
 
+    /// Returns the file descriptor the event is for, if the OS selector
+    /// provides it.
+    ///
+    /// This can be used for logging, or to perform an operation on the file
+    /// descriptor without having to map the [`token`] back to the source.
+    ///
+    /// # Notes
+    ///
+    /// Not all OS selectors provide the file descriptor, in which case this
+    /// returns `None` and the token must be used instead.
+    ///
+    /// | [OS selector] | File descriptor |
+    /// |---------------|-----------------|
+    /// | [epoll]       | *Not supported*, always `None` |
+    /// | [kqueue]      | `ident` of `EVFILT_READ` and `EVFILT_WRITE` events |
+    /// | [poll]        | Supported, except for [`Waker`] events |
+    ///
+    /// [`token`]: Event::token
+    /// [OS selector]: ../struct.Poll.html#implementation-notes
+    /// [epoll]: https://man7.org/linux/man-pages/man7/epoll.7.html
+    /// [kqueue]: https://www.freebsd.org/cgi/man.cgi?query=kqueue&sektion=2
+    /// [poll]: https://man7.org/linux/man-pages/man2/poll.2.html
+    /// [`Waker`]: crate::Waker
+    #[cfg(unix)]
+    pub fn raw_fd(&self) -> Option<RawFd> {
+        sys::event::raw_fd(&self.inner)
+    }
+
+    /// Returns the readiness of the event.
+    ///
+    /// This combines the [`is_readable`], [`is_writable`], [`is_error`],
+    /// [`is_read_closed`], [`is_write_closed`] and [`is_priority`] methods
+    /// into a single [`Readiness`] value, e.g. to store it in a
+    /// [`ReadinessCache`].
+    ///
+    /// [`is_readable`]: Event::is_readable
+    /// [`is_writable`]: Event::is_writable
+    /// [`is_error`]: Event::is_error
+    /// [`is_read_closed`]: Event::is_read_closed
+    /// [`is_write_closed`]: Event::is_write_closed
+    /// [`is_priority`]: Event::is_priority
+    /// [`ReadinessCache`]: crate::event::ReadinessCache
+    pub fn readiness(&self) -> Readiness {
+        let mut readiness = Readiness::EMPTY;
+        let flags = [
+            (self.is_readable(), Readiness::READABLE),
+            (self.is_writable(), Readiness::WRITABLE),
+            (self.is_error(), Readiness::ERROR),
+            (self.is_read_closed(), Readiness::READ_CLOSED),
+            (self.is_write_closed(), Readiness::WRITE_CLOSED),
+            (self.is_priority(), Readiness::PRIORITY),
+        ];
+        for (set, flag) in flags {
+            if set {
+                readiness |= flag;
+            }
+        }
+        readiness
+    }
+
     /// Create a reference to an `Event` from a platform specific event.
     pub(crate) fn from_sys_event_ref(sys_event: &sys::Event) -> &Event {
         unsafe {
@@ -191,6 +271,16 @@ impl Event {
             &*(sys_event as *const sys::Event as *const Event)
         }
     }
+
+    /// Create a mutable reference to an `Event` from a platform specific
+    /// event.
+    pub(crate) fn from_sys_event_mut(sys_event: &mut sys::Event) -> &mut Event {
+        unsafe {
+            // This is safe for the same reason as `from_sys_event_ref`, the
+            // layout is checked by the assertions below `Event`.
+            &mut *(sys_event as *mut sys::Event as *mut Event)
+        }
+    }
 }
 
 /// When the [alternate] flag is enabled this will print platform specific
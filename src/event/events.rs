@@ -2,6 +2,7 @@ use crate::event::Event;
 use crate::sys;
 
 use std::fmt;
+use std::mem::ManuallyDrop;
 
 /// A collection of readiness events.
 ///
@@ -78,6 +79,15 @@ pub struct Iter<'a> {
     pos: usize,
 }
 
+/// Mutable [`Events`] iterator.
+///
+/// This struct is created by the [`iter_mut`] method on [`Events`].
+///
+/// [`iter_mut`]: Events::iter_mut
+pub struct IterMut<'a> {
+    inner: std::slice::IterMut<'a, sys::Event>,
+}
+
 impl Events {
     /// Return a new `Events` capable of holding up to `capacity` events.
     ///
@@ -95,6 +105,59 @@ impl Events {
         }
     }
 
+    /// Create a new `Events` using the allocation of `events`, e.g. one
+    /// returned by [`Events::into_vec`].
+    ///
+    /// This allows the buffer of events to be reused, e.g. for short lived
+    /// `Poll` instances, without allocating. `events` is cleared and the
+    /// capacity of the returned `Events` is the capacity of `events`.
+    ///
+    /// # Notes
+    ///
+    /// On Windows `Events` also holds a buffer of completion statuses, which
+    /// is allocated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mio::Events;
+    ///
+    /// let events = Events::with_capacity(1024);
+    /// // Reuse the allocation.
+    /// let events = Events::from_vec(events.into_vec());
+    /// assert_eq!(1024, events.capacity());
+    /// ```
+    pub fn from_vec(events: Vec<Event>) -> Events {
+        let mut events = ManuallyDrop::new(events);
+        events.clear();
+        // SAFETY: `Event` has the same layout as `sys::Event` (see the
+        // assertions below `Event`), so the allocation can be taken over.
+        let events = unsafe {
+            Vec::from_raw_parts(
+                events.as_mut_ptr().cast::<sys::Event>(),
+                0,
+                events.capacity(),
+            )
+        };
+        Events {
+            inner: sys::events_from_vec(events),
+        }
+    }
+
+    /// Returns the allocation backing `self` as a `Vec`, containing the
+    /// current events, see [`Events::from_vec`].
+    pub fn into_vec(self) -> Vec<Event> {
+        let mut events = ManuallyDrop::new(sys::events_into_vec(self.inner));
+        // SAFETY: see `from_vec`.
+        unsafe {
+            Vec::from_raw_parts(
+                events.as_mut_ptr().cast::<Event>(),
+                events.len(),
+                events.capacity(),
+            )
+        }
+    }
+
     /// Returns the number of `Event` values that `self` can hold.
     ///
     /// ```
@@ -121,6 +184,64 @@ impl Events {
         self.inner.is_empty()
     }
 
+    /// Returns `true` if `self` is filled to its [`capacity`].
+    ///
+    /// If this returns `true` after a call to [`Poll::poll`] there are likely
+    /// more events ready than could be returned. These will be returned by the
+    /// next call to `poll`, but it can be used as a signal to grow the buffer
+    /// (see [`suggest_event_capacity`]) or adjust scheduling, to ensure all
+    /// event sources get a turn.
+    ///
+    /// [`capacity`]: Events::capacity
+    /// [`Poll::poll`]: crate::Poll::poll
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mio::Events;
+    ///
+    /// let events = Events::with_capacity(1024);
+    /// assert!(!events.is_full());
+    /// ```
+    pub fn is_full(&self) -> bool {
+        self.inner.len() >= self.inner.capacity()
+    }
+
+    /// Returns the `Event` at `index`, or `None` if `index` is out of bounds.
+    ///
+    /// This doesn't consume the event, it's still returned by [`iter`], which
+    /// allows looking ahead before processing the events.
+    ///
+    /// [`iter`]: Events::iter
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mio::Events;
+    ///
+    /// let events = Events::with_capacity(1024);
+    /// assert!(events.get(0).is_none());
+    /// ```
+    pub fn get(&self, index: usize) -> Option<&Event> {
+        self.inner.get(index).map(Event::from_sys_event_ref)
+    }
+
+    /// Returns the first `Event`, or `None` if `self` is empty.
+    ///
+    /// This is the same as calling [`get(0)`](Events::get).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mio::Events;
+    ///
+    /// let events = Events::with_capacity(1024);
+    /// assert!(events.first().is_none());
+    /// ```
+    pub fn first(&self) -> Option<&Event> {
+        self.get(0)
+    }
+
     /// Returns an iterator over the `Event` values.
     ///
     /// # Examples
@@ -152,6 +273,69 @@ impl Events {
         }
     }
 
+    /// Returns an iterator over mutable references to the `Event` values.
+    ///
+    /// `Event`s don't have any setters, but this allows events to be reordered
+    /// in place, e.g. using [`mem::swap`], or overwritten (with a clone of
+    /// another event) without copying them into a separate collection.
+    /// Modifying the events only changes this in-memory copy, it doesn't
+    /// affect the registrations or the OS in any way.
+    ///
+    /// [`mem::swap`]: std::mem::swap
+    pub fn iter_mut(&mut self) -> IterMut<'_> {
+        IterMut {
+            inner: self.inner.as_mut_slice().iter_mut(),
+        }
+    }
+
+    /// Retains only the events for which `f` returns `true`, removing all
+    /// other events, keeping the order of the retained events.
+    ///
+    /// This can be used to filter out events handled in an earlier stage of a
+    /// multi-stage dispatch. Like [`iter_mut`] this only affects this
+    /// in-memory copy of the events.
+    ///
+    /// [`iter_mut`]: Events::iter_mut
+    ///
+    /// # Examples
+    ///
+    #[cfg_attr(feature = "os-poll", doc = "```")]
+    #[cfg_attr(not(feature = "os-poll"), doc = "```ignore")]
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mio::{Events, Poll, Token};
+    /// use std::time::Duration;
+    ///
+    /// const CONTROL: Token = Token(0);
+    ///
+    /// let mut events = Events::with_capacity(1024);
+    /// let mut poll = Poll::new()?;
+    ///
+    /// // Register handles with `poll`.
+    ///
+    /// poll.poll(&mut events, Some(Duration::from_millis(100)))?;
+    ///
+    /// // Handle the events for the control connection first.
+    /// for event in events.iter().filter(|event| event.token() == CONTROL) {
+    ///     println!("Got a control event: {:?}", event);
+    /// }
+    ///
+    /// // Handle the remaining events later.
+    /// events.retain(|event| event.token() != CONTROL);
+    /// for event in events.iter() {
+    ///     println!("Got an event for {:?}", event.token());
+    /// }
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&Event) -> bool,
+    {
+        self.inner
+            .retain(|sys_event| f(Event::from_sys_event_ref(sys_event)));
+    }
+
     /// Clearing all `Event` values from container explicitly.
     ///
     /// # Notes
@@ -200,15 +384,20 @@ impl<'a> IntoIterator for &'a Events {
     }
 }
 
+impl<'a> IntoIterator for &'a mut Events {
+    type Item = &'a mut Event;
+    type IntoIter = IterMut<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
 impl<'a> Iterator for Iter<'a> {
     type Item = &'a Event;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let ret = self
-            .inner
-            .inner
-            .get(self.pos)
-            .map(Event::from_sys_event_ref);
+        let ret = self.inner.get(self.pos);
         self.pos += 1;
         ret
     }
@@ -223,6 +412,63 @@ impl<'a> Iterator for Iter<'a> {
     }
 }
 
+impl<'a> Iterator for IterMut<'a> {
+    type Item = &'a mut Event;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(Event::from_sys_event_mut)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+/// Suggest a capacity for [`Events`] based on the number of `registered`
+/// event sources.
+///
+/// The suggested capacity is the number of registered sources, but at least 64
+/// and at most 1024. Larger capacities rarely help as events that don't fit are
+/// simply returned in the next call to [`Poll::poll`].
+///
+/// [`Poll::poll`]: crate::Poll::poll
+///
+/// # Examples
+///
+#[cfg_attr(
+    all(unix, feature = "os-poll", feature = "track-registrations"),
+    doc = "```"
+)]
+#[cfg_attr(
+    not(all(unix, feature = "os-poll", feature = "track-registrations")),
+    doc = "```ignore"
+)]
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use mio::{suggest_event_capacity, Events, Poll};
+///
+/// let poll = Poll::new()?;
+///
+/// // Register event sources with `poll`.
+///
+/// let capacity = suggest_event_capacity(poll.registry().registered_count());
+/// let events = Events::with_capacity(capacity);
+/// # assert_eq!(events.capacity(), 64);
+/// #     Ok(())
+/// # }
+/// ```
+pub fn suggest_event_capacity(registered: usize) -> usize {
+    registered.clamp(64, 1024)
+}
+
+impl fmt::Debug for IterMut<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let events = self.inner.as_slice().iter();
+        f.debug_list()
+            .entries(events.map(Event::from_sys_event_ref))
+            .finish()
+    }
+}
+
 impl fmt::Debug for Events {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_list().entries(self).finish()
@@ -3,8 +3,16 @@
 #[allow(clippy::module_inception)]
 mod event;
 mod events;
+mod priority;
+mod readiness;
 mod source;
+#[cfg(all(unix, any(feature = "net", feature = "os-ext")))]
+mod split;
 
 pub use self::event::Event;
-pub use self::events::{Events, Iter};
+pub use self::events::{suggest_event_capacity, Events, Iter, IterMut};
+pub use self::priority::{PriorityEvents, PriorityIter};
+pub use self::readiness::{Readiness, ReadinessCache};
 pub use self::source::Source;
+#[cfg(all(unix, any(feature = "net", feature = "os-ext")))]
+pub use self::split::SplitRegistration;
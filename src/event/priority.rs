@@ -0,0 +1,166 @@
+use std::cmp::Reverse;
+use std::time::Duration;
+use std::{fmt, io};
+
+use crate::event::Event;
+use crate::{Events, Poll, Token};
+
+/// [`Events`] that are yielded in priority order.
+///
+/// Each token is mapped to a priority using a user defined function, for
+/// example to process events for control-plane sources before events for
+/// bulk data sources. After a call to [`PriorityEvents::poll`], [`iter`]
+/// returns the events with the highest priority first. Events with the same
+/// priority are returned in the order in which they were returned by the OS.
+///
+/// The priority function is called once per event after each poll, it should
+/// be cheap, e.g. checking if a token is within a certain range.
+///
+/// [`iter`]: PriorityEvents::iter
+///
+/// # Examples
+///
+#[cfg_attr(feature = "os-poll", doc = "```")]
+#[cfg_attr(not(feature = "os-poll"), doc = "```ignore")]
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use std::time::Duration;
+///
+/// use mio::{Poll, PriorityEvents, Token};
+///
+/// // Tokens below 16 are used for control connections.
+/// fn priority(token: Token) -> u8 {
+///     if token.0 < 16 { 1 } else { 0 }
+/// }
+///
+/// let mut poll = Poll::new()?;
+/// let mut events = PriorityEvents::with_capacity(128, priority);
+///
+/// // Register event sources with `poll`.
+///
+/// events.poll(&mut poll, Some(Duration::from_millis(100)))?;
+///
+/// for event in events.iter() {
+///     // Events for control connections are returned first.
+///     println!("Got an event for {:?}", event.token());
+/// }
+/// #     Ok(())
+/// # }
+/// ```
+pub struct PriorityEvents<F = fn(Token) -> u8> {
+    events: Events,
+    priority: F,
+    /// Priority and index into `events`, sorted by priority.
+    order: Vec<(Reverse<u8>, usize)>,
+}
+
+/// [`PriorityEvents`] iterator.
+///
+/// This struct is created by the [`iter`] method on [`PriorityEvents`].
+///
+/// [`iter`]: PriorityEvents::iter
+#[derive(Debug, Clone)]
+pub struct PriorityIter<'a> {
+    events: &'a Events,
+    order: std::slice::Iter<'a, (Reverse<u8>, usize)>,
+}
+
+impl<F> PriorityEvents<F>
+where
+    F: FnMut(Token) -> u8,
+{
+    /// Return a new `PriorityEvents` capable of holding up to `capacity`
+    /// events, using `priority` to determine the priority of each token.
+    ///
+    /// A higher priority means the event is returned earlier.
+    pub fn with_capacity(capacity: usize, priority: F) -> PriorityEvents<F> {
+        PriorityEvents {
+            events: Events::with_capacity(capacity),
+            priority,
+            order: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// Poll for events using [`Poll::poll`] and sort them by priority.
+    pub fn poll(&mut self, poll: &mut Poll, timeout: Option<Duration>) -> io::Result<()> {
+        self.order.clear();
+        poll.poll(&mut self.events, timeout)?;
+        self.sort();
+        Ok(())
+    }
+
+    /// Sort the events by priority.
+    fn sort(&mut self) {
+        let priority = &mut self.priority;
+        self.order.extend(
+            self.events
+                .iter()
+                .enumerate()
+                .map(|(index, event)| (Reverse(priority(event.token())), index)),
+        );
+        // The index is unique, so this keeps the order of the events with
+        // the same priority without requiring an allocation like a stable
+        // sort does.
+        self.order.sort_unstable();
+    }
+}
+
+impl<F> PriorityEvents<F> {
+    /// Returns an iterator over the `Event` values, with the highest priority
+    /// events first.
+    pub fn iter(&self) -> PriorityIter<'_> {
+        PriorityIter {
+            events: &self.events,
+            order: self.order.iter(),
+        }
+    }
+
+    /// Returns the events in the order returned by the OS.
+    pub fn events(&self) -> &Events {
+        &self.events
+    }
+
+    /// Returns the number of `Event` values that `self` can hold.
+    pub fn capacity(&self) -> usize {
+        self.events.capacity()
+    }
+
+    /// Returns `true` if `self` contains no `Event` values.
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    /// Clearing all `Event` values from container explicitly.
+    pub fn clear(&mut self) {
+        self.events.clear();
+        self.order.clear();
+    }
+}
+
+impl<'a, F> IntoIterator for &'a PriorityEvents<F> {
+    type Item = &'a Event;
+    type IntoIter = PriorityIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a> Iterator for PriorityIter<'a> {
+    type Item = &'a Event;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (_, index) = self.order.next()?;
+        self.events.get(*index)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.order.size_hint()
+    }
+}
+
+impl<F> fmt::Debug for PriorityEvents<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self).finish()
+    }
+}
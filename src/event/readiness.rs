@@ -0,0 +1,260 @@
+use std::collections::HashMap;
+use std::{fmt, ops};
+
+use crate::{Events, Token};
+
+const READABLE: u8 = 0b00_0001;
+const WRITABLE: u8 = 0b00_0010;
+const ERROR: u8 = 0b00_0100;
+const READ_CLOSED: u8 = 0b00_1000;
+const WRITE_CLOSED: u8 = 0b01_0000;
+const PRIORITY: u8 = 0b10_0000;
+
+/// A snapshot of the readiness of an event source.
+///
+/// Unlike an [`Event`], which is returned by the OS selector, `Readiness` is
+/// independent of the selector. It's returned by [`Source::poll_ready`] and
+/// [`Registry::register_and_probe`], to determine the readiness of a source at
+/// a specific point in time.
+///
+/// The accessors have the same meaning as the methods of the same name on
+/// [`Event`].
+///
+/// [`Event`]: crate::event::Event
+/// [`Registry::register_and_probe`]: crate::Registry::register_and_probe
+/// [`Source::poll_ready`]: crate::event::Source::poll_ready
+#[derive(Copy, Clone, Default, PartialEq, Eq)]
+pub struct Readiness(u8);
+
+impl Readiness {
+    /// Empty readiness, i.e. the source is not ready for anything.
+    pub const EMPTY: Readiness = Readiness(0);
+    /// Readable readiness.
+    pub const READABLE: Readiness = Readiness(READABLE);
+    /// Writable readiness.
+    pub const WRITABLE: Readiness = Readiness(WRITABLE);
+    /// Error readiness.
+    pub const ERROR: Readiness = Readiness(ERROR);
+    /// Read closed readiness.
+    pub const READ_CLOSED: Readiness = Readiness(READ_CLOSED);
+    /// Write closed readiness.
+    pub const WRITE_CLOSED: Readiness = Readiness(WRITE_CLOSED);
+    /// Priority readiness.
+    pub const PRIORITY: Readiness = Readiness(PRIORITY);
+
+    /// Returns true if the source is not ready for anything.
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Returns true if the value includes readable readiness.
+    pub const fn is_readable(self) -> bool {
+        self.0 & READABLE != 0
+    }
+
+    /// Returns true if the value includes writable readiness.
+    pub const fn is_writable(self) -> bool {
+        self.0 & WRITABLE != 0
+    }
+
+    /// Returns true if the value includes error readiness.
+    pub const fn is_error(self) -> bool {
+        self.0 & ERROR != 0
+    }
+
+    /// Returns true if the value includes read closed readiness.
+    pub const fn is_read_closed(self) -> bool {
+        self.0 & READ_CLOSED != 0
+    }
+
+    /// Returns true if the value includes write closed readiness.
+    pub const fn is_write_closed(self) -> bool {
+        self.0 & WRITE_CLOSED != 0
+    }
+
+    /// Returns true if the value includes priority readiness.
+    pub const fn is_priority(self) -> bool {
+        self.0 & PRIORITY != 0
+    }
+
+    /// Add together two `Readiness` values.
+    pub const fn add(self, other: Readiness) -> Readiness {
+        Readiness(self.0 | other.0)
+    }
+
+    /// Removes the readiness in `other` from `self`.
+    pub const fn remove(self, other: Readiness) -> Readiness {
+        Readiness(self.0 & !other.0)
+    }
+
+    /// Returns true if `self` includes all readiness in `other`.
+    pub const fn contains(self, other: Readiness) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl ops::BitOr for Readiness {
+    type Output = Self;
+
+    #[inline]
+    fn bitor(self, other: Self) -> Self {
+        self.add(other)
+    }
+}
+
+impl ops::BitOrAssign for Readiness {
+    #[inline]
+    fn bitor_assign(&mut self, other: Self) {
+        *self = *self | other;
+    }
+}
+
+impl fmt::Debug for Readiness {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let flags = [
+            (READABLE, "READABLE"),
+            (WRITABLE, "WRITABLE"),
+            (ERROR, "ERROR"),
+            (READ_CLOSED, "READ_CLOSED"),
+            (WRITE_CLOSED, "WRITE_CLOSED"),
+            (PRIORITY, "PRIORITY"),
+        ];
+        let mut one = false;
+        for (flag, name) in flags {
+            if self.0 & flag != 0 {
+                if one {
+                    write!(fmt, " | ")?
+                }
+                write!(fmt, "{}", name)?;
+                one = true
+            }
+        }
+        if !one {
+            write!(fmt, "(empty)")?;
+        }
+        Ok(())
+    }
+}
+
+/// Cache of the most recently seen readiness per [`Token`].
+///
+/// Because Mio uses edge-triggered notifications an event is only returned
+/// when the readiness of a source changes, so users have to remember the
+/// readiness of a source until they've acted upon it. `ReadinessCache` is a
+/// simple implementation of this bookkeeping: it records the readiness of all
+/// [`Events`] and allows querying it (using [`readiness`]) between calls to
+/// [`Poll::poll`].
+///
+/// [`readiness`]: ReadinessCache::readiness
+/// [`Poll::poll`]: crate::Poll::poll
+///
+/// # Invalidation
+///
+/// The cached readiness is **not** cleared by new calls to `poll`, only by
+/// the user. Once an I/O operation returns a [`WouldBlock`] error the source
+/// is no longer ready and the readiness must be cleared using [`clear`], e.g.
+/// removing [`Readiness::READABLE`] after `read` returns `WouldBlock`. A new
+/// event will be returned once the source becomes ready again, which will set
+/// the readiness again. Clearing the readiness before receiving a
+/// `WouldBlock` error means losing the readiness, as no new event will be
+/// returned for it.
+///
+/// When a source is [reregistered] the OS selector will return a new event if
+/// it's (still) ready, so the cached readiness can be cleared. When a source
+/// is deregistered use [`remove`] to remove it from the cache, as its token
+/// might be reused.
+///
+/// [`WouldBlock`]: std::io::ErrorKind::WouldBlock
+/// [`clear`]: ReadinessCache::clear
+/// [reregistered]: crate::Registry::reregister
+/// [`remove`]: ReadinessCache::remove
+///
+/// # Examples
+///
+#[cfg_attr(all(feature = "os-poll", feature = "net"), doc = "```")]
+#[cfg_attr(not(all(feature = "os-poll", feature = "net")), doc = "```ignore")]
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use std::io;
+/// use std::time::Duration;
+///
+/// use mio::event::{Readiness, ReadinessCache};
+/// use mio::net::UdpSocket;
+/// use mio::{Events, Interest, Poll, Token};
+///
+/// let mut poll = Poll::new()?;
+/// let mut events = Events::with_capacity(8);
+/// let mut cache = ReadinessCache::new();
+///
+/// let mut socket = UdpSocket::bind("127.0.0.1:0".parse()?)?;
+/// poll.registry().register(&mut socket, Token(0), Interest::READABLE | Interest::WRITABLE)?;
+///
+/// poll.poll(&mut events, Some(Duration::from_millis(100)))?;
+/// cache.update(&events);
+///
+/// if cache.readiness(Token(0)).is_readable() {
+///     let mut buf = [0; 1024];
+///     match socket.recv(&mut buf) {
+///         Ok(_) => { /* Process the data. */ },
+///         Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => {
+///             // No longer readable, wait for the next event.
+///             cache.clear(Token(0), Readiness::READABLE);
+///         },
+///         Err(err) => return Err(err.into()),
+///     }
+/// }
+/// #     Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ReadinessCache {
+    readiness: HashMap<Token, Readiness>,
+}
+
+impl ReadinessCache {
+    /// Create a new, empty, `ReadinessCache`.
+    pub fn new() -> ReadinessCache {
+        ReadinessCache {
+            readiness: HashMap::new(),
+        }
+    }
+
+    /// Add the readiness of all `events` to the cache.
+    ///
+    /// The readiness is added to the readiness already cached for the token,
+    /// see [`Event::readiness`].
+    ///
+    /// [`Event::readiness`]: crate::event::Event::readiness
+    pub fn update(&mut self, events: &Events) {
+        for event in events.iter() {
+            *self.readiness.entry(event.token()).or_default() |= event.readiness();
+        }
+    }
+
+    /// Returns the cached readiness for `token`.
+    ///
+    /// Returns [`Readiness::EMPTY`] if no events were recorded for `token`.
+    pub fn readiness(&self, token: Token) -> Readiness {
+        self.readiness.get(&token).copied().unwrap_or_default()
+    }
+
+    /// Remove `readiness` from the cached readiness for `token`.
+    ///
+    /// See the [type documentation] when to call this.
+    ///
+    /// [type documentation]: ReadinessCache#invalidation
+    pub fn clear(&mut self, token: Token, readiness: Readiness) {
+        if let Some(cached) = self.readiness.get_mut(&token) {
+            *cached = cached.remove(readiness);
+            if cached.is_empty() {
+                let _ = self.readiness.remove(&token);
+            }
+        }
+    }
+
+    /// Remove `token` from the cache, e.g. after deregistering the
+    /// associated event source.
+    pub fn remove(&mut self, token: Token) {
+        let _ = self.readiness.remove(&token);
+    }
+}
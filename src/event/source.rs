@@ -1,3 +1,4 @@
+use crate::event::Readiness;
 use crate::{Interest, Registry, Token};
 
 use std::io;
@@ -109,6 +110,50 @@ pub trait Source {
     ///
     /// [`Registry::deregister`]: ../struct.Registry.html#method.deregister
     fn deregister(&mut self, registry: &Registry) -> io::Result<()>;
+
+    /// Determine the current readiness of `self` for `interests`, without
+    /// blocking.
+    ///
+    /// Unlike the methods above this may be called directly, it doesn't
+    /// require `self` to be registered and doesn't change the registration
+    /// (if any), i.e. events are still returned by [`Poll::poll`]. The
+    /// returned [`Readiness`] is a snapshot, it may already be outdated by
+    /// the time it's returned.
+    ///
+    /// The net types, and the Unix types that wrap a file descriptor,
+    /// implement this using a non-blocking `poll(2)` call on Unix. The
+    /// default implementation returns an error of kind
+    /// [`io::ErrorKind::Unsupported`], which is also returned for all types
+    /// on other platforms.
+    ///
+    /// [`Poll::poll`]: crate::Poll::poll
+    ///
+    /// # Examples
+    ///
+    #[cfg_attr(all(unix, feature = "os-poll", feature = "net"), doc = "```")]
+    #[cfg_attr(
+        not(all(unix, feature = "os-poll", feature = "net")),
+        doc = "```ignore"
+    )]
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mio::event::Source;
+    /// use mio::net::UdpSocket;
+    /// use mio::Interest;
+    ///
+    /// let socket = UdpSocket::bind("127.0.0.1:0".parse()?)?;
+    ///
+    /// let readiness = socket.poll_ready(Interest::READABLE | Interest::WRITABLE)?;
+    /// // Nothing was received yet, but the socket can be written to.
+    /// assert!(!readiness.is_readable());
+    /// assert!(readiness.is_writable());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    fn poll_ready(&self, interests: Interest) -> io::Result<Readiness> {
+        let _ = interests;
+        Err(io::ErrorKind::Unsupported.into())
+    }
 }
 
 impl<T> Source for Box<T>
@@ -136,4 +181,8 @@ where
     fn deregister(&mut self, registry: &Registry) -> io::Result<()> {
         (**self).deregister(registry)
     }
+
+    fn poll_ready(&self, interests: Interest) -> io::Result<Readiness> {
+        (**self).poll_ready(interests)
+    }
 }
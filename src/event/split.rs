@@ -0,0 +1,67 @@
+use std::io;
+use std::os::fd::{AsRawFd, OwnedFd};
+
+use crate::{event, Interest, Registry, Token};
+
+/// The writable half of a split registration, created by
+/// [`Registry::register_split`].
+///
+/// This owns a duplicate of the file descriptor of the registered source,
+/// which is registered with the write token. It implements [`event::Source`],
+/// so it can be reregistered (e.g. to change the write token) and
+/// deregistered like any other source.
+///
+/// [`Registry::register_split`]: crate::Registry::register_split
+///
+/// # Notes
+///
+/// The `SplitRegistration` **must** be deregistered before it's dropped. On
+/// epoll and kqueue the registration of a duplicate file descriptor is only
+/// removed once all file descriptors referring to the same socket are closed,
+/// so dropping a `SplitRegistration` while the source is still open leaves the
+/// registration in place. Events for the write token will then be returned
+/// until the source is closed, without any way to remove them.
+#[derive(Debug)]
+pub struct SplitRegistration {
+    /// Duplicate of the source's file descriptor.
+    fd: OwnedFd,
+}
+
+impl SplitRegistration {
+    /// Create a new `SplitRegistration` for `fd`, not yet registered.
+    pub(crate) fn new(fd: OwnedFd) -> SplitRegistration {
+        SplitRegistration { fd }
+    }
+}
+
+impl event::Source for SplitRegistration {
+    fn register(
+        &mut self,
+        registry: &Registry,
+        token: Token,
+        interests: Interest,
+    ) -> io::Result<()> {
+        let fd = self.fd.as_raw_fd();
+        registry.selector().register(fd, token, interests)?;
+        registry.track_new_registration(fd, token, interests);
+        Ok(())
+    }
+
+    fn reregister(
+        &mut self,
+        registry: &Registry,
+        token: Token,
+        interests: Interest,
+    ) -> io::Result<()> {
+        let fd = self.fd.as_raw_fd();
+        registry.selector().reregister(fd, token, interests)?;
+        registry.track_registration(fd, token, interests);
+        Ok(())
+    }
+
+    fn deregister(&mut self, registry: &Registry) -> io::Result<()> {
+        let fd = self.fd.as_raw_fd();
+        registry.untrack_registration(fd);
+        registry.selector().deregister(fd)
+    }
+}
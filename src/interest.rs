@@ -23,6 +23,7 @@ const WRITABLE: u8 = 0b0010;
 const AIO: u8 = 0b0100;
 const LIO: u8 = 0b1000;
 const PRIORITY: u8 = 0b10000;
+const ERROR: u8 = 0b100000;
 
 impl Interest {
     /// Returns a `Interest` set representing readable interests.
@@ -51,6 +52,35 @@ impl Interest {
     #[cfg(any(target_os = "linux", target_os = "android"))]
     pub const PRIORITY: Interest = Interest(unsafe { NonZeroU8::new_unchecked(PRIORITY) });
 
+    /// Returns a `Interest` set that doesn't monitor for readable or writable
+    /// readiness, only for error and hang up events.
+    ///
+    /// Events delivered for sources registered with this interest will only
+    /// have [`is_error`] and/or the closed indicators (e.g. [`is_read_closed`])
+    /// set. This can be used to, for example, watch idle connections without
+    /// being woken up for incoming data.
+    ///
+    /// This is only available on platforms where the OS reports error and hang
+    /// up conditions regardless of the requested readiness (`epoll(7)` and
+    /// `poll(2)`). kqueue and Windows only report these conditions as part of
+    /// read or write readiness, on those platforms the minimum interest is
+    /// [`Interest::READABLE`].
+    ///
+    /// Note that adding this to another interest has no effect, error and hang
+    /// up events are always delivered.
+    ///
+    /// [`is_error`]: crate::event::Event::is_error
+    /// [`is_read_closed`]: crate::event::Event::is_read_closed
+    #[cfg(any(
+        target_os = "android",
+        target_os = "illumos",
+        target_os = "linux",
+        target_os = "redox",
+    ))]
+    pub const fn error_only() -> Interest {
+        Interest(unsafe { NonZeroU8::new_unchecked(ERROR) })
+    }
+
     /// Add together two `Interest`.
     ///
     /// This does the same thing as the `BitOr` implementation, but is a
@@ -123,6 +153,13 @@ impl Interest {
     pub const fn is_priority(self) -> bool {
         (self.0.get() & PRIORITY) != 0
     }
+
+    /// Returns true if `Interest` *only* contains error readiness, see
+    /// [`Interest::error_only`].
+    #[must_use]
+    pub const fn is_error_only(self) -> bool {
+        self.0.get() == ERROR
+    }
 }
 
 impl ops::BitOr for Interest {
@@ -196,6 +233,13 @@ impl fmt::Debug for Interest {
                 one = true
             }
         }
+        if self.0.get() & ERROR != 0 {
+            if one {
+                write!(fmt, " | ")?
+            }
+            write!(fmt, "ERROR")?;
+            one = true
+        }
         debug_assert!(one, "printing empty interests");
         Ok(())
     }
@@ -0,0 +1,152 @@
+//! I/O utilities.
+//!
+//! See [`VectoredWriter`].
+
+use std::io::{self, IoSlice, Write};
+
+/// Maximum number of buffers passed to a single `write_vectored` call.
+const MAX_BUFS: usize = 64;
+
+/// A set of buffers written using vectored I/O, tracking partial progress.
+///
+/// Writing a number of buffers to a non-blocking writer, e.g. a
+/// [`TcpStream`], often only partially succeeds before the writer returns a
+/// [`WouldBlock`] error. The caller then has to keep track of what was written
+/// and advance the [`IoSlice`]s accordingly, before retrying once the writer is
+/// writable again. `VectoredWriter` does this bookkeeping.
+///
+/// [`write_to`] writes as much of the buffers as possible, continuing where
+/// the previous call left off. It returns `Ok(())` once all buffers are
+/// written, or the `WouldBlock` error if the writer is not ready, in which
+/// case `write_to` should be called again after the next writable event.
+///
+/// [`TcpStream`]: crate::net::TcpStream
+/// [`WouldBlock`]: io::ErrorKind::WouldBlock
+/// [`write_to`]: VectoredWriter::write_to
+///
+/// # Examples
+///
+#[cfg_attr(all(feature = "os-poll", feature = "net"), doc = "```")]
+#[cfg_attr(not(all(feature = "os-poll", feature = "net")), doc = "```ignore")]
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use std::io;
+///
+/// use mio::io::VectoredWriter;
+/// use mio::net::TcpStream;
+/// # let listener = std::net::TcpListener::bind("127.0.0.1:0")?;
+/// # let address = listener.local_addr()?;
+///
+/// let stream = TcpStream::connect(address)?;
+///
+/// let header = b"HTTP/1.1 200 OK\r\n\r\n".to_vec();
+/// let body = b"Hello world!".to_vec();
+/// let mut writer = VectoredWriter::new(vec![header, body]);
+///
+/// // Call this on every writable event until it returns `Ok`.
+/// match writer.write_to(&mut &stream) {
+///     Ok(()) => println!("response written"),
+///     Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => {
+///         println!("{} bytes left to write", writer.remaining());
+///     }
+///     // The stream might not be connected yet.
+///     Err(err) => println!("failed to write: {err}"),
+/// }
+/// #     Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct VectoredWriter<B> {
+    bufs: Vec<B>,
+    /// Index of the first buffer not (fully) written.
+    index: usize,
+    /// Number of bytes of `bufs[index]` already written.
+    offset: usize,
+}
+
+impl<B: AsRef<[u8]>> VectoredWriter<B> {
+    /// Create a new `VectoredWriter` that writes `bufs`, in order.
+    pub fn new(bufs: Vec<B>) -> VectoredWriter<B> {
+        let mut writer = VectoredWriter {
+            bufs,
+            index: 0,
+            offset: 0,
+        };
+        writer.skip_written();
+        writer
+    }
+
+    /// Add `buf` to the end of the buffers to write.
+    pub fn push(&mut self, buf: B) {
+        self.bufs.push(buf);
+        self.skip_written();
+    }
+
+    /// Write the remaining buffers to `writer`.
+    ///
+    /// Returns `Ok(())` once all buffers are written. If `writer` returns an
+    /// error, e.g. `WouldBlock`, it is returned and the next call continues
+    /// where this one left off. Interrupted writes are retried, and a write of
+    /// zero bytes results in a `WriteZero` error.
+    pub fn write_to<W: Write + ?Sized>(&mut self, writer: &mut W) -> io::Result<()> {
+        while !self.is_done() {
+            let mut slices = [IoSlice::new(&[]); MAX_BUFS];
+            let mut len = 0;
+            for (slice, buf) in slices.iter_mut().zip(&self.bufs[self.index..]) {
+                *slice = IoSlice::new(buf.as_ref());
+                len += 1;
+            }
+            slices[0] = IoSlice::new(&self.bufs[self.index].as_ref()[self.offset..]);
+
+            match writer.write_vectored(&slices[..len]) {
+                Ok(0) => return Err(io::ErrorKind::WriteZero.into()),
+                Ok(n) => self.advance(n),
+                Err(ref err) if err.kind() == io::ErrorKind::Interrupted => {}
+                Err(err) => return Err(err),
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns `true` if all buffers are written.
+    pub fn is_done(&self) -> bool {
+        self.index == self.bufs.len()
+    }
+
+    /// Returns the number of bytes left to write.
+    pub fn remaining(&self) -> usize {
+        self.bufs[self.index..]
+            .iter()
+            .map(|buf| buf.as_ref().len())
+            .sum::<usize>()
+            - self.offset
+    }
+
+    /// Returns the buffers, including the ones already written.
+    pub fn into_inner(self) -> Vec<B> {
+        self.bufs
+    }
+
+    /// Mark `n` bytes as written.
+    fn advance(&mut self, mut n: usize) {
+        while n > 0 {
+            let left = self.bufs[self.index].as_ref().len() - self.offset;
+            if n < left {
+                self.offset += n;
+                return;
+            }
+            n -= left;
+            self.index += 1;
+            self.offset = 0;
+        }
+        self.skip_written();
+    }
+
+    /// Skip empty buffers, so that `bufs[index]` (if any) has bytes to write.
+    fn skip_written(&mut self) {
+        while self.index < self.bufs.len() && self.bufs[self.index].as_ref().len() == self.offset {
+            self.index += 1;
+            self.offset = 0;
+        }
+    }
+}
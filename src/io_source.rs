@@ -12,6 +12,10 @@ use std::sync::atomic::{AtomicUsize, Ordering};
 use std::{fmt, io};
 
 use crate::sys::IoSourceState;
+#[cfg(debug_assertions)]
+use crate::Error;
+#[cfg(unix)]
+use crate::event::Readiness;
 use crate::{event, Interest, Registry, Token};
 
 /// Adapter for a [`RawFd`] or [`RawSocket`] providing an [`event::Source`]
@@ -78,7 +82,11 @@ impl<T> IoSource<T> {
     ///
     /// [`deregister`]: Registry::deregister
     pub fn into_inner(self) -> T {
-        self.inner
+        let IoSource {
+            mut state, inner, ..
+        } = self;
+        state.detach();
+        inner
     }
 }
 
@@ -138,6 +146,11 @@ where
         self.selector_id.remove_association(registry)?;
         self.state.deregister(registry, self.inner.as_raw_fd())
     }
+
+    #[cfg(unix)]
+    fn poll_ready(&self, interests: Interest) -> io::Result<Readiness> {
+        crate::sys::probe(self.inner.as_raw_fd(), interests)
+    }
 }
 
 #[cfg(windows)]
@@ -251,10 +264,7 @@ impl SelectorId {
         if previous_id == Self::UNASSOCIATED {
             Ok(())
         } else {
-            Err(io::Error::new(
-                io::ErrorKind::AlreadyExists,
-                "I/O source already registered with a `Registry`",
-            ))
+            Err(Error::AlreadyRegistered.into())
         }
     }
 
@@ -268,15 +278,9 @@ impl SelectorId {
         if id == registry_id {
             Ok(())
         } else if id == Self::UNASSOCIATED {
-            Err(io::Error::new(
-                io::ErrorKind::NotFound,
-                "I/O source not registered with `Registry`",
-            ))
+            Err(Error::NotRegistered.into())
         } else {
-            Err(io::Error::new(
-                io::ErrorKind::AlreadyExists,
-                "I/O source already registered with a different `Registry`",
-            ))
+            Err(Error::WrongRegistry.into())
         }
     }
 
@@ -289,10 +293,7 @@ impl SelectorId {
         if previous_id == registry_id {
             Ok(())
         } else {
-            Err(io::Error::new(
-                io::ErrorKind::NotFound,
-                "I/O source not registered with `Registry`",
-            ))
+            Err(Error::NotRegistered.into())
         }
     }
 }
@@ -47,6 +47,7 @@ compile_error!("This wasm target is unsupported by mio. If using Tokio, disable
 #[macro_use]
 mod macros;
 
+mod error;
 mod interest;
 mod poll;
 mod sys;
@@ -55,22 +56,25 @@ mod token;
 mod waker;
 
 pub mod event;
+pub mod io;
 
 cfg_io_source! {
     mod io_source;
 }
 
 cfg_net! {
+    pub mod buf;
     pub mod net;
 }
 
 #[doc(no_inline)]
-pub use event::Events;
+pub use error::Error;
+pub use event::{suggest_event_capacity, Events, PriorityEvents};
 pub use interest::Interest;
-pub use poll::{Poll, Registry};
+pub use poll::{Capabilities, Poll, PollStatus, Registry, SharedPoll};
 pub use token::Token;
 #[cfg(not(target_os = "wasi"))]
-pub use waker::Waker;
+pub use waker::{CancelNotifier, Waker, WakerChannel, WakerSender};
 
 #[cfg(all(unix, feature = "os-ext"))]
 #[cfg_attr(docsrs, doc(cfg(all(unix, feature = "os-ext"))))]
@@ -85,7 +89,19 @@ pub mod unix {
         pub use crate::sys::pipe::{new, Receiver, Sender};
     }
 
-    pub use crate::sys::SourceFd;
+    pub use crate::sys::completion::{Completer, Completion};
+    pub use crate::sys::{is_cloexec, is_nonblocking, set_cloexec, set_nonblocking, SourceFd};
+
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub use crate::sys::splice::SplicePipe;
+
+    #[cfg(feature = "net")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "net")))]
+    pub use crate::sys::socket::{IcmpSocket, Socket};
+
+    #[cfg(all(feature = "net", any(target_os = "android", target_os = "linux")))]
+    #[cfg_attr(docsrs, doc(cfg(feature = "net")))]
+    pub use crate::sys::socket::NetlinkSocket;
 }
 
 #[cfg(all(target_os = "hermit", feature = "os-ext"))]
@@ -102,6 +118,7 @@ pub mod windows {
     //! Windows only extensions.
 
     pub use crate::sys::named_pipe::NamedPipe;
+    pub use crate::sys::set_nonblocking;
 }
 
 pub mod features {
@@ -128,6 +145,45 @@ pub mod features {
     #![cfg_attr(not(feature = "net"), doc = "## Network types (disabled)")]
     //!
     //! The `net` feature enables networking primitives in the `net` module.
+    //!
+    #![cfg_attr(feature = "io-uring", doc = "## `io-uring` (enabled)")]
+    #![cfg_attr(not(feature = "io-uring"), doc = "## `io-uring` (disabled)")]
+    //!
+    //! On Linux `io-uring` makes `Poll` use [io_uring] instead of epoll, using
+    //! poll requests for the registrations. If the kernel doesn't support
+    //! io_uring (or it's disabled), epoll is used. Linux 5.13 or later is
+    //! required. This feature has no effect on other platforms.
+    //!
+    //! Unlike epoll, a file descriptor isn't removed from io_uring when it's
+    //! closed, the registration keeps the file open. Mio's types remove their
+    //! registration when dropped, but file descriptors registered using
+    //! `SourceFd`, or taken out of Mio's types (e.g. using `IntoRawFd`),
+    //! **must** be deregistered before they're closed.
+    //!
+    //! [io_uring]: https://man7.org/linux/man-pages/man7/io_uring.7.html
+    //!
+    #![cfg_attr(
+        feature = "track-registrations",
+        doc = "## `track-registrations` (enabled)"
+    )]
+    #![cfg_attr(
+        not(feature = "track-registrations"),
+        doc = "## `track-registrations` (disabled)"
+    )]
+    //!
+    //! On Unix `track-registrations` makes `Registry` keep track of all
+    //! registered file descriptors, with their token and interests. This
+    //! enables `Registry::clear`, `Registry::registered_tokens`,
+    //! `Registry::set_writable_interest`, `Registry::ensure_interest` and
+    //! `Poll::shutdown`, and (with debug assertions enabled) a warning when a
+    //! file descriptor that was closed without being deregistered is reused.
+    //! It implies `os-ext`.
+    //!
+    //! The tracking isn't free, which is why it's opt-in: all
+    //! (de)registrations lock a table shared by the `Registry` and its clones,
+    //! registering calls `fstat(2)`, and Mio's I/O sources are a pointer
+    //! larger to remove their file descriptor from the table when they're
+    //! dropped.
 }
 
 pub mod guide {
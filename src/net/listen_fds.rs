@@ -0,0 +1,141 @@
+use std::env;
+use std::os::fd::{AsRawFd, FromRawFd, OwnedFd, RawFd};
+use std::{io, process};
+
+use crate::net::{TcpListener, UnixListener};
+use crate::{event, sys, Interest, Registry, Token};
+
+/// The first file descriptor passed by systemd, `SD_LISTEN_FDS_START`.
+const LISTEN_FDS_START: RawFd = 3;
+
+/// A listening socket passed by systemd, returned by [`listen_fds`].
+#[derive(Debug)]
+pub enum ListenFd {
+    /// A TCP (IPv4 or IPv6) listener.
+    Tcp(TcpListener),
+    /// A Unix stream socket listener.
+    Unix(UnixListener),
+}
+
+/// Returns the listening sockets passed by systemd socket activation.
+///
+/// Systemd passes the sockets starting at file descriptor 3, setting the
+/// `LISTEN_FDS` environment variable to the number of sockets and
+/// `LISTEN_PID` to the process id they're meant for. If `LISTEN_PID` is not
+/// set, or set to a different process, this returns no sockets.
+///
+/// Each socket is validated and prepared like
+/// [`TcpListener::from_inherited_fd`] and [`UnixListener::from_inherited_fd`],
+/// using the address family of the socket to determine its type. Only
+/// listening stream sockets are supported, an `InvalidInput` error is returned
+/// for any other kind of file descriptor.
+///
+/// The environment variables (including `LISTEN_FDNAMES`) are removed, so this
+/// returns the sockets only once, calling it again returns no sockets.
+///
+/// # Notes
+///
+/// This modifies the environment of the process, see [`env::remove_var`], so
+/// it's best called at the start of the program, before any threads are
+/// started.
+///
+/// # Examples
+///
+#[cfg_attr(feature = "os-poll", doc = "```")]
+#[cfg_attr(not(feature = "os-poll"), doc = "```ignore")]
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use mio::net::{listen_fds, ListenFd};
+/// use mio::{Interest, Poll, Token};
+///
+/// let poll = Poll::new()?;
+///
+/// let mut listeners = listen_fds()?;
+/// for (i, listener) in listeners.iter_mut().enumerate() {
+///     if let ListenFd::Tcp(listener) = listener {
+///         println!("listening on {}", listener.local_addr()?);
+///     }
+///     poll.registry().register(listener, Token(i), Interest::READABLE)?;
+/// }
+/// #     Ok(())
+/// # }
+/// ```
+pub fn listen_fds() -> io::Result<Vec<ListenFd>> {
+    let pid = env::var("LISTEN_PID").ok();
+    if pid.and_then(|pid| pid.parse::<u32>().ok()) != Some(process::id()) {
+        return Ok(Vec::new());
+    }
+    let n = env::var("LISTEN_FDS")
+        .ok()
+        .and_then(|n| n.parse::<RawFd>().ok())
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "invalid `LISTEN_FDS`"))?;
+    env::remove_var("LISTEN_PID");
+    env::remove_var("LISTEN_FDS");
+    env::remove_var("LISTEN_FDNAMES");
+
+    // Take ownership of all sockets first, so that all of them are closed if
+    // one of them is invalid.
+    let fds = (LISTEN_FDS_START..LISTEN_FDS_START.saturating_add(n))
+        // SAFETY: systemd passed these file descriptors to us, and we removed
+        // the environment variables so this is done only once.
+        .map(|fd| unsafe { OwnedFd::from_raw_fd(fd) })
+        .collect::<Vec<_>>();
+    fds.into_iter()
+        .map(|fd| {
+            if sys::uds::listener::is_unix_socket(fd.as_raw_fd())? {
+                UnixListener::from_inherited_fd(fd).map(ListenFd::Unix)
+            } else {
+                TcpListener::from_inherited_fd(fd).map(ListenFd::Tcp)
+            }
+        })
+        .collect()
+}
+
+impl event::Source for ListenFd {
+    fn register(
+        &mut self,
+        registry: &Registry,
+        token: Token,
+        interests: Interest,
+    ) -> io::Result<()> {
+        match self {
+            ListenFd::Tcp(listener) => listener.register(registry, token, interests),
+            ListenFd::Unix(listener) => listener.register(registry, token, interests),
+        }
+    }
+
+    fn reregister(
+        &mut self,
+        registry: &Registry,
+        token: Token,
+        interests: Interest,
+    ) -> io::Result<()> {
+        match self {
+            ListenFd::Tcp(listener) => listener.reregister(registry, token, interests),
+            ListenFd::Unix(listener) => listener.reregister(registry, token, interests),
+        }
+    }
+
+    fn deregister(&mut self, registry: &Registry) -> io::Result<()> {
+        match self {
+            ListenFd::Tcp(listener) => listener.deregister(registry),
+            ListenFd::Unix(listener) => listener.deregister(registry),
+        }
+    }
+
+    fn poll_ready(&self, interests: Interest) -> io::Result<event::Readiness> {
+        match self {
+            ListenFd::Tcp(listener) => listener.poll_ready(interests),
+            ListenFd::Unix(listener) => listener.poll_ready(interests),
+        }
+    }
+}
+
+impl AsRawFd for ListenFd {
+    fn as_raw_fd(&self) -> RawFd {
+        match self {
+            ListenFd::Tcp(listener) => listener.as_raw_fd(),
+            ListenFd::Unix(listener) => listener.as_raw_fd(),
+        }
+    }
+}
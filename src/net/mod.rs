@@ -27,13 +27,31 @@
 
 mod tcp;
 pub use self::tcp::{TcpListener, TcpStream};
+#[cfg(unix)]
+pub use self::tcp::{AcceptOptions, ConnDiagnosis, TcpOptions};
+#[cfg(target_os = "linux")]
+pub use self::tcp::{ReuseportGroup, TcpRepairState};
+
+#[cfg(target_os = "linux")]
+mod sctp;
+#[cfg(target_os = "linux")]
+pub use self::sctp::{SctpInfo, SctpSocket};
 
 #[cfg(not(target_os = "wasi"))]
 mod udp;
 #[cfg(not(target_os = "wasi"))]
 pub use self::udp::UdpSocket;
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub use self::udp::{MtuDiscover, RecvInfo, SockFilter, TimestampMode};
+#[cfg(unix)]
+pub use self::udp::TruncationPolicy;
 
 #[cfg(unix)]
 mod uds;
 #[cfg(unix)]
 pub use self::uds::{UnixDatagram, UnixListener, UnixStream};
+
+#[cfg(unix)]
+mod listen_fds;
+#[cfg(unix)]
+pub use self::listen_fds::{listen_fds, ListenFd};
@@ -0,0 +1,281 @@
+//! Primitives for working with SCTP.
+
+use std::net::SocketAddr;
+use std::os::fd::{AsFd, AsRawFd, BorrowedFd, FromRawFd, IntoRawFd, OwnedFd, RawFd};
+use std::{fmt, io};
+
+use crate::io_source::IoSource;
+use crate::{event, sys, Interest, Registry, Token};
+
+/// A non-blocking, one-to-many style SCTP socket (`SOCK_SEQPACKET`).
+///
+/// A single socket handles all associations with peers. Associations are set
+/// up implicitly: incoming associations are accepted automatically and
+/// sending to a new peer address establishes a new association. Every
+/// message is sent and received with its [`SctpInfo`], containing the stream
+/// and association of the message.
+///
+/// The socket is readable if a message is available on any of its
+/// associations. An association can be branched off into a separate socket
+/// using [`peel_off`], which can be registered with its own token. This can
+/// be used to give busy associations their own readiness events.
+///
+/// Mio doesn't subscribe to SCTP notifications, all received messages are
+/// data messages.
+///
+/// [`peel_off`]: SctpSocket::peel_off
+///
+/// # Notes
+///
+/// This is only available on Linux. If the kernel doesn't support SCTP (e.g.
+/// the `sctp` kernel module isn't loaded) [`bind`] returns an error with kind
+/// [`Unsupported`].
+///
+/// [`bind`]: SctpSocket::bind
+/// [`Unsupported`]: io::ErrorKind::Unsupported
+///
+/// # Examples
+///
+#[cfg_attr(feature = "os-poll", doc = "```")]
+#[cfg_attr(not(feature = "os-poll"), doc = "```ignore")]
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use std::io;
+///
+/// use mio::net::SctpSocket;
+/// use mio::{Interest, Poll, Token};
+///
+/// let mut socket = match SctpSocket::bind("127.0.0.1:0".parse()?) {
+///     Ok(socket) => socket,
+///     // Kernel doesn't support SCTP.
+///     Err(ref err) if err.kind() == io::ErrorKind::Unsupported => return Ok(()),
+///     Err(err) => return Err(err.into()),
+/// };
+///
+/// let poll = Poll::new()?;
+/// poll.registry().register(&mut socket, Token(0), Interest::READABLE)?;
+///
+/// // Once readable, reply to the message on the same stream.
+/// let mut buf = [0; 1500];
+/// match socket.recv_from(&mut buf) {
+///     Ok((n, _, info)) => {
+///         socket.send(&buf[..n], info)?;
+///     }
+///     Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => {}
+///     Err(err) => return Err(err.into()),
+/// }
+/// #     Ok(())
+/// # }
+/// ```
+pub struct SctpSocket {
+    inner: IoSource<OwnedFd>,
+}
+
+impl SctpSocket {
+    /// Creates an SCTP socket bound to `addr`, accepting new associations.
+    pub fn bind(addr: SocketAddr) -> io::Result<SctpSocket> {
+        sys::sctp::bind(addr).map(|socket| SctpSocket {
+            inner: IoSource::new(socket),
+        })
+    }
+
+    /// Returns the socket address this socket is bound to.
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        sys::sctp::local_addr(&self.inner)
+    }
+
+    /// Sends a message to `addr`, on the stream and with the payload protocol
+    /// identifier of `info`. The association of `info` is ignored, if there
+    /// is no association with `addr` yet one is established.
+    ///
+    /// On success, returns the number of bytes written.
+    pub fn send_to(&self, buf: &[u8], addr: SocketAddr, info: SctpInfo) -> io::Result<usize> {
+        self.inner
+            .do_io(|inner| sys::sctp::send_to(inner, buf, Some(addr), &info))
+    }
+
+    /// Sends a message on the association of `info`, e.g. to reply to a
+    /// message received using [`recv_from`].
+    ///
+    /// On success, returns the number of bytes written.
+    ///
+    /// [`recv_from`]: SctpSocket::recv_from
+    pub fn send(&self, buf: &[u8], info: SctpInfo) -> io::Result<usize> {
+        self.inner
+            .do_io(|inner| sys::sctp::send_to(inner, buf, None, &info))
+    }
+
+    /// Receives a single message, returning the number of bytes read, the
+    /// address of the peer and the stream and association of the message.
+    ///
+    /// If `buf` is too small to hold the entire message the remainder is
+    /// returned by the next call(s).
+    pub fn recv_from(&self, buf: &mut [u8]) -> io::Result<(usize, SocketAddr, SctpInfo)> {
+        self.inner.do_io(|inner| sys::sctp::recv_from(inner, buf))
+    }
+
+    /// Branches off the association `assoc_id` into a new socket
+    /// (`SCTP_SOCKOPT_PEELOFF_FLAGS`).
+    ///
+    /// Messages for the association are no longer received by this socket,
+    /// only by the returned socket, which can be registered separately.
+    pub fn peel_off(&self, assoc_id: i32) -> io::Result<SctpSocket> {
+        sys::sctp::peel_off(&self.inner, assoc_id).map(|socket| SctpSocket {
+            inner: IoSource::new(socket),
+        })
+    }
+
+    /// Get the value of the `SO_ERROR` option on this socket.
+    ///
+    /// This will retrieve the stored error in the underlying socket, clearing
+    /// the field in the process. This can be useful for checking errors between
+    /// calls.
+    pub fn take_error(&self) -> io::Result<Option<io::Error>> {
+        sys::sctp::take_error(&self.inner)
+    }
+
+    /// Execute an I/O operation ensuring that the socket receives more events
+    /// if it hits a [`WouldBlock`] error.
+    ///
+    /// See [`UdpSocket::try_io`] for more information.
+    ///
+    /// [`WouldBlock`]: io::ErrorKind::WouldBlock
+    /// [`UdpSocket::try_io`]: crate::net::UdpSocket::try_io
+    pub fn try_io<F, T>(&self, f: F) -> io::Result<T>
+    where
+        F: FnOnce() -> io::Result<T>,
+    {
+        self.inner.do_io(|_| f())
+    }
+}
+
+/// Stream and association of an SCTP message, see [`SctpSocket`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct SctpInfo {
+    pub(crate) stream: u16,
+    pub(crate) ppid: u32,
+    pub(crate) assoc_id: i32,
+}
+
+impl SctpInfo {
+    /// Create a new `SctpInfo` to send a message on `stream`, with payload
+    /// protocol identifier `ppid`.
+    ///
+    /// The payload protocol identifier is passed to the peer unchanged, by
+    /// convention it's in network byte order.
+    pub const fn new(stream: u16, ppid: u32) -> SctpInfo {
+        SctpInfo {
+            stream,
+            ppid,
+            assoc_id: 0,
+        }
+    }
+
+    /// Returns the stream number.
+    pub const fn stream(&self) -> u16 {
+        self.stream
+    }
+
+    /// Returns the payload protocol identifier.
+    pub const fn ppid(&self) -> u32 {
+        self.ppid
+    }
+
+    /// Returns the association identifier, 0 if the message isn't (yet)
+    /// associated, i.e. for an `SctpInfo` created using [`SctpInfo::new`].
+    pub const fn assoc_id(&self) -> i32 {
+        self.assoc_id
+    }
+
+    /// Returns the same `SctpInfo` for association `assoc_id`, see
+    /// [`SctpSocket::send`].
+    pub const fn with_assoc_id(self, assoc_id: i32) -> SctpInfo {
+        SctpInfo { assoc_id, ..self }
+    }
+}
+
+impl event::Source for SctpSocket {
+    fn register(
+        &mut self,
+        registry: &Registry,
+        token: Token,
+        interests: Interest,
+    ) -> io::Result<()> {
+        self.inner.register(registry, token, interests)
+    }
+
+    fn reregister(
+        &mut self,
+        registry: &Registry,
+        token: Token,
+        interests: Interest,
+    ) -> io::Result<()> {
+        self.inner.reregister(registry, token, interests)
+    }
+
+    fn deregister(&mut self, registry: &Registry) -> io::Result<()> {
+        self.inner.deregister(registry)
+    }
+
+    fn poll_ready(&self, interests: Interest) -> io::Result<event::Readiness> {
+        self.inner.poll_ready(interests)
+    }
+}
+
+impl fmt::Debug for SctpSocket {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.inner.fmt(f)
+    }
+}
+
+impl IntoRawFd for SctpSocket {
+    fn into_raw_fd(self) -> RawFd {
+        self.inner.into_inner().into_raw_fd()
+    }
+}
+
+impl AsRawFd for SctpSocket {
+    fn as_raw_fd(&self) -> RawFd {
+        self.inner.as_raw_fd()
+    }
+}
+
+impl FromRawFd for SctpSocket {
+    /// Converts a `RawFd` to a `SctpSocket`.
+    ///
+    /// # Notes
+    ///
+    /// The caller is responsible for ensuring that the socket is in
+    /// non-blocking mode.
+    unsafe fn from_raw_fd(fd: RawFd) -> SctpSocket {
+        SctpSocket {
+            inner: IoSource::new(OwnedFd::from_raw_fd(fd)),
+        }
+    }
+}
+
+impl From<SctpSocket> for OwnedFd {
+    fn from(socket: SctpSocket) -> Self {
+        socket.inner.into_inner()
+    }
+}
+
+impl AsFd for SctpSocket {
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        self.inner.as_fd()
+    }
+}
+
+impl From<OwnedFd> for SctpSocket {
+    /// Converts an `OwnedFd` to a `SctpSocket`.
+    ///
+    /// # Notes
+    ///
+    /// The caller is responsible for ensuring that the socket is in
+    /// non-blocking mode.
+    fn from(fd: OwnedFd) -> Self {
+        SctpSocket {
+            inner: IoSource::new(fd),
+        }
+    }
+}
@@ -1,3 +1,4 @@
+use std::mem::MaybeUninit;
 use std::net::{self, SocketAddr};
 #[cfg(any(unix, target_os = "wasi"))]
 use std::os::fd::{AsFd, AsRawFd, BorrowedFd, FromRawFd, IntoRawFd, OwnedFd, RawFd};
@@ -9,6 +10,8 @@ use std::os::hermit::io::{AsFd, AsRawFd, BorrowedFd, FromRawFd, IntoRawFd, Owned
 use std::os::windows::io::{
     AsRawSocket, AsSocket, BorrowedSocket, FromRawSocket, IntoRawSocket, OwnedSocket, RawSocket,
 };
+#[cfg(any(target_os = "android", target_os = "linux"))]
+use std::time::Duration;
 use std::{fmt, io};
 
 use crate::io_source::IoSource;
@@ -59,8 +62,36 @@ impl TcpListener {
     /// 2. Set the `SO_REUSEADDR` option on the socket on Unix.
     /// 3. Bind the socket to the specified address.
     /// 4. Calls `listen` on the socket to prepare it to receive new connections.
+    ///
+    /// If the port of `addr` is 0 the OS assigns a port in step 3, so
+    /// [`local_addr`] returns the assigned port as soon as this returns.
+    ///
+    /// The listener is created with a backlog of 1024, use
+    /// [`bind_with_backlog`] to use a different value.
+    ///
+    /// [`local_addr`]: TcpListener::local_addr
+    /// [`bind_with_backlog`]: TcpListener::bind_with_backlog
     #[cfg(not(target_os = "wasi"))]
     pub fn bind(addr: SocketAddr) -> io::Result<TcpListener> {
+        TcpListener::bind_with_backlog(addr, 1024)
+    }
+
+    /// Same as [`bind`], but calls `listen` with `backlog`, the maximum number
+    /// of connections waiting to be accepted.
+    ///
+    /// A negative `backlog` uses the maximum value allowed by the platform.
+    ///
+    /// [`bind`]: TcpListener::bind
+    ///
+    /// # Notes
+    ///
+    /// The OS silently limits `backlog` to its maximum, `SOMAXCONN`. On Linux
+    /// this is the `net.core.somaxconn` sysctl (4096 by default since Linux
+    /// 5.4), on macOS and the BSDs the `kern.ipc.somaxconn` sysctl. The limit
+    /// is applied when `listen` is called, so raising it afterwards doesn't
+    /// affect existing listeners.
+    #[cfg(not(target_os = "wasi"))]
+    pub fn bind_with_backlog(addr: SocketAddr, backlog: i32) -> io::Result<TcpListener> {
         let socket = new_for_addr(addr)?;
         #[cfg(any(unix, target_os = "hermit"))]
         let listener = unsafe { TcpListener::from_raw_fd(socket) };
@@ -77,11 +108,156 @@ impl TcpListener {
         #[cfg(not(windows))]
         set_reuseaddr(&listener.inner, true)?;
 
+        bind(&listener.inner, addr)?;
+        // Larger values are limited to `SOMAXCONN`.
+        listen(&listener.inner, backlog.try_into().unwrap_or(u32::MAX))?;
+        Ok(listener)
+    }
+
+    /// Same as [`bind`], but adds the listener to a load balancing group of
+    /// listeners bound to the same `addr`.
+    ///
+    /// This allows multiple listeners (e.g. one per thread) to be bound to the
+    /// same address, with the kernel distributing the incoming connections
+    /// across all listeners in the group. All listeners in such a group must
+    /// have been created using this function.
+    ///
+    /// [`bind`]: TcpListener::bind
+    ///
+    /// # Notes
+    ///
+    /// On FreeBSD this sets the `SO_REUSEPORT_LB` option before binding. Plain
+    /// `SO_REUSEPORT` on FreeBSD (and the other BSDs) only allows binding
+    /// multiple sockets to the same address, without load balancing.
+    ///
+    /// On Linux this sets the `SO_REUSEPORT` option, which already load
+    /// balances connections by hashing the four-tuple of the connection
+    /// (source and destination address and port). Adding or removing a
+    /// listener from the group changes the hashing, and connections that are
+    /// still queued on a listener that is closed are reset. Linux only allows
+    /// sockets created by the same effective user id to join a group.
+    ///
+    /// On all other platforms this returns an error of kind
+    /// [`io::ErrorKind::Unsupported`].
+    ///
+    /// # Examples
+    ///
+    #[cfg_attr(feature = "os-poll", doc = "```")]
+    #[cfg_attr(not(feature = "os-poll"), doc = "```ignore")]
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use std::io;
+    ///
+    /// use mio::net::TcpListener;
+    ///
+    /// let listener1 = match TcpListener::bind_reuseport_lb("127.0.0.1:0".parse()?) {
+    ///     Ok(listener) => listener,
+    ///     // Not supported on this platform.
+    ///     Err(err) if err.kind() == io::ErrorKind::Unsupported => return Ok(()),
+    ///     Err(err) => return Err(err.into()),
+    /// };
+    /// // Bind a second listener to the same address, e.g. for another thread.
+    /// let listener2 = TcpListener::bind_reuseport_lb(listener1.local_addr()?)?;
+    /// assert_eq!(listener1.local_addr()?, listener2.local_addr()?);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    #[cfg(not(target_os = "wasi"))]
+    pub fn bind_reuseport_lb(addr: SocketAddr) -> io::Result<TcpListener> {
+        let socket = new_for_addr(addr)?;
+        #[cfg(any(unix, target_os = "hermit"))]
+        let listener = unsafe { TcpListener::from_raw_fd(socket) };
+        #[cfg(windows)]
+        let listener = unsafe { TcpListener::from_raw_socket(socket as _) };
+
+        // See `bind`.
+        #[cfg(not(windows))]
+        set_reuseaddr(&listener.inner, true)?;
+        // Must be set before binding, otherwise the kernel won't add the
+        // socket to the group of sockets bound to `addr`.
+        sys::tcp::set_reuseport_lb(&listener.inner)?;
+
         bind(&listener.inner, addr)?;
         listen(&listener.inner, 1024)?;
         Ok(listener)
     }
 
+    /// Same as [`bind`], but creates the socket using `protocol`, rather than
+    /// letting the OS pick the default protocol for a stream socket, i.e. TCP.
+    ///
+    /// This can be used to create listeners for other stream based protocols,
+    /// such as Multipath TCP (`IPPROTO_MPTCP` on Linux). The `protocol` is
+    /// passed as is to `socket(2)`, it's up to the caller to ensure the
+    /// protocol behaves like TCP, e.g. connections can be accepted on the
+    /// listener.
+    ///
+    /// [`bind`]: TcpListener::bind
+    #[cfg(unix)]
+    pub fn bind_with_protocol(addr: SocketAddr, protocol: i32) -> io::Result<TcpListener> {
+        let socket = sys::tcp::new_for_addr_with_protocol(addr, protocol)?;
+        let listener = unsafe { TcpListener::from_raw_fd(socket) };
+        set_reuseaddr(&listener.inner, true)?;
+        bind(&listener.inner, addr)?;
+        listen(&listener.inner, 1024)?;
+        Ok(listener)
+    }
+
+    /// Creates a new `TcpListener` from a listening socket inherited from the
+    /// parent process.
+    ///
+    /// This is intended for daemons that bind privileged ports before
+    /// dropping privileges by executing a new process, and for socket
+    /// activation, e.g. by systemd. The parent must clear `FD_CLOEXEC` on the
+    /// socket for it to be inherited. This function validates that `fd` is a
+    /// listening TCP (IPv4 or IPv6) socket, returning an `InvalidInput` error
+    /// if it's not, sets it in non-blocking mode and sets `FD_CLOEXEC` again,
+    /// so that it's not leaked to further child processes. After this the
+    /// listener can be registered with a new `Poll` instance like any other.
+    ///
+    /// On error `fd` is closed.
+    ///
+    /// # Systemd socket activation
+    ///
+    /// Systemd passes the sockets starting at file descriptor 3
+    /// (`SD_LISTEN_FDS_START`), setting the `LISTEN_FDS` environment variable
+    /// to the number of sockets and `LISTEN_PID` to the process id they're
+    /// meant for.
+    ///
+    /// # Examples
+    ///
+    #[cfg_attr(feature = "os-poll", doc = "```")]
+    #[cfg_attr(not(feature = "os-poll"), doc = "```ignore")]
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use std::env;
+    /// use std::os::fd::{FromRawFd, OwnedFd, RawFd};
+    /// use std::process;
+    ///
+    /// use mio::net::TcpListener;
+    ///
+    /// const SD_LISTEN_FDS_START: RawFd = 3;
+    ///
+    /// let mut listeners = Vec::new();
+    /// let pid = env::var("LISTEN_PID").ok().and_then(|pid| pid.parse::<u32>().ok());
+    /// if pid == Some(process::id()) {
+    ///     let n: RawFd = env::var("LISTEN_FDS")?.parse()?;
+    ///     for fd in SD_LISTEN_FDS_START..SD_LISTEN_FDS_START + n {
+    ///         // SAFETY: systemd passed these file descriptors to us.
+    ///         let fd = unsafe { OwnedFd::from_raw_fd(fd) };
+    ///         listeners.push(TcpListener::from_inherited_fd(fd)?);
+    ///     }
+    /// }
+    /// // Register the `listeners` with `Poll`.
+    /// #     Ok(())
+    /// # }
+    /// ```
+    #[cfg(unix)]
+    pub fn from_inherited_fd(fd: OwnedFd) -> io::Result<TcpListener> {
+        let listener = net::TcpListener::from(fd);
+        sys::tcp::prepare_inherited(&listener)?;
+        Ok(TcpListener::from_std(listener))
+    }
+
     /// Creates a new `TcpListener` from a standard `net::TcpListener`.
     ///
     /// This function is intended to be used to wrap a TCP listener from the
@@ -108,6 +284,131 @@ impl TcpListener {
         })
     }
 
+    /// Accepts multiple new `TcpStream`s, writing them into `out`.
+    ///
+    /// This calls [`accept`] until `out` is full or it returns a `WouldBlock`
+    /// error, returning the number of streams accepted. Only the first `n`
+    /// elements of `out` are initialised, where `n` is the returned value. The
+    /// caller is responsible for dropping those (e.g. using
+    /// [`MaybeUninit::assume_init_read`]), otherwise the streams are leaked.
+    ///
+    /// Using a caller provided buffer means no allocation is required per
+    /// call, this buffer can be reused across calls (after taking the
+    /// accepted streams out of it).
+    ///
+    /// If no streams could be accepted the error returned by `accept` is
+    /// returned, e.g. a `WouldBlock` error. If an error occurs after one or
+    /// more streams were accepted the number of accepted streams is returned
+    /// instead and the error is dropped, so the accepted streams aren't lost.
+    /// For `WouldBlock` this simply means all pending connections were
+    /// accepted. Errors that persist, such as running out of file descriptors
+    /// (`EMFILE`), are returned again by the next call, but errors for a
+    /// single connection (e.g. `ECONNABORTED`) are not. If `out` is empty this
+    /// returns `Ok(0)` without calling `accept`.
+    ///
+    /// [`accept`]: TcpListener::accept
+    ///
+    /// # Examples
+    ///
+    #[cfg_attr(feature = "os-poll", doc = "```")]
+    #[cfg_attr(not(feature = "os-poll"), doc = "```ignore")]
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use std::io;
+    /// use std::mem::MaybeUninit;
+    /// use std::net::SocketAddr;
+    ///
+    /// use mio::net::{TcpListener, TcpStream};
+    ///
+    /// let listener = TcpListener::bind("127.0.0.1:0".parse()?)?;
+    ///
+    /// let mut out: [MaybeUninit<(TcpStream, SocketAddr)>; 16] =
+    ///     std::array::from_fn(|_| MaybeUninit::uninit());
+    /// match listener.accept_many(&mut out) {
+    ///     Ok(n) => {
+    ///         for accepted in &mut out[..n] {
+    ///             // SAFETY: `accept_many` initialised the first `n` elements.
+    ///             let (stream, address) = unsafe { accepted.assume_init_read() };
+    ///             // Register the stream with `Poll`.
+    ///             # drop((stream, address));
+    ///         }
+    ///     }
+    ///     // No more connections to accept, wait for the next event.
+    ///     Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => {}
+    ///     Err(err) => return Err(err.into()),
+    /// }
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn accept_many(
+        &self,
+        out: &mut [MaybeUninit<(TcpStream, SocketAddr)>],
+    ) -> io::Result<usize> {
+        let mut n = 0;
+        while n < out.len() {
+            match self.accept() {
+                Ok(accepted) => {
+                    let _ = out[n].write(accepted);
+                    n += 1;
+                }
+                Err(err) if n == 0 => return Err(err),
+                // No more pending connections.
+                Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => break,
+                // Returning the error would lose the accepted streams, see
+                // the docs above.
+                Err(_) => break,
+            }
+        }
+        Ok(n)
+    }
+
+    /// Accepts a new `TcpStream`, applying the socket options in `opts` to it.
+    ///
+    /// This is the same as [`accept`], but the returned stream is fully
+    /// configured, which centralises the setup of every connection. If
+    /// setting any of the options fails the accepted stream is closed and the
+    /// error is returned, the connection is not returned half configured.
+    ///
+    /// [`accept`]: TcpListener::accept
+    ///
+    /// # Notes
+    ///
+    /// This is only available on Unix.
+    ///
+    /// # Examples
+    ///
+    #[cfg_attr(feature = "os-poll", doc = "```")]
+    #[cfg_attr(not(feature = "os-poll"), doc = "```ignore")]
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use std::io;
+    ///
+    /// use mio::net::{AcceptOptions, TcpListener};
+    ///
+    /// let listener = TcpListener::bind("127.0.0.1:0".parse()?)?;
+    /// let opts = AcceptOptions::new().nodelay(true).keepalive(true);
+    ///
+    /// match listener.accept_with_opts(&opts) {
+    ///     Ok((stream, address)) => {
+    ///         // Register the stream with `Poll`.
+    ///         # drop((stream, address));
+    ///     }
+    ///     // No more connections to accept, wait for the next event.
+    ///     Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => {}
+    ///     Err(err) => return Err(err.into()),
+    /// }
+    /// #     Ok(())
+    /// # }
+    /// ```
+    #[cfg(unix)]
+    pub fn accept_with_opts(&self, opts: &AcceptOptions) -> io::Result<(TcpStream, SocketAddr)> {
+        self.inner.do_io(|inner| {
+            let (stream, addr) = sys::tcp::accept(inner)?;
+            opts.apply(&stream)?;
+            Ok((TcpStream::from_std(stream), addr))
+        })
+    }
+
     /// Returns the local socket address of this listener.
     pub fn local_addr(&self) -> io::Result<SocketAddr> {
         self.inner.local_addr()
@@ -130,6 +431,142 @@ impl TcpListener {
         self.inner.ttl()
     }
 
+    /// Sets the value for the `IPV6_UNICAST_HOPS` option on this socket.
+    ///
+    /// This is the IPv6 equivalent of [`set_ttl`], it sets the hop limit field
+    /// that is used in every unicast packet sent from this socket. This only
+    /// works on IPv6 sockets, for IPv4 sockets use `set_ttl`.
+    ///
+    /// [`set_ttl`]: TcpListener::set_ttl
+    #[cfg(unix)]
+    pub fn set_hop_limit(&self, hops: u32) -> io::Result<()> {
+        sys::tcp::set_hop_limit(self.inner.as_raw_fd(), hops)
+    }
+
+    /// Gets the value of the `IPV6_UNICAST_HOPS` option for this socket, see
+    /// [`set_hop_limit`].
+    ///
+    /// [`set_hop_limit`]: TcpListener::set_hop_limit
+    #[cfg(unix)]
+    pub fn hop_limit(&self) -> io::Result<u32> {
+        sys::tcp::hop_limit(self.inner.as_raw_fd())
+    }
+
+    /// Sets the value for the `TCP_DEFER_ACCEPT` option on this socket.
+    ///
+    /// When set the listener will not become readable, i.e. the connection
+    /// won't be returned by [`accept`], until the client sent its first bytes
+    /// of data. The `timeout` is the maximum amount of time to wait for this
+    /// data, it's rounded up to whole seconds. Passing `None` disables the
+    /// option.
+    ///
+    /// Note that once the timeout expires Linux will still complete the
+    /// connection, it's *not* dropped. Unlike other socket options this can't
+    /// be inspected at a later time as the kernel converts the timeout into a
+    /// number of SYN-ACK retransmits.
+    ///
+    /// # Notes
+    ///
+    /// This is only available on Linux and Android. FreeBSD and NetBSD offer a
+    /// similar feature using the `SO_ACCEPTFILTER` socket option with the
+    /// `dataready` accept filter, which requires a kernel module and can be set
+    /// using the raw file descriptor. macOS and OpenBSD offer no equivalent.
+    ///
+    /// [`accept`]: TcpListener::accept
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn set_defer_accept(&self, timeout: Option<Duration>) -> io::Result<()> {
+        sys::tcp::set_defer_accept(&self.inner, timeout)
+    }
+
+    /// Sets the value of the `IP_TRANSPARENT` option on this socket.
+    ///
+    /// This allows the socket to bind to, and accept connections for, non-local addresses, as
+    /// used by transparent proxies in combination with the `TPROXY` iptables
+    /// target. The option applies to both IPv4 and IPv6 sockets.
+    ///
+    /// Setting this option requires the `CAP_NET_ADMIN` capability, without it
+    /// an error with kind [`PermissionDenied`] is returned.
+    ///
+    /// [`PermissionDenied`]: io::ErrorKind::PermissionDenied
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn set_ip_transparent(&self, on: bool) -> io::Result<()> {
+        sys::tcp::set_ip_transparent(&self.inner, on)
+    }
+
+    /// Sets the value of the `SO_INCOMING_CPU` option on this socket.
+    ///
+    /// For a group of listeners bound using [`bind_reuseport_lb`] the kernel
+    /// prefers the listener whose CPU matches the CPU that handles the
+    /// incoming connection (the CPU processing the network packets). Combined
+    /// with one accepting thread per CPU, pinned to that CPU, this keeps the
+    /// processing of a connection local to a single CPU (and NUMA node). See
+    /// [`ReuseportGroup`] for a helper to set this up.
+    ///
+    /// [`bind_reuseport_lb`]: TcpListener::bind_reuseport_lb
+    /// [`ReuseportGroup`]: crate::net::ReuseportGroup
+    ///
+    /// This is only available on Linux.
+    #[cfg(target_os = "linux")]
+    pub fn set_incoming_cpu(&self, cpu: usize) -> io::Result<()> {
+        sys::tcp::set_incoming_cpu(&self.inner, cpu)
+    }
+
+    /// Gets the value of the `SO_INCOMING_CPU` option on this socket, `None`
+    /// if it's not set, see [`set_incoming_cpu`].
+    ///
+    /// [`set_incoming_cpu`]: TcpListener::set_incoming_cpu
+    #[cfg(target_os = "linux")]
+    pub fn incoming_cpu(&self) -> io::Result<Option<usize>> {
+        sys::tcp::incoming_cpu(&self.inner)
+    }
+
+    /// Sets the value of the `TCP_NODELAY` option on this socket, which is
+    /// inherited by all streams returned by [`accept`].
+    ///
+    /// The option is set by the kernel as part of accepting the connection, so
+    /// it's already applied before the stream is returned. This means the
+    /// first write on an accepted stream isn't delayed by Nagle's algorithm,
+    /// without the cost of calling [`TcpStream::set_nodelay`] for every
+    /// stream.
+    ///
+    /// This is only available on Linux and Android, other OSs don't reliably
+    /// copy the option to accepted sockets.
+    ///
+    /// [`accept`]: TcpListener::accept
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn set_nodelay_on_accept(&self, nodelay: bool) -> io::Result<()> {
+        sys::tcp::set_nodelay(&self.inner, nodelay)
+    }
+
+    /// Gets the value of the `TCP_NODELAY` option on this socket, see
+    /// [`set_nodelay_on_accept`].
+    ///
+    /// [`set_nodelay_on_accept`]: TcpListener::set_nodelay_on_accept
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn nodelay_on_accept(&self) -> io::Result<bool> {
+        sys::tcp::nodelay(&self.inner)
+    }
+
+    /// Sets or clears the close-on-exec flag (`FD_CLOEXEC`) of the socket.
+    ///
+    /// Mio creates all sockets with close-on-exec set, clearing it allows a
+    /// child process to inherit the socket. Note that any child process
+    /// started while the flag is cleared, e.g. by another thread, inherits the
+    /// socket as well.
+    #[cfg(unix)]
+    pub fn set_cloexec(&self, cloexec: bool) -> io::Result<()> {
+        sys::set_cloexec(self.inner.as_raw_fd(), cloexec)
+    }
+
+    /// Returns whether or not the close-on-exec flag is set, see
+    /// [`set_cloexec`].
+    ///
+    /// [`set_cloexec`]: TcpListener::set_cloexec
+    #[cfg(unix)]
+    pub fn cloexec(&self) -> io::Result<bool> {
+        sys::is_cloexec(self.inner.as_raw_fd())
+    }
+
     /// Get the value of the `SO_ERROR` option on this socket.
     ///
     /// This will retrieve the stored error in the underlying socket, clearing
@@ -140,6 +577,91 @@ impl TcpListener {
     }
 }
 
+/// Socket options applied to accepted streams, see
+/// [`TcpListener::accept_with_opts`].
+///
+/// Options that are not set are left alone, i.e. they keep the value the
+/// stream inherits from the listener or the OS default. Accepted streams are
+/// always in non-blocking mode, like streams returned by
+/// [`TcpListener::accept`]. To set options on an existing stream see
+/// [`TcpStream::configure`].
+#[cfg(unix)]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct AcceptOptions {
+    nodelay: Option<bool>,
+    keepalive: Option<bool>,
+    recv_buffer_size: Option<usize>,
+    send_buffer_size: Option<usize>,
+}
+
+#[cfg(unix)]
+impl AcceptOptions {
+    /// Create a new `AcceptOptions` that doesn't set any options.
+    pub const fn new() -> AcceptOptions {
+        AcceptOptions {
+            nodelay: None,
+            keepalive: None,
+            recv_buffer_size: None,
+            send_buffer_size: None,
+        }
+    }
+
+    /// Set the `TCP_NODELAY` option, see [`TcpStream::set_nodelay`].
+    pub const fn nodelay(self, nodelay: bool) -> AcceptOptions {
+        AcceptOptions {
+            nodelay: Some(nodelay),
+            ..self
+        }
+    }
+
+    /// Set the `SO_KEEPALIVE` option, enabling or disabling the sending of
+    /// keepalive probes.
+    pub const fn keepalive(self, keepalive: bool) -> AcceptOptions {
+        AcceptOptions {
+            keepalive: Some(keepalive),
+            ..self
+        }
+    }
+
+    /// Set the size of the receive buffer (`SO_RCVBUF`).
+    ///
+    /// The OS may adjust the size, e.g. Linux doubles it to allow space for
+    /// bookkeeping and limits it to the `net.core.rmem_max` sysctl.
+    pub const fn recv_buffer_size(self, size: usize) -> AcceptOptions {
+        AcceptOptions {
+            recv_buffer_size: Some(size),
+            ..self
+        }
+    }
+
+    /// Set the size of the send buffer (`SO_SNDBUF`).
+    ///
+    /// The OS may adjust the size, e.g. Linux doubles it to allow space for
+    /// bookkeeping and limits it to the `net.core.wmem_max` sysctl.
+    pub const fn send_buffer_size(self, size: usize) -> AcceptOptions {
+        AcceptOptions {
+            send_buffer_size: Some(size),
+            ..self
+        }
+    }
+
+    fn apply(&self, stream: &net::TcpStream) -> io::Result<()> {
+        if let Some(nodelay) = self.nodelay {
+            stream.set_nodelay(nodelay)?;
+        }
+        if let Some(keepalive) = self.keepalive {
+            sys::tcp::set_keepalive(stream, keepalive)?;
+        }
+        if let Some(size) = self.recv_buffer_size {
+            sys::tcp::set_recv_buffer_size(stream, size)?;
+        }
+        if let Some(size) = self.send_buffer_size {
+            sys::tcp::set_send_buffer_size(stream, size)?;
+        }
+        Ok(())
+    }
+}
+
 impl event::Source for TcpListener {
     fn register(
         &mut self,
@@ -162,6 +684,10 @@ impl event::Source for TcpListener {
     fn deregister(&mut self, registry: &Registry) -> io::Result<()> {
         self.inner.deregister(registry)
     }
+
+    fn poll_ready(&self, interests: Interest) -> io::Result<event::Readiness> {
+        self.inner.poll_ready(interests)
+    }
 }
 
 impl fmt::Debug for TcpListener {
@@ -1,5 +1,16 @@
 mod listener;
 pub use self::listener::TcpListener;
+#[cfg(unix)]
+pub use self::listener::AcceptOptions;
+
+#[cfg(target_os = "linux")]
+mod reuseport;
+#[cfg(target_os = "linux")]
+pub use self::reuseport::ReuseportGroup;
 
 mod stream;
 pub use self::stream::TcpStream;
+#[cfg(unix)]
+pub use self::stream::{ConnDiagnosis, TcpOptions};
+#[cfg(target_os = "linux")]
+pub use self::stream::TcpRepairState;
@@ -0,0 +1,118 @@
+use std::io;
+use std::net::SocketAddr;
+
+use crate::net::TcpListener;
+
+/// A group of listeners bound to the same address, one per CPU.
+///
+/// This sets up the common pattern for NUMA-aware servers of having one
+/// accepting thread per CPU. All listeners are bound to the same address
+/// using [`TcpListener::bind_reuseport_lb`], and each listener is assigned a
+/// CPU using [`TcpListener::set_incoming_cpu`]. The kernel then prefers the
+/// listener assigned to the CPU that handles the incoming connection, so if
+/// each listener is used by a thread pinned to its CPU the connection is
+/// processed on a single CPU (and NUMA node). Mio doesn't pin any threads,
+/// that is left to the caller, e.g. using `sched_setaffinity(2)`.
+///
+/// The listeners are not registered, use [`into_listeners`] to move each
+/// listener to its thread and register it with a [`Poll`] instance there.
+///
+/// [`into_listeners`]: ReuseportGroup::into_listeners
+/// [`Poll`]: crate::Poll
+///
+/// # Notes
+///
+/// This is only available on Linux. Not all kernels take the incoming CPU
+/// into account for listeners bound using `SO_REUSEPORT`, in which case the
+/// connections are distributed across the listeners as described in
+/// [`TcpListener::bind_reuseport_lb`].
+///
+/// # Examples
+///
+#[cfg_attr(feature = "os-poll", doc = "```")]
+#[cfg_attr(not(feature = "os-poll"), doc = "```ignore")]
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use std::thread;
+///
+/// use mio::net::ReuseportGroup;
+/// use mio::{Events, Interest, Poll, Token};
+///
+/// let cpus: Vec<usize> = (0..thread::available_parallelism()?.get()).collect();
+/// let group = ReuseportGroup::bind("127.0.0.1:0".parse()?, &cpus)?;
+///
+/// let handles: Vec<_> = group
+///     .into_listeners()
+///     .into_iter()
+///     .map(|mut listener| {
+///         thread::spawn(move || -> std::io::Result<()> {
+///             // Pin the thread to `listener.incoming_cpu()` here.
+///             let mut poll = Poll::new()?;
+///             poll.registry()
+///                 .register(&mut listener, Token(0), Interest::READABLE)?;
+///             let mut events = Events::with_capacity(128);
+///             // Run the event loop, accepting connections.
+/// #           poll.poll(&mut events, Some(std::time::Duration::ZERO))?;
+///             Ok(())
+///         })
+///     })
+///     .collect();
+/// # for handle in handles {
+/// #     handle.join().unwrap()?;
+/// # }
+/// #     Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct ReuseportGroup {
+    listeners: Vec<TcpListener>,
+}
+
+impl ReuseportGroup {
+    /// Creates a listener bound to `addr` for each CPU in `cpus`, in the same
+    /// order.
+    ///
+    /// If the port of `addr` is 0 the first listener picks a port, which is
+    /// used for all other listeners. If `cpus` is empty an error with kind
+    /// [`InvalidInput`] is returned.
+    ///
+    /// [`InvalidInput`]: io::ErrorKind::InvalidInput
+    pub fn bind(addr: SocketAddr, cpus: &[usize]) -> io::Result<ReuseportGroup> {
+        if cpus.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "reuseport group without any CPUs",
+            ));
+        }
+
+        let mut listeners = Vec::with_capacity(cpus.len());
+        let mut addr = addr;
+        for cpu in cpus {
+            let listener = TcpListener::bind_reuseport_lb(addr)?;
+            listener.set_incoming_cpu(*cpu)?;
+            addr = listener.local_addr()?;
+            listeners.push(listener);
+        }
+        Ok(ReuseportGroup { listeners })
+    }
+
+    /// Returns the socket address all listeners are bound to.
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listeners[0].local_addr()
+    }
+
+    /// Returns the listeners, in the order of the CPUs passed to [`bind`].
+    ///
+    /// [`bind`]: ReuseportGroup::bind
+    pub fn listeners(&self) -> &[TcpListener] {
+        &self.listeners
+    }
+
+    /// Returns the listeners, in the order of the CPUs passed to [`bind`], to
+    /// move each to its own thread.
+    ///
+    /// [`bind`]: ReuseportGroup::bind
+    pub fn into_listeners(self) -> Vec<TcpListener> {
+        self.listeners
+    }
+}
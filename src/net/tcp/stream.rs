@@ -1,6 +1,8 @@
 use std::fmt;
 use std::io::{self, IoSlice, IoSliceMut, Read, Write};
 use std::net::{self, Shutdown, SocketAddr};
+#[cfg(unix)]
+use std::time::Duration;
 #[cfg(any(unix, target_os = "wasi"))]
 use std::os::fd::{AsFd, AsRawFd, BorrowedFd, FromRawFd, IntoRawFd, OwnedFd, RawFd};
 // TODO: once <https://github.com/rust-lang/rust/issues/126198> is fixed this
@@ -13,8 +15,12 @@ use std::os::windows::io::{
 };
 
 use crate::io_source::IoSource;
+#[cfg(unix)]
+use crate::sys;
 #[cfg(not(target_os = "wasi"))]
 use crate::sys::tcp::{connect, new_for_addr};
+#[cfg(unix)]
+use crate::sys::tcp::new_for_addr_with_protocol;
 use crate::{event, Interest, Registry, Token};
 
 /// A non-blocking TCP stream between a local socket and a remote socket.
@@ -98,6 +104,45 @@ impl TcpStream {
         Ok(stream)
     }
 
+    /// Same as [`connect`], but sets the `TCP_NODELAY` option on the socket.
+    ///
+    /// The option is set before the connection is started, and thus before
+    /// the stream is returned. This ensures that the first write isn't delayed
+    /// by Nagle's algorithm, without having to wait for the connection to be
+    /// established to call [`set_nodelay`]. The same notes as for [`connect`]
+    /// apply.
+    ///
+    /// [`connect`]: TcpStream::connect
+    /// [`set_nodelay`]: TcpStream::set_nodelay
+    #[cfg(not(target_os = "wasi"))]
+    pub fn connect_nodelay(addr: SocketAddr) -> io::Result<TcpStream> {
+        let socket = new_for_addr(addr)?;
+        #[cfg(any(unix, target_os = "hermit"))]
+        let stream = unsafe { TcpStream::from_raw_fd(socket) };
+        #[cfg(windows)]
+        let stream = unsafe { TcpStream::from_raw_socket(socket as _) };
+        stream.inner.set_nodelay(true)?;
+        connect(&stream.inner, addr)?;
+        Ok(stream)
+    }
+
+    /// Same as [`connect`], but creates the socket using `protocol`, rather
+    /// than letting the OS pick the default protocol for a stream socket, i.e.
+    /// TCP.
+    ///
+    /// This can be used to connect using other stream based protocols, such as
+    /// Multipath TCP (`IPPROTO_MPTCP` on Linux). The `protocol` is passed as
+    /// is to `socket(2)`. The same notes as for [`connect`] apply.
+    ///
+    /// [`connect`]: TcpStream::connect
+    #[cfg(unix)]
+    pub fn connect_with_protocol(addr: SocketAddr, protocol: i32) -> io::Result<TcpStream> {
+        let socket = new_for_addr_with_protocol(addr, protocol)?;
+        let stream = unsafe { TcpStream::from_raw_fd(socket) };
+        connect(&stream.inner, addr)?;
+        Ok(stream)
+    }
+
     /// Creates a new `TcpStream` from a standard `net::TcpStream`.
     ///
     /// This function is intended to be used to wrap a TCP stream from the
@@ -196,6 +241,458 @@ impl TcpStream {
         self.inner.ttl()
     }
 
+    /// Sets the value for the `IPV6_UNICAST_HOPS` option on this socket.
+    ///
+    /// This is the IPv6 equivalent of [`set_ttl`], it sets the hop limit field
+    /// that is used in every unicast packet sent from this socket. This only
+    /// works on IPv6 sockets, for IPv4 sockets use `set_ttl`.
+    ///
+    /// [`set_ttl`]: TcpStream::set_ttl
+    #[cfg(unix)]
+    pub fn set_hop_limit(&self, hops: u32) -> io::Result<()> {
+        sys::tcp::set_hop_limit(self.inner.as_raw_fd(), hops)
+    }
+
+    /// Gets the value of the `IPV6_UNICAST_HOPS` option for this socket, see
+    /// [`set_hop_limit`].
+    ///
+    /// [`set_hop_limit`]: TcpStream::set_hop_limit
+    #[cfg(unix)]
+    pub fn hop_limit(&self) -> io::Result<u32> {
+        sys::tcp::hop_limit(self.inner.as_raw_fd())
+    }
+
+    /// Sets the value of the `TCP_USER_TIMEOUT` option on this socket.
+    ///
+    /// This is the maximum amount of time that transmitted data may remain
+    /// unacknowledged before the kernel forcibly closes the connection, with
+    /// an `ETIMEDOUT` error. This detects dead peers much sooner than the
+    /// default retransmission behaviour, which can take up to about 15
+    /// minutes. `None` (or a zero duration) restores the default behaviour.
+    /// The timeout is rounded up to whole milliseconds.
+    ///
+    /// When TCP keepalive is also enabled (`SO_KEEPALIVE`), the user timeout
+    /// overrides the keepalive probe count (`TCP_KEEPCNT`): the connection is
+    /// closed once the user timeout is exceeded while sending keepalive probes,
+    /// rather than after a number of unanswered probes. It's advised to set
+    /// the user timeout larger than `TCP_KEEPIDLE + TCP_KEEPINTVL *
+    /// TCP_KEEPCNT` to not cut the keepalive mechanism short.
+    ///
+    /// # Notes
+    ///
+    /// This is only supported on Android and Linux, on other platforms an
+    /// [`Unsupported`] error is returned.
+    ///
+    /// [`Unsupported`]: io::ErrorKind::Unsupported
+    #[cfg(unix)]
+    pub fn set_user_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        sys::tcp::set_user_timeout(&self.inner, timeout)
+    }
+
+    /// Gets the value of the `TCP_USER_TIMEOUT` option on this socket, see
+    /// [`set_user_timeout`].
+    ///
+    /// [`set_user_timeout`]: TcpStream::set_user_timeout
+    #[cfg(unix)]
+    pub fn user_timeout(&self) -> io::Result<Option<Duration>> {
+        sys::tcp::user_timeout(&self.inner)
+    }
+
+    /// Sets the value of the `SO_PRIORITY` option on this socket.
+    ///
+    /// This sets the priority of all packets sent on this socket, which the
+    /// Linux queueing disciplines (qdisc) use to schedule packets locally.
+    /// Unlike the DSCP/TOS field (`IP_TOS`) this doesn't affect the packets
+    /// on the wire. Values outside of 0 to 6 require the `CAP_NET_ADMIN`
+    /// capability, without it an error with kind [`PermissionDenied`] is
+    /// returned.
+    ///
+    /// [`PermissionDenied`]: io::ErrorKind::PermissionDenied
+    ///
+    /// This is only available on Linux and Android.
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn set_priority(&self, priority: u32) -> io::Result<()> {
+        sys::tcp::set_priority(&self.inner, priority)
+    }
+
+    /// Gets the value of the `SO_PRIORITY` option on this socket, see
+    /// [`set_priority`].
+    ///
+    /// [`set_priority`]: TcpStream::set_priority
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn priority(&self) -> io::Result<u32> {
+        sys::tcp::priority(&self.inner)
+    }
+
+    /// Sets the congestion control algorithm used for this socket, using the
+    /// `TCP_CONGESTION` option, e.g. `"cubic"` or `"bbr"`.
+    ///
+    /// If the algorithm is unknown to the kernel (e.g. its module isn't loaded)
+    /// the kernel's error (`ENOENT`) is returned. Algorithms not listed in
+    /// `net.ipv4.tcp_allowed_congestion_control` require the `CAP_NET_ADMIN`
+    /// capability, without it an error with kind [`PermissionDenied`] is
+    /// returned.
+    ///
+    /// [`PermissionDenied`]: io::ErrorKind::PermissionDenied
+    ///
+    /// This is only available on Linux and Android.
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn set_congestion(&self, algo: &str) -> io::Result<()> {
+        sys::tcp::set_congestion(&self.inner, algo)
+    }
+
+    /// Gets the congestion control algorithm used for this socket, see
+    /// [`set_congestion`].
+    ///
+    /// [`set_congestion`]: TcpStream::set_congestion
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn congestion(&self) -> io::Result<String> {
+        sys::tcp::congestion(&self.inner)
+    }
+
+    /// Returns the original destination address of a connection redirected
+    /// by netfilter, e.g. using the iptables `REDIRECT` target.
+    ///
+    /// This reads the `SO_ORIGINAL_DST` option (`IP6T_SO_ORIGINAL_DST` for
+    /// IPv6 sockets) and should be called on a stream returned by
+    /// [`TcpListener::accept`], as required by transparent proxies. For
+    /// connections that were not redirected (and not tracked by netfilter)
+    /// the kernel returns an error, usually `ENOENT`, or `ENOPROTOOPT` if
+    /// netfilter's connection tracking is not available.
+    ///
+    /// [`TcpListener::accept`]: crate::net::TcpListener::accept
+    ///
+    /// This is only available on Linux and Android.
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn original_dst(&self) -> io::Result<SocketAddr> {
+        sys::tcp::original_dst(&self.inner)
+    }
+
+    /// Saves the state of the connection, to restore it in another process
+    /// using [`repair_restore`].
+    ///
+    /// This puts the socket in repair mode (`TCP_REPAIR`) and reads the
+    /// sequence numbers, the contents of the send and receive queues and the
+    /// options negotiated with the peer. The socket is left in repair mode, in
+    /// which dropping it closes the socket without notifying the peer (no
+    /// `FIN` or `RST` is sent), so it can be restored elsewhere. After this
+    /// returns successfully the stream shouldn't be used for I/O anymore. If
+    /// this fails the socket leaves repair mode again.
+    ///
+    /// Repair mode requires the `CAP_NET_ADMIN` capability, without it an
+    /// error with kind [`PermissionDenied`] is returned.
+    ///
+    /// [`repair_restore`]: TcpStream::repair_restore
+    /// [`PermissionDenied`]: io::ErrorKind::PermissionDenied
+    ///
+    /// This is only available on Linux.
+    #[cfg(target_os = "linux")]
+    pub fn repair_save(&self) -> io::Result<TcpRepairState> {
+        sys::tcp::repair_save(&self.inner)
+    }
+
+    /// Restores a connection saved using [`repair_save`].
+    ///
+    /// This creates a new socket in repair mode, binds it to the saved local
+    /// address and puts it in the established state without any packets
+    /// being exchanged. Next it restores the negotiated options and the send
+    /// and receive queues, and leaves repair mode. At that point the kernel
+    /// sends a window probe to the peer, after which the connection continues
+    /// as normal. The original socket must be closed before calling this, as
+    /// they share the local address.
+    ///
+    /// Like [`repair_save`] this requires the `CAP_NET_ADMIN` capability.
+    ///
+    /// [`repair_save`]: TcpStream::repair_save
+    ///
+    /// This is only available on Linux.
+    #[cfg(target_os = "linux")]
+    pub fn repair_restore(state: &TcpRepairState) -> io::Result<TcpStream> {
+        sys::tcp::repair_restore(state).map(TcpStream::from_std)
+    }
+
+    /// Returns the number of bytes in the kernel's receive buffer, i.e.
+    /// received but not yet read.
+    ///
+    /// This uses the `FIONREAD` ioctl (`SIOCINQ` on Linux).
+    ///
+    /// This is only available on Unix.
+    #[cfg(unix)]
+    pub fn recv_queue_len(&self) -> io::Result<usize> {
+        sys::tcp::recv_queue_len(&self.inner)
+    }
+
+    /// Returns the number of bytes in the kernel's send buffer, i.e. written
+    /// but not yet sent or not yet acknowledged by the peer.
+    ///
+    /// A growing send queue indicates a slow consumer (or a slow network),
+    /// which can be used to apply backpressure before the buffer fills up and
+    /// writes return `WouldBlock` errors.
+    ///
+    /// This uses the `SIOCOUTQ` ioctl on Linux and Android, the `FIONWRITE`
+    /// ioctl on FreeBSD and NetBSD, and the `SO_NWRITE` option on Apple
+    /// platforms. On other platforms this returns an error of kind
+    /// [`io::ErrorKind::Unsupported`].
+    ///
+    /// This is only available on Unix.
+    #[cfg(unix)]
+    pub fn send_queue_len(&self) -> io::Result<usize> {
+        sys::tcp::send_queue_len(&self.inner)
+    }
+
+    /// Sets the value of the `SO_RCVLOWAT` option on this socket.
+    ///
+    /// This is the minimum number of bytes that must be buffered before the
+    /// socket is considered readable, i.e. before a readable event is returned
+    /// and before `read` returns data (without it returning `WouldBlock`).
+    /// This can be used to avoid waking up for small partial reads, trading
+    /// latency for throughput. The default is 1 byte.
+    ///
+    /// # Notes
+    ///
+    /// If `bytes` is larger than the receive buffer (`SO_RCVBUF`) the socket
+    /// might never be reported as readable, as the data to reach the watermark
+    /// can't be buffered. Linux increases the receive buffer to fit the
+    /// watermark (if allowed), but not all OSs do this.
+    ///
+    /// End of file and errors are still reported as readable, even if fewer
+    /// bytes are buffered.
+    #[cfg(unix)]
+    pub fn set_recv_low_watermark(&self, bytes: usize) -> io::Result<()> {
+        sys::tcp::set_recv_low_watermark(&self.inner, bytes)
+    }
+
+    /// Gets the value of the `SO_RCVLOWAT` option on this socket, see
+    /// [`set_recv_low_watermark`].
+    ///
+    /// [`set_recv_low_watermark`]: TcpStream::set_recv_low_watermark
+    #[cfg(unix)]
+    pub fn recv_low_watermark(&self) -> io::Result<usize> {
+        sys::tcp::recv_low_watermark(&self.inner)
+    }
+
+    /// Sets the value of the `SO_SNDLOWAT` option on this socket.
+    ///
+    /// On the BSDs (including macOS) the socket is only reported as writable
+    /// once at least `bytes` of space is available in the send buffer. This
+    /// can be used to avoid waking up to write only a small amount of data.
+    /// The default is platform dependent, typically 2048 bytes.
+    ///
+    /// # Notes
+    ///
+    /// Linux doesn't support changing this option and returns an error
+    /// (`ENOPROTOOPT`), the send low watermark is always 1 byte there. Use
+    /// [`set_notsent_lowat`] instead to limit the amount of unsent data, which
+    /// is supported on Linux and macOS.
+    ///
+    /// If `bytes` is larger than the send buffer (`SO_SNDBUF`) the OS limits
+    /// the watermark to the size of the send buffer.
+    ///
+    /// [`set_notsent_lowat`]: TcpStream::set_notsent_lowat
+    #[cfg(unix)]
+    pub fn set_send_low_watermark(&self, bytes: usize) -> io::Result<()> {
+        sys::tcp::set_send_low_watermark(&self.inner, bytes)
+    }
+
+    /// Gets the value of the `SO_SNDLOWAT` option on this socket, see
+    /// [`set_send_low_watermark`].
+    ///
+    /// [`set_send_low_watermark`]: TcpStream::set_send_low_watermark
+    #[cfg(unix)]
+    pub fn send_low_watermark(&self) -> io::Result<usize> {
+        sys::tcp::send_low_watermark(&self.inner)
+    }
+
+    /// Sets the value of the `TCP_NOTSENT_LOWAT` option on this socket.
+    ///
+    /// Once set the socket is only writable if the amount of unsent data in
+    /// the send buffer is below `bytes`, so that a socket isn't reported as
+    /// writable (and writes return a [`WouldBlock`] error) while there is
+    /// already enough data queued. This reduces the latency of new data, which
+    /// would otherwise be queued behind a deep send buffer.
+    ///
+    /// On Linux a value of zero (the default) uses the system wide setting, see
+    /// `net.ipv4.tcp_notsent_lowat`.
+    ///
+    /// # Notes
+    ///
+    /// This is only supported on Android, Linux and Apple platforms, on other
+    /// platforms an [`Unsupported`] error is returned.
+    ///
+    /// [`WouldBlock`]: io::ErrorKind::WouldBlock
+    /// [`Unsupported`]: io::ErrorKind::Unsupported
+    #[cfg(unix)]
+    pub fn set_notsent_lowat(&self, bytes: u32) -> io::Result<()> {
+        sys::tcp::set_notsent_lowat(&self.inner, bytes)
+    }
+
+    /// Gets the value of the `TCP_NOTSENT_LOWAT` option on this socket, see
+    /// [`set_notsent_lowat`].
+    ///
+    /// [`set_notsent_lowat`]: TcpStream::set_notsent_lowat
+    #[cfg(unix)]
+    pub fn notsent_lowat(&self) -> io::Result<u32> {
+        sys::tcp::notsent_lowat(&self.inner)
+    }
+
+    /// Sets the value of the `TCP_MAXSEG` option on this socket, clamping the
+    /// maximum segment size (MSS) of outgoing segments to `mss` bytes.
+    ///
+    /// This can be used to avoid fragmentation when the path has a lower MTU
+    /// than the local interface, e.g. for tunnels. Most OSs only allow
+    /// lowering the MSS, and some only allow setting it before the connection
+    /// is established, in which case the OS error is returned.
+    #[cfg(unix)]
+    pub fn set_mss(&self, mss: u32) -> io::Result<()> {
+        sys::tcp::set_mss(&self.inner, mss)
+    }
+
+    /// Gets the value of the `TCP_MAXSEG` option on this socket.
+    ///
+    /// For a connected socket this is the effective maximum segment size,
+    /// which can be used to size writes to full segments. Before the
+    /// connection is established this is the default, or the value set using
+    /// [`set_mss`].
+    ///
+    /// [`set_mss`]: TcpStream::set_mss
+    #[cfg(unix)]
+    pub fn mss(&self) -> io::Result<u32> {
+        sys::tcp::mss(&self.inner)
+    }
+
+    /// Send up to `count` bytes of `file`, starting at `offset`, without
+    /// copying the data into user space, using `sendfile(2)`.
+    ///
+    /// On success, returns the number of bytes sent. Like [`write`] this can
+    /// send fewer bytes than requested, to resume call this again with the
+    /// returned number of bytes added to `offset` (and subtracted from
+    /// `count`). If the socket's send buffer is full this returns a
+    /// [`WouldBlock`] error, wait for a writable event before trying again.
+    /// The file's own offset (used by `read`) is not changed.
+    ///
+    /// Returns `Ok(0)` if `count` is zero, or if `offset` is at or beyond the
+    /// end of the file.
+    ///
+    /// [`write`]: io::Write::write
+    /// [`WouldBlock`]: io::ErrorKind::WouldBlock
+    ///
+    /// # Notes
+    ///
+    /// `file` must be a regular file (or a file type supported by the OS's
+    /// `sendfile(2)`), it's not required to be in non-blocking mode.
+    ///
+    /// This is only available on Android, Linux, the BSDs with `sendfile(2)`
+    /// (DragonFly BSD, FreeBSD) and Apple platforms.
+    ///
+    /// # Examples
+    ///
+    #[cfg_attr(feature = "os-poll", doc = "```no_run")]
+    #[cfg_attr(not(feature = "os-poll"), doc = "```ignore")]
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use std::fs::File;
+    /// use std::io;
+    ///
+    /// use mio::net::TcpStream;
+    ///
+    /// let stream = TcpStream::connect("127.0.0.1:8080".parse()?)?;
+    /// let file = File::open("index.html")?;
+    /// let len = file.metadata()?.len();
+    ///
+    /// // Wait until the stream is writable...
+    ///
+    /// let mut offset = 0;
+    /// while offset < len {
+    ///     let count = (len - offset) as usize;
+    ///     match stream.send_file(&file, offset, count) {
+    ///         // Resume at the correct offset after a partial send.
+    ///         Ok(n) => offset += n as u64,
+    ///         Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => {
+    ///             // Wait until the stream is writable again...
+    ///         }
+    ///         Err(err) => return Err(err.into()),
+    ///     }
+    /// }
+    /// #     Ok(())
+    /// # }
+    /// ```
+    #[cfg(any(
+        target_os = "android",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "ios",
+        target_os = "linux",
+        target_os = "macos",
+        target_os = "tvos",
+        target_os = "visionos",
+        target_os = "watchos",
+    ))]
+    pub fn send_file<F>(&self, file: &F, offset: u64, count: usize) -> io::Result<usize>
+    where
+        F: AsRawFd + ?Sized,
+    {
+        self.inner
+            .do_io(|inner| sys::tcp::send_file(inner, file.as_raw_fd(), offset, count))
+    }
+
+    /// Sets or clears the close-on-exec flag (`FD_CLOEXEC`) of the socket.
+    ///
+    /// Mio creates all sockets with close-on-exec set, clearing it allows a
+    /// child process to inherit the socket. Note that any child process
+    /// started while the flag is cleared, e.g. by another thread, inherits the
+    /// socket as well.
+    #[cfg(unix)]
+    pub fn set_cloexec(&self, cloexec: bool) -> io::Result<()> {
+        sys::set_cloexec(self.inner.as_raw_fd(), cloexec)
+    }
+
+    /// Returns whether or not the close-on-exec flag is set, see
+    /// [`set_cloexec`].
+    ///
+    /// [`set_cloexec`]: TcpStream::set_cloexec
+    #[cfg(unix)]
+    pub fn cloexec(&self) -> io::Result<bool> {
+        sys::is_cloexec(self.inner.as_raw_fd())
+    }
+
+    /// Sets all options that are set in `opts`, options that are `None` are
+    /// left alone.
+    ///
+    /// The options are set one by one, if setting an option fails the error
+    /// is returned and the remaining options are not set.
+    #[cfg(unix)]
+    pub fn configure(&self, opts: &TcpOptions) -> io::Result<()> {
+        if let Some(nodelay) = opts.nodelay {
+            self.set_nodelay(nodelay)?;
+        }
+        if let Some(keepalive) = opts.keepalive {
+            sys::tcp::set_keepalive(&self.inner, keepalive)?;
+        }
+        if let Some(linger) = opts.linger {
+            sys::tcp::set_linger(&self.inner, linger)?;
+        }
+        if let Some(size) = opts.recv_buffer_size {
+            sys::tcp::set_recv_buffer_size(&self.inner, size)?;
+        }
+        if let Some(size) = opts.send_buffer_size {
+            sys::tcp::set_send_buffer_size(&self.inner, size)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the current value of all options in [`TcpOptions`], i.e. all
+    /// fields are `Some`.
+    #[cfg(unix)]
+    pub fn options(&self) -> io::Result<TcpOptions> {
+        Ok(TcpOptions {
+            nodelay: Some(self.nodelay()?),
+            keepalive: Some(sys::tcp::keepalive(&self.inner)?),
+            linger: Some(sys::tcp::linger(&self.inner)?),
+            recv_buffer_size: Some(sys::tcp::recv_buffer_size(&self.inner)?),
+            send_buffer_size: Some(sys::tcp::send_buffer_size(&self.inner)?),
+        })
+    }
+
     /// Get the value of the `SO_ERROR` option on this socket.
     ///
     /// This will retrieve the stored error in the underlying socket, clearing
@@ -205,6 +702,101 @@ impl TcpStream {
         self.inner.take_error()
     }
 
+    /// Determines the state of the connection, e.g. after receiving an error
+    /// or (read/write) closed event.
+    ///
+    /// This first takes the pending error (`SO_ERROR`, see [`take_error`]),
+    /// if any the connection failed and [`ConnDiagnosis::Reset`] is returned.
+    /// Otherwise it peeks at the receive queue (without removing any data)
+    /// to determine if the read half is closed, which is the case once the
+    /// peer closed its write half and all data was read. Unlike the hints in
+    /// [`Event`], this is based on the state of the socket at the time of the
+    /// call.
+    ///
+    /// [`take_error`]: TcpStream::take_error
+    /// [`Event`]: crate::event::Event
+    ///
+    /// # Notes
+    ///
+    /// Only on Linux the write half is checked (using the TCP state of
+    /// `TCP_INFO`), other platforms can't determine if the write half is
+    /// closed without writing, so [`ConnDiagnosis::WriteClosed`] is never
+    /// returned there. On platforms other than Linux a stream that is still
+    /// connecting is reported as [`ConnDiagnosis::Closed`].
+    #[cfg(unix)]
+    pub fn diagnose(&self) -> io::Result<ConnDiagnosis> {
+        sys::tcp::diagnose(&self.inner)
+    }
+
+    /// Returns the result of a non-blocking [`connect`].
+    ///
+    /// This implements steps 4 and 5 described in [`connect`], and should be
+    /// called after receiving a (writable) event for a stream that is still
+    /// connecting, before reading from or writing to it. It returns:
+    ///
+    ///  * `Ok(Some(()))` if the stream is connected.
+    ///  * `Ok(None)` if the stream is still connecting, wait for another event.
+    ///  * `Err(..)` if connecting failed, e.g. with an error of kind
+    ///    [`io::ErrorKind::ConnectionRefused`].
+    ///
+    /// This can be called repeatedly. Once connected it keeps returning
+    /// `Ok(Some(()))`. The error of a failed connect is taken (see
+    /// [`take_error`]) by the first call that returns it, on Unix following
+    /// calls return an error of kind [`io::ErrorKind::NotConnected`]. On other
+    /// platforms following calls can't distinguish a failed connect from one
+    /// that is still in progress and return `Ok(None)`.
+    ///
+    /// [`connect`]: TcpStream::connect
+    /// [`take_error`]: TcpStream::take_error
+    ///
+    /// # Examples
+    ///
+    #[cfg_attr(feature = "os-poll", doc = "```")]
+    #[cfg_attr(not(feature = "os-poll"), doc = "```ignore")]
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mio::net::{TcpListener, TcpStream};
+    /// use mio::{Events, Interest, Poll, Token};
+    ///
+    /// let mut poll = Poll::new()?;
+    /// let mut events = Events::with_capacity(8);
+    ///
+    /// let listener = TcpListener::bind("127.0.0.1:0".parse()?)?;
+    /// let mut stream = TcpStream::connect(listener.local_addr()?)?;
+    /// poll.registry().register(&mut stream, Token(0), Interest::WRITABLE)?;
+    ///
+    /// loop {
+    ///     poll.poll(&mut events, None)?;
+    ///     if stream.take_connect_result()?.is_some() {
+    ///         // Connected, the stream can be used now.
+    ///         break;
+    ///     }
+    /// }
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn take_connect_result(&self) -> io::Result<Option<()>> {
+        if let Some(err) = self.take_error()? {
+            return Err(err);
+        }
+        match self.peer_addr() {
+            Ok(_) => Ok(Some(())),
+            Err(err) if is_connecting(&err) => {
+                // A stream that is still connecting isn't ready for anything,
+                // a stream that failed to connect is (e.g. hang up).
+                #[cfg(unix)]
+                if !event::Source::poll_ready(self, Interest::WRITABLE)?.is_empty() {
+                    return Err(io::Error::new(
+                        io::ErrorKind::NotConnected,
+                        "failed to connect, the error was already taken",
+                    ));
+                }
+                Ok(None)
+            }
+            Err(err) => Err(err),
+        }
+    }
+
     /// Receives data on the socket from the remote address to which it is
     /// connected, without removing that data from the queue. On success,
     /// returns the number of bytes peeked.
@@ -215,6 +807,27 @@ impl TcpStream {
         self.inner.peek(buf)
     }
 
+    /// Receives data into the two regions of a ring buffer using a single
+    /// vectored read.
+    ///
+    /// When the free space of a ring (circular) buffer wraps around, it
+    /// consists of two slices: `first` from the write position to the end of
+    /// the buffer, and `second` from the start of the buffer. `first` is filled
+    /// before `second`. Returns the total number of bytes received, of which
+    /// the first `first.len()` bytes (at most) are stored in `first`.
+    ///
+    /// Either slice may be empty. Like [`read`], if both are empty this returns
+    /// `Ok(0)`, which can't be distinguished from the end of the stream.
+    ///
+    /// This is the same as calling [`read_vectored`] with both slices.
+    ///
+    /// [`read`]: Read::read
+    /// [`read_vectored`]: Read::read_vectored
+    pub fn recv_ring(&self, first: &mut [u8], second: &mut [u8]) -> io::Result<usize> {
+        let mut bufs = [IoSliceMut::new(first), IoSliceMut::new(second)];
+        self.inner.do_io(|mut inner| inner.read_vectored(&mut bufs))
+    }
+
     /// Execute an I/O operation ensuring that the socket receives more events
     /// if it hits a [`WouldBlock`] error.
     ///
@@ -284,7 +897,7 @@ impl Read for TcpStream {
     }
 }
 
-impl<'a> Read for &'a TcpStream {
+impl Read for &TcpStream {
     fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
         self.inner.do_io(|mut inner| inner.read(buf))
     }
@@ -308,7 +921,7 @@ impl Write for TcpStream {
     }
 }
 
-impl<'a> Write for &'a TcpStream {
+impl Write for &TcpStream {
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
         self.inner.do_io(|mut inner| inner.write(buf))
     }
@@ -322,6 +935,16 @@ impl<'a> Write for &'a TcpStream {
     }
 }
 
+/// Returns true if `err`, returned by `peer_addr`, means the stream is not
+/// (yet) connected.
+fn is_connecting(err: &io::Error) -> bool {
+    #[cfg(unix)]
+    if err.raw_os_error() == Some(libc::EINPROGRESS) {
+        return true;
+    }
+    err.kind() == io::ErrorKind::NotConnected
+}
+
 impl event::Source for TcpStream {
     fn register(
         &mut self,
@@ -344,6 +967,10 @@ impl event::Source for TcpStream {
     fn deregister(&mut self, registry: &Registry) -> io::Result<()> {
         self.inner.deregister(registry)
     }
+
+    fn poll_ready(&self, interests: Interest) -> io::Result<event::Readiness> {
+        self.inner.poll_ready(interests)
+    }
 }
 
 impl fmt::Debug for TcpStream {
@@ -477,3 +1104,97 @@ impl From<TcpStream> for net::TcpStream {
         }
     }
 }
+
+/// Group of common socket options, see [`TcpStream::configure`] and
+/// [`TcpStream::options`].
+///
+/// # Examples
+///
+#[cfg_attr(feature = "os-poll", doc = "```")]
+#[cfg_attr(not(feature = "os-poll"), doc = "```ignore")]
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// # let listener = std::net::TcpListener::bind("127.0.0.1:0")?;
+/// use mio::net::{TcpOptions, TcpStream};
+///
+/// let stream = TcpStream::connect(listener.local_addr()?)?;
+/// stream.configure(&TcpOptions {
+///     nodelay: Some(true),
+///     keepalive: Some(true),
+///     ..TcpOptions::default()
+/// })?;
+/// #     Ok(())
+/// # }
+/// ```
+#[cfg(unix)]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct TcpOptions {
+    /// The `TCP_NODELAY` option, see [`TcpStream::set_nodelay`].
+    pub nodelay: Option<bool>,
+    /// The `SO_KEEPALIVE` option, enabling or disabling the sending of
+    /// keepalive probes.
+    pub keepalive: Option<bool>,
+    /// The `SO_LINGER` option, `Some(None)` disables lingering.
+    ///
+    /// With lingering enabled dropping the stream blocks until the remaining
+    /// data is sent or the duration (in whole seconds, rounded up) elapsed.
+    /// A duration of zero resets the connection when the stream is dropped.
+    pub linger: Option<Option<Duration>>,
+    /// The size of the receive buffer (`SO_RCVBUF`).
+    ///
+    /// The OS may adjust the size, e.g. Linux doubles it to allow space for
+    /// bookkeeping and limits it to the `net.core.rmem_max` sysctl.
+    pub recv_buffer_size: Option<usize>,
+    /// The size of the send buffer (`SO_SNDBUF`), adjusted by the OS like
+    /// `recv_buffer_size`.
+    pub send_buffer_size: Option<usize>,
+}
+
+/// State of a connection, returned by [`TcpStream::diagnose`].
+#[cfg(unix)]
+#[derive(Debug)]
+pub enum ConnDiagnosis {
+    /// Both halves of the connection are open.
+    Alive,
+    /// The read half is closed, i.e. the peer closed its write half and all
+    /// data was read. Writing to the peer might still be possible.
+    ReadClosed,
+    /// The write half is closed, e.g. using [`TcpStream::shutdown`]. Reading
+    /// might still be possible.
+    WriteClosed,
+    /// The connection failed (e.g. reset by the peer, `ECONNRESET`) with the
+    /// contained error.
+    Reset(io::Error),
+    /// Both halves of the connection are closed, or the stream isn't
+    /// connected.
+    Closed,
+}
+
+/// State of a TCP connection, see [`TcpStream::repair_save`].
+///
+/// All fields are public so the state can be transferred to another process
+/// in any format, and restored there using [`TcpStream::repair_restore`].
+#[cfg(target_os = "linux")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TcpRepairState {
+    /// Local address of the connection.
+    pub local_addr: SocketAddr,
+    /// Address of the peer.
+    pub peer_addr: SocketAddr,
+    /// Sequence number following the last byte in `send_queue`.
+    pub send_seq: u32,
+    /// Sequence number following the last byte in `recv_queue`.
+    pub recv_seq: u32,
+    /// Data written but not yet acknowledged by the peer.
+    pub send_queue: Vec<u8>,
+    /// Data received but not yet read.
+    pub recv_queue: Vec<u8>,
+    /// Maximum segment size of the connection.
+    pub mss: u32,
+    /// Send and receive window scale, if negotiated.
+    pub window_scale: Option<(u8, u8)>,
+    /// Whether selective acknowledgements (SACK) were negotiated.
+    pub sack: bool,
+    /// Current TCP timestamp, if timestamps were negotiated.
+    pub timestamp: Option<u32>,
+}
@@ -7,6 +7,8 @@
 //!
 //! [portability guidelines]: ../struct.Poll.html#portability
 
+#[cfg(any(target_os = "android", target_os = "linux"))]
+use std::net::IpAddr;
 use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr};
 #[cfg(any(unix, target_os = "wasi"))]
 use std::os::fd::{AsFd, AsRawFd, BorrowedFd, FromRawFd, IntoRawFd, OwnedFd, RawFd};
@@ -18,6 +20,8 @@ use std::os::hermit::io::{AsFd, AsRawFd, BorrowedFd, FromRawFd, IntoRawFd, Owned
 use std::os::windows::io::{
     AsRawSocket, AsSocket, BorrowedSocket, FromRawSocket, IntoRawSocket, OwnedSocket, RawSocket,
 };
+#[cfg(any(target_os = "android", target_os = "linux"))]
+use std::time::SystemTime;
 use std::{fmt, io, net};
 
 use crate::io_source::IoSource;
@@ -126,6 +130,156 @@ impl UdpSocket {
         sys::udp::bind(addr).map(UdpSocket::from_std)
     }
 
+    /// Creates a UDP socket bound to the given address with the `SO_REUSEPORT`
+    /// option set.
+    ///
+    /// `SO_REUSEPORT` is set before the socket is bound, allowing multiple
+    /// sockets (e.g. one per thread) to be bound to the same `addr`. All
+    /// sockets in such a group must have been created using this function.
+    ///
+    /// # Notes
+    ///
+    /// On Linux the kernel load balances incoming datagrams across all sockets
+    /// in the group by hashing the datagram's four-tuple (source and
+    /// destination address and port). This means that all datagrams of a
+    /// single flow are delivered to the same socket, as long as the group
+    /// doesn't change. Adding or removing a socket from the group changes the
+    /// hashing, which can cause a flow to move to another socket. Furthermore
+    /// Linux only allows sockets created by the same effective user id to join
+    /// a group.
+    ///
+    /// On the BSDs (including macOS) `SO_REUSEPORT` allows multiple sockets to
+    /// be bound to the same address, but doesn't load balance unicast
+    /// datagrams: only the socket bound last receives them.
+    ///
+    /// # Examples
+    ///
+    #[cfg_attr(feature = "os-poll", doc = "```")]
+    #[cfg_attr(not(feature = "os-poll"), doc = "```ignore")]
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mio::net::UdpSocket;
+    ///
+    /// let socket1 = UdpSocket::bind_reuseport("127.0.0.1:0".parse()?)?;
+    /// // Bind a second socket to the same address.
+    /// let socket2 = UdpSocket::bind_reuseport(socket1.local_addr()?)?;
+    /// assert_eq!(socket1.local_addr()?, socket2.local_addr()?);
+    /// #    Ok(())
+    /// # }
+    /// ```
+    #[cfg(any(
+        target_os = "android",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "fuchsia",
+        target_os = "ios",
+        target_os = "linux",
+        target_os = "macos",
+        target_os = "netbsd",
+        target_os = "openbsd",
+        target_os = "tvos",
+        target_os = "visionos",
+        target_os = "watchos",
+    ))]
+    pub fn bind_reuseport(addr: SocketAddr) -> io::Result<UdpSocket> {
+        sys::udp::bind_reuseport(addr).map(UdpSocket::from_std)
+    }
+
+    /// Same as [`bind`], but creates the socket using `protocol`, rather than
+    /// letting the OS pick the default protocol for a datagram socket, i.e.
+    /// UDP.
+    ///
+    /// This can be used to create sockets for other datagram based protocols,
+    /// such as UDP-Lite (`IPPROTO_UDPLITE` on Linux). The `protocol` is passed
+    /// as is to `socket(2)`, it's up to the caller to ensure the protocol
+    /// behaves like UDP.
+    ///
+    /// [`bind`]: UdpSocket::bind
+    #[cfg(unix)]
+    pub fn bind_with_protocol(addr: SocketAddr, protocol: i32) -> io::Result<UdpSocket> {
+        sys::udp::bind_with_protocol(addr, protocol).map(UdpSocket::from_std)
+    }
+
+    /// Creates a dual-stack UDP socket bound to `[::]:port`, receiving both
+    /// IPv4 and IPv6 datagrams.
+    ///
+    /// `IPV6_V6ONLY` is disabled before the socket is bound. Datagrams
+    /// received from IPv4 peers are returned by [`recv_from`] with an
+    /// IPv4-mapped IPv6 address (`::ffff:a.b.c.d`), see
+    /// [`Ipv6Addr::to_ipv4_mapped`], which can be passed to [`send_to`] to
+    /// reply. A `port` of 0 lets the OS pick a port.
+    ///
+    /// Returns an error of kind [`io::ErrorKind::Unsupported`] if the platform
+    /// doesn't support dual-stack sockets, e.g. on OpenBSD, or IPv6 is not
+    /// available.
+    ///
+    /// [`recv_from`]: UdpSocket::recv_from
+    /// [`send_to`]: UdpSocket::send_to
+    ///
+    /// # Examples
+    ///
+    #[cfg_attr(feature = "os-poll", doc = "```")]
+    #[cfg_attr(not(feature = "os-poll"), doc = "```ignore")]
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mio::net::UdpSocket;
+    ///
+    /// let socket = UdpSocket::bind_dual_stack(0)?;
+    /// assert!(socket.local_addr()?.is_ipv6());
+    /// assert!(!socket.only_v6()?);
+    /// #    Ok(())
+    /// # }
+    /// ```
+    #[cfg(unix)]
+    pub fn bind_dual_stack(port: u16) -> io::Result<UdpSocket> {
+        sys::udp::bind_dual_stack(port).map(UdpSocket::from_std)
+    }
+
+    /// Creates a new, unbound, IPv4 UDP socket.
+    ///
+    /// The socket is in non-blocking mode and can be registered right away.
+    /// This allows socket options to be set before the socket is used. The OS
+    /// assigns a local address once the socket is first used, e.g. when
+    /// calling [`connect`] or [`send_to`].
+    ///
+    /// [`connect`]: UdpSocket::connect
+    /// [`send_to`]: UdpSocket::send_to
+    ///
+    /// # Notes
+    ///
+    /// On Windows receiving on a socket that is not yet bound returns an
+    /// error (`WSAEINVAL`), rather than blocking.
+    ///
+    /// # Examples
+    ///
+    #[cfg_attr(feature = "os-poll", doc = "```")]
+    #[cfg_attr(not(feature = "os-poll"), doc = "```ignore")]
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mio::net::UdpSocket;
+    ///
+    /// let socket = UdpSocket::new_v4()?;
+    /// socket.set_ttl(16)?;
+    /// // Binds the socket to a local address picked by the OS.
+    /// socket.connect("127.0.0.1:9000".parse()?)?;
+    /// assert!(socket.local_addr()?.is_ipv4());
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn new_v4() -> io::Result<UdpSocket> {
+        sys::udp::new_unbound(false).map(UdpSocket::from_std)
+    }
+
+    /// Creates a new, unbound, IPv6 UDP socket.
+    ///
+    /// See [`UdpSocket::new_v4`].
+    pub fn new_v6() -> io::Result<UdpSocket> {
+        sys::udp::new_unbound(true).map(UdpSocket::from_std)
+    }
+
     /// Creates a new `UdpSocket` from a standard `net::UdpSocket`.
     ///
     /// This function is intended to be used to wrap a UDP socket from the
@@ -167,6 +321,12 @@ impl UdpSocket {
 
     /// Returns the socket address of the remote peer this socket was connected to.
     ///
+    /// If the socket isn't connected, see [`connect`], this returns an error
+    /// with kind [`NotConnected`].
+    ///
+    /// [`connect`]: UdpSocket::connect
+    /// [`NotConnected`]: io::ErrorKind::NotConnected
+    ///
     /// # Examples
     ///
     #[cfg_attr(feature = "os-poll", doc = "```")]
@@ -251,6 +411,95 @@ impl UdpSocket {
         self.inner.do_io(|inner| inner.recv_from(buf))
     }
 
+    /// Receives multiple datagrams from the socket, each into its own buffer.
+    ///
+    /// The datagrams are received in order, filling `buffers` from the start,
+    /// up to the number of datagrams that are ready to be received. On success
+    /// it returns the number of bytes read and the address from whence the
+    /// data came for each received datagram, i.e. the first `n` buffers are
+    /// filled, where `n` is the length of the returned vector. It returns an
+    /// empty vector only if `buffers` is empty.
+    ///
+    /// Like [`recv_from`] the length of each buffer (not its capacity) limits
+    /// the size of the datagram that can be received into it, see the notes of
+    /// that method about truncation.
+    ///
+    /// If no datagrams are ready to be received this returns an error with
+    /// kind [`WouldBlock`], the same as all other I/O methods.
+    ///
+    /// On Linux and Android this uses a single `recvmmsg(2)` system call, with
+    /// `MSG_WAITFORONE` semantics: it returns the first datagram and whatever
+    /// else is immediately available, it doesn't wait to fill all `buffers`.
+    /// On other platforms it calls [`recv_from`] until it would block. Either
+    /// way this drains the receive queue (up to the number of `buffers`) after
+    /// a readable event. Because the socket is non-blocking it never waits for
+    /// the first datagram, so it still returns an error with kind
+    /// [`WouldBlock`] if it's called before the socket is readable.
+    ///
+    /// [`recv_from`]: UdpSocket::recv_from
+    /// [`WouldBlock`]: io::ErrorKind::WouldBlock
+    pub fn recv_batch(&self, buffers: &mut [Vec<u8>]) -> io::Result<Vec<(usize, SocketAddr)>> {
+        if buffers.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        #[cfg(any(target_os = "android", target_os = "linux"))]
+        {
+            self.inner
+                .do_io(|inner| sys::udp::recv_batch(inner, buffers))
+        }
+
+        #[cfg(not(any(target_os = "android", target_os = "linux")))]
+        {
+            let mut received = Vec::new();
+            for buf in buffers.iter_mut() {
+                match self.recv_from(buf) {
+                    Ok(res) => received.push(res),
+                    Err(ref err)
+                        if err.kind() == io::ErrorKind::WouldBlock && !received.is_empty() =>
+                    {
+                        break
+                    }
+                    Err(err) => return Err(err),
+                }
+            }
+            Ok(received)
+        }
+    }
+
+    /// Same as [`recv_from`], but uses `policy` to determine what to do if the
+    /// datagram is larger than `buf`.
+    ///
+    /// How truncated datagrams are reported by [`recv_from`] differs per
+    /// platform, this can be used to get the same behaviour on all Unix
+    /// platforms. See [`TruncationPolicy`] for the available policies.
+    ///
+    /// [`recv_from`]: UdpSocket::recv_from
+    #[cfg(unix)]
+    pub fn recv_from_with_truncation(
+        &self,
+        buf: &mut [u8],
+        policy: TruncationPolicy,
+    ) -> io::Result<(usize, SocketAddr)> {
+        self.inner
+            .do_io(|inner| sys::udp::recv_from_with_truncation(inner, buf, policy))
+    }
+
+    /// Same as [`recv`], but uses `policy` to determine what to do if the
+    /// datagram is larger than `buf`, see [`recv_from_with_truncation`].
+    ///
+    /// [`recv`]: UdpSocket::recv
+    /// [`recv_from_with_truncation`]: UdpSocket::recv_from_with_truncation
+    #[cfg(unix)]
+    pub fn recv_with_truncation(
+        &self,
+        buf: &mut [u8],
+        policy: TruncationPolicy,
+    ) -> io::Result<usize> {
+        self.inner
+            .do_io(|inner| sys::udp::recv_with_truncation(inner, buf, policy))
+    }
+
     /// Receives data from the socket, without removing it from the input queue.
     /// On success, returns the number of bytes read and the address from whence
     /// the data came.
@@ -498,6 +747,27 @@ impl UdpSocket {
         self.inner.ttl()
     }
 
+    /// Sets the value for the `IPV6_UNICAST_HOPS` option on this socket.
+    ///
+    /// This is the IPv6 equivalent of [`set_ttl`], it sets the hop limit field
+    /// that is used in every unicast packet sent from this socket. This only
+    /// works on IPv6 sockets, for IPv4 sockets use `set_ttl`.
+    ///
+    /// [`set_ttl`]: UdpSocket::set_ttl
+    #[cfg(unix)]
+    pub fn set_hop_limit(&self, hops: u32) -> io::Result<()> {
+        sys::udp::set_hop_limit(self.inner.as_raw_fd(), hops)
+    }
+
+    /// Gets the value of the `IPV6_UNICAST_HOPS` option for this socket, see
+    /// [`set_hop_limit`].
+    ///
+    /// [`set_hop_limit`]: UdpSocket::set_hop_limit
+    #[cfg(unix)]
+    pub fn hop_limit(&self) -> io::Result<u32> {
+        sys::udp::hop_limit(self.inner.as_raw_fd())
+    }
+
     /// Executes an operation of the `IP_ADD_MEMBERSHIP` type.
     ///
     /// This function specifies a new multicast group for this socket to join.
@@ -548,6 +818,298 @@ impl UdpSocket {
         sys::udp::only_v6(&self.inner)
     }
 
+    /// Sets the scope id (interface index) used for sending to IPv6
+    /// addresses that require one, such as link-local unicast (`fe80::/10`)
+    /// and link-local multicast (`ff02::/16`) addresses, when the address
+    /// passed to [`send_to`] has a `scope_id` of 0.
+    ///
+    /// A non-zero [`SocketAddrV6::scope_id`] passed to `send_to` is always
+    /// used as is and takes precedence over this default. Without any scope
+    /// id the OS has to pick the interface based on its routing table, which
+    /// fails (e.g. with `EINVAL` or `ENETUNREACH`) or picks the wrong
+    /// interface on hosts with multiple link-local interfaces.
+    ///
+    /// This sets the `IPV6_MULTICAST_IF` option and, on Linux and Android,
+    /// the `IPV6_UNICAST_IF` option. On other platforms it only applies to
+    /// multicast addresses. Use `0` to reset it.
+    ///
+    /// Note that on Linux and Android `IPV6_UNICAST_IF` pins *all* unicast
+    /// traffic sent on the socket to the interface, not just traffic to
+    /// link-local addresses. Sending to a global address that isn't reachable
+    /// through that interface fails or is routed via the wrong interface. Use
+    /// a separate socket for such traffic.
+    ///
+    /// The options only exist for IPv6, calling this on an IPv4 socket
+    /// returns an error (`ENOPROTOOPT` on Linux).
+    ///
+    /// [`send_to`]: UdpSocket::send_to
+    /// [`SocketAddrV6::scope_id`]: std::net::SocketAddrV6::scope_id
+    #[cfg(unix)]
+    pub fn set_default_scope_id(&self, scope_id: u32) -> io::Result<()> {
+        sys::udp::set_default_scope_id(&self.inner, scope_id)
+    }
+
+    /// Gets the default scope id used for sending, see
+    /// [`set_default_scope_id`].
+    ///
+    /// This reads the `IPV6_MULTICAST_IF` option.
+    ///
+    /// [`set_default_scope_id`]: UdpSocket::set_default_scope_id
+    #[cfg(unix)]
+    pub fn default_scope_id(&self) -> io::Result<u32> {
+        sys::udp::default_scope_id(&self.inner)
+    }
+
+    /// Sets the value of the `IP_TRANSPARENT` option on this socket.
+    ///
+    /// This allows the socket to bind to, and send from, non-local addresses, as
+    /// used by transparent proxies in combination with the `TPROXY` iptables
+    /// target. The option applies to both IPv4 and IPv6 sockets.
+    ///
+    /// Setting this option requires the `CAP_NET_ADMIN` capability, without it
+    /// an error with kind [`PermissionDenied`] is returned.
+    ///
+    /// [`PermissionDenied`]: io::ErrorKind::PermissionDenied
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn set_ip_transparent(&self, on: bool) -> io::Result<()> {
+        sys::udp::set_ip_transparent(&self.inner, on)
+    }
+
+    /// Sets the value of the `SO_PRIORITY` option on this socket.
+    ///
+    /// This sets the priority of all packets sent on this socket, which the
+    /// Linux queueing disciplines (qdisc) use to schedule packets locally.
+    /// Unlike the DSCP/TOS field (`IP_TOS`) this doesn't affect the packets
+    /// on the wire. Values outside of 0 to 6 require the `CAP_NET_ADMIN`
+    /// capability, without it an error with kind [`PermissionDenied`] is
+    /// returned.
+    ///
+    /// [`PermissionDenied`]: io::ErrorKind::PermissionDenied
+    ///
+    /// This is only available on Linux and Android.
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn set_priority(&self, priority: u32) -> io::Result<()> {
+        sys::udp::set_priority(&self.inner, priority)
+    }
+
+    /// Gets the value of the `SO_PRIORITY` option on this socket, see
+    /// [`set_priority`].
+    ///
+    /// [`set_priority`]: UdpSocket::set_priority
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn priority(&self) -> io::Result<u32> {
+        sys::udp::priority(&self.inner)
+    }
+
+    /// Sets the don't-fragment (DF) bit on datagrams sent on this socket.
+    ///
+    /// When enabled the kernel no longer fragments datagrams, which is
+    /// required for protocols that do their own path MTU discovery, such as
+    /// QUIC. On Linux and Android this sets the `IP_MTU_DISCOVER` (IPv4) or
+    /// `IPV6_MTU_DISCOVER` (IPv6) option to `IP_PMTUDISC_DO` (or back to
+    /// `IP_PMTUDISC_WANT` when disabled), on other platforms the
+    /// `IP_DONTFRAG` or `IPV6_DONTFRAG` option is used. For IPv6 sockets that
+    /// are not [`only_v6`] on Linux and Android the IPv4 option is set as well.
+    ///
+    /// [`only_v6`]: UdpSocket::only_v6
+    ///
+    /// # Notes
+    ///
+    /// With the don't-fragment bit set sending a datagram larger than the
+    /// (known) path MTU fails with an `EMSGSIZE` error, which the caller must
+    /// handle, e.g. by sending smaller datagrams. See [`path_mtu`] to get the
+    /// current path MTU.
+    ///
+    /// [`path_mtu`]: UdpSocket::path_mtu
+    #[cfg(any(
+        target_os = "android",
+        target_os = "freebsd",
+        target_os = "ios",
+        target_os = "linux",
+        target_os = "macos",
+        target_os = "tvos",
+        target_os = "visionos",
+        target_os = "watchos",
+    ))]
+    pub fn set_dontfrag(&self, dontfrag: bool) -> io::Result<()> {
+        sys::udp::set_dontfrag(&self.inner, dontfrag)
+    }
+
+    /// Sets the path MTU discovery mode of this socket.
+    ///
+    /// This sets the `IP_MTU_DISCOVER` (IPv4) or `IPV6_MTU_DISCOVER` (IPv6)
+    /// option. Like [`set_dontfrag`], for IPv6 sockets that are not
+    /// [`only_v6`] the IPv4 option is set as well.
+    ///
+    /// [`set_dontfrag`]: UdpSocket::set_dontfrag
+    /// [`only_v6`]: UdpSocket::only_v6
+    ///
+    /// # Notes
+    ///
+    /// With [`MtuDiscover::Do`] and [`MtuDiscover::Probe`] the don't-fragment
+    /// bit is set, so sending a datagram larger than the path MTU fails with
+    /// an `EMSGSIZE` error (for `Probe` only if it's larger than the
+    /// interface MTU). See [`path_mtu`] to get the current path MTU.
+    ///
+    /// [`path_mtu`]: UdpSocket::path_mtu
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn set_mtu_discover(&self, mode: MtuDiscover) -> io::Result<()> {
+        sys::udp::set_mtu_discover(&self.inner, mode)
+    }
+
+    /// Returns the current known path MTU of the socket.
+    ///
+    /// This gets the `IP_MTU` (IPv4) or `IPV6_MTU` (IPv6) option. The socket
+    /// must be [connected], otherwise an error is returned.
+    ///
+    /// [connected]: UdpSocket::connect
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn path_mtu(&self) -> io::Result<u32> {
+        sys::udp::path_mtu(&self.inner)
+    }
+
+    /// Sets what kind of receive timestamps the kernel records for datagrams
+    /// received on this socket.
+    ///
+    /// The timestamps can be retrieved using [`recv_from_with_timestamp`].
+    /// This sets the `SO_TIMESTAMPNS` option for [`TimestampMode::Software`]
+    /// and the `SO_TIMESTAMPING` option for [`TimestampMode::Hardware`].
+    ///
+    /// [`recv_from_with_timestamp`]: UdpSocket::recv_from_with_timestamp
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn set_timestamping(&self, mode: TimestampMode) -> io::Result<()> {
+        sys::udp::set_timestamping(&self.inner, mode)
+    }
+
+    /// Same as [`recv_from`], but also returns the time at which the datagram
+    /// was received, if recorded.
+    ///
+    /// Timestamps must first be enabled using [`set_timestamping`], otherwise
+    /// `None` is returned. Software timestamps are taken from the system's
+    /// realtime clock, hardware timestamps from the clock of the NIC, which
+    /// may not be synchronised with the system's clock.
+    ///
+    /// [`recv_from`]: UdpSocket::recv_from
+    /// [`set_timestamping`]: UdpSocket::set_timestamping
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn recv_from_with_timestamp(
+        &self,
+        buf: &mut [u8],
+    ) -> io::Result<(usize, SocketAddr, Option<SystemTime>)> {
+        self.inner
+            .do_io(|inner| sys::udp::recv_from_with_timestamp(inner, buf))
+    }
+
+    /// Sets whether the interface and destination address of received
+    /// datagrams are recorded.
+    ///
+    /// This sets the `IP_PKTINFO` option (IPv4) or the `IPV6_RECVPKTINFO`
+    /// option (IPv6), for dual-stack IPv6 sockets both options are set. The
+    /// information can be retrieved using [`recv_from_with_info`].
+    ///
+    /// [`recv_from_with_info`]: UdpSocket::recv_from_with_info
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn set_recv_pktinfo(&self, enable: bool) -> io::Result<()> {
+        sys::udp::set_recv_pktinfo(&self.inner, enable)
+    }
+
+    /// Gets whether the interface and destination address of received
+    /// datagrams are recorded, see [`set_recv_pktinfo`].
+    ///
+    /// [`set_recv_pktinfo`]: UdpSocket::set_recv_pktinfo
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn recv_pktinfo(&self) -> io::Result<bool> {
+        sys::udp::recv_pktinfo(&self.inner)
+    }
+
+    /// Same as [`recv_from`], but also returns the interface the datagram
+    /// arrived on and its destination address, see [`RecvInfo`].
+    ///
+    /// This must first be enabled using [`set_recv_pktinfo`], otherwise the
+    /// returned `RecvInfo` is empty. On multi-homed hosts this allows replying
+    /// using the same interface and source address, e.g. by binding a socket to
+    /// the destination address, or by using the interface index as the scope
+    /// id of an IPv6 link-local address or with [`join_multicast_v6`].
+    ///
+    /// [`recv_from`]: UdpSocket::recv_from
+    /// [`set_recv_pktinfo`]: UdpSocket::set_recv_pktinfo
+    /// [`join_multicast_v6`]: UdpSocket::join_multicast_v6
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn recv_from_with_info(&self, buf: &mut [u8]) -> io::Result<(usize, SocketAddr, RecvInfo)> {
+        self.inner
+            .do_io(|inner| sys::udp::recv_from_with_info(inner, buf))
+    }
+
+    /// Attach a classic BPF `program` to the `SO_REUSEPORT` group of this
+    /// socket, setting the `SO_ATTACH_REUSEPORT_CBPF` option.
+    ///
+    /// The program selects the socket in the group that receives a datagram:
+    /// its return value is used as index into the group, in the order in which
+    /// the sockets were bound (see [`bind_reuseport`]). If the index is out of
+    /// bounds the kernel falls back to its default hashing. The program is
+    /// shared by the entire group, so it only needs to be attached to a single
+    /// socket, usually the first one.
+    ///
+    /// [`bind_reuseport`]: UdpSocket::bind_reuseport
+    ///
+    /// # Examples
+    ///
+    #[cfg_attr(feature = "os-poll", doc = "```")]
+    #[cfg_attr(not(feature = "os-poll"), doc = "```ignore")]
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mio::net::{SockFilter, UdpSocket};
+    ///
+    /// let socket1 = UdpSocket::bind_reuseport("127.0.0.1:0".parse()?)?;
+    /// let socket2 = UdpSocket::bind_reuseport(socket1.local_addr()?)?;
+    ///
+    /// // `BPF_RET | BPF_K`: deliver all datagrams to the first socket.
+    /// socket1.attach_reuseport_cbpf(&[SockFilter::new(0x06, 0, 0, 0)])?;
+    /// #    drop(socket2);
+    /// #    Ok(())
+    /// # }
+    /// ```
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn attach_reuseport_cbpf(&self, program: &[SockFilter]) -> io::Result<()> {
+        sys::udp::attach_reuseport_cbpf(&self.inner, program)
+    }
+
+    /// Attach a loaded eBPF `program`, of type
+    /// `BPF_PROG_TYPE_SOCKET_FILTER` or `BPF_PROG_TYPE_SK_REUSEPORT`, to the
+    /// `SO_REUSEPORT` group of this socket, setting the
+    /// `SO_ATTACH_REUSEPORT_EBPF` option.
+    ///
+    /// See [`attach_reuseport_cbpf`] for how the program is used. Loading the
+    /// program, using `bpf(2)`, is left to the caller. The kernel takes its
+    /// own reference to the program, so `program` can be closed afterwards.
+    ///
+    /// [`attach_reuseport_cbpf`]: UdpSocket::attach_reuseport_cbpf
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn attach_reuseport_ebpf(&self, program: BorrowedFd<'_>) -> io::Result<()> {
+        sys::udp::attach_reuseport_ebpf(&self.inner, program)
+    }
+
+    /// Sets or clears the close-on-exec flag (`FD_CLOEXEC`) of the socket.
+    ///
+    /// Mio creates all sockets with close-on-exec set, clearing it allows a
+    /// child process to inherit the socket. Note that any child process
+    /// started while the flag is cleared, e.g. by another thread, inherits the
+    /// socket as well.
+    #[cfg(unix)]
+    pub fn set_cloexec(&self, cloexec: bool) -> io::Result<()> {
+        sys::set_cloexec(self.inner.as_raw_fd(), cloexec)
+    }
+
+    /// Returns whether or not the close-on-exec flag is set, see
+    /// [`set_cloexec`].
+    ///
+    /// [`set_cloexec`]: UdpSocket::set_cloexec
+    #[cfg(unix)]
+    pub fn cloexec(&self) -> io::Result<bool> {
+        sys::is_cloexec(self.inner.as_raw_fd())
+    }
+
     /// Get the value of the `SO_ERROR` option on this socket.
     ///
     /// This will retrieve the stored error in the underlying socket, clearing
@@ -616,6 +1178,114 @@ impl UdpSocket {
     }
 }
 
+/// Path MTU discovery mode, see [`UdpSocket::set_mtu_discover`].
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum MtuDiscover {
+    /// Use per-route settings, the kernel default (`IP_PMTUDISC_WANT`).
+    Want,
+    /// Never do path MTU discovery, datagrams may be fragmented
+    /// (`IP_PMTUDISC_DONT`).
+    Dont,
+    /// Always do path MTU discovery, setting the don't-fragment bit
+    /// (`IP_PMTUDISC_DO`).
+    Do,
+    /// Set the don't-fragment bit, but ignore the path MTU, only the interface
+    /// MTU is enforced (`IP_PMTUDISC_PROBE`).
+    Probe,
+}
+
+/// Kind of receive timestamps to record, see [`UdpSocket::set_timestamping`].
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TimestampMode {
+    /// Don't record receive timestamps.
+    Disabled,
+    /// Record timestamps in software, when the kernel receives the datagram.
+    Software,
+    /// Record timestamps in hardware, when the NIC receives the datagram. If
+    /// the NIC doesn't support this a software timestamp is recorded instead.
+    ///
+    /// Hardware timestamping must also be enabled on the network interface,
+    /// using the `SIOCSHWTSTAMP` ioctl, which requires the `CAP_NET_ADMIN`
+    /// capability.
+    Hardware,
+}
+
+/// Information about a received datagram, see
+/// [`UdpSocket::recv_from_with_info`].
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct RecvInfo {
+    pub(crate) interface_index: Option<u32>,
+    pub(crate) destination: Option<IpAddr>,
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+impl RecvInfo {
+    /// Returns the index of the network interface the datagram arrived on.
+    ///
+    /// The name of the interface can be retrieved using `if_indextoname(3)`.
+    pub fn interface_index(&self) -> Option<u32> {
+        self.interface_index
+    }
+
+    /// Returns the destination address of the datagram, i.e. the local
+    /// address it was sent to.
+    ///
+    /// This can differ from the local address of the socket, e.g. for sockets
+    /// bound to the unspecified address or for multicast datagrams.
+    pub fn destination(&self) -> Option<IpAddr> {
+        self.destination
+    }
+}
+
+/// How to handle a datagram larger than the receive buffer, see
+/// [`UdpSocket::recv_from_with_truncation`].
+#[cfg(unix)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TruncationPolicy {
+    /// Fill the buffer and silently discard the remainder of the datagram,
+    /// returning the number of bytes written to the buffer. This is what
+    /// [`UdpSocket::recv_from`] does on Unix.
+    Truncate,
+    /// Return an error with the `EMSGSIZE` OS error, which is what
+    /// [`UdpSocket::recv_from`] does on Windows. The datagram is still
+    /// removed from the receive queue.
+    Error,
+    /// Fill the buffer and discard the remainder of the datagram, but return
+    /// the full length of the datagram, which can be larger than the buffer
+    /// (`MSG_TRUNC`). This can be used to detect truncation and retry with a
+    /// larger buffer for the next datagram.
+    ///
+    /// Only supported on Linux and Android, returns an `Unsupported` error
+    /// on other platforms.
+    ReportLength,
+}
+
+/// A classic BPF instruction (`struct sock_filter`), see
+/// [`UdpSocket::attach_reuseport_cbpf`].
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[repr(C)]
+pub struct SockFilter {
+    code: u16,
+    jt: u8,
+    jf: u8,
+    k: u32,
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+impl SockFilter {
+    /// Create a new instruction, the arguments match the `BPF_STMT` and
+    /// `BPF_JUMP` macros from `<linux/filter.h>`: the opcode (`code`), the
+    /// jump offsets if true (`jt`) and false (`jf`), and a generic value
+    /// (`k`).
+    pub const fn new(code: u16, jt: u8, jf: u8, k: u32) -> SockFilter {
+        SockFilter { code, jt, jf, k }
+    }
+}
+
 impl event::Source for UdpSocket {
     fn register(
         &mut self,
@@ -638,6 +1308,10 @@ impl event::Source for UdpSocket {
     fn deregister(&mut self, registry: &Registry) -> io::Result<()> {
         self.inner.deregister(registry)
     }
+
+    fn poll_ready(&self, interests: Interest) -> io::Result<event::Readiness> {
+        self.inner.poll_ready(interests)
+    }
 }
 
 impl fmt::Debug for UdpSocket {
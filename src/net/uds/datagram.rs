@@ -5,6 +5,7 @@ use std::path::Path;
 use std::{fmt, io};
 
 use crate::io_source::IoSource;
+use crate::net::TruncationPolicy;
 use crate::{event, sys, Interest, Registry, Token};
 
 /// A Unix datagram socket.
@@ -50,6 +51,13 @@ impl UnixDatagram {
     }
 
     /// Create an unnamed pair of connected sockets.
+    ///
+    /// Both sockets are created in non-blocking mode with close-on-exec set.
+    /// Where supported (e.g. Linux and the BSDs) this is done atomically by
+    /// passing `SOCK_NONBLOCK` and `SOCK_CLOEXEC` to `socketpair(2)`. On
+    /// platforms lacking these flags, such as macOS, they're set using
+    /// `fcntl(2)` after creating the sockets, which means another thread can
+    /// `fork(2)` and `exec(2)` before close-on-exec is set.
     pub fn pair() -> io::Result<(UnixDatagram, UnixDatagram)> {
         sys::uds::datagram::pair().map(|(socket1, socket2)| {
             (
@@ -86,6 +94,19 @@ impl UnixDatagram {
         self.inner.do_io(|inner| inner.recv(buf))
     }
 
+    /// Same as [`recv`], but uses `policy` to determine what to do if the
+    /// datagram is larger than `buf`, see [`TruncationPolicy`].
+    ///
+    /// [`recv`]: UnixDatagram::recv
+    pub fn recv_with_truncation(
+        &self,
+        buf: &mut [u8],
+        policy: TruncationPolicy,
+    ) -> io::Result<usize> {
+        self.inner
+            .do_io(|inner| sys::uds::datagram::recv_with_truncation(inner, buf, policy))
+    }
+
     /// Sends data on the socket to the specified address.
     ///
     /// On success, returns the number of bytes written.
@@ -103,6 +124,24 @@ impl UnixDatagram {
         self.inner.do_io(|inner| inner.send(buf))
     }
 
+    /// Sets or clears the close-on-exec flag (`FD_CLOEXEC`) of the socket.
+    ///
+    /// Mio creates all sockets with close-on-exec set, clearing it allows a
+    /// child process to inherit the socket. Note that any child process
+    /// started while the flag is cleared, e.g. by another thread, inherits the
+    /// socket as well.
+    pub fn set_cloexec(&self, cloexec: bool) -> io::Result<()> {
+        sys::set_cloexec(self.inner.as_raw_fd(), cloexec)
+    }
+
+    /// Returns whether or not the close-on-exec flag is set, see
+    /// [`set_cloexec`].
+    ///
+    /// [`set_cloexec`]: UnixDatagram::set_cloexec
+    pub fn cloexec(&self) -> io::Result<bool> {
+        sys::is_cloexec(self.inner.as_raw_fd())
+    }
+
     /// Returns the value of the `SO_ERROR` option.
     pub fn take_error(&self) -> io::Result<Option<io::Error>> {
         self.inner.take_error()
@@ -208,6 +247,10 @@ impl event::Source for UnixDatagram {
     fn deregister(&mut self, registry: &Registry) -> io::Result<()> {
         self.inner.deregister(registry)
     }
+
+    fn poll_ready(&self, interests: Interest) -> io::Result<event::Readiness> {
+        self.inner.poll_ready(interests)
+    }
 }
 
 impl fmt::Debug for UnixDatagram {
@@ -24,6 +24,21 @@ impl UnixListener {
         sys::uds::listener::bind_addr(address).map(UnixListener::from_std)
     }
 
+    /// Creates a new `UnixListener` from a listening socket inherited from the
+    /// parent process.
+    ///
+    /// This validates that `fd` is a listening Unix stream socket, sets it in
+    /// non-blocking mode and sets `FD_CLOEXEC`, see
+    /// [`TcpListener::from_inherited_fd`] for more details. On error `fd` is
+    /// closed.
+    ///
+    /// [`TcpListener::from_inherited_fd`]: crate::net::TcpListener::from_inherited_fd
+    pub fn from_inherited_fd(fd: OwnedFd) -> io::Result<UnixListener> {
+        let listener = net::UnixListener::from(fd);
+        sys::uds::listener::prepare_inherited(&listener)?;
+        Ok(UnixListener::from_std(listener))
+    }
+
     /// Creates a new `UnixListener` from a standard `net::UnixListener`.
     ///
     /// This function is intended to be used to wrap a Unix listener from the
@@ -49,6 +64,24 @@ impl UnixListener {
         self.inner.local_addr()
     }
 
+    /// Sets or clears the close-on-exec flag (`FD_CLOEXEC`) of the socket.
+    ///
+    /// Mio creates all sockets with close-on-exec set, clearing it allows a
+    /// child process to inherit the socket. Note that any child process
+    /// started while the flag is cleared, e.g. by another thread, inherits the
+    /// socket as well.
+    pub fn set_cloexec(&self, cloexec: bool) -> io::Result<()> {
+        sys::set_cloexec(self.inner.as_raw_fd(), cloexec)
+    }
+
+    /// Returns whether or not the close-on-exec flag is set, see
+    /// [`set_cloexec`].
+    ///
+    /// [`set_cloexec`]: UnixListener::set_cloexec
+    pub fn cloexec(&self) -> io::Result<bool> {
+        sys::is_cloexec(self.inner.as_raw_fd())
+    }
+
     /// Returns the value of the `SO_ERROR` option.
     pub fn take_error(&self) -> io::Result<Option<io::Error>> {
         self.inner.take_error()
@@ -77,6 +110,10 @@ impl event::Source for UnixListener {
     fn deregister(&mut self, registry: &Registry) -> io::Result<()> {
         self.inner.deregister(registry)
     }
+
+    fn poll_ready(&self, interests: Interest) -> io::Result<event::Readiness> {
+        self.inner.poll_ready(interests)
+    }
 }
 
 impl fmt::Debug for UnixListener {
@@ -1,26 +1,40 @@
-#[cfg(all(
-    unix,
-    not(mio_unsupported_force_poll_poll),
-    not(any(
-        target_os = "aix",
-        target_os = "espidf",
-        target_os = "fuchsia",
-        target_os = "haiku",
-        target_os = "hermit",
-        target_os = "hurd",
-        target_os = "nto",
-        target_os = "solaris",
-        target_os = "vita"
-    )),
+#[cfg(all(unix, feature = "track-registrations"))]
+use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet};
+#[cfg(any(
+    all(
+        unix,
+        not(mio_unsupported_force_poll_poll),
+        not(any(
+            target_os = "aix",
+            target_os = "espidf",
+            target_os = "fuchsia",
+            target_os = "haiku",
+            target_os = "hermit",
+            target_os = "hurd",
+            target_os = "nto",
+            target_os = "solaris",
+            target_os = "vita"
+        )),
+    ),
+    all(unix, any(feature = "net", feature = "os-ext")),
 ))]
 use std::os::fd::{AsRawFd, RawFd};
-#[cfg(all(debug_assertions, not(target_os = "wasi")))]
-use std::sync::atomic::{AtomicBool, Ordering};
-#[cfg(all(debug_assertions, not(target_os = "wasi")))]
-use std::sync::Arc;
-use std::time::Duration;
+#[cfg(all(target_os = "hermit", any(feature = "net", feature = "os-ext")))]
+use std::os::hermit::io::RawFd;
+#[cfg(not(target_os = "wasi"))]
+use std::sync::atomic::AtomicBool;
+#[cfg(all(unix, feature = "track-registrations"))]
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use std::{fmt, io};
 
+#[cfg(not(target_os = "wasi"))]
+use crate::CancelNotifier;
+#[cfg(any(not(target_os = "wasi"), all(unix, feature = "track-registrations")))]
+use crate::Error;
 use crate::{event, sys, Events, Interest, Token};
 
 /// Polls for readiness events on all registered values.
@@ -244,6 +258,9 @@ use crate::{event, sys, Events, Interest, Token};
 /// | Windows       | [IOCP]    |
 /// | macOS         | [kqueue]  |
 ///
+/// On Linux the `io-uring` feature makes `Poll` use [io_uring] instead of
+/// epoll, if the kernel supports it, see the [`features`] module.
+///
 /// On all supported platforms, socket operations are handled by using the
 /// system selector. Platform specific extensions (e.g. [`SourceFd`]) allow
 /// accessing other features provided by individual system selectors. For
@@ -259,6 +276,8 @@ use crate::{event, sys, Events, Interest, Token};
 /// kernel.
 ///
 /// [epoll]: https://man7.org/linux/man-pages/man7/epoll.7.html
+/// [io_uring]: https://man7.org/linux/man-pages/man7/io_uring.7.html
+/// [`features`]: crate::features
 /// [kqueue]: https://www.freebsd.org/cgi/man.cgi?query=kqueue&sektion=2
 /// [IOCP]: https://docs.microsoft.com/en-us/windows/win32/fileio/i-o-completion-ports
 /// [`signalfd`]: https://man7.org/linux/man-pages/man2/signalfd.2.html
@@ -266,14 +285,188 @@ use crate::{event, sys, Events, Interest, Token};
 /// [`Poll::poll`]: struct.Poll.html#method.poll
 pub struct Poll {
     registry: Registry,
+    /// Waker used by `poll_cancellable`, created by `cancel_notifier`.
+    #[cfg(not(target_os = "wasi"))]
+    cancel_waker: Mutex<Option<Arc<sys::CancelWaker>>>,
 }
 
+/// Token used by the waker of [`Poll::cancel_notifier`], reserved once it's
+/// created.
+#[cfg(not(target_os = "wasi"))]
+const CANCEL_TOKEN: Token = Token(usize::MAX);
+
 /// Registers I/O resources.
 pub struct Registry {
     selector: sys::Selector,
     /// Whether this selector currently has an associated waker.
     #[cfg(all(debug_assertions, not(target_os = "wasi")))]
     has_waker: Arc<AtomicBool>,
+    /// Whether `CANCEL_TOKEN` is reserved, i.e. `Poll::cancel_notifier` was
+    /// called.
+    #[cfg(not(target_os = "wasi"))]
+    cancel_token_reserved: Arc<AtomicBool>,
+    /// All file descriptors registered using this `Registry` (or its clones),
+    /// used by `Registry::clear` and `Registry::registered_count`.
+    #[cfg(all(unix, feature = "track-registrations"))]
+    registrations: Arc<Registrations>,
+    /// Deadlines set using `Registry::set_deadline`, shared between all cloned
+    /// `Registry`s.
+    deadlines: Arc<Mutex<Deadlines>>,
+}
+
+/// File descriptors registered using a `Registry`, or any of its clones, see
+/// [`Registry::clear`].
+#[cfg(all(unix, feature = "track-registrations"))]
+#[derive(Debug, Default)]
+pub(crate) struct Registrations {
+    fds: Mutex<HashMap<RawFd, Registration>>,
+    /// Number of `Waker`s alive, see [`CountedWaker`].
+    wakers: AtomicUsize,
+}
+
+#[cfg(all(unix, feature = "track-registrations"))]
+#[derive(Copy, Clone, Debug)]
+struct Registration {
+    token: Token,
+    interests: Interest,
+    /// Device and inode number of the registered file, to detect file
+    /// descriptors that were closed (without being deregistered) and reused.
+    file: (u64, u64),
+}
+
+#[cfg(all(unix, feature = "track-registrations"))]
+impl Registrations {
+    fn get(&self, fd: RawFd) -> Option<(Token, Interest)> {
+        let fds = self.fds.lock().unwrap();
+        fds.get(&fd)
+            .map(|registration| (registration.token, registration.interests))
+    }
+
+    fn remove(&self, fd: RawFd) {
+        let _ = self.fds.lock().unwrap().remove(&fd);
+    }
+
+    /// Remove all registrations from `selector`, calling `on_error` for the
+    /// ones that fail.
+    ///
+    /// File descriptors that no longer refer to the registered file are not
+    /// deregistered, they were closed without being deregistered (which
+    /// already removed them from the selector, or they're removed once
+    /// they're reused) and might now belong to a source that isn't tracked,
+    /// e.g. a `Waker`.
+    fn clear<F>(&self, selector: &sys::Selector, mut on_error: F)
+    where
+        F: FnMut(Token, io::Error),
+    {
+        let fds = std::mem::take(&mut *self.fds.lock().unwrap());
+        for (fd, registration) in fds {
+            trace!("deregistering tracked file descriptor: fd={}", fd);
+            let res = match sys::file_id(fd) {
+                Ok(file) if file == registration.file => selector.deregister(fd),
+                Ok(_) => Err(Error::NotRegistered.into()),
+                Err(err) => Err(err),
+            };
+            if let Err(err) = res {
+                on_error(registration.token, err);
+            }
+        }
+    }
+}
+
+/// Removes the file descriptor of an I/O source from the registrations
+/// tracked by its `Registry` when dropped, see [`Registry::untrack_on_drop`].
+///
+/// Without the `track-registrations` feature this is zero sized and does
+/// nothing.
+#[cfg(all(
+    any(unix, target_os = "hermit"),
+    any(feature = "net", feature = "os-ext")
+))]
+#[derive(Debug, Default)]
+pub(crate) struct UntrackOnDrop {
+    #[cfg(all(unix, feature = "track-registrations"))]
+    inner: Option<(Arc<Registrations>, RawFd)>,
+}
+
+#[cfg(all(
+    any(unix, target_os = "hermit"),
+    any(feature = "net", feature = "os-ext")
+))]
+impl UntrackOnDrop {
+    /// Don't remove the file descriptor when dropped, used when the file
+    /// descriptor outlives the I/O source and stays registered.
+    pub(crate) fn disarm(&mut self) {
+        #[cfg(all(unix, feature = "track-registrations"))]
+        {
+            self.inner = None;
+        }
+    }
+}
+
+#[cfg(all(unix, feature = "track-registrations"))]
+impl Drop for UntrackOnDrop {
+    fn drop(&mut self) {
+        if let Some((registrations, fd)) = self.inner.take() {
+            registrations.remove(fd);
+        }
+    }
+}
+
+/// Counts a [`Waker`] in [`Registry::registered_count`] while it's alive.
+///
+/// [`Waker`]: crate::Waker
+#[cfg(all(unix, feature = "track-registrations"))]
+#[derive(Debug)]
+pub(crate) struct CountedWaker {
+    registrations: Arc<Registrations>,
+}
+
+#[cfg(all(unix, feature = "track-registrations"))]
+impl Drop for CountedWaker {
+    fn drop(&mut self) {
+        let _ = self.registrations.wakers.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// Deadlines per token, see [`Registry::set_deadline`].
+#[derive(Debug, Default)]
+struct Deadlines {
+    /// Deadlines sorted by time.
+    sorted: BTreeSet<(Instant, Token)>,
+    /// Deadline per token.
+    tokens: BTreeMap<Token, Instant>,
+}
+
+impl Deadlines {
+    fn set(&mut self, token: Token, deadline: Instant) {
+        if let Some(old) = self.tokens.insert(token, deadline) {
+            let _ = self.sorted.remove(&(old, token));
+        }
+        let _ = self.sorted.insert((deadline, token));
+    }
+
+    fn clear(&mut self, token: Token) {
+        if let Some(old) = self.tokens.remove(&token) {
+            let _ = self.sorted.remove(&(old, token));
+        }
+    }
+
+    fn next(&self) -> Option<Instant> {
+        self.sorted.first().map(|(deadline, _)| *deadline)
+    }
+
+    /// Remove all deadlines before or at `now`, adding their tokens to
+    /// `expired`.
+    fn expire(&mut self, now: Instant, expired: &mut Vec<Token>) {
+        while let Some(&(deadline, token)) = self.sorted.first() {
+            if deadline > now {
+                break;
+            }
+            let _ = self.sorted.pop_first();
+            let _ = self.tokens.remove(&token);
+            expired.push(token);
+        }
+    }
 }
 
 impl Poll {
@@ -324,9 +517,54 @@ impl Poll {
                     selector,
                     #[cfg(all(debug_assertions, not(target_os = "wasi")))]
                     has_waker: Arc::new(AtomicBool::new(false)),
+                    #[cfg(not(target_os = "wasi"))]
+                    cancel_token_reserved: Arc::new(AtomicBool::new(false)),
+                    #[cfg(all(unix, feature = "track-registrations"))]
+                    registrations: Arc::new(Registrations::default()),
+                    deadlines: Arc::new(Mutex::new(Deadlines::default())),
                 },
+                #[cfg(not(target_os = "wasi"))]
+                cancel_waker: Mutex::new(None),
             })
         }
+
+        /// Return a new `Poll` handle, with room for `expected_sources`
+        /// registrations.
+        ///
+        /// This is the same as [`Poll::new`], but pre-sizes Mio's internal
+        /// per-source bookkeeping to hold `expected_sources` without
+        /// reallocating. More sources than `expected_sources` can be registered,
+        /// this is only a hint.
+        ///
+        /// Note that not all selectors keep any per-source state, e.g. epoll and
+        /// kqueue keep all state in the kernel, in which case this is the same
+        /// as [`Poll::new`].
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// # use std::error::Error;
+        /// # fn main() -> Result<(), Box<dyn Error>> {
+        /// use mio::Poll;
+        ///
+        /// // A server expecting a lot of connections.
+        /// let poll = Poll::with_capacity(10_000)?;
+        /// # drop(poll);
+        /// #     Ok(())
+        /// # }
+        /// ```
+        pub fn with_capacity(expected_sources: usize) -> io::Result<Poll> {
+            let poll = Poll::new()?;
+            poll.registry.selector.reserve(expected_sources);
+            #[cfg(all(unix, feature = "track-registrations"))]
+            poll.registry
+                .registrations
+                .fds
+                .lock()
+                .unwrap()
+                .reserve(expected_sources);
+            Ok(poll)
+        }
     }
 
     /// Create a separate `Registry` which can be used to register
@@ -335,6 +573,106 @@ impl Poll {
         &self.registry
     }
 
+    /// Convert this `Poll` into a [`SharedPoll`], which can be polled from
+    /// multiple threads at the same time.
+    ///
+    /// Event sources registered with this `Poll` stay registered.
+    pub fn into_shared(self) -> SharedPoll {
+        let Poll { registry, .. } = self;
+        SharedPoll {
+            registry,
+            select_lock: Mutex::new(()),
+        }
+    }
+
+    /// Returns the capabilities of the OS selector used by this `Poll` instance.
+    ///
+    /// This allows code to adapt to what the running system supports, e.g. the
+    /// precision of timeouts, without having to match on the target OS. The
+    /// capabilities are determined once (per process), if needed by probing the
+    /// kernel.
+    ///
+    /// # Examples
+    ///
+    #[cfg_attr(feature = "os-poll", doc = "```")]
+    #[cfg_attr(not(feature = "os-poll"), doc = "```ignore")]
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use std::time::Duration;
+    ///
+    /// use mio::Poll;
+    ///
+    /// let poll = Poll::new()?;
+    /// let capabilities = poll.capabilities();
+    ///
+    /// // Timer wheels can use a finer granularity if the selector supports it.
+    /// let tick = if capabilities.precise_timeout() {
+    ///     Duration::from_micros(100)
+    /// } else {
+    ///     Duration::from_millis(1)
+    /// };
+    /// #     _ = tick;
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn capabilities(&self) -> Capabilities {
+        self.registry.selector.capabilities()
+    }
+
+    /// Deregister all event sources and close the `Poll` instance.
+    ///
+    /// This is the same as [`Registry::clear`] followed by dropping the `Poll`
+    /// instance, but it returns the errors deregistering the sources, with
+    /// the token they were registered with, rather than ignoring them. For
+    /// example if a source registered using [`SourceFd`] was closed without
+    /// being deregistered first, in which case the error is `EBADF`, or an
+    /// error of kind [`NotRegistered`] if the file descriptor has been reused
+    /// since. The errors are sorted by token. Like for [`Registry::clear`]
+    /// the sources themselves are not closed.
+    ///
+    /// Afterwards the `Poll` instance is consumed, the selector is closed once
+    /// all clones of its `Registry` (see [`Registry::try_clone`]) are dropped
+    /// as well.
+    ///
+    /// [`SourceFd`]: crate::unix::SourceFd
+    /// [`NotRegistered`]: crate::Error::NotRegistered
+    ///
+    /// # Notes
+    ///
+    /// This is only available on Unix, with the `track-registrations` feature
+    /// enabled.
+    ///
+    /// # Examples
+    ///
+    #[cfg_attr(all(feature = "os-poll", feature = "net"), doc = "```")]
+    #[cfg_attr(not(all(feature = "os-poll", feature = "net")), doc = "```ignore")]
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mio::net::TcpListener;
+    /// use mio::{Interest, Poll, Token};
+    ///
+    /// let poll = Poll::new()?;
+    /// let mut listener = TcpListener::bind("127.0.0.1:0".parse()?)?;
+    /// poll.registry().register(&mut listener, Token(0), Interest::READABLE)?;
+    ///
+    /// for (token, err) in poll.shutdown() {
+    ///     eprintln!("failed to deregister source with {token:?}: {err}");
+    /// }
+    /// #     Ok(())
+    /// # }
+    /// ```
+    #[cfg(all(unix, feature = "track-registrations"))]
+    #[cfg_attr(docsrs, doc(cfg(all(unix, feature = "track-registrations"))))]
+    pub fn shutdown(self) -> Vec<(Token, io::Error)> {
+        let registry = &self.registry;
+        let mut errors = Vec::new();
+        registry
+            .registrations
+            .clear(&registry.selector, |token, err| errors.push((token, err)));
+        errors.sort_by_key(|(token, _)| *token);
+        errors
+    }
+
     /// Wait for readiness events
     ///
     /// Blocks the current thread and waits for readiness events for any of the
@@ -378,6 +716,12 @@ impl Poll {
     /// triggering this will return `Ok(())`. However we're not guaranteeing
     /// this behaviour as this depends on the OS.
     ///
+    /// Because spurious wake ups can also return without any events, an empty
+    /// `events` doesn't mean the `timeout` elapsed. Use [`Poll::poll_ext`] to
+    /// determine whether the call timed out, it uses the result of the OS wait
+    /// (e.g. `epoll_wait(2)` returning zero events once the timeout elapsed)
+    /// to report a time out separately from spurious wake ups.
+    ///
     /// # Examples
     ///
     /// A basic example -- establishing a `TcpStream` connection.
@@ -433,7 +777,566 @@ impl Poll {
     ///
     /// [struct]: #
     pub fn poll(&mut self, events: &mut Events, timeout: Option<Duration>) -> io::Result<()> {
-        self.registry.selector.select(events.sys(), timeout)
+        self.registry
+            .selector
+            .select(events.sys(), timeout)
+            .map(|_| ())
+    }
+
+    /// Poll for readiness events without blocking.
+    ///
+    /// This is the same as calling [`poll`] with a zero timeout
+    /// (`Some(Duration::ZERO)`): it returns immediately with the events that
+    /// are ready, which might be none. Note that passing `None` as timeout to
+    /// `poll` means blocking until an event is ready, not the opposite.
+    ///
+    /// [`poll`]: Poll::poll
+    ///
+    /// # Examples
+    ///
+    #[cfg_attr(feature = "os-poll", doc = "```")]
+    #[cfg_attr(not(feature = "os-poll"), doc = "```ignore")]
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mio::{Events, Poll};
+    ///
+    /// let mut poll = Poll::new()?;
+    /// let mut events = Events::with_capacity(16);
+    ///
+    /// // Nothing is registered, but this returns immediately.
+    /// poll.poll_now(&mut events)?;
+    /// assert!(events.is_empty());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn poll_now(&mut self, events: &mut Events) -> io::Result<()> {
+        self.poll(events, Some(Duration::ZERO))
+    }
+
+    /// Same as [`poll`], but reports why the call returned.
+    ///
+    /// See [`PollStatus`] for the possible outcomes. Unlike `poll` an
+    /// interrupted call (`EINTR`) is not returned as an error, but as
+    /// [`PollStatus::Interrupted`].
+    ///
+    /// [`poll`]: Poll::poll
+    ///
+    /// # Examples
+    ///
+    #[cfg_attr(feature = "os-poll", doc = "```")]
+    #[cfg_attr(not(feature = "os-poll"), doc = "```ignore")]
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use std::time::Duration;
+    ///
+    /// use mio::{Events, Poll, PollStatus};
+    ///
+    /// let mut poll = Poll::new()?;
+    /// let mut events = Events::with_capacity(16);
+    ///
+    /// // Nothing is registered, so this will time out.
+    /// let status = poll.poll_ext(&mut events, Some(Duration::from_millis(10)))?;
+    /// assert_eq!(status, PollStatus::TimedOut);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn poll_ext(
+        &mut self,
+        events: &mut Events,
+        timeout: Option<Duration>,
+    ) -> io::Result<PollStatus> {
+        // The selector reports whether the OS wait (e.g. `epoll_wait(2)`) timed
+        // out, rather than `events` being empty.
+        match self.registry.selector.select(events.sys(), timeout) {
+            Ok(true) => Ok(PollStatus::TimedOut),
+            Ok(false) => Ok(PollStatus::Events(events.iter().count())),
+            Err(ref err) if err.kind() == io::ErrorKind::Interrupted => Ok(PollStatus::Interrupted),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Same as [`poll`], but returns early if `cancel` is set.
+    ///
+    /// Returns `true` if the call returned because `cancel` is set, `false`
+    /// otherwise. If `cancel` is already set when this is called it returns
+    /// `true` immediately, without polling. Otherwise it polls for events
+    /// like `poll`, which returns once [`CancelNotifier::notify`] is called.
+    /// So to cancel the call, e.g. on shutdown, first set `cancel` and then
+    /// call `notify` on a notifier returned by [`Poll::cancel_notifier`]. Note
+    /// that even if this returns `true` `events` can contain events.
+    ///
+    /// This is the same as using a [`Waker`] to wake up the poll call, but
+    /// this doesn't require the caller to manage a token. Instead the
+    /// notifications are removed from `events`.
+    ///
+    /// [`poll`]: Poll::poll
+    /// [`Waker`]: crate::Waker
+    ///
+    /// # Notes
+    ///
+    /// This is not available on WASI.
+    ///
+    /// The notifications use `Token(usize::MAX)`, which is reserved once
+    /// [`Poll::cancel_notifier`] is called: registering an event source (or a
+    /// [`Waker`]) with it returns an [`Error::ReservedToken`] error. Event
+    /// sources registered with it before that must be reregistered with a
+    /// different token, as their events are removed by this function. The
+    /// notifications are only removed by this function, calls to `poll` return
+    /// them as readable events.
+    ///
+    /// [`Error::ReservedToken`]: crate::Error::ReservedToken
+    ///
+    /// # Examples
+    ///
+    #[cfg_attr(feature = "os-poll", doc = "```")]
+    #[cfg_attr(not(feature = "os-poll"), doc = "```ignore")]
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use std::sync::atomic::{AtomicBool, Ordering};
+    /// use std::sync::Arc;
+    /// use std::thread;
+    ///
+    /// use mio::{Events, Poll};
+    ///
+    /// let mut poll = Poll::new()?;
+    /// let mut events = Events::with_capacity(16);
+    /// let shutdown = Arc::new(AtomicBool::new(false));
+    ///
+    /// let notifier = poll.cancel_notifier()?;
+    /// let flag = shutdown.clone();
+    /// let handle = thread::spawn(move || {
+    ///     flag.store(true, Ordering::Release);
+    ///     notifier.notify().expect("unable to notify");
+    /// });
+    ///
+    /// // Without a timeout this only returns once the other thread sets the
+    /// // flag (or a spurious wake up happens).
+    /// while !poll.poll_cancellable(&mut events, None, &shutdown)? {
+    ///     // Process the events.
+    /// }
+    /// # handle.join().unwrap();
+    /// #     Ok(())
+    /// # }
+    /// ```
+    #[cfg(not(target_os = "wasi"))]
+    pub fn poll_cancellable(
+        &mut self,
+        events: &mut Events,
+        timeout: Option<Duration>,
+        cancel: &AtomicBool,
+    ) -> io::Result<bool> {
+        if cancel.load(Ordering::Acquire) {
+            events.clear();
+            return Ok(true);
+        }
+
+        self.poll(events, timeout)?;
+        if events.iter().any(|event| event.token() == CANCEL_TOKEN) {
+            events.retain(|event| event.token() != CANCEL_TOKEN);
+            if let Some(waker) = &*self.cancel_waker.get_mut().unwrap() {
+                waker.rearm(&self.registry.selector, CANCEL_TOKEN)?;
+            }
+        }
+        Ok(cancel.load(Ordering::Acquire))
+    }
+
+    /// Returns a [`CancelNotifier`] to wake up [`Poll::poll_cancellable`].
+    ///
+    /// The first call registers an internal waker, which uses one file
+    /// descriptor (on platforms that use file descriptors for it), with `Poll`.
+    /// Later calls return notifiers using the same waker.
+    ///
+    /// # Notes
+    ///
+    /// This is not available on WASI.
+    #[cfg(not(target_os = "wasi"))]
+    pub fn cancel_notifier(&self) -> io::Result<CancelNotifier> {
+        let mut cancel_waker = self.cancel_waker.lock().unwrap();
+        let waker = match &*cancel_waker {
+            Some(waker) => waker.clone(),
+            None => {
+                let waker = sys::CancelWaker::new(&self.registry.selector, CANCEL_TOKEN)?;
+                self.registry
+                    .cancel_token_reserved
+                    .store(true, Ordering::Release);
+                cancel_waker.insert(Arc::new(waker)).clone()
+            }
+        };
+        Ok(CancelNotifier::new(waker))
+    }
+
+    /// Same as [`poll`], but blocks until `deadline`, rather than for a
+    /// timeout, returning the time at which the call returned.
+    ///
+    /// The returned `Instant` can be used to compute the remaining time until
+    /// `deadline` (using [`Instant::saturating_duration_since`]) or the next
+    /// deadline, without reading the clock again. If `deadline` already passed
+    /// this doesn't block, but it still polls for events.
+    ///
+    /// Like [`poll`] this can return before `deadline` without any events,
+    /// i.e. a spurious wake up. Because the OS rounds the timeout up it
+    /// doesn't time out before `deadline`, but it can return (slightly) after
+    /// it.
+    ///
+    /// [`poll`]: Poll::poll
+    ///
+    /// # Examples
+    ///
+    #[cfg_attr(feature = "os-poll", doc = "```")]
+    #[cfg_attr(not(feature = "os-poll"), doc = "```ignore")]
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use std::time::{Duration, Instant};
+    ///
+    /// use mio::{Events, Poll};
+    ///
+    /// let mut poll = Poll::new()?;
+    /// let mut events = Events::with_capacity(1024);
+    ///
+    /// // Register event sources with `poll`.
+    ///
+    /// let deadline = Instant::now() + Duration::from_millis(10);
+    /// loop {
+    ///     let now = poll.poll_deadline(&mut events, deadline)?;
+    ///     for event in events.iter() {
+    ///         println!("Got an event for {:?}", event.token());
+    ///     }
+    ///     if now >= deadline {
+    ///         break;
+    ///     }
+    ///     println!("{:?} remaining", deadline.saturating_duration_since(now));
+    /// }
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn poll_deadline(&mut self, events: &mut Events, deadline: Instant) -> io::Result<Instant> {
+        let timeout = deadline.saturating_duration_since(Instant::now());
+        self.poll(events, Some(timeout))?;
+        Ok(Instant::now())
+    }
+
+    /// Same as [`poll`], but also reports the tokens of which the deadline,
+    /// set using [`Registry::set_deadline`], expired.
+    ///
+    /// The `timeout` is shortened to the nearest deadline, so that this
+    /// returns once a deadline expires, even if no events are ready. After
+    /// polling for events the tokens of all expired deadlines are written
+    /// to `expired` (which is cleared first), sorted by deadline, and their
+    /// deadlines are removed. A token can be in both `events` and `expired`.
+    ///
+    /// The event sources of expired tokens are **not** deregistered, Mio
+    /// doesn't own the sources. It's up to the caller to deregister (or drop)
+    /// the source, or to set a new deadline.
+    ///
+    /// [`poll`]: Poll::poll
+    ///
+    /// # Accuracy
+    ///
+    /// Deadlines never expire early, but can be reported late. The timeout
+    /// passed to the OS is rounded up to the precision of the OS selector,
+    /// which is a millisecond for `epoll_wait(2)` (Linux and Android) and
+    /// `poll(2)`, and (at best) a nanosecond for `kqueue(2)`. On top of that
+    /// the OS can wake the thread later than requested, depending on the load
+    /// of the system and timer slack.
+    ///
+    /// Deadlines changed by another thread, using a cloned [`Registry`],
+    /// while this is blocked waiting for events are only taken into account
+    /// once it returns, use a [`Waker`] to wake it up.
+    ///
+    /// [`Waker`]: crate::Waker
+    ///
+    /// # Examples
+    ///
+    #[cfg_attr(all(feature = "os-poll", feature = "net"), doc = "```")]
+    #[cfg_attr(not(all(feature = "os-poll", feature = "net")), doc = "```ignore")]
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use std::time::{Duration, Instant};
+    ///
+    /// use mio::net::UdpSocket;
+    /// use mio::{Events, Interest, Poll, Token};
+    ///
+    /// const IDLE_TIMEOUT: Duration = Duration::from_millis(10);
+    ///
+    /// let mut poll = Poll::new()?;
+    /// let mut events = Events::with_capacity(16);
+    /// let mut expired = Vec::new();
+    ///
+    /// let mut socket = UdpSocket::bind("127.0.0.1:0".parse()?)?;
+    /// poll.registry().register_with_deadline(
+    ///     &mut socket,
+    ///     Token(0),
+    ///     Interest::READABLE,
+    ///     Instant::now() + IDLE_TIMEOUT,
+    /// )?;
+    ///
+    /// // No datagrams are received, so this returns once the deadline expires.
+    /// poll.poll_with_deadlines(&mut events, &mut expired, None)?;
+    /// # // Could be a spurious wake up, but unlikely.
+    /// assert_eq!(expired, [Token(0)]);
+    ///
+    /// // The socket has been idle for too long.
+    /// poll.registry().deregister(&mut socket)?;
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn poll_with_deadlines(
+        &mut self,
+        events: &mut Events,
+        expired: &mut Vec<Token>,
+        timeout: Option<Duration>,
+    ) -> io::Result<()> {
+        expired.clear();
+        let timeout = match self.registry.deadlines.lock().unwrap().next() {
+            Some(deadline) => {
+                let until = deadline.saturating_duration_since(Instant::now());
+                Some(timeout.map_or(until, |timeout| timeout.min(until)))
+            }
+            None => timeout,
+        };
+        self.poll(events, timeout)?;
+        self.registry
+            .deadlines
+            .lock()
+            .unwrap()
+            .expire(Instant::now(), expired);
+        Ok(())
+    }
+}
+
+/// A [`Poll`] instance that can be polled from multiple threads at the same
+/// time.
+///
+/// `Poll::poll` takes `&mut self`, so only a single thread can poll for
+/// events. `SharedPoll::poll` takes `&self`, allowing multiple threads to poll
+/// the same instance (e.g. shared using an `Arc`), distributing the events
+/// across the threads. Created using [`SharedPoll::new`] or
+/// [`Poll::into_shared`].
+///
+/// # Delivery contract
+///
+/// Mio's registrations are edge-triggered, so each event is returned by
+/// exactly one call to [`SharedPoll::poll`], i.e. to a single thread. However
+/// once the source becomes ready again a new event is created, which can be
+/// returned to another thread, while the first thread is still handling the
+/// first event. Thus the same source can be handled by multiple threads at the
+/// same time, which can be prevented by:
+///  * deregistering the source when receiving an event and registering it
+///    again once the event is handled, which works like a one-shot
+///    registration, or
+///  * synchronising access to the source, e.g. using a `Mutex`, and handling
+///    all readiness (until a [`WouldBlock`] error is returned) in the thread
+///    that holds the lock.
+///
+/// [`WouldBlock`]: io::ErrorKind::WouldBlock
+///
+/// # Implementation notes
+///
+/// Concurrent polling is supported by epoll and kqueue. Using the other
+/// selectors (`poll(2)`, io_uring, IOCP and WASI) calls to `SharedPoll::poll`
+/// are serialised using a lock, this still allows multiple threads to poll,
+/// but only a single thread waits for events at a time.
+///
+/// # Examples
+///
+#[cfg_attr(all(feature = "os-poll", feature = "net"), doc = "```")]
+#[cfg_attr(not(all(feature = "os-poll", feature = "net")), doc = "```ignore")]
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use std::sync::Arc;
+/// use std::thread;
+/// use std::time::Duration;
+///
+/// use mio::net::UdpSocket;
+/// use mio::{Events, Interest, SharedPoll, Token};
+///
+/// let poll = Arc::new(SharedPoll::new()?);
+/// let mut socket = UdpSocket::bind("127.0.0.1:0".parse()?)?;
+/// poll.registry().register(&mut socket, Token(0), Interest::READABLE)?;
+///
+/// let handles: Vec<_> = (0..4)
+///     .map(|_| {
+///         let poll = poll.clone();
+///         thread::spawn(move || {
+///             let mut events = Events::with_capacity(64);
+///             poll.poll(&mut events, Some(Duration::from_millis(10)))
+///                 .expect("failed to poll");
+///             for event in events.iter() {
+///                 println!("got an event: {event:?}");
+///             }
+///         })
+///     })
+///     .collect();
+/// for handle in handles {
+///     handle.join().unwrap();
+/// }
+/// #     Ok(())
+/// # }
+/// ```
+pub struct SharedPoll {
+    registry: Registry,
+    /// Serialises the calls to `select` if the selector doesn't support
+    /// concurrent polling, see [`CONCURRENT_SELECT`].
+    select_lock: Mutex<()>,
+}
+
+/// Whether the selector supports calling `select` from multiple threads at
+/// the same time, each event is only returned to a single thread.
+const CONCURRENT_SELECT: bool = cfg!(all(
+    not(mio_unsupported_force_poll_poll),
+    any(
+        all(
+            not(all(feature = "io-uring", target_os = "linux")),
+            any(
+                target_os = "android",
+                target_os = "illumos",
+                target_os = "linux",
+                target_os = "redox",
+            )
+        ),
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "ios",
+        target_os = "macos",
+        target_os = "netbsd",
+        target_os = "openbsd",
+        target_os = "tvos",
+        target_os = "visionos",
+        target_os = "watchos",
+    )
+));
+
+impl SharedPoll {
+    cfg_os_poll! {
+        /// Return a new `SharedPoll` handle.
+        ///
+        /// This is the same as calling [`Poll::new`] followed by
+        /// [`Poll::into_shared`].
+        pub fn new() -> io::Result<SharedPoll> {
+            Poll::new().map(Poll::into_shared)
+        }
+    }
+
+    /// Returns the `Registry` of this `SharedPoll`, used to register
+    /// `event::Source`s.
+    pub fn registry(&self) -> &Registry {
+        &self.registry
+    }
+
+    /// Wait for readiness events, see [`Poll::poll`].
+    ///
+    /// This can be called from multiple threads at the same time, each
+    /// thread must use its own `events`. See the [delivery contract] for the
+    /// guarantees about which thread receives an event.
+    ///
+    /// [delivery contract]: SharedPoll#delivery-contract
+    pub fn poll(&self, events: &mut Events, timeout: Option<Duration>) -> io::Result<()> {
+        let _guard = (!CONCURRENT_SELECT).then(|| self.select_lock.lock().unwrap());
+        self.registry
+            .selector
+            .select(events.sys(), timeout)
+            .map(|_| ())
+    }
+}
+
+impl fmt::Debug for SharedPoll {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("SharedPoll").finish()
+    }
+}
+
+/// Outcome of a call to [`Poll::poll_ext`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum PollStatus {
+    /// The call returned with the provided number of events.
+    ///
+    /// This can be `Events(0)` if the OS returned before the timeout elapsed,
+    /// but without any events for the caller, i.e. a spurious wake up. For
+    /// example when all events were internal notifications of the selector.
+    Events(usize),
+    /// The timeout elapsed without any events.
+    ///
+    /// This is reported by the OS selector, e.g. `epoll_wait(2)` returning
+    /// zero events, `ETIME` for io_uring or `WAIT_TIMEOUT` for IOCP, not
+    /// determined by the time the call took.
+    TimedOut,
+    /// The call was interrupted, e.g. by a signal (`EINTR`), before any events
+    /// were received or the timeout elapsed.
+    Interrupted,
+}
+
+/// Capabilities of the OS selector, returned by [`Poll::capabilities`].
+///
+/// Mio itself always uses edge-triggered notifications (where the OS supports
+/// them), but knowing what the OS selector supports is useful when, for
+/// example, registering file descriptors directly with the selector using the
+/// raw file descriptor of [`Poll`].
+///
+/// | OS selector | `precise_timeout` | `exclusive` | `oneshot` | `level_trigger` |
+/// |-------------|-------------------|-------------|-----------|-----------------|
+/// | epoll       | no                | probed      | yes       | yes             |
+/// | io_uring    | yes               | no          | yes       | yes             |
+/// | kqueue      | yes               | no          | yes       | yes             |
+/// | poll        | no                | no          | no        | yes             |
+/// | IOCP        | no                | no          | yes       | no              |
+/// | WASI        | yes               | no          | no        | yes             |
+///
+/// `priority_interest` is supported on Android and Linux, see
+/// [`Interest::PRIORITY`].
+///
+#[cfg_attr(
+    any(target_os = "android", target_os = "linux"),
+    doc = "[`Interest::PRIORITY`]: crate::Interest::PRIORITY"
+)]
+#[cfg_attr(
+    not(any(target_os = "android", target_os = "linux")),
+    doc = "[`Interest::PRIORITY`]: crate::Interest"
+)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Capabilities {
+    pub(crate) precise_timeout: bool,
+    pub(crate) exclusive: bool,
+    pub(crate) oneshot: bool,
+    pub(crate) priority_interest: bool,
+    pub(crate) level_trigger: bool,
+}
+
+impl Capabilities {
+    /// Returns true if timeouts are passed to the OS with (at least)
+    /// microsecond precision, rather than being rounded up to milliseconds.
+    pub const fn precise_timeout(self) -> bool {
+        self.precise_timeout
+    }
+
+    /// Returns true if the OS supports exclusive wake ups, i.e. only waking one
+    /// of the selectors waiting on the same source (`EPOLLEXCLUSIVE`).
+    pub const fn exclusive(self) -> bool {
+        self.exclusive
+    }
+
+    /// Returns true if the OS supports one shot registrations, which are
+    /// disabled after a single event (`EPOLLONESHOT`, `EV_ONESHOT`).
+    pub const fn oneshot(self) -> bool {
+        self.oneshot
+    }
+
+    /// Returns true if [`Interest::PRIORITY`] is supported.
+    ///
+    #[cfg_attr(
+        any(target_os = "android", target_os = "linux"),
+        doc = "[`Interest::PRIORITY`]: crate::Interest::PRIORITY"
+    )]
+    #[cfg_attr(
+        not(any(target_os = "android", target_os = "linux")),
+        doc = "[`Interest::PRIORITY`]: crate::Interest"
+    )]
+    pub const fn priority_interest(self) -> bool {
+        self.priority_interest
+    }
+
+    /// Returns true if the OS supports level-triggered notifications.
+    pub const fn level_trigger(self) -> bool {
+        self.level_trigger
     }
 }
 
@@ -504,6 +1407,14 @@ impl Registry {
     /// call to [`deregister`] has already occurred. Consecutive calls to
     /// `register` is unspecified behavior.
     ///
+    /// If a source is closed without being deregistered its file descriptor
+    /// can be reused by a new source. Once registered events for the old
+    /// token may still be returned, which are easily mistaken for events of
+    /// the new source. On Unix, with the `track-registrations` feature and
+    /// debug assertions enabled, Mio logs a warning if a file descriptor is
+    /// registered while it's still tracked as registered using a different
+    /// token.
+    ///
     /// Unless otherwise specified, the caller should assume that once an event
     /// source is registered with a `Poll` instance, it is bound to that `Poll`
     /// instance for the lifetime of the event source. This remains true even
@@ -573,6 +1484,7 @@ impl Registry {
             token,
             interests
         );
+        self.check_token(token)?;
         source.register(self, token, interests)
     }
 
@@ -641,6 +1553,7 @@ impl Registry {
             token,
             interests
         );
+        self.check_token(token)?;
         source.reregister(self, token, interests)
     }
 
@@ -654,6 +1567,14 @@ impl Registry {
     /// as a happens-before relationship is established between this call and
     /// the poll.
     ///
+    /// Deregistering is not deferred on any platform, there is nothing to
+    /// flush: once this returns the source can be closed and its file
+    /// descriptor (or socket handle) reused for a new registration, without
+    /// events for the old token showing up. Readiness of the source that the
+    /// OS already queued is dropped, e.g. a completion for an io_uring poll
+    /// operation that was cancelled by deregistering. Events already returned
+    /// by [`Poll::poll`] (i.e. in an [`Events`] value) are not affected.
+    ///
     /// The event source must have previously been registered with this instance
     /// of `Poll`, otherwise the behavior is unspecified.
     ///
@@ -703,6 +1624,550 @@ impl Registry {
         source.deregister(self)
     }
 
+    /// Replace the registration of `old` with `new`, using `token` and
+    /// `interests`.
+    ///
+    /// This deregisters `old` and then registers `new`, for example to replace
+    /// a connection with a wrapper around it, without the caller having to
+    /// deal with the intermediate state. If registering `new` fails `old` is
+    /// registered again using `token` and `interests`, and the original error
+    /// is returned.
+    ///
+    /// # Notes
+    ///
+    /// The two steps are not atomic, but no readiness of `new` is lost: on all
+    /// platforms registering a source reports its current readiness, so any
+    /// readiness that occurred before the swap is returned by the next call to
+    /// [`Poll::poll`]. Once this returns no new events will be created for
+    /// `old`. However events for `old` that were already returned by a call to
+    /// [`Poll::poll`] on another thread, racing with this call, can still be
+    /// processed by that thread using `token`. Platform specific details:
+    ///
+    ///  * epoll/kqueue/poll(2): deregistering is synchronous, so events for
+    ///    `old` are never returned after `swap` returns, as long as a
+    ///    happens-before relationship is established between this call and the
+    ///    poll (same as for [`deregister`]).
+    ///  * Windows: a pending poll operation for `old` might already have
+    ///    completed in the kernel, it's dropped instead of returned.
+    ///
+    /// [`deregister`]: Registry::deregister
+    ///
+    /// # Examples
+    ///
+    #[cfg_attr(all(feature = "os-poll", feature = "net"), doc = "```")]
+    #[cfg_attr(not(all(feature = "os-poll", feature = "net")), doc = "```ignore")]
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mio::{Poll, Interest, Token};
+    /// use mio::net::UdpSocket;
+    ///
+    /// let poll = Poll::new()?;
+    ///
+    /// let mut old = UdpSocket::bind("127.0.0.1:0".parse()?)?;
+    /// let mut new = UdpSocket::bind("127.0.0.1:0".parse()?)?;
+    ///
+    /// poll.registry().register(&mut old, Token(0), Interest::READABLE)?;
+    ///
+    /// // Replace `old` with `new`, keeping the same token.
+    /// poll.registry().swap(&mut old, &mut new, Token(0), Interest::READABLE)?;
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn swap<O, N>(
+        &self,
+        old: &mut O,
+        new: &mut N,
+        token: Token,
+        interests: Interest,
+    ) -> io::Result<()>
+    where
+        O: event::Source + ?Sized,
+        N: event::Source + ?Sized,
+    {
+        self.deregister(old)?;
+        if let Err(err) = self.register(new, token, interests) {
+            if let Err(err) = self.register(old, token, interests) {
+                warn!("error restoring registration after failed swap: {}", err);
+            }
+            return Err(err);
+        }
+        Ok(())
+    }
+
+    /// Add or remove [`Interest::WRITABLE`] for `source`, keeping its other
+    /// interests.
+    ///
+    /// A common pattern is to only be interested in writable events while
+    /// there is buffered data to write, as otherwise every poll would return a
+    /// writable event. This method takes care of the required reregistering:
+    ///
+    /// * If `source` isn't registered an error of kind [`NotRegistered`] is
+    ///   returned.
+    /// * If `writable` is `false` and `source` is only registered with writable
+    ///   interest it's reregistered with [`Interest::error_only`], so it stays
+    ///   registered (and keeps receiving error and hang up events).
+    /// * Otherwise `source` is reregistered with writable interest added or
+    ///   removed, if that changes its interests or `token`.
+    ///
+    /// The current interests are retrieved from the file descriptors Mio
+    /// tracks for all its event sources (see [`clear`]), which is why `source`
+    /// must implement `AsRawFd`. This means `source` must have been registered
+    /// using its own file descriptor, e.g. a source wrapping a
+    /// [`TcpStream`] should return the stream's file descriptor.
+    ///
+    /// [`NotRegistered`]: crate::Error::NotRegistered
+    /// [`clear`]: Registry::clear
+    /// [`TcpStream`]: crate::net::TcpStream
+    ///
+    /// # Notes
+    ///
+    /// This is only available on Unix, with the `track-registrations` feature
+    /// enabled.
+    ///
+    /// On platforms without [`Interest::error_only`] removing the only
+    /// interest returns an error of kind [`Unsupported`], leaving the
+    /// registration unchanged.
+    ///
+    /// [`Unsupported`]: crate::Error::Unsupported
+    ///
+    /// # Examples
+    ///
+    #[cfg_attr(all(feature = "os-poll", feature = "net"), doc = "```")]
+    #[cfg_attr(not(all(feature = "os-poll", feature = "net")), doc = "```ignore")]
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mio::{Interest, Poll, Token};
+    /// use mio::net::UdpSocket;
+    ///
+    /// let poll = Poll::new()?;
+    /// let mut socket = UdpSocket::bind("127.0.0.1:0".parse()?)?;
+    /// poll.registry().register(&mut socket, Token(0), Interest::READABLE)?;
+    ///
+    /// // We have data to send, but the socket might not be ready for it.
+    /// poll.registry().set_writable_interest(&mut socket, Token(0), true)?;
+    ///
+    /// // Once all data is send we're no longer interested in writable events.
+    /// poll.registry().set_writable_interest(&mut socket, Token(0), false)?;
+    /// #     Ok(())
+    /// # }
+    /// ```
+    #[cfg(all(unix, feature = "track-registrations"))]
+    #[cfg_attr(docsrs, doc(cfg(all(unix, feature = "track-registrations"))))]
+    pub fn set_writable_interest<S>(
+        &self,
+        source: &mut S,
+        token: Token,
+        writable: bool,
+    ) -> io::Result<()>
+    where
+        S: event::Source + AsRawFd + ?Sized,
+    {
+        let Some((current_token, interests)) = self.registrations.get(source.as_raw_fd()) else {
+            return Err(Error::NotRegistered.into());
+        };
+        let new_interests = if writable {
+            interests.add(Interest::WRITABLE)
+        } else {
+            match interests.remove(Interest::WRITABLE) {
+                Some(interests) => interests,
+                #[cfg(any(
+                    target_os = "android",
+                    target_os = "illumos",
+                    target_os = "linux",
+                    target_os = "redox",
+                ))]
+                None => Interest::error_only(),
+                #[cfg(not(any(
+                    target_os = "android",
+                    target_os = "illumos",
+                    target_os = "linux",
+                    target_os = "redox",
+                )))]
+                None => return Err(Error::Unsupported.into()),
+            }
+        };
+        if new_interests == interests && current_token == token {
+            Ok(())
+        } else {
+            self.reregister(source, token, new_interests)
+        }
+    }
+
+    /// Ensure `source` is registered with `token` and `interests`, only
+    /// (re)registering it if that changes its registration.
+    ///
+    /// * If `source` isn't registered it's registered.
+    /// * If `source` is registered with a different `token` or different
+    ///   `interests` it's reregistered.
+    /// * Otherwise this does nothing, not even a system call.
+    ///
+    /// This allows toggling interests, e.g. in response to every write,
+    /// without paying for the (re)registration when nothing changes. Like
+    /// [`set_writable_interest`] the current registration is retrieved from
+    /// the file descriptors Mio tracks for all its event sources, so `source`
+    /// must have been registered using its own file descriptor.
+    ///
+    /// [`set_writable_interest`]: Registry::set_writable_interest
+    ///
+    /// # Notes
+    ///
+    /// This is only available on Unix, with the `track-registrations` feature
+    /// enabled.
+    ///
+    /// # Examples
+    ///
+    #[cfg_attr(all(feature = "os-poll", feature = "net"), doc = "```")]
+    #[cfg_attr(not(all(feature = "os-poll", feature = "net")), doc = "```ignore")]
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mio::{Interest, Poll, Token};
+    /// use mio::net::UdpSocket;
+    ///
+    /// let poll = Poll::new()?;
+    /// let mut socket = UdpSocket::bind("127.0.0.1:0".parse()?)?;
+    ///
+    /// // Registers the socket.
+    /// poll.registry().ensure_interest(&mut socket, Token(0), Interest::READABLE)?;
+    /// // Already registered like this, does nothing.
+    /// poll.registry().ensure_interest(&mut socket, Token(0), Interest::READABLE)?;
+    /// // Reregisters the socket.
+    /// let interests = Interest::READABLE | Interest::WRITABLE;
+    /// poll.registry().ensure_interest(&mut socket, Token(0), interests)?;
+    /// #     Ok(())
+    /// # }
+    /// ```
+    #[cfg(all(unix, feature = "track-registrations"))]
+    #[cfg_attr(docsrs, doc(cfg(all(unix, feature = "track-registrations"))))]
+    pub fn ensure_interest<S>(
+        &self,
+        source: &mut S,
+        token: Token,
+        interests: Interest,
+    ) -> io::Result<()>
+    where
+        S: event::Source + AsRawFd + ?Sized,
+    {
+        let current = self.registrations.get(source.as_raw_fd());
+        match current {
+            None => self.register(source, token, interests),
+            Some(current) if current == (token, interests) => Ok(()),
+            Some(_) => self.reregister(source, token, interests),
+        }
+    }
+
+    /// Register `source` and immediately determine its current readiness.
+    ///
+    /// This registers `source` like [`register`] and afterwards does a
+    /// one-shot, non-blocking check (using `poll(2)` with a zero timeout) of the
+    /// readiness of the file descriptor for `interests`. It returns `None` if
+    /// the source is not ready for any of the `interests`.
+    ///
+    /// This is useful for sources that might already be ready when they're
+    /// registered, e.g. a listener with pending connections or a socket with
+    /// buffered data, which would otherwise only be detected on the next call
+    /// to [`Poll::poll`].
+    ///
+    /// [`register`]: Registry::register
+    ///
+    /// # Notes
+    ///
+    /// Mio uses edge-triggered notifications, this doesn't change that.
+    /// If the source is ready when it's registered the OS selector will still
+    /// deliver an event for that same readiness on the next call to
+    /// [`Poll::poll`], even if the returned readiness was already acted upon.
+    /// Users must be prepared to handle such (spurious) events.
+    ///
+    /// `source` must be registered using its own file descriptor, i.e. the
+    /// one returned by `AsRawFd`.
+    ///
+    /// This is only available on Unix.
+    ///
+    /// # Examples
+    ///
+    #[cfg_attr(all(feature = "os-poll", feature = "net"), doc = "```")]
+    #[cfg_attr(not(all(feature = "os-poll", feature = "net")), doc = "```ignore")]
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mio::{Interest, Poll, Token};
+    /// use mio::net::UdpSocket;
+    ///
+    /// let poll = Poll::new()?;
+    /// let mut socket = UdpSocket::bind("127.0.0.1:0".parse()?)?;
+    ///
+    /// let readiness = poll
+    ///     .registry()
+    ///     .register_and_probe(&mut socket, Token(0), Interest::WRITABLE)?;
+    /// // A new UDP socket can be written to straight away.
+    /// assert!(readiness.unwrap().is_writable());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    #[cfg(all(unix, any(feature = "net", feature = "os-ext")))]
+    pub fn register_and_probe<S>(
+        &self,
+        source: &mut S,
+        token: Token,
+        interests: Interest,
+    ) -> io::Result<Option<event::Readiness>>
+    where
+        S: event::Source + AsRawFd + ?Sized,
+    {
+        self.register(source, token, interests)?;
+        let readiness = sys::probe(source.as_raw_fd(), interests)?;
+        Ok(if readiness.is_empty() {
+            None
+        } else {
+            Some(readiness)
+        })
+    }
+
+    /// Register `source` so that readable and writable events are returned
+    /// using different tokens.
+    ///
+    /// A single registration with the OS selector has one token, so this
+    /// duplicates the file descriptor of `source` (using
+    /// `fcntl(F_DUPFD_CLOEXEC)`). `source` is registered with `read_token`
+    /// for all `interests` except writable interest, the duplicate is
+    /// registered with `write_token` for writable interest. The returned
+    /// [`SplitRegistration`] owns the duplicate, it must be deregistered
+    /// separately from `source`, see its documentation.
+    ///
+    /// `interests` must contain writable interest and at least one other
+    /// interest, otherwise an error of kind [`io::ErrorKind::InvalidInput`]
+    /// is returned; use [`register`] to register a source with a single
+    /// token.
+    ///
+    /// [`SplitRegistration`]: event::SplitRegistration
+    /// [`register`]: Registry::register
+    ///
+    /// # Notes
+    ///
+    /// The duplicate costs an additional file descriptor per source and an
+    /// additional registration with the OS selector, e.g. an extra
+    /// `epoll_ctl(2)` call for each registration, reregistration and
+    /// deregistration. Read and write closed events can be returned for both
+    /// tokens.
+    ///
+    /// This is only available on Unix.
+    ///
+    /// # Examples
+    ///
+    #[cfg_attr(all(feature = "os-poll", feature = "net"), doc = "```")]
+    #[cfg_attr(not(all(feature = "os-poll", feature = "net")), doc = "```ignore")]
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mio::{Interest, Poll, Token};
+    /// use mio::net::UdpSocket;
+    ///
+    /// const READER: Token = Token(0);
+    /// const WRITER: Token = Token(1);
+    ///
+    /// let poll = Poll::new()?;
+    /// let mut socket = UdpSocket::bind("127.0.0.1:0".parse()?)?;
+    ///
+    /// let mut split = poll.registry().register_split(
+    ///     &mut socket,
+    ///     READER,
+    ///     WRITER,
+    ///     Interest::READABLE | Interest::WRITABLE,
+    /// )?;
+    ///
+    /// // Readable events are returned for `READER`, writable events for
+    /// // `WRITER`.
+    ///
+    /// // Both halves must be deregistered.
+    /// poll.registry().deregister(&mut split)?;
+    /// poll.registry().deregister(&mut socket)?;
+    /// #     Ok(())
+    /// # }
+    /// ```
+    #[cfg(all(unix, any(feature = "net", feature = "os-ext")))]
+    pub fn register_split<S>(
+        &self,
+        source: &mut S,
+        read_token: Token,
+        write_token: Token,
+        interests: Interest,
+    ) -> io::Result<event::SplitRegistration>
+    where
+        S: event::Source + AsRawFd + ?Sized,
+    {
+        let read_interests = match interests.remove(Interest::WRITABLE) {
+            Some(read_interests) if interests.is_writable() => read_interests,
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "split registration requires writable and another interest",
+                ))
+            }
+        };
+
+        // SAFETY: the file descriptor is only borrowed for the duration of
+        // the call.
+        let fd = unsafe { std::os::fd::BorrowedFd::borrow_raw(source.as_raw_fd()) };
+        let mut split = event::SplitRegistration::new(fd.try_clone_to_owned()?);
+        self.register(&mut split, write_token, Interest::WRITABLE)?;
+        if let Err(err) = self.register(source, read_token, read_interests) {
+            let _ = self.deregister(&mut split);
+            return Err(err);
+        }
+        Ok(split)
+    }
+
+    /// Register `source` like [`register`] and set a `deadline` for `token`.
+    ///
+    /// See [`Registry::set_deadline`] and [`Poll::poll_with_deadlines`].
+    ///
+    /// [`register`]: Registry::register
+    pub fn register_with_deadline<S>(
+        &self,
+        source: &mut S,
+        token: Token,
+        interests: Interest,
+        deadline: Instant,
+    ) -> io::Result<()>
+    where
+        S: event::Source + ?Sized,
+    {
+        self.register(source, token, interests)?;
+        self.set_deadline(token, deadline);
+        Ok(())
+    }
+
+    /// Set the `deadline` for `token`, replacing any previous deadline.
+    ///
+    /// Once the deadline expires `token` is reported as expired by
+    /// [`Poll::poll_with_deadlines`] and the deadline is removed. `token`
+    /// doesn't have to be registered, but it usually is.
+    ///
+    /// For example, to reap idle connections set a deadline when registering
+    /// the connection and move it forward each time the connection is used.
+    ///
+    /// Deadlines are shared between all cloned `Registry`s. They are not
+    /// removed when deregistering a source, use [`clear_deadline`] for that.
+    ///
+    /// [`clear_deadline`]: Registry::clear_deadline
+    pub fn set_deadline(&self, token: Token, deadline: Instant) {
+        self.deadlines.lock().unwrap().set(token, deadline);
+    }
+
+    /// Remove the deadline for `token`, if any.
+    pub fn clear_deadline(&self, token: Token) {
+        self.deadlines.lock().unwrap().clear(token);
+    }
+
+    /// Returns the number of event sources currently registered.
+    ///
+    /// This is the number of file descriptors tracked for this `Registry` and
+    /// its clones (see [`try_clone`]), the same ones used by [`clear`] and
+    /// [`registered_tokens`], plus the number of [`Waker`]s alive. Mio's own
+    /// sources are no longer counted once they're deregistered or dropped,
+    /// and [`clear`] removes all of them. A file descriptor registered using
+    /// [`SourceFd`] that is closed without being deregistered is still
+    /// counted, until it's cleared or its file descriptor is registered again.
+    ///
+    /// [`try_clone`]: Registry::try_clone
+    /// [`clear`]: Registry::clear
+    /// [`registered_tokens`]: Registry::registered_tokens
+    /// [`Waker`]: crate::Waker
+    /// [`SourceFd`]: crate::unix::SourceFd
+    ///
+    /// See [`suggest_event_capacity`] to use this to determine the capacity of
+    /// [`Events`].
+    ///
+    /// [`suggest_event_capacity`]: crate::suggest_event_capacity
+    /// [`Events`]: crate::Events
+    ///
+    /// # Notes
+    ///
+    /// This is only available on Unix, with the `track-registrations` feature
+    /// enabled.
+    #[cfg(all(unix, feature = "track-registrations"))]
+    #[cfg_attr(docsrs, doc(cfg(all(unix, feature = "track-registrations"))))]
+    pub fn registered_count(&self) -> usize {
+        let fds = self.registrations.fds.lock().unwrap().len();
+        fds + self.registrations.wakers.load(Ordering::Relaxed)
+    }
+
+    /// Deregister all event sources registered with this `Registry`, or any
+    /// of its clones (see [`try_clone`]).
+    ///
+    /// With the `track-registrations` feature enabled Mio tracks the file
+    /// descriptors registered by its own event sources (including
+    /// [`SourceFd`]) and removes all of them from the selector, this includes
+    /// the file descriptors of sources that were registered by calling
+    /// [`event::Source::register`] directly. After this returns no more events
+    /// will be returned for these sources, as long as a happens-before
+    /// relationship is established between this call and the poll (same as
+    /// for [`deregister`]). The [`Waker`] is not affected.
+    ///
+    /// Mio's own sources stop being tracked once they're dropped. A file
+    /// descriptor registered using [`SourceFd`] that is closed without being
+    /// deregistered is still tracked, but it's only removed from the selector
+    /// if it still refers to the same file (based on its device and inode
+    /// number), so a reused file descriptor isn't deregistered.
+    ///
+    /// Note that the sources themselves are not dropped (or closed), they are
+    /// only removed from the selector. Dropping a cleared source is fine. To
+    /// register a cleared source again first call [`deregister`] on it,
+    /// ignoring the (possible) error as the selector no longer knows about it.
+    ///
+    /// This is useful when tearing down a reactor, without having to keep track
+    /// of all registered sources.
+    ///
+    /// [`try_clone`]: Registry::try_clone
+    /// [`SourceFd`]: crate::unix::SourceFd
+    /// [`deregister`]: Registry::deregister
+    /// [`Waker`]: crate::Waker
+    ///
+    /// # Notes
+    ///
+    /// This is only available on Unix, with the `track-registrations` feature
+    /// enabled. Any errors deregistering are ignored, e.g. the file descriptor
+    /// might already be closed.
+    #[cfg(all(unix, feature = "track-registrations"))]
+    #[cfg_attr(docsrs, doc(cfg(all(unix, feature = "track-registrations"))))]
+    pub fn clear(&self) {
+        self.registrations.clear(&self.selector, |_, _| {});
+    }
+
+    /// Returns the tokens of all event sources currently registered with this
+    /// `Registry`, or any of its clones (see [`try_clone`]).
+    ///
+    /// This uses the same file descriptor tracking as [`clear`], so it
+    /// reflects registrations made by calling [`event::Source::register`]
+    /// directly as well. A reregistration with a different token replaces the
+    /// old token, deregistered sources are removed. The [`Waker`] is not
+    /// included. The tokens are sorted, and multiple sources registered using
+    /// the same token only return the token once.
+    ///
+    /// [`try_clone`]: Registry::try_clone
+    /// [`clear`]: Registry::clear
+    /// [`Waker`]: crate::Waker
+    ///
+    /// # Notes
+    ///
+    /// This is only available on Unix, with the `track-registrations` feature
+    /// enabled.
+    ///
+    /// This allocates and locks the tracking table shared with
+    /// (de)registrations, it's intended for diagnostics, not hot paths.
+    #[cfg(all(unix, feature = "track-registrations"))]
+    #[cfg_attr(docsrs, doc(cfg(all(unix, feature = "track-registrations"))))]
+    pub fn registered_tokens(&self) -> Vec<Token> {
+        let mut tokens: Vec<Token> = self
+            .registrations
+            .fds
+            .lock()
+            .unwrap()
+            .values()
+            .map(|registration| registration.token)
+            .collect();
+        tokens.sort_unstable();
+        tokens.dedup();
+        tokens
+    }
+
     /// Creates a new independently owned `Registry`.
     ///
     /// Event sources registered with this `Registry` will be registered with
@@ -712,9 +2177,25 @@ impl Registry {
             selector,
             #[cfg(all(debug_assertions, not(target_os = "wasi")))]
             has_waker: Arc::clone(&self.has_waker),
+            #[cfg(not(target_os = "wasi"))]
+            cancel_token_reserved: Arc::clone(&self.cancel_token_reserved),
+            #[cfg(all(unix, feature = "track-registrations"))]
+            registrations: Arc::clone(&self.registrations),
+            deadlines: Arc::clone(&self.deadlines),
         })
     }
 
+    /// Returns an error if `token` is reserved, see [`Poll::poll_cancellable`].
+    pub(crate) fn check_token(&self, token: Token) -> io::Result<()> {
+        #[cfg(not(target_os = "wasi"))]
+        if token == CANCEL_TOKEN && self.cancel_token_reserved.load(Ordering::Acquire) {
+            return Err(Error::ReservedToken.into());
+        }
+        #[cfg(target_os = "wasi")]
+        let _ = token;
+        Ok(())
+    }
+
     /// Internal check to ensure only a single `Waker` is active per [`Poll`]
     /// instance.
     #[cfg(all(debug_assertions, not(target_os = "wasi")))]
@@ -725,11 +2206,131 @@ impl Registry {
         );
     }
 
+    /// Count a `Waker` in `registered_count` while the returned value is alive.
+    #[cfg(all(unix, feature = "track-registrations"))]
+    pub(crate) fn count_waker(&self) -> CountedWaker {
+        let _ = self.registrations.wakers.fetch_add(1, Ordering::Relaxed);
+        CountedWaker {
+            registrations: Arc::clone(&self.registrations),
+        }
+    }
+
     /// Get access to the `sys::Selector`.
     #[cfg(any(not(target_os = "wasi"), feature = "net"))]
     pub(crate) fn selector(&self) -> &sys::Selector {
         &self.selector
     }
+
+    /// Track that `fd` was registered with the selector, see [`Registry::clear`].
+    #[cfg(all(
+        any(unix, target_os = "hermit"),
+        any(feature = "net", feature = "os-ext")
+    ))]
+    #[cfg_attr(not(all(unix, feature = "track-registrations")), inline)]
+    pub(crate) fn track_registration(&self, fd: RawFd, token: Token, interests: Interest) {
+        #[cfg(all(unix, feature = "track-registrations"))]
+        {
+            let mut fds = self.registrations.fds.lock().unwrap();
+            if let Some(registration) = fds.get_mut(&fd) {
+                registration.token = token;
+                registration.interests = interests;
+                return;
+            }
+            drop(fds);
+            self.track_new_registration(fd, token, interests);
+        }
+        #[cfg(not(all(unix, feature = "track-registrations")))]
+        let _ = (fd, token, interests);
+    }
+
+    /// Track that `fd` was newly registered with the selector, see
+    /// [`Registry::track_registration`].
+    ///
+    /// With debug assertions enabled this warns if `fd` is still tracked as
+    /// registered using a different token. As the selector accepted the
+    /// registration the old source must have been closed without being
+    /// deregistered, and its file descriptor reused. Events for the old token
+    /// may still be returned, e.g. from the same call to `Poll::poll`, and be
+    /// mistaken for events of the old source.
+    #[cfg(all(
+        any(unix, target_os = "hermit"),
+        any(feature = "net", feature = "os-ext")
+    ))]
+    #[cfg_attr(not(all(unix, feature = "track-registrations")), inline)]
+    pub(crate) fn track_new_registration(&self, fd: RawFd, token: Token, interests: Interest) {
+        #[cfg(all(unix, feature = "track-registrations"))]
+        {
+            let Ok(file) = sys::file_id(fd) else {
+                // The selector accepted the file descriptor, so this shouldn't
+                // happen, but it also means we can't deregister it later.
+                return;
+            };
+            let registration = Registration {
+                token,
+                interests,
+                file,
+            };
+            let old = self
+                .registrations
+                .fds
+                .lock()
+                .unwrap()
+                .insert(fd, registration);
+            #[cfg(debug_assertions)]
+            if let Some(old) = old {
+                if old.token != token {
+                    warn!(
+                        "registered file descriptor that is still registered using a different \
+                        token, it was likely closed without being deregistered and reused: fd={}, \
+                        token={:?}, old_token={:?}",
+                        fd, token, old.token
+                    );
+                }
+            }
+            #[cfg(not(debug_assertions))]
+            let _ = old;
+        }
+        #[cfg(not(all(unix, feature = "track-registrations")))]
+        let _ = (fd, token, interests);
+    }
+
+    /// Track that `fd` was deregistered from the selector.
+    #[cfg(all(
+        any(unix, target_os = "hermit"),
+        any(feature = "net", feature = "os-ext")
+    ))]
+    #[cfg_attr(not(all(unix, feature = "track-registrations")), inline)]
+    pub(crate) fn untrack_registration(&self, fd: RawFd) {
+        #[cfg(all(unix, feature = "track-registrations"))]
+        self.registrations.remove(fd);
+        #[cfg(not(all(unix, feature = "track-registrations")))]
+        let _ = fd;
+    }
+
+    /// Returns a guard that stops tracking `fd` when it's dropped, used by
+    /// I/O sources that close `fd` when they're dropped.
+    ///
+    /// Closing a file descriptor removes it from the selector (or for the
+    /// selectors that keep per source state, the I/O source deregisters it
+    /// when it's dropped), without `deregister` being called.
+    #[cfg(all(
+        any(unix, target_os = "hermit"),
+        any(feature = "net", feature = "os-ext")
+    ))]
+    #[cfg_attr(not(all(unix, feature = "track-registrations")), inline)]
+    pub(crate) fn untrack_on_drop(&self, fd: RawFd) -> UntrackOnDrop {
+        #[cfg(all(unix, feature = "track-registrations"))]
+        {
+            UntrackOnDrop {
+                inner: Some((Arc::clone(&self.registrations), fd)),
+            }
+        }
+        #[cfg(not(all(unix, feature = "track-registrations")))]
+        {
+            let _ = fd;
+            UntrackOnDrop::default()
+        }
+    }
 }
 
 impl fmt::Debug for Registry {
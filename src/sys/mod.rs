@@ -3,14 +3,17 @@
 //! Required types:
 //!
 //! * `Event`: a type alias for the system specific event, e.g. `kevent` or
-//!            `epoll_event`.
+//!   `epoll_event`.
 //! * `event`: a module with various helper functions for `Event`, see
-//!            [`crate::event::Event`] for the required functions.
+//!   [`crate::event::Event`] for the required functions.
 //! * `Events`: collection of `Event`s, see [`crate::Events`].
+//! * `events_from_vec` and `events_into_vec`: conversions between `Events`
+//!   and a `Vec` of `Event`s, see [`crate::Events::from_vec`].
 //! * `IoSourceState`: state for the `IoSource` type.
 //! * `Selector`: selector used to register event sources and poll for events,
-//!               see [`crate::Poll`] and [`crate::Registry`] for required
-//!               methods.
+//!   see [`crate::Poll`] and [`crate::Registry`] for required methods. Its
+//!   `select` method returns whether the OS wait timed out, see
+//!   [`crate::PollStatus`].
 //! * `tcp` and `udp` modules: see the [`crate::net`] module.
 //! * `Waker`: see [`crate::Waker`].
 
@@ -78,6 +81,8 @@ cfg_not_os_poll! {
     cfg_any_os_ext! {
         mod unix;
         #[cfg(feature = "os-ext")]
-        pub use self::unix::SourceFd;
+        pub use self::unix::{is_nonblocking, set_nonblocking, SourceFd};
+        pub(crate) use self::unix::probe;
+        pub use self::unix::{is_cloexec, set_cloexec};
     }
 }
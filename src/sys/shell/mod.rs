@@ -5,18 +5,21 @@ macro_rules! os_required {
 }
 
 mod selector;
-pub(crate) use self::selector::{event, Event, Events, Selector};
+pub(crate) use self::selector::{event, events_from_vec, events_into_vec, Event, Events, Selector};
 
 #[cfg(not(target_os = "wasi"))]
 mod waker;
 #[cfg(not(target_os = "wasi"))]
 pub(crate) use self::waker::Waker;
+pub(crate) use self::waker::Waker as CancelWaker;
 
 cfg_net! {
     pub(crate) mod tcp;
     pub(crate) mod udp;
     #[cfg(unix)]
     pub(crate) mod uds;
+    #[cfg(target_os = "linux")]
+    pub(crate) mod sctp;
 }
 
 cfg_io_source! {
@@ -40,6 +43,9 @@ cfg_io_source! {
             IoSourceState
         }
 
+        /// The I/O source outlives the state, nothing to do.
+        pub fn detach(&mut self) {}
+
         pub fn do_io<T, F, R>(&self, f: F, io: &T) -> io::Result<R>
         where
             F: FnOnce(&T) -> io::Result<R>,
@@ -0,0 +1,34 @@
+use std::io;
+use std::net::SocketAddr;
+use std::os::fd::OwnedFd;
+
+use crate::net::SctpInfo;
+
+pub(crate) fn bind(_: SocketAddr) -> io::Result<OwnedFd> {
+    os_required!();
+}
+
+pub(crate) fn local_addr(_: &OwnedFd) -> io::Result<SocketAddr> {
+    os_required!();
+}
+
+pub(crate) fn send_to(
+    _: &OwnedFd,
+    _: &[u8],
+    _: Option<SocketAddr>,
+    _: &SctpInfo,
+) -> io::Result<usize> {
+    os_required!();
+}
+
+pub(crate) fn recv_from(_: &OwnedFd, _: &mut [u8]) -> io::Result<(usize, SocketAddr, SctpInfo)> {
+    os_required!();
+}
+
+pub(crate) fn peel_off(_: &OwnedFd, _: i32) -> io::Result<OwnedFd> {
+    os_required!();
+}
+
+pub(crate) fn take_error(_: &OwnedFd) -> io::Result<Option<io::Error>> {
+    os_required!();
+}
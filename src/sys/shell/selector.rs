@@ -3,10 +3,22 @@ use std::io;
 use std::os::fd::{AsRawFd, RawFd};
 use std::time::Duration;
 
+use crate::Capabilities;
+
 pub type Event = usize;
 
 pub type Events = Vec<Event>;
 
+/// Converts an allocation of events into `Events`, see `Events::from_vec`.
+pub fn events_from_vec(events: Vec<Event>) -> Events {
+    events
+}
+
+/// Converts `Events` into its allocation, see `Events::into_vec`.
+pub fn events_into_vec(events: Events) -> Vec<Event> {
+    events
+}
+
 #[derive(Debug)]
 pub struct Selector {}
 
@@ -15,7 +27,11 @@ impl Selector {
         os_required!();
     }
 
-    pub fn select(&self, _: &mut Events, _: Option<Duration>) -> io::Result<()> {
+    pub fn select(&self, _: &mut Events, _: Option<Duration>) -> io::Result<bool> {
+        os_required!();
+    }
+
+    pub fn capabilities(&self) -> Capabilities {
         os_required!();
     }
 }
@@ -116,6 +132,11 @@ pub mod event {
         os_required!();
     }
 
+    #[cfg(unix)]
+    pub fn raw_fd(_: &Event) -> Option<std::os::fd::RawFd> {
+        os_required!();
+    }
+
     pub fn debug_details(_: &mut fmt::Formatter<'_>, _: &Event) -> fmt::Result {
         os_required!();
     }
@@ -1,11 +1,23 @@
 use std::io;
 use std::net::{self, SocketAddr};
+#[cfg(unix)]
+use std::time::Duration;
+
+#[cfg(unix)]
+use crate::net::ConnDiagnosis;
+#[cfg(target_os = "linux")]
+use crate::net::TcpRepairState;
 
 #[cfg(not(target_os = "wasi"))]
 pub(crate) fn new_for_addr(_: SocketAddr) -> io::Result<i32> {
     os_required!();
 }
 
+#[cfg(unix)]
+pub(crate) fn new_for_addr_with_protocol(_: SocketAddr, _: i32) -> io::Result<i32> {
+    os_required!();
+}
+
 #[cfg(not(target_os = "wasi"))]
 pub(crate) fn bind(_: &net::TcpListener, _: SocketAddr) -> io::Result<()> {
     os_required!();
@@ -21,11 +33,216 @@ pub(crate) fn listen(_: &net::TcpListener, _: u32) -> io::Result<()> {
     os_required!();
 }
 
+#[cfg(not(target_os = "wasi"))]
+pub(crate) fn set_reuseport_lb(_: &net::TcpListener) -> io::Result<()> {
+    os_required!();
+}
+
+#[cfg(unix)]
+pub(crate) fn prepare_inherited(_: &net::TcpListener) -> io::Result<()> {
+    os_required!();
+}
+
 #[cfg(any(unix, target_os = "hermit"))]
 pub(crate) fn set_reuseaddr(_: &net::TcpListener, _: bool) -> io::Result<()> {
     os_required!();
 }
 
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn set_defer_accept(_: &net::TcpListener, _: Option<Duration>) -> io::Result<()> {
+    os_required!();
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn set_ip_transparent(_: &net::TcpListener, _: bool) -> io::Result<()> {
+    os_required!();
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn set_nodelay(_: &net::TcpListener, _: bool) -> io::Result<()> {
+    os_required!();
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn nodelay(_: &net::TcpListener) -> io::Result<bool> {
+    os_required!();
+}
+
+#[cfg(unix)]
+pub(crate) fn set_user_timeout(_: &net::TcpStream, _: Option<Duration>) -> io::Result<()> {
+    os_required!();
+}
+
+#[cfg(unix)]
+pub(crate) fn user_timeout(_: &net::TcpStream) -> io::Result<Option<Duration>> {
+    os_required!();
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn set_priority(_: &net::TcpStream, _: u32) -> io::Result<()> {
+    os_required!();
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn priority(_: &net::TcpStream) -> io::Result<u32> {
+    os_required!();
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn set_incoming_cpu(_: &net::TcpListener, _: usize) -> io::Result<()> {
+    os_required!();
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn incoming_cpu(_: &net::TcpListener) -> io::Result<Option<usize>> {
+    os_required!();
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn set_congestion(_: &net::TcpStream, _: &str) -> io::Result<()> {
+    os_required!();
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn congestion(_: &net::TcpStream) -> io::Result<String> {
+    os_required!();
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn original_dst(_: &net::TcpStream) -> io::Result<SocketAddr> {
+    os_required!();
+}
+
+#[cfg(unix)]
+pub(crate) fn recv_queue_len(_: &net::TcpStream) -> io::Result<usize> {
+    os_required!();
+}
+
+#[cfg(unix)]
+pub(crate) fn send_queue_len(_: &net::TcpStream) -> io::Result<usize> {
+    os_required!();
+}
+
+#[cfg(unix)]
+pub(crate) fn set_keepalive(_: &net::TcpStream, _: bool) -> io::Result<()> {
+    os_required!();
+}
+
+#[cfg(unix)]
+pub(crate) fn keepalive(_: &net::TcpStream) -> io::Result<bool> {
+    os_required!();
+}
+
+#[cfg(unix)]
+pub(crate) fn set_recv_buffer_size(_: &net::TcpStream, _: usize) -> io::Result<()> {
+    os_required!();
+}
+
+#[cfg(unix)]
+pub(crate) fn recv_buffer_size(_: &net::TcpStream) -> io::Result<usize> {
+    os_required!();
+}
+
+#[cfg(unix)]
+pub(crate) fn set_send_buffer_size(_: &net::TcpStream, _: usize) -> io::Result<()> {
+    os_required!();
+}
+
+#[cfg(unix)]
+pub(crate) fn send_buffer_size(_: &net::TcpStream) -> io::Result<usize> {
+    os_required!();
+}
+
+#[cfg(unix)]
+pub(crate) fn set_linger(_: &net::TcpStream, _: Option<Duration>) -> io::Result<()> {
+    os_required!();
+}
+
+#[cfg(unix)]
+pub(crate) fn linger(_: &net::TcpStream) -> io::Result<Option<Duration>> {
+    os_required!();
+}
+
+#[cfg(unix)]
+pub(crate) fn set_recv_low_watermark(_: &net::TcpStream, _: usize) -> io::Result<()> {
+    os_required!();
+}
+
+#[cfg(unix)]
+pub(crate) fn recv_low_watermark(_: &net::TcpStream) -> io::Result<usize> {
+    os_required!();
+}
+
+#[cfg(unix)]
+pub(crate) fn set_send_low_watermark(_: &net::TcpStream, _: usize) -> io::Result<()> {
+    os_required!();
+}
+
+#[cfg(unix)]
+pub(crate) fn send_low_watermark(_: &net::TcpStream) -> io::Result<usize> {
+    os_required!();
+}
+
+#[cfg(unix)]
+pub(crate) fn set_notsent_lowat(_: &net::TcpStream, _: u32) -> io::Result<()> {
+    os_required!();
+}
+
+#[cfg(unix)]
+pub(crate) fn notsent_lowat(_: &net::TcpStream) -> io::Result<u32> {
+    os_required!();
+}
+
+#[cfg(unix)]
+pub(crate) fn set_mss(_: &net::TcpStream, _: u32) -> io::Result<()> {
+    os_required!();
+}
+
+#[cfg(unix)]
+pub(crate) fn mss(_: &net::TcpStream) -> io::Result<u32> {
+    os_required!();
+}
+
+#[cfg(unix)]
+pub(crate) fn diagnose(_: &net::TcpStream) -> io::Result<ConnDiagnosis> {
+    os_required!();
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn repair_save(_: &net::TcpStream) -> io::Result<TcpRepairState> {
+    os_required!();
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn repair_restore(_: &TcpRepairState) -> io::Result<net::TcpStream> {
+    os_required!();
+}
+
+#[cfg(any(
+    target_os = "android",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "ios",
+    target_os = "linux",
+    target_os = "macos",
+    target_os = "tvos",
+    target_os = "visionos",
+    target_os = "watchos",
+))]
+pub(crate) fn send_file(_: &net::TcpStream, _: i32, _: u64, _: usize) -> io::Result<usize> {
+    os_required!();
+}
+
 pub(crate) fn accept(_: &net::TcpListener) -> io::Result<(net::TcpStream, SocketAddr)> {
     os_required!();
 }
+
+#[cfg(unix)]
+pub(crate) fn set_hop_limit(_: i32, _: u32) -> io::Result<()> {
+    os_required!();
+}
+
+#[cfg(unix)]
+pub(crate) fn hop_limit(_: i32) -> io::Result<u32> {
+    os_required!();
+}
@@ -1,11 +1,175 @@
 #![cfg(not(target_os = "wasi"))]
 use std::io;
 use std::net::{self, SocketAddr};
+#[cfg(any(target_os = "android", target_os = "linux"))]
+use std::os::fd::BorrowedFd;
+#[cfg(any(target_os = "android", target_os = "linux"))]
+use std::time::SystemTime;
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+use crate::net::{MtuDiscover, RecvInfo, SockFilter, TimestampMode};
+#[cfg(unix)]
+use crate::net::TruncationPolicy;
 
 pub fn bind(_: SocketAddr) -> io::Result<net::UdpSocket> {
     os_required!()
 }
 
+pub(crate) fn new_unbound(_: bool) -> io::Result<net::UdpSocket> {
+    os_required!()
+}
+
+#[cfg(unix)]
+pub(crate) fn bind_with_protocol(_: SocketAddr, _: i32) -> io::Result<net::UdpSocket> {
+    os_required!()
+}
+
+#[cfg(unix)]
+pub(crate) fn bind_dual_stack(_: u16) -> io::Result<net::UdpSocket> {
+    os_required!()
+}
+
+#[cfg(any(
+    target_os = "android",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "fuchsia",
+    target_os = "ios",
+    target_os = "linux",
+    target_os = "macos",
+    target_os = "netbsd",
+    target_os = "openbsd",
+    target_os = "tvos",
+    target_os = "visionos",
+    target_os = "watchos",
+))]
+pub(crate) fn bind_reuseport(_: SocketAddr) -> io::Result<net::UdpSocket> {
+    os_required!()
+}
+
 pub(crate) fn only_v6(_: &net::UdpSocket) -> io::Result<bool> {
     os_required!()
 }
+
+#[cfg(unix)]
+pub(crate) fn set_default_scope_id(_: &net::UdpSocket, _: u32) -> io::Result<()> {
+    os_required!()
+}
+
+#[cfg(unix)]
+pub(crate) fn default_scope_id(_: &net::UdpSocket) -> io::Result<u32> {
+    os_required!()
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn set_ip_transparent(_: &net::UdpSocket, _: bool) -> io::Result<()> {
+    os_required!()
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn set_priority(_: &net::UdpSocket, _: u32) -> io::Result<()> {
+    os_required!()
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn priority(_: &net::UdpSocket) -> io::Result<u32> {
+    os_required!()
+}
+
+#[cfg(any(
+    target_os = "android",
+    target_os = "freebsd",
+    target_os = "ios",
+    target_os = "linux",
+    target_os = "macos",
+    target_os = "tvos",
+    target_os = "visionos",
+    target_os = "watchos",
+))]
+pub(crate) fn set_dontfrag(_: &net::UdpSocket, _: bool) -> io::Result<()> {
+    os_required!()
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn set_mtu_discover(_: &net::UdpSocket, _: MtuDiscover) -> io::Result<()> {
+    os_required!()
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn path_mtu(_: &net::UdpSocket) -> io::Result<u32> {
+    os_required!()
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn set_timestamping(_: &net::UdpSocket, _: TimestampMode) -> io::Result<()> {
+    os_required!()
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn attach_reuseport_cbpf(_: &net::UdpSocket, _: &[SockFilter]) -> io::Result<()> {
+    os_required!()
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn attach_reuseport_ebpf(_: &net::UdpSocket, _: BorrowedFd<'_>) -> io::Result<()> {
+    os_required!()
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn recv_from_with_timestamp(
+    _: &net::UdpSocket,
+    _: &mut [u8],
+) -> io::Result<(usize, SocketAddr, Option<SystemTime>)> {
+    os_required!()
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn set_recv_pktinfo(_: &net::UdpSocket, _: bool) -> io::Result<()> {
+    os_required!()
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn recv_pktinfo(_: &net::UdpSocket) -> io::Result<bool> {
+    os_required!()
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn recv_from_with_info(
+    _: &net::UdpSocket,
+    _: &mut [u8],
+) -> io::Result<(usize, SocketAddr, RecvInfo)> {
+    os_required!()
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn recv_batch(_: &net::UdpSocket, _: &mut [Vec<u8>]) -> io::Result<Vec<(usize, SocketAddr)>> {
+    os_required!()
+}
+
+#[cfg(unix)]
+pub(crate) fn set_hop_limit(_: i32, _: u32) -> io::Result<()> {
+    os_required!()
+}
+
+#[cfg(unix)]
+pub(crate) fn hop_limit(_: i32) -> io::Result<u32> {
+    os_required!()
+}
+
+#[cfg(unix)]
+pub(crate) fn recv_from_with_truncation(
+    _: &net::UdpSocket,
+    _: &mut [u8],
+    _: TruncationPolicy,
+) -> io::Result<(usize, SocketAddr)> {
+    os_required!()
+}
+
+#[cfg(unix)]
+pub(crate) fn recv_with_truncation(
+    _: &net::UdpSocket,
+    _: &mut [u8],
+    _: TruncationPolicy,
+) -> io::Result<usize> {
+    os_required!()
+}
@@ -2,6 +2,8 @@ pub(crate) mod datagram {
     use std::io;
     use std::os::unix::net::{self, SocketAddr};
 
+    use crate::net::TruncationPolicy;
+
     pub(crate) fn bind_addr(_: &SocketAddr) -> io::Result<net::UnixDatagram> {
         os_required!()
     }
@@ -13,10 +15,19 @@ pub(crate) mod datagram {
     pub(crate) fn pair() -> io::Result<(net::UnixDatagram, net::UnixDatagram)> {
         os_required!()
     }
+
+    pub(crate) fn recv_with_truncation(
+        _: &net::UnixDatagram,
+        _: &mut [u8],
+        _: TruncationPolicy,
+    ) -> io::Result<usize> {
+        os_required!()
+    }
 }
 
 pub(crate) mod listener {
     use std::io;
+    use std::os::fd::RawFd;
     use std::os::unix::net::{self, SocketAddr};
 
     use crate::net::UnixStream;
@@ -28,6 +39,14 @@ pub(crate) mod listener {
     pub(crate) fn accept(_: &net::UnixListener) -> io::Result<(UnixStream, SocketAddr)> {
         os_required!()
     }
+
+    pub(crate) fn prepare_inherited(_: &net::UnixListener) -> io::Result<()> {
+        os_required!()
+    }
+
+    pub(crate) fn is_unix_socket(_: RawFd) -> io::Result<bool> {
+        os_required!()
+    }
 }
 
 pub(crate) mod stream {
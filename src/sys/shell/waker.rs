@@ -13,4 +13,17 @@ impl Waker {
     pub fn wake(&self) -> io::Result<()> {
         os_required!();
     }
+
+    #[cfg(unix)]
+    pub fn wake_from_signal(&self) -> io::Result<()> {
+        os_required!();
+    }
+
+    pub fn reset(&self) -> io::Result<()> {
+        os_required!();
+    }
+
+    pub fn rearm(&self, _: &Selector, _: Token) -> io::Result<()> {
+        os_required!();
+    }
 }
@@ -0,0 +1,148 @@
+use std::io::{self, Write};
+use std::sync::{Arc, Mutex};
+
+use crate::sys::pipe::{self, Receiver, Sender};
+use crate::{event, Interest, Registry, Token};
+
+/// A one-shot result, completed by another thread, that can be registered
+/// with [`Poll`].
+///
+/// This can be used to integrate operations running on other threads, e.g.
+/// a DNS lookup, into an event loop. The operation's thread
+/// [`complete`]s the [`Completer`], which stores the result and makes the
+/// `Completion` readable, the thread calling [`Poll::poll`] can then
+/// [`take`] the result. Unlike a [`Waker`] there can be any number of
+/// `Completion`s per [`Poll`].
+///
+/// If the [`Completer`] is dropped without completing it the `Completion`
+/// gets a [read closed] event, without being readable, and [`take`] returns
+/// `None`.
+///
+/// [`Poll`]: crate::Poll
+/// [`Poll::poll`]: crate::Poll::poll
+/// [`complete`]: Completer::complete
+/// [`take`]: Completion::take
+/// [`Waker`]: crate::Waker
+/// [read closed]: crate::event::Event::is_read_closed
+///
+/// # Implementation notes
+///
+/// The result is stored in a shared slot, the readiness is provided by a pipe.
+/// Completing the `Completer` writes a single byte and closes the sending end
+/// of the pipe, so each `Completion` uses two file descriptors until then, and
+/// one after.
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use std::net::ToSocketAddrs;
+/// use std::thread;
+///
+/// use mio::unix::Completion;
+/// use mio::{Events, Interest, Poll, Token};
+///
+/// const LOOKUP: Token = Token(0);
+///
+/// let mut poll = Poll::new()?;
+/// let mut events = Events::with_capacity(8);
+///
+/// let (mut lookup, completer) = Completion::new()?;
+/// poll.registry().register(&mut lookup, LOOKUP, Interest::READABLE)?;
+///
+/// let handle = thread::spawn(move || {
+///     let addresses = ("localhost", 80).to_socket_addrs().map(|a| a.collect::<Vec<_>>());
+///     completer.complete(addresses)
+/// });
+///
+/// let addresses = loop {
+///     poll.poll(&mut events, None)?;
+///     if let Some(result) = lookup.take() {
+///         break result;
+///     }
+/// };
+/// println!("localhost resolved to: {:?}", addresses);
+/// # handle.join().unwrap();
+/// #     Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct Completion<T> {
+    receiver: Receiver,
+    slot: Arc<Mutex<Option<T>>>,
+}
+
+/// Completing half of a [`Completion`].
+///
+/// Created by [`Completion::new`], can be sent to another thread to complete
+/// the `Completion` once the result is available.
+#[derive(Debug)]
+pub struct Completer<T> {
+    sender: Sender,
+    slot: Arc<Mutex<Option<T>>>,
+}
+
+impl<T> Completion<T> {
+    /// Create a new `Completion` and its [`Completer`].
+    pub fn new() -> io::Result<(Completion<T>, Completer<T>)> {
+        let (sender, receiver) = pipe::new()?;
+        let slot = Arc::new(Mutex::new(None));
+        let completion = Completion {
+            receiver,
+            slot: slot.clone(),
+        };
+        Ok((completion, Completer { sender, slot }))
+    }
+
+    /// Take the result, if the `Completion` was completed.
+    ///
+    /// Returns `None` if the [`Completer`] hasn't completed it yet, or if it
+    /// was dropped without completing it, and once the result was taken.
+    pub fn take(&mut self) -> Option<T> {
+        self.slot.lock().unwrap().take()
+    }
+}
+
+impl<T> Completer<T> {
+    /// Complete the [`Completion`] with `value`, waking up the [`Poll`] it's
+    /// registered with.
+    ///
+    /// [`Poll`]: crate::Poll
+    pub fn complete(mut self, value: T) {
+        *self.slot.lock().unwrap() = Some(value);
+        // Writing a byte makes the receiving end readable, the pipe is empty so
+        // this can't fail with `WouldBlock`. Because the slot is set before
+        // it, the value is always available once the event is returned.
+        // Dropping the sender afterwards closes the pipe.
+        let _ = self.sender.write(&[1]);
+    }
+}
+
+impl<T> event::Source for Completion<T> {
+    fn register(
+        &mut self,
+        registry: &Registry,
+        token: Token,
+        interests: Interest,
+    ) -> io::Result<()> {
+        self.receiver.register(registry, token, interests)
+    }
+
+    fn reregister(
+        &mut self,
+        registry: &Registry,
+        token: Token,
+        interests: Interest,
+    ) -> io::Result<()> {
+        self.receiver.reregister(registry, token, interests)
+    }
+
+    fn deregister(&mut self, registry: &Registry) -> io::Result<()> {
+        self.receiver.deregister(registry)
+    }
+
+    fn poll_ready(&self, interests: Interest) -> io::Result<event::Readiness> {
+        self.receiver.poll_ready(interests)
+    }
+}
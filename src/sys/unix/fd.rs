@@ -0,0 +1,165 @@
+use std::io;
+use std::os::fd::RawFd;
+
+use crate::event::Readiness;
+use crate::Interest;
+
+/// Sets the file descriptor in non-blocking or blocking mode.
+///
+/// Mio requires all sources to be in non-blocking mode. This can be used to
+/// prepare file descriptors not created by Mio before registering them using
+/// [`SourceFd`], without having to use `libc` directly.
+///
+/// The mode is shared by all file descriptors that refer to the same open file
+/// description, e.g. all duplicates created using `dup(2)` or inherited from a
+/// parent process.
+///
+/// [`SourceFd`]: crate::unix::SourceFd
+#[cfg(all(
+    feature = "os-ext",
+    not(any(target_os = "illumos", target_os = "solaris", target_os = "vita"))
+))]
+pub fn set_nonblocking(fd: RawFd, nonblocking: bool) -> io::Result<()> {
+    let value = nonblocking as libc::c_int;
+    syscall!(ioctl(fd, libc::FIONBIO, &value)).map(|_| ())
+}
+
+/// Sets the file descriptor in non-blocking or blocking mode.
+///
+/// Mio requires all sources to be in non-blocking mode. This can be used to
+/// prepare file descriptors not created by Mio before registering them using
+/// [`SourceFd`], without having to use `libc` directly.
+///
+/// The mode is shared by all file descriptors that refer to the same open file
+/// description, e.g. all duplicates created using `dup(2)` or inherited from a
+/// parent process.
+///
+/// [`SourceFd`]: crate::unix::SourceFd
+#[cfg(all(
+    feature = "os-ext",
+    any(target_os = "illumos", target_os = "solaris", target_os = "vita")
+))]
+pub fn set_nonblocking(fd: RawFd, nonblocking: bool) -> io::Result<()> {
+    let flags = syscall!(fcntl(fd, libc::F_GETFL))?;
+    let nflags = if nonblocking {
+        flags | libc::O_NONBLOCK
+    } else {
+        flags & !libc::O_NONBLOCK
+    };
+    if flags != nflags {
+        syscall!(fcntl(fd, libc::F_SETFL, nflags))?;
+    }
+    Ok(())
+}
+
+/// Returns whether or not the file descriptor is in non-blocking mode, see
+/// [`set_nonblocking`].
+#[cfg(feature = "os-ext")]
+pub fn is_nonblocking(fd: RawFd) -> io::Result<bool> {
+    syscall!(fcntl(fd, libc::F_GETFL)).map(|flags| flags & libc::O_NONBLOCK != 0)
+}
+
+/// Sets or clears the close-on-exec flag (`FD_CLOEXEC`) of the file descriptor.
+///
+/// Mio creates all file descriptors with close-on-exec set, so they are not
+/// inherited by child processes. Clearing it allows passing the file descriptor
+/// to a child process. Note that any child process started (by another thread)
+/// while the flag is cleared also inherits the file descriptor.
+///
+/// Unlike the non-blocking mode (see [`set_nonblocking`]) this is a property of
+/// the file descriptor, it doesn't affect any duplicates.
+pub fn set_cloexec(fd: RawFd, cloexec: bool) -> io::Result<()> {
+    let flags = syscall!(fcntl(fd, libc::F_GETFD))?;
+    let nflags = if cloexec {
+        flags | libc::FD_CLOEXEC
+    } else {
+        flags & !libc::FD_CLOEXEC
+    };
+    if flags != nflags {
+        syscall!(fcntl(fd, libc::F_SETFD, nflags))?;
+    }
+    Ok(())
+}
+
+/// Returns whether or not the close-on-exec flag is set on the file
+/// descriptor, see [`set_cloexec`].
+pub fn is_cloexec(fd: RawFd) -> io::Result<bool> {
+    syscall!(fcntl(fd, libc::F_GETFD)).map(|flags| flags & libc::FD_CLOEXEC != 0)
+}
+
+/// Returns the device and inode number of the file `fd` refers to, which
+/// identifies the file independent of the file descriptor.
+#[cfg(feature = "track-registrations")]
+#[allow(clippy::unnecessary_cast)] // The types differ per platform.
+pub(crate) fn file_id(fd: RawFd) -> io::Result<(u64, u64)> {
+    let mut stat = std::mem::MaybeUninit::<libc::stat>::uninit();
+    syscall!(fstat(fd, stat.as_mut_ptr()))?;
+    // SAFETY: `fstat(2)` initialised `stat`.
+    let stat = unsafe { stat.assume_init() };
+    Ok((stat.st_dev as u64, stat.st_ino as u64))
+}
+
+/// Determine the current readiness of `fd` for `interests`, without blocking.
+pub(crate) fn probe(fd: RawFd, interests: Interest) -> io::Result<Readiness> {
+    let mut events = 0;
+    if interests.is_readable() {
+        events |= libc::POLLIN;
+    }
+    if interests.is_writable() {
+        events |= libc::POLLOUT;
+    }
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    {
+        events |= libc::POLLRDHUP;
+        if interests.is_priority() {
+            events |= libc::POLLPRI;
+        }
+    }
+
+    let mut pollfd = libc::pollfd {
+        fd,
+        events,
+        revents: 0,
+    };
+    loop {
+        match syscall!(poll(&mut pollfd, 1, 0)) {
+            Ok(_) => break,
+            Err(ref err) if err.kind() == io::ErrorKind::Interrupted => continue,
+            Err(err) => return Err(err),
+        }
+    }
+
+    let revents = pollfd.revents;
+    if revents & libc::POLLNVAL != 0 {
+        return Err(io::Error::from_raw_os_error(libc::EBADF));
+    }
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    let read_hup = revents & libc::POLLRDHUP != 0;
+    #[cfg(not(any(target_os = "android", target_os = "linux")))]
+    let read_hup = false;
+
+    let mut readiness = Readiness::EMPTY;
+    if revents & libc::POLLIN != 0 {
+        readiness |= Readiness::READABLE;
+    }
+    if revents & libc::POLLOUT != 0 {
+        readiness |= Readiness::WRITABLE;
+    }
+    if revents & libc::POLLERR != 0 {
+        readiness |= Readiness::ERROR;
+    }
+    if revents & libc::POLLHUP != 0 || read_hup {
+        readiness |= Readiness::READ_CLOSED;
+    }
+    // Same logic as the `poll(2)` based selector, see `is_write_closed` there.
+    if revents & libc::POLLHUP != 0
+        || (revents & libc::POLLOUT != 0 && revents & libc::POLLERR != 0)
+        || revents == libc::POLLERR
+    {
+        readiness |= Readiness::WRITE_CLOSED;
+    }
+    if revents & libc::POLLPRI != 0 {
+        readiness |= Readiness::PRIORITY;
+    }
+    Ok(readiness)
+}
@@ -17,6 +17,7 @@ macro_rules! syscall {
 cfg_os_poll! {
     #[cfg_attr(all(
         not(mio_unsupported_force_poll_poll),
+        not(all(feature = "io-uring", target_os = "linux")),
         any(
             target_os = "android",
             target_os = "illumos",
@@ -24,6 +25,11 @@ cfg_os_poll! {
             target_os = "redox",
         )
     ), path = "selector/epoll.rs")]
+    #[cfg_attr(all(
+        not(mio_unsupported_force_poll_poll),
+        feature = "io-uring",
+        target_os = "linux",
+    ), path = "selector/uring.rs")]
     #[cfg_attr(all(
         not(mio_unsupported_force_poll_poll),
         any(
@@ -53,6 +59,16 @@ cfg_os_poll! {
     mod selector;
     pub(crate) use self::selector::*;
 
+    // The io_uring selector falls back to epoll if the kernel doesn't support
+    // io_uring, and uses the same `Event` type.
+    #[cfg(all(
+        not(mio_unsupported_force_poll_poll),
+        feature = "io-uring",
+        target_os = "linux",
+    ))]
+    #[path = "selector/epoll.rs"]
+    mod epoll;
+
     #[cfg_attr(all(
         not(mio_unsupported_force_waker_pipe),
         any(
@@ -105,11 +121,25 @@ cfg_os_poll! {
     mod waker;
     // NOTE: the `Waker` type is expected in the selector module as the
     // `poll(2)` implementation needs to do some special stuff.
+    // `Poll::poll_cancellable` uses a waker registered as a regular event
+    // source, also when using the `poll(2)` implementation.
+    pub(crate) use self::waker::Waker as CancelWaker;
 
     mod sourcefd;
     #[cfg(feature = "os-ext")]
     pub use self::sourcefd::SourceFd;
 
+    #[cfg(all(unix, any(feature = "net", feature = "os-ext")))]
+    mod fd;
+    #[cfg(all(unix, feature = "os-ext"))]
+    pub use self::fd::{is_nonblocking, set_nonblocking};
+    #[cfg(all(unix, any(feature = "net", feature = "os-ext")))]
+    pub(crate) use self::fd::probe;
+    #[cfg(all(unix, feature = "track-registrations"))]
+    pub(crate) use self::fd::file_id;
+    #[cfg(all(unix, any(feature = "net", feature = "os-ext")))]
+    pub use self::fd::{is_cloexec, set_cloexec};
+
     cfg_net! {
         mod net;
 
@@ -117,6 +147,11 @@ cfg_os_poll! {
         pub(crate) mod udp;
         #[cfg(not(target_os = "hermit"))]
         pub(crate) mod uds;
+        #[cfg(target_os = "linux")]
+        pub(crate) mod sctp;
+
+        #[cfg(all(unix, feature = "os-ext"))]
+        pub(crate) mod socket;
     }
 
     #[cfg(all(
@@ -153,6 +188,12 @@ cfg_os_poll! {
         not(target_os = "hermit"),
     ))]
     pub(crate) mod pipe;
+
+    #[cfg(all(unix, feature = "os-ext"))]
+    pub(crate) mod completion;
+
+    #[cfg(all(feature = "os-ext", any(target_os = "android", target_os = "linux")))]
+    pub(crate) mod splice;
 }
 
 cfg_not_os_poll! {
@@ -160,5 +201,14 @@ cfg_not_os_poll! {
         mod sourcefd;
         #[cfg(feature = "os-ext")]
         pub use self::sourcefd::SourceFd;
+
+        #[cfg(unix)]
+        mod fd;
+        #[cfg(all(unix, feature = "os-ext"))]
+        pub use self::fd::{is_nonblocking, set_nonblocking};
+        #[cfg(unix)]
+        pub(crate) use self::fd::probe;
+        #[cfg(unix)]
+        pub use self::fd::{is_cloexec, set_cloexec};
     }
 }
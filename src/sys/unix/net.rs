@@ -1,18 +1,36 @@
 use std::io;
 use std::mem::size_of;
+#[cfg(unix)]
+use std::mem::MaybeUninit;
 use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
 
-pub(crate) fn new_ip_socket(addr: SocketAddr, socket_type: libc::c_int) -> io::Result<libc::c_int> {
+#[cfg(unix)]
+use crate::net::TruncationPolicy;
+
+pub(crate) fn new_ip_socket(
+    addr: SocketAddr,
+    socket_type: libc::c_int,
+    protocol: libc::c_int,
+) -> io::Result<libc::c_int> {
     let domain = match addr {
         SocketAddr::V4(..) => libc::AF_INET,
         SocketAddr::V6(..) => libc::AF_INET6,
     };
 
-    new_socket(domain, socket_type)
+    new_socket_with_protocol(domain, socket_type, protocol)
 }
 
 /// Create a new non-blocking socket.
 pub(crate) fn new_socket(domain: libc::c_int, socket_type: libc::c_int) -> io::Result<libc::c_int> {
+    new_socket_with_protocol(domain, socket_type, 0)
+}
+
+/// Create a new non-blocking socket using `protocol`.
+pub(crate) fn new_socket_with_protocol(
+    domain: libc::c_int,
+    socket_type: libc::c_int,
+    protocol: libc::c_int,
+) -> io::Result<libc::c_int> {
     #[cfg(any(
         target_os = "android",
         target_os = "dragonfly",
@@ -29,7 +47,7 @@ pub(crate) fn new_socket(domain: libc::c_int, socket_type: libc::c_int) -> io::R
     #[cfg(target_os = "nto")]
     let socket_type = socket_type | libc::SOCK_CLOEXEC;
 
-    let socket = syscall!(socket(domain, socket_type, 0))?;
+    let socket = syscall!(socket(domain, socket_type, protocol))?;
 
     // Mimick `libstd` and set `SO_NOSIGPIPE` on apple systems.
     #[cfg(any(
@@ -76,6 +94,144 @@ pub(crate) fn new_socket(domain: libc::c_int, socket_type: libc::c_int) -> io::R
     Ok(socket)
 }
 
+/// Set the socket option `name` at `level` on `fd` to `value`.
+pub(crate) fn setsockopt<T>(
+    fd: libc::c_int,
+    level: libc::c_int,
+    name: libc::c_int,
+    value: T,
+) -> io::Result<()> {
+    syscall!(setsockopt(
+        fd,
+        level,
+        name,
+        &value as *const T as *const libc::c_void,
+        size_of::<T>() as libc::socklen_t,
+    ))
+    .map(|_| ())
+}
+
+/// Get the socket option `name` at `level` on `fd`.
+#[cfg(unix)]
+pub(crate) fn getsockopt<T: Copy>(
+    fd: libc::c_int,
+    level: libc::c_int,
+    name: libc::c_int,
+) -> io::Result<T> {
+    let mut value: MaybeUninit<T> = MaybeUninit::zeroed();
+    let mut len = size_of::<T>() as libc::socklen_t;
+    syscall!(getsockopt(
+        fd,
+        level,
+        name,
+        value.as_mut_ptr().cast(),
+        &mut len,
+    ))?;
+    debug_assert_eq!(len as usize, size_of::<T>());
+    // SAFETY: `getsockopt(2)` initialised the value for us.
+    Ok(unsafe { value.assume_init() })
+}
+
+/// Receive a datagram on `fd` into `buf`, using `policy` for datagrams larger
+/// than `buf`. If `addr` is provided the source address is written to it.
+#[cfg(unix)]
+pub(crate) fn recv_with_truncation(
+    fd: libc::c_int,
+    buf: &mut [u8],
+    addr: Option<&mut MaybeUninit<libc::sockaddr_storage>>,
+    policy: TruncationPolicy,
+) -> io::Result<usize> {
+    let flags = match policy {
+        TruncationPolicy::Truncate | TruncationPolicy::Error => 0,
+        #[cfg(any(target_os = "android", target_os = "linux"))]
+        TruncationPolicy::ReportLength => libc::MSG_TRUNC,
+        #[cfg(not(any(target_os = "android", target_os = "linux")))]
+        TruncationPolicy::ReportLength => {
+            return Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "reporting the length of truncated datagrams is not supported on this platform",
+            ))
+        }
+    };
+
+    let mut iov = libc::iovec {
+        iov_base: buf.as_mut_ptr().cast(),
+        iov_len: buf.len(),
+    };
+    // SAFETY: all zeroes is valid for `msghdr`, it also takes care of the
+    // private padding fields in some libc implementations.
+    let mut msg: libc::msghdr = unsafe { std::mem::zeroed() };
+    if let Some(addr) = addr {
+        msg.msg_name = addr.as_mut_ptr().cast();
+        msg.msg_namelen = size_of::<libc::sockaddr_storage>() as libc::socklen_t;
+    }
+    msg.msg_iov = &mut iov;
+    msg.msg_iovlen = 1;
+
+    let n = syscall!(recvmsg(fd, &mut msg, flags))?;
+    if policy == TruncationPolicy::Error && msg.msg_flags & libc::MSG_TRUNC != 0 {
+        return Err(io::Error::from_raw_os_error(libc::EMSGSIZE));
+    }
+    Ok(n as usize)
+}
+
+/// Returns the address family of the socket `fd`.
+#[cfg(unix)]
+pub(crate) fn socket_family(fd: libc::c_int) -> io::Result<libc::c_int> {
+    let mut storage: MaybeUninit<libc::sockaddr_storage> = MaybeUninit::zeroed();
+    let mut len = size_of::<libc::sockaddr_storage>() as libc::socklen_t;
+    syscall!(getsockname(fd, storage.as_mut_ptr().cast(), &mut len))?;
+    // SAFETY: `getsockname(2)` initialised (at least) the family for us, the
+    // remainder is zeroed.
+    Ok(libc::c_int::from(unsafe { storage.assume_init() }.ss_family))
+}
+
+/// Prepare a listening socket inherited from the parent process for use with
+/// Mio, after validating it's a listening stream socket of one of the address
+/// `families`.
+#[cfg(unix)]
+pub(crate) fn prepare_inherited_listener(
+    fd: libc::c_int,
+    families: &[libc::c_int],
+) -> io::Result<()> {
+    let socket_type: libc::c_int = getsockopt(fd, libc::SOL_SOCKET, libc::SO_TYPE)?;
+    let listening: libc::c_int = getsockopt(fd, libc::SOL_SOCKET, libc::SO_ACCEPTCONN)?;
+    if socket_type != libc::SOCK_STREAM || listening == 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "file descriptor is not a listening stream socket",
+        ));
+    }
+    if !families.contains(&socket_family(fd)?) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "file descriptor has an unexpected address family",
+        ));
+    }
+    let flags = syscall!(fcntl(fd, libc::F_GETFL))?;
+    if flags & libc::O_NONBLOCK == 0 {
+        syscall!(fcntl(fd, libc::F_SETFL, flags | libc::O_NONBLOCK))?;
+    }
+    // The parent cleared `FD_CLOEXEC` to pass the socket to us, don't leak it
+    // to our own child processes.
+    syscall!(fcntl(fd, libc::F_SETFD, libc::FD_CLOEXEC))?;
+    Ok(())
+}
+
+/// Set the `IPV6_UNICAST_HOPS` option on `fd`.
+#[cfg(unix)]
+pub(crate) fn set_hop_limit(fd: libc::c_int, hops: u32) -> io::Result<()> {
+    let hops: libc::c_int = hops.try_into().unwrap_or(libc::c_int::MAX);
+    setsockopt(fd, libc::IPPROTO_IPV6, libc::IPV6_UNICAST_HOPS, hops)
+}
+
+/// Get the `IPV6_UNICAST_HOPS` option on `fd`.
+#[cfg(unix)]
+pub(crate) fn hop_limit(fd: libc::c_int) -> io::Result<u32> {
+    getsockopt::<libc::c_int>(fd, libc::IPPROTO_IPV6, libc::IPV6_UNICAST_HOPS)
+        .map(|hops| hops as u32)
+}
+
 /// A type with the same memory layout as `libc::sockaddr`. Used in converting Rust level
 /// SocketAddr* types into their system representation. The benefit of this specific
 /// type over using `libc::sockaddr_storage` is that this type is exactly as large as it
@@ -66,9 +66,10 @@ cfg_os_ext! {
 use std::fs::File;
 use std::io::{IoSlice, IoSliceMut, Read, Write};
 use std::os::fd::{AsFd, AsRawFd, BorrowedFd, FromRawFd, IntoRawFd, OwnedFd};
-use std::process::{ChildStderr, ChildStdin, ChildStdout};
+use std::process::{Child, ChildStderr, ChildStdin, ChildStdout};
 
 use crate::io_source::IoSource;
+use crate::sys::unix::fd::set_nonblocking;
 use crate::{event, Interest, Registry, Token};
 
 /// Create a new non-blocking Unix pipe.
@@ -213,6 +214,14 @@ pub fn new() -> io::Result<(Sender, Receiver)> {
     Ok((w, r))
 }
 
+/// Error returned when a `Child` doesn't have the requested pipe.
+fn missing_pipe(name: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::NotFound,
+        format!("child process has no piped {name}"),
+    )
+}
+
 /// Sending end of an Unix pipe.
 ///
 /// See [`new`] for documentation, including examples.
@@ -222,6 +231,22 @@ pub struct Sender {
 }
 
 impl Sender {
+    /// Take the standard input pipe of `child`, returning a non-blocking
+    /// `Sender`.
+    ///
+    /// This takes [`Child::stdin`], leaving `None` in its place, so that the
+    /// `Child` no longer owns the pipe. Returns an error of kind
+    /// [`io::ErrorKind::NotFound`] if standard input wasn't set up as a pipe,
+    /// using [`Stdio::piped`], or was already taken.
+    ///
+    /// [`Stdio::piped`]: std::process::Stdio::piped
+    pub fn from_child_stdin(child: &mut Child) -> io::Result<Sender> {
+        let stdin = child.stdin.take().ok_or_else(|| missing_pipe("stdin"))?;
+        let sender = Sender::from(stdin);
+        sender.set_nonblocking(true)?;
+        Ok(sender)
+    }
+
     /// Set the `Sender` into or out of non-blocking mode.
     pub fn set_nonblocking(&self, nonblocking: bool) -> io::Result<()> {
         set_nonblocking(self.inner.as_raw_fd(), nonblocking)
@@ -318,6 +343,10 @@ impl event::Source for Sender {
     fn deregister(&mut self, registry: &Registry) -> io::Result<()> {
         self.inner.deregister(registry)
     }
+
+    fn poll_ready(&self, interests: Interest) -> io::Result<event::Readiness> {
+        self.inner.poll_ready(interests)
+    }
 }
 
 impl Write for Sender {
@@ -407,6 +436,69 @@ pub struct Receiver {
 }
 
 impl Receiver {
+    /// Take the standard output pipe of `child`, returning a non-blocking
+    /// `Receiver`.
+    ///
+    /// This takes [`Child::stdout`], leaving `None` in its place, so that the
+    /// `Child` no longer owns the pipe. Returns an error of kind
+    /// [`io::ErrorKind::NotFound`] if standard output wasn't set up as a pipe,
+    /// using [`Stdio::piped`], or was already taken.
+    ///
+    /// [`Stdio::piped`]: std::process::Stdio::piped
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io::{self, Read};
+    /// use std::process::{Command, Stdio};
+    ///
+    /// use mio::unix::pipe::Receiver;
+    /// use mio::{Events, Interest, Poll, Token};
+    ///
+    /// # fn main() -> io::Result<()> {
+    /// let mut poll = Poll::new()?;
+    /// let mut events = Events::with_capacity(8);
+    ///
+    /// let mut child = Command::new("echo")
+    ///     .arg("hello")
+    ///     .stdout(Stdio::piped())
+    ///     .spawn()?;
+    /// let mut stdout = Receiver::from_child_stdout(&mut child)?;
+    /// poll.registry().register(&mut stdout, Token(0), Interest::READABLE)?;
+    ///
+    /// let mut output = Vec::new();
+    /// loop {
+    ///     poll.poll(&mut events, None)?;
+    ///     match stdout.read_to_end(&mut output) {
+    ///         // Read all output, `echo` exited.
+    ///         Ok(_) => break,
+    ///         Err(err) if err.kind() == io::ErrorKind::WouldBlock => continue,
+    ///         Err(err) => return Err(err),
+    ///     }
+    /// }
+    /// assert_eq!(output, b"hello\n");
+    /// child.wait()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_child_stdout(child: &mut Child) -> io::Result<Receiver> {
+        let stdout = child.stdout.take().ok_or_else(|| missing_pipe("stdout"))?;
+        let receiver = Receiver::from(stdout);
+        receiver.set_nonblocking(true)?;
+        Ok(receiver)
+    }
+
+    /// Take the standard error pipe of `child`, returning a non-blocking
+    /// `Receiver`.
+    ///
+    /// Same as [`Receiver::from_child_stdout`], but for [`Child::stderr`].
+    pub fn from_child_stderr(child: &mut Child) -> io::Result<Receiver> {
+        let stderr = child.stderr.take().ok_or_else(|| missing_pipe("stderr"))?;
+        let receiver = Receiver::from(stderr);
+        receiver.set_nonblocking(true)?;
+        Ok(receiver)
+    }
+
     /// Set the `Receiver` into or out of non-blocking mode.
     pub fn set_nonblocking(&self, nonblocking: bool) -> io::Result<()> {
         set_nonblocking(self.inner.as_raw_fd(), nonblocking)
@@ -503,6 +595,10 @@ impl event::Source for Receiver {
     fn deregister(&mut self, registry: &Registry) -> io::Result<()> {
         self.inner.deregister(registry)
     }
+
+    fn poll_ready(&self, interests: Interest) -> io::Result<event::Readiness> {
+        self.inner.poll_ready(interests)
+    }
 }
 
 impl Read for Receiver {
@@ -585,35 +681,4 @@ impl From<OwnedFd> for Receiver {
     }
 }
 
-#[cfg(not(any(target_os = "illumos", target_os = "solaris", target_os = "vita")))]
-fn set_nonblocking(fd: RawFd, nonblocking: bool) -> io::Result<()> {
-    let value = nonblocking as libc::c_int;
-    if unsafe { libc::ioctl(fd, libc::FIONBIO, &value) } == -1 {
-        Err(io::Error::last_os_error())
-    } else {
-        Ok(())
-    }
-}
-
-#[cfg(any(target_os = "illumos", target_os = "solaris", target_os = "vita"))]
-fn set_nonblocking(fd: RawFd, nonblocking: bool) -> io::Result<()> {
-    let flags = unsafe { libc::fcntl(fd, libc::F_GETFL) };
-    if flags < 0 {
-        return Err(io::Error::last_os_error());
-    }
-
-    let nflags = if nonblocking {
-        flags | libc::O_NONBLOCK
-    } else {
-        flags & !libc::O_NONBLOCK
-    };
-
-    if flags != nflags {
-        if unsafe { libc::fcntl(fd, libc::F_SETFL, nflags) } < 0 {
-            return Err(io::Error::last_os_error());
-        }
-    }
-
-    Ok(())
-}
 } // `cfg_os_ext!`.
@@ -0,0 +1,218 @@
+use std::mem::{self, MaybeUninit};
+use std::net::SocketAddr;
+use std::os::fd::{AsRawFd, FromRawFd, OwnedFd};
+use std::{io, ptr};
+
+use crate::net::SctpInfo;
+use crate::sys::unix::net::{
+    getsockopt, new_socket_with_protocol, setsockopt, socket_addr, to_socket_addr,
+};
+
+// Definitions from `<linux/sctp.h>`, not (yet) provided by the libc crate.
+const SOL_SCTP: libc::c_int = 132;
+const SCTP_RECVRCVINFO: libc::c_int = 32;
+const SCTP_SOCKOPT_PEELOFF_FLAGS: libc::c_int = 122;
+// Control message types.
+const SCTP_SNDINFO: libc::c_int = 2;
+const SCTP_RCVINFO: libc::c_int = 3;
+
+/// `struct sctp_sndinfo`.
+#[repr(C)]
+#[derive(Copy, Clone)]
+struct SndInfo {
+    snd_sid: u16,
+    snd_flags: u16,
+    snd_ppid: u32,
+    snd_context: u32,
+    snd_assoc_id: i32,
+}
+
+/// `struct sctp_rcvinfo`.
+#[repr(C)]
+#[derive(Copy, Clone)]
+#[allow(dead_code)] // Not all fields are exposed.
+struct RcvInfo {
+    rcv_sid: u16,
+    rcv_ssn: u16,
+    rcv_flags: u16,
+    rcv_ppid: u32,
+    rcv_tsn: u32,
+    rcv_cumtsn: u32,
+    rcv_context: u32,
+    rcv_assoc_id: i32,
+}
+
+/// `sctp_peeloff_flags_arg_t`.
+#[repr(C)]
+struct PeeloffFlagsArg {
+    assoc_id: i32,
+    sd: libc::c_int,
+    flags: libc::c_uint,
+}
+
+/// Create a one-to-many style SCTP socket bound to `addr`, that accepts
+/// incoming associations.
+pub(crate) fn bind(addr: SocketAddr) -> io::Result<OwnedFd> {
+    let domain = match addr {
+        SocketAddr::V4(..) => libc::AF_INET,
+        SocketAddr::V6(..) => libc::AF_INET6,
+    };
+    let socket = match new_socket_with_protocol(domain, libc::SOCK_SEQPACKET, libc::IPPROTO_SCTP) {
+        // SAFETY: `new_socket_with_protocol` returns a valid file descriptor.
+        Ok(fd) => unsafe { OwnedFd::from_raw_fd(fd) },
+        Err(err)
+            if matches!(
+                err.raw_os_error(),
+                Some(libc::EPROTONOSUPPORT | libc::ESOCKTNOSUPPORT)
+            ) =>
+        {
+            return Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "SCTP is not supported by the kernel",
+            ))
+        }
+        Err(err) => return Err(err),
+    };
+
+    let (raw_addr, raw_addr_length) = socket_addr(&addr);
+    syscall!(bind(socket.as_raw_fd(), raw_addr.as_ptr(), raw_addr_length))?;
+    syscall!(listen(socket.as_raw_fd(), 1024))?;
+    // Receive the stream and association of every message.
+    setsockopt(
+        socket.as_raw_fd(),
+        SOL_SCTP,
+        SCTP_RECVRCVINFO,
+        1 as libc::c_int,
+    )?;
+    Ok(socket)
+}
+
+pub(crate) fn local_addr(socket: &OwnedFd) -> io::Result<SocketAddr> {
+    let mut addr: MaybeUninit<libc::sockaddr_storage> = MaybeUninit::uninit();
+    let mut length = mem::size_of::<libc::sockaddr_storage>() as libc::socklen_t;
+    syscall!(getsockname(
+        socket.as_raw_fd(),
+        addr.as_mut_ptr().cast(),
+        &mut length
+    ))?;
+    // SAFETY: `getsockname(2)` initialised the address for us.
+    unsafe { to_socket_addr(addr.as_ptr()) }
+}
+
+/// Send `buf` to `addr`, or on the association of `info` if `addr` is `None`.
+pub(crate) fn send_to(
+    socket: &OwnedFd,
+    buf: &[u8],
+    addr: Option<SocketAddr>,
+    info: &SctpInfo,
+) -> io::Result<usize> {
+    let mut iov = libc::iovec {
+        iov_base: buf.as_ptr() as *mut _,
+        iov_len: buf.len(),
+    };
+    // Large enough to hold a `SCTP_SNDINFO` message, using `u64` to get the
+    // alignment required for `cmsghdr`.
+    let mut control = [0u64; 8];
+
+    // SAFETY: all zeroes is valid for `msghdr`, it also takes care of the
+    // private padding fields in some libc implementations.
+    let mut msg: libc::msghdr = unsafe { mem::zeroed() };
+    let raw_addr = addr.as_ref().map(socket_addr);
+    if let Some((raw_addr, raw_addr_length)) = &raw_addr {
+        msg.msg_name = raw_addr.as_ptr() as *mut _;
+        msg.msg_namelen = *raw_addr_length;
+    }
+    msg.msg_iov = &mut iov;
+    msg.msg_iovlen = 1;
+    msg.msg_control = control.as_mut_ptr().cast();
+    // SAFETY: `CMSG_SPACE` is always safe to call.
+    msg.msg_controllen = unsafe { libc::CMSG_SPACE(mem::size_of::<SndInfo>() as _) } as _;
+
+    let sndinfo = SndInfo {
+        snd_sid: info.stream,
+        snd_flags: 0,
+        snd_ppid: info.ppid,
+        snd_context: 0,
+        snd_assoc_id: if addr.is_some() { 0 } else { info.assoc_id },
+    };
+    // SAFETY: `control` is large enough for a single `SCTP_SNDINFO` message.
+    unsafe {
+        let cmsg = libc::CMSG_FIRSTHDR(&msg);
+        (*cmsg).cmsg_level = SOL_SCTP;
+        (*cmsg).cmsg_type = SCTP_SNDINFO;
+        (*cmsg).cmsg_len = libc::CMSG_LEN(mem::size_of::<SndInfo>() as _) as _;
+        ptr::write_unaligned(libc::CMSG_DATA(cmsg).cast(), sndinfo);
+    }
+
+    syscall!(sendmsg(socket.as_raw_fd(), &msg, libc::MSG_NOSIGNAL)).map(|n| n as usize)
+}
+
+pub(crate) fn recv_from(
+    socket: &OwnedFd,
+    buf: &mut [u8],
+) -> io::Result<(usize, SocketAddr, SctpInfo)> {
+    let mut addr: MaybeUninit<libc::sockaddr_storage> = MaybeUninit::uninit();
+    let mut iov = libc::iovec {
+        iov_base: buf.as_mut_ptr().cast(),
+        iov_len: buf.len(),
+    };
+    // Large enough to hold a `SCTP_RCVINFO` message, using `u64` to get the
+    // alignment required for `cmsghdr`.
+    let mut control = [0u64; 16];
+
+    // SAFETY: all zeroes is valid for `msghdr`, it also takes care of the
+    // private padding fields in some libc implementations.
+    let mut msg: libc::msghdr = unsafe { mem::zeroed() };
+    msg.msg_name = addr.as_mut_ptr().cast();
+    msg.msg_namelen = mem::size_of::<libc::sockaddr_storage>() as libc::socklen_t;
+    msg.msg_iov = &mut iov;
+    msg.msg_iovlen = 1;
+    msg.msg_control = control.as_mut_ptr().cast();
+    msg.msg_controllen = mem::size_of_val(&control) as _;
+
+    let n = syscall!(recvmsg(socket.as_raw_fd(), &mut msg, 0))?;
+    // SAFETY: `recvmsg(2)` initialised the address for us.
+    let addr = unsafe { to_socket_addr(addr.as_ptr())? };
+
+    let mut info = SctpInfo::new(0, 0);
+    // SAFETY: the kernel wrote `msg_controllen` bytes of valid control
+    // messages, which the `CMSG_*` macros stay within.
+    unsafe {
+        let mut cmsg = libc::CMSG_FIRSTHDR(&msg);
+        while !cmsg.is_null() {
+            if ((*cmsg).cmsg_level, (*cmsg).cmsg_type) == (SOL_SCTP, SCTP_RCVINFO) {
+                let rcvinfo: RcvInfo = ptr::read_unaligned(libc::CMSG_DATA(cmsg).cast());
+                info.stream = rcvinfo.rcv_sid;
+                info.ppid = rcvinfo.rcv_ppid;
+                info.assoc_id = rcvinfo.rcv_assoc_id;
+            }
+            cmsg = libc::CMSG_NXTHDR(&msg, cmsg);
+        }
+    }
+
+    Ok((n as usize, addr, info))
+}
+
+/// Branch off the association `assoc_id` into a new (non-blocking) socket.
+pub(crate) fn peel_off(socket: &OwnedFd, assoc_id: i32) -> io::Result<OwnedFd> {
+    let mut arg = PeeloffFlagsArg {
+        assoc_id,
+        sd: -1,
+        flags: (libc::SOCK_NONBLOCK | libc::SOCK_CLOEXEC) as libc::c_uint,
+    };
+    let mut length = mem::size_of::<PeeloffFlagsArg>() as libc::socklen_t;
+    syscall!(getsockopt(
+        socket.as_raw_fd(),
+        SOL_SCTP,
+        SCTP_SOCKOPT_PEELOFF_FLAGS,
+        (&mut arg as *mut PeeloffFlagsArg).cast(),
+        &mut length,
+    ))?;
+    // SAFETY: the kernel returned a new file descriptor.
+    Ok(unsafe { OwnedFd::from_raw_fd(arg.sd) })
+}
+
+pub(crate) fn take_error(socket: &OwnedFd) -> io::Result<Option<io::Error>> {
+    getsockopt::<libc::c_int>(socket.as_raw_fd(), libc::SOL_SOCKET, libc::SO_ERROR)
+        .map(|err| (err != 0).then(|| io::Error::from_raw_os_error(err)))
+}
@@ -1,12 +1,14 @@
 use std::os::fd::{AsRawFd, FromRawFd, OwnedFd, RawFd};
+use std::os::unix::net::UnixDatagram;
 #[cfg(debug_assertions)]
 use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::OnceLock;
 use std::time::Duration;
 use std::{io, ptr};
 
 use libc::{EPOLLET, EPOLLIN, EPOLLOUT, EPOLLPRI, EPOLLRDHUP};
 
-use crate::{Interest, Token};
+use crate::{Capabilities, Interest, Token};
 
 /// Unique id for use as `SelectorId`.
 #[cfg(debug_assertions)]
@@ -21,8 +23,7 @@ pub struct Selector {
 
 impl Selector {
     pub fn new() -> io::Result<Selector> {
-        // SAFETY: `epoll_create1(2)` ensures the fd is valid.
-        let ep = unsafe { OwnedFd::from_raw_fd(syscall!(epoll_create1(libc::EPOLL_CLOEXEC))?) };
+        let ep = new_epoll(|| syscall!(epoll_create1(libc::EPOLL_CLOEXEC)))?;
         Ok(Selector {
             #[cfg(debug_assertions)]
             id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
@@ -30,6 +31,22 @@ impl Selector {
         })
     }
 
+    /// Reserve capacity for at least `additional` more registrations, this
+    /// selector doesn't keep any per registration state so this is a no-op.
+    pub fn reserve(&self, _: usize) {}
+
+    pub fn capabilities(&self) -> Capabilities {
+        static EXCLUSIVE: OnceLock<bool> = OnceLock::new();
+        Capabilities {
+            // `epoll_wait` only supports milliseconds.
+            precise_timeout: false,
+            exclusive: *EXCLUSIVE.get_or_init(probe_exclusive),
+            oneshot: true,
+            priority_interest: cfg!(any(target_os = "android", target_os = "linux")),
+            level_trigger: true,
+        }
+    }
+
     pub fn try_clone(&self) -> io::Result<Selector> {
         self.ep.try_clone().map(|ep| Selector {
             // It's the same selector, so we use the same id.
@@ -39,7 +56,7 @@ impl Selector {
         })
     }
 
-    pub fn select(&self, events: &mut Events, timeout: Option<Duration>) -> io::Result<()> {
+    pub fn select(&self, events: &mut Events, timeout: Option<Duration>) -> io::Result<bool> {
         let timeout = timeout
             .map(|to| {
                 // `Duration::as_millis` truncates, so round up. This avoids
@@ -63,6 +80,8 @@ impl Selector {
             // This is safe because `epoll_wait` ensures that `n_events` are
             // assigned.
             unsafe { events.set_len(n_events as usize) };
+            // `epoll_wait` only returns zero events if the timeout elapsed.
+            n_events == 0
         })
     }
 
@@ -75,7 +94,8 @@ impl Selector {
         };
 
         let ep = self.ep.as_raw_fd();
-        syscall!(epoll_ctl(ep, libc::EPOLL_CTL_ADD, fd, &mut event)).map(|_| ())
+        syscall!(epoll_ctl(ep, libc::EPOLL_CTL_ADD, fd, &mut event))
+            .map(|_| ())
     }
 
     pub fn reregister(&self, fd: RawFd, token: Token, interests: Interest) -> io::Result<()> {
@@ -87,12 +107,14 @@ impl Selector {
         };
 
         let ep = self.ep.as_raw_fd();
-        syscall!(epoll_ctl(ep, libc::EPOLL_CTL_MOD, fd, &mut event)).map(|_| ())
+        syscall!(epoll_ctl(ep, libc::EPOLL_CTL_MOD, fd, &mut event))
+            .map(|_| ())
     }
 
     pub fn deregister(&self, fd: RawFd) -> io::Result<()> {
         let ep = self.ep.as_raw_fd();
-        syscall!(epoll_ctl(ep, libc::EPOLL_CTL_DEL, fd, ptr::null_mut())).map(|_| ())
+        syscall!(epoll_ctl(ep, libc::EPOLL_CTL_DEL, fd, ptr::null_mut()))
+            .map(|_| ())
     }
 }
 
@@ -132,8 +154,19 @@ fn interests_to_epoll(interests: Interest) -> u32 {
 pub type Event = libc::epoll_event;
 pub type Events = Vec<Event>;
 
+/// Converts an allocation of events into `Events`, see `Events::from_vec`.
+pub fn events_from_vec(events: Vec<Event>) -> Events {
+    events
+}
+
+/// Converts `Events` into its allocation, see `Events::into_vec`.
+pub fn events_into_vec(events: Events) -> Vec<Event> {
+    events
+}
+
 pub mod event {
     use std::fmt;
+    use std::os::fd::RawFd;
 
     use crate::sys::Event;
     use crate::Token;
@@ -187,6 +220,11 @@ pub mod event {
         false
     }
 
+    pub fn raw_fd(_: &Event) -> Option<RawFd> {
+        // Only the token is stored.
+        None
+    }
+
     pub fn debug_details(f: &mut fmt::Formatter<'_>, event: &Event) -> fmt::Result {
         #[allow(clippy::trivially_copy_pass_by_ref)]
         fn check_events(got: &u32, want: &libc::c_int) -> bool {
@@ -222,10 +260,129 @@ pub mod event {
     }
 }
 
-// No special requirement from the implementation around waking.
+/// Returns true if the kernel supports `EPOLLEXCLUSIVE` (Linux 4.5+).
+///
+/// Older kernels silently ignore the flag when adding a file descriptor, but
+/// kernels supporting it reject it with `EINVAL` when modifying a registration.
+fn probe_exclusive() -> bool {
+    let probe = || -> io::Result<bool> {
+        let ep = new_epoll(|| syscall!(epoll_create1(libc::EPOLL_CLOEXEC)))?;
+        let socket = UnixDatagram::unbound()?;
+        let mut event = libc::epoll_event {
+            events: (EPOLLIN | libc::EPOLLEXCLUSIVE) as u32,
+            u64: 0,
+        };
+        let (ep, fd) = (ep.as_raw_fd(), socket.as_raw_fd());
+        syscall!(epoll_ctl(ep, libc::EPOLL_CTL_ADD, fd, &mut event))?;
+        match syscall!(epoll_ctl(ep, libc::EPOLL_CTL_MOD, fd, &mut event)) {
+            Ok(_) => Ok(false),
+            Err(err) => Ok(err.raw_os_error() == Some(libc::EINVAL)),
+        }
+    };
+    probe().unwrap_or(false)
+}
+
+/// Create a new epoll instance with close-on-exec set, using `epoll_create1`.
+///
+/// Some restricted environments (e.g. sandboxes emulating the Linux system
+/// call interface) don't support `epoll_create1(2)` or its flags, in which
+/// case this falls back to `epoll_create(2)` and sets close-on-exec using
+/// `fcntl(2)`. If epoll is not available at all a clear error is returned,
+/// rather than just `ENOSYS`.
+fn new_epoll<F>(epoll_create1: F) -> io::Result<OwnedFd>
+where
+    F: FnOnce() -> io::Result<RawFd>,
+{
+    let err = match epoll_create1() {
+        // SAFETY: `epoll_create1(2)` ensures the fd is valid.
+        Ok(ep) => return Ok(unsafe { OwnedFd::from_raw_fd(ep) }),
+        Err(err) => err,
+    };
+
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    let err = match err.raw_os_error() {
+        Some(libc::ENOSYS | libc::EINVAL) => {
+            // The size argument is ignored, but must be positive.
+            match syscall!(epoll_create(1024)) {
+                Ok(ep) => {
+                    // SAFETY: `epoll_create(2)` ensures the fd is valid.
+                    let ep = unsafe { OwnedFd::from_raw_fd(ep) };
+                    syscall!(fcntl(ep.as_raw_fd(), libc::F_SETFD, libc::FD_CLOEXEC))?;
+                    return Ok(ep);
+                }
+                Err(err) => err,
+            }
+        }
+        _ => err,
+    };
+
+    if err.raw_os_error() == Some(libc::ENOSYS) {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            format!("epoll is not available on this system: {err}"),
+        ))
+    } else {
+        Err(err)
+    }
+}
+
+// No special requirement from the implementation around waking. When used as
+// fallback for the io_uring selector that selector provides the `Waker` and
+// `IoSourceState`.
+#[cfg(not(all(feature = "io-uring", target_os = "linux")))]
 pub(crate) use crate::sys::unix::waker::Waker;
 
+#[cfg(not(all(feature = "io-uring", target_os = "linux")))]
 cfg_io_source! {
     mod stateless_io_source;
     pub(crate) use stateless_io_source::IoSourceState;
 }
+
+#[cfg(test)]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+mod tests {
+    use std::io;
+    use std::os::fd::{AsRawFd, FromRawFd, OwnedFd};
+    use std::time::Duration;
+
+    use super::{new_epoll, Events, Selector};
+    use crate::{Interest, Token};
+
+    #[test]
+    fn new_epoll_fallback() {
+        for errno in [libc::ENOSYS, libc::EINVAL] {
+            let ep = new_epoll(|| Err(io::Error::from_raw_os_error(errno))).unwrap();
+            let flags = unsafe { libc::fcntl(ep.as_raw_fd(), libc::F_GETFD) };
+            assert!(flags & libc::FD_CLOEXEC != 0, "CLOEXEC not set");
+        }
+    }
+
+    #[test]
+    fn new_epoll_error() {
+        let err = new_epoll(|| Err(io::Error::from_raw_os_error(libc::EMFILE))).unwrap_err();
+        assert_eq!(err.raw_os_error(), Some(libc::EMFILE));
+    }
+
+    #[test]
+    fn select_timed_out() {
+        let selector = Selector::new().unwrap();
+        let mut events = Events::with_capacity(8);
+
+        let timed_out = selector
+            .select(&mut events, Some(Duration::from_millis(10)))
+            .unwrap();
+        assert!(timed_out);
+        assert!(events.is_empty());
+
+        let fd = syscall!(eventfd(1, libc::EFD_CLOEXEC | libc::EFD_NONBLOCK)).unwrap();
+        let fd = unsafe { OwnedFd::from_raw_fd(fd) };
+        selector
+            .register(fd.as_raw_fd(), Token(1), Interest::READABLE)
+            .unwrap();
+        let timed_out = selector
+            .select(&mut events, Some(Duration::from_millis(10)))
+            .unwrap();
+        assert!(!timed_out);
+        assert_eq!(events.len(), 1);
+    }
+}
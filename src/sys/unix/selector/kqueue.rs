@@ -1,4 +1,4 @@
-use crate::{Interest, Token};
+use crate::{Capabilities, Interest, Token};
 use std::mem::{self, MaybeUninit};
 use std::ops::{Deref, DerefMut};
 use std::os::fd::{AsRawFd, FromRawFd, OwnedFd, RawFd};
@@ -82,6 +82,22 @@ impl Selector {
         })
     }
 
+    /// Reserve capacity for at least `additional` more registrations, this
+    /// selector doesn't keep any per registration state so this is a no-op.
+    pub fn reserve(&self, _: usize) {}
+
+    pub fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            // Timeouts are passed as `timespec`.
+            precise_timeout: true,
+            exclusive: false,
+            oneshot: true,
+            priority_interest: false,
+            // Without `EV_CLEAR`.
+            level_trigger: true,
+        }
+    }
+
     pub fn try_clone(&self) -> io::Result<Selector> {
         self.kq.try_clone().map(|kq| Selector {
             // It's the same selector, so we use the same id.
@@ -91,7 +107,7 @@ impl Selector {
         })
     }
 
-    pub fn select(&self, events: &mut Events, timeout: Option<Duration>) -> io::Result<()> {
+    pub fn select(&self, events: &mut Events, timeout: Option<Duration>) -> io::Result<bool> {
         let timeout = timeout.map(|to| libc::timespec {
             tv_sec: cmp::min(to.as_secs(), libc::time_t::MAX as u64) as libc::time_t,
             // `Duration::subsec_nanos` is guaranteed to be less than one
@@ -118,6 +134,8 @@ impl Selector {
             // This is safe because `kevent` ensures that `n_events` are
             // assigned.
             unsafe { events.set_len(n_events as usize) };
+            // `kevent` only returns zero events if the timeout elapsed.
+            n_events == 0
         })
     }
 
@@ -217,10 +235,10 @@ impl Selector {
         target_os = "visionos",
         target_os = "watchos"
     ))]
-    pub fn setup_waker(&self, token: Token) -> io::Result<()> {
+    pub fn setup_waker(&self, ident: usize, token: Token) -> io::Result<()> {
         // First attempt to accept user space notifications.
         let mut kevent = kevent!(
-            0,
+            ident,
             libc::EVFILT_USER,
             libc::EV_ADD | libc::EV_CLEAR | libc::EV_RECEIPT,
             token.0
@@ -245,9 +263,9 @@ impl Selector {
         target_os = "visionos",
         target_os = "watchos"
     ))]
-    pub fn wake(&self, token: Token) -> io::Result<()> {
+    pub fn wake(&self, ident: usize, token: Token) -> io::Result<()> {
         let mut kevent = kevent!(
-            0,
+            ident,
             libc::EVFILT_USER,
             libc::EV_ADD | libc::EV_RECEIPT,
             token.0
@@ -263,14 +281,44 @@ impl Selector {
             }
         })
     }
+
+    // Used by `Waker`.
+    #[cfg(any(
+        target_os = "freebsd",
+        target_os = "ios",
+        target_os = "macos",
+        target_os = "tvos",
+        target_os = "visionos",
+        target_os = "watchos"
+    ))]
+    pub fn reset_waker(&self, ident: usize, token: Token) -> io::Result<()> {
+        // A triggered user space notification can't be untriggered, so we
+        // remove it (dropping the pending event, if any) and add it again.
+        let mut changes: [libc::kevent; 2] = [
+            kevent!(ident, libc::EVFILT_USER, libc::EV_DELETE | libc::EV_RECEIPT, token.0),
+            kevent!(
+                ident,
+                libc::EVFILT_USER,
+                libc::EV_ADD | libc::EV_CLEAR | libc::EV_RECEIPT,
+                token.0
+            ),
+        ];
+        kevent_register(self.kq.as_raw_fd(), &mut changes, &[libc::ENOENT as i64])
+    }
 }
 
 /// Register `changes` with `kq`ueue.
+///
+/// All `changes` must use the `EV_RECEIPT` flag. This way a failure to apply a
+/// change is not returned from the `kevent(2)` call itself, but as an event
+/// with the `EV_ERROR` flag set and the error (`errno`) in the `data` field,
+/// see `check_errors`.
 fn kevent_register(
     kq: RawFd,
     changes: &mut [libc::kevent],
     ignored_errors: &[i64],
 ) -> io::Result<()> {
+    debug_assert!(changes.iter().all(|c| c.flags & libc::EV_RECEIPT != 0));
     syscall!(kevent(
         kq,
         changes.as_ptr(),
@@ -279,18 +327,20 @@ fn kevent_register(
         changes.len() as Count,
         ptr::null(),
     ))
-    .map(|_| ())
+    .map(|n| n as usize)
     .or_else(|err| {
         // According to the manual page of FreeBSD: "When kevent() call fails
         // with EINTR error, all changes in the changelist have been applied",
         // so we can safely ignore it.
         if err.raw_os_error() == Some(libc::EINTR) {
-            Ok(())
+            Ok(0)
         } else {
             Err(err)
         }
     })
-    .and_then(|()| check_errors(changes, ignored_errors))
+    // Only check the receipts returned by the kernel, the remainder of
+    // `changes` still contains the original changes.
+    .and_then(|n| check_errors(&changes[..n], ignored_errors))
 }
 
 /// Check all events for possible errors, it returns the first error found.
@@ -332,6 +382,16 @@ impl Events {
     }
 }
 
+/// Converts an allocation of events into `Events`, see `Events::from_vec`.
+pub fn events_from_vec(events: Vec<Event>) -> Events {
+    Events(events)
+}
+
+/// Converts `Events` into its allocation, see `Events::into_vec`.
+pub fn events_into_vec(events: Events) -> Vec<Event> {
+    events.0
+}
+
 impl Deref for Events {
     type Target = Vec<libc::kevent>;
 
@@ -356,6 +416,7 @@ unsafe impl Sync for Events {}
 
 pub mod event {
     use std::fmt;
+    use std::os::fd::RawFd;
 
     use crate::sys::Event;
     use crate::Token;
@@ -460,6 +521,16 @@ pub mod event {
         }
     }
 
+    pub fn raw_fd(event: &Event) -> Option<RawFd> {
+        // For the read and write filters `ident` is the file descriptor, for
+        // the other filters (e.g. the `Waker`'s `EVFILT_USER`) it's not.
+        if event.filter == libc::EVFILT_READ || event.filter == libc::EVFILT_WRITE {
+            Some(event.ident as RawFd)
+        } else {
+            None
+        }
+    }
+
     pub fn debug_details(f: &mut fmt::Formatter<'_>, event: &Event) -> fmt::Result {
         debug_detail!(
             FilterDetails(Filter),
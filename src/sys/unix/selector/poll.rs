@@ -17,7 +17,7 @@ use std::time::Duration;
 use std::{cmp, fmt, io};
 
 use crate::sys::unix::waker::Waker as WakerInternal;
-use crate::{Interest, Token};
+use crate::{Capabilities, Error, Interest, Token};
 
 /// Unique id for use as `SelectorId`.
 #[cfg(debug_assertions)]
@@ -37,13 +37,28 @@ impl Selector {
         })
     }
 
+    pub fn reserve(&self, additional: usize) {
+        self.state.reserve(additional)
+    }
+
+    pub fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            // `poll(2)` only supports milliseconds.
+            precise_timeout: false,
+            exclusive: false,
+            oneshot: false,
+            priority_interest: cfg!(any(target_os = "android", target_os = "linux")),
+            level_trigger: true,
+        }
+    }
+
     pub fn try_clone(&self) -> io::Result<Selector> {
         let state = self.state.clone();
 
         Ok(Selector { state })
     }
 
-    pub fn select(&self, events: &mut Events, timeout: Option<Duration>) -> io::Result<()> {
+    pub fn select(&self, events: &mut Events, timeout: Option<Duration>) -> io::Result<bool> {
         self.state.select(events, timeout)
     }
 
@@ -73,6 +88,15 @@ impl Selector {
         self.state.wake(token)
     }
 
+    #[cfg(unix)]
+    pub fn wake_from_signal(&self, token: Token) -> io::Result<()> {
+        self.state.wake_from_signal(token)
+    }
+
+    pub fn reset_wake(&self) {
+        self.state.reset_wake()
+    }
+
     cfg_io_source! {
         #[cfg(debug_assertions)]
         pub fn id(&self) -> usize {
@@ -96,7 +120,12 @@ struct SelectorState {
     /// Token associated with Waker that have recently asked to wake.  This will
     /// cause a synthetic behaviour where on any wakeup we add all pending tokens
     /// to the list of emitted events.
-    pending_wake_token: Mutex<Option<Token>>,
+    ///
+    /// This uses atomics, rather than a lock, so that `Waker::wake_from_signal`
+    /// is async-signal-safe. `pending_wake` is set after `pending_wake_token`
+    /// is stored.
+    pending_wake_token: AtomicUsize,
+    pending_wake: AtomicBool,
 
     /// Data is written to this to wake up the current instance of `wait`, which can occur when the
     /// user notifies it (in which case `notified` would have been set) or when an operation needs
@@ -173,7 +202,8 @@ impl SelectorState {
                 fd_data: HashMap::new(),
             }),
             pending_removal: Mutex::new(Vec::new()),
-            pending_wake_token: Mutex::new(None),
+            pending_wake_token: AtomicUsize::new(0),
+            pending_wake: AtomicBool::new(false),
             notify_waker,
             waiting_operations: AtomicUsize::new(0),
             operations_complete: Condvar::new(),
@@ -182,7 +212,13 @@ impl SelectorState {
         })
     }
 
-    pub fn select(&self, events: &mut Events, timeout: Option<Duration>) -> io::Result<()> {
+    pub fn reserve(&self, additional: usize) {
+        let mut fds = self.fds.lock().unwrap();
+        fds.poll_fds.reserve(additional);
+        fds.fd_data.reserve(additional);
+    }
+
+    pub fn select(&self, events: &mut Events, timeout: Option<Duration>) -> io::Result<bool> {
         events.clear();
 
         let mut fds = self.fds.lock().unwrap();
@@ -209,14 +245,18 @@ impl SelectorState {
             trace!("Poll finished: {:?}", &fds);
 
             if num_events == 0 {
-                return Ok(());
+                return Ok(true);
             }
 
             let waker_events = fds.poll_fds[0].0.revents;
             let notified = waker_events != 0;
             let mut num_fd_events = if notified { num_events - 1 } else { num_events };
 
-            let pending_wake_token = self.pending_wake_token.lock().unwrap().take();
+            let pending_wake_token = if self.pending_wake.swap(false, Ordering::AcqRel) {
+                Some(Token(self.pending_wake_token.load(Ordering::Acquire)))
+            } else {
+                None
+            };
 
             if notified {
                 self.notify_waker.ack_and_reset();
@@ -242,6 +282,7 @@ impl SelectorState {
                     events.push(Event {
                         token: pending_wake_token,
                         events: waker_events,
+                        fd: None,
                     });
                 }
 
@@ -258,6 +299,7 @@ impl SelectorState {
                         events.push(Event {
                             token: fd_data.token,
                             events: poll_fd.revents,
+                            fd: Some(poll_fd.fd),
                         });
 
                         if poll_fd.revents & (libc::POLLHUP | libc::POLLERR) != 0 {
@@ -288,7 +330,9 @@ impl SelectorState {
         drop(fds);
         let _ = self.deregister_all(&closed_raw_fds);
 
-        Ok(())
+        // NOTE: `events` can be empty if all events were for file descriptors
+        // removed while polling, which isn't a time out.
+        Ok(false)
     }
 
     pub fn register(&self, fd: RawFd, token: Token, interests: Interest) -> io::Result<()> {
@@ -326,11 +370,9 @@ impl SelectorState {
 
         self.modify_fds(|fds| {
             if fds.fd_data.contains_key(&fd) {
-                return Err(io::Error::new(
-                    io::ErrorKind::AlreadyExists,
-                    "I/O source already registered this `Registry` \
-                    (an old file descriptor might have been closed without deregistration)",
-                ));
+                // NOTE: this can also happen if an old file descriptor was
+                // closed without deregistering it.
+                return Err(Error::AlreadyRegistered.into());
             }
 
             let poll_fds_index = fds.poll_fds.len();
@@ -356,7 +398,7 @@ impl SelectorState {
 
     pub fn reregister(&self, fd: RawFd, token: Token, interests: Interest) -> io::Result<()> {
         self.modify_fds(|fds| {
-            let data = fds.fd_data.get_mut(&fd).ok_or(io::ErrorKind::NotFound)?;
+            let data = fds.fd_data.get_mut(&fd).ok_or(Error::NotRegistered)?;
             data.token = token;
             let poll_fds_index = data.poll_fds_index;
             fds.poll_fds[poll_fds_index].0.events = interests_to_poll(interests);
@@ -367,7 +409,7 @@ impl SelectorState {
 
     pub fn deregister(&self, fd: RawFd) -> io::Result<()> {
         self.deregister_all(&[fd])
-            .map_err(|_| io::ErrorKind::NotFound)?;
+            .map_err(|_| Error::NotRegistered)?;
         Ok(())
     }
 
@@ -434,9 +476,28 @@ impl SelectorState {
     }
 
     pub fn wake(&self, token: Token) -> io::Result<()> {
-        self.pending_wake_token.lock().unwrap().replace(token);
+        self.set_pending_wake(token);
         self.notify_waker.wake()
     }
+
+    #[cfg(unix)]
+    pub fn wake_from_signal(&self, token: Token) -> io::Result<()> {
+        self.set_pending_wake(token);
+        self.notify_waker.wake_from_signal()
+    }
+
+    /// Clear the pending wake up, if any.
+    ///
+    /// This doesn't empty `notify_waker`, if it was already woken up `select`
+    /// will find no pending wake up and poll again.
+    pub fn reset_wake(&self) {
+        self.pending_wake.store(false, Ordering::Release);
+    }
+
+    fn set_pending_wake(&self, token: Token) {
+        self.pending_wake_token.store(token.0, Ordering::Release);
+        self.pending_wake.store(true, Ordering::Release);
+    }
 }
 
 /// Shared record between IoSourceState and SelectorState that allows us to internally
@@ -541,10 +602,23 @@ fn poll(fds: &mut [PollFd], timeout: Option<Duration>) -> io::Result<usize> {
 pub struct Event {
     token: Token,
     events: libc::c_short,
+    /// File descriptor the event is for, `None` for the `Waker`.
+    #[cfg_attr(target_os = "hermit", allow(dead_code))]
+    fd: Option<RawFd>,
 }
 
 pub type Events = Vec<Event>;
 
+/// Converts an allocation of events into `Events`, see `Events::from_vec`.
+pub fn events_from_vec(events: Vec<Event>) -> Events {
+    events
+}
+
+/// Converts `Events` into its allocation, see `Events::into_vec`.
+pub fn events_into_vec(events: Events) -> Vec<Event> {
+    events
+}
+
 pub mod event {
     use std::fmt;
 
@@ -599,6 +673,11 @@ pub mod event {
         false
     }
 
+    #[cfg(unix)]
+    pub fn raw_fd(event: &Event) -> Option<std::os::fd::RawFd> {
+        event.fd
+    }
+
     pub fn debug_details(f: &mut fmt::Formatter<'_>, event: &Event) -> fmt::Result {
         #[allow(clippy::trivially_copy_pass_by_ref)]
         fn check_events(got: &libc::c_short, want: &libc::c_short) -> bool {
@@ -642,9 +721,22 @@ impl Waker {
     pub(crate) fn wake(&self) -> io::Result<()> {
         self.selector.wake(self.token)
     }
+
+    /// Same as `wake`, but async-signal-safe: it only uses atomics and a
+    /// single `write(2)` call, see `SelectorState::wake_from_signal`.
+    #[cfg(unix)]
+    pub(crate) fn wake_from_signal(&self) -> io::Result<()> {
+        self.selector.wake_from_signal(self.token)
+    }
+
+    pub(crate) fn reset(&self) -> io::Result<()> {
+        self.selector.reset_wake();
+        Ok(())
+    }
 }
 
 cfg_io_source! {
+    use crate::poll::UntrackOnDrop;
     use crate::Registry;
 
     struct InternalState {
@@ -653,6 +745,7 @@ cfg_io_source! {
         interests: Interest,
         fd: RawFd,
         shared_record: Arc<RegistrationRecord>,
+        untrack: UntrackOnDrop,
     }
 
     impl Drop for InternalState {
@@ -699,17 +792,19 @@ cfg_io_source! {
             fd: RawFd,
         ) -> io::Result<()> {
             if self.inner.is_some() {
-                Err(io::ErrorKind::AlreadyExists.into())
+                Err(Error::AlreadyRegistered.into())
             } else {
                 let selector = registry.selector().try_clone()?;
 
                 selector.register_internal(fd, token, interests).map(move |shared_record| {
+                    registry.track_new_registration(fd, token, interests);
                     let state = InternalState {
                         selector,
                         token,
                         interests,
                         fd,
                         shared_record,
+                        untrack: registry.untrack_on_drop(fd),
                     };
 
                     self.inner = Some(Box::new(state));
@@ -729,21 +824,63 @@ cfg_io_source! {
                 .selector()
                 .reregister(fd, token, interests)
                 .map(|()| {
+                    registry.track_registration(fd, token, interests);
                     state.token = token;
                     state.interests = interests;
                 }),
-                None => Err(io::ErrorKind::NotFound.into()),
+                None => Err(Error::NotRegistered.into()),
             }
         }
 
         pub fn deregister(&mut self, registry: &Registry, fd: RawFd) -> io::Result<()> {
-            if let Some(state) = self.inner.take() {
+            if let Some(mut state) = self.inner.take() {
                 // Marking unregistered will short circuit the drop behaviour of calling
                 // deregister so the call to deregister below is strictly required.
                 state.shared_record.mark_unregistered();
+                state.untrack.disarm();
             }
 
+            registry.untrack_registration(fd);
             registry.selector().deregister(fd)
         }
+
+        /// The file descriptor outlives the state. Like for a closed file
+        /// descriptor the state deregisters it when dropped.
+        pub fn detach(&mut self) {}
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(target_os = "hermit"))]
+mod tests {
+    use std::net;
+    use std::os::fd::AsRawFd;
+    use std::time::Duration;
+
+    use super::{Events, Selector};
+    use crate::{Interest, Token};
+
+    #[test]
+    fn select_timed_out() {
+        let selector = Selector::new().unwrap();
+        let mut events = Events::with_capacity(8);
+
+        let timed_out = selector
+            .select(&mut events, Some(Duration::from_millis(10)))
+            .unwrap();
+        assert!(timed_out);
+        assert!(events.is_empty());
+
+        // All events are for a file descriptor that is removed while polling,
+        // which returns early without events.
+        let socket = net::UdpSocket::bind("127.0.0.1:0").unwrap();
+        let fd = socket.as_raw_fd();
+        selector.register(fd, Token(1), Interest::WRITABLE).unwrap();
+        selector.state.pending_removal.lock().unwrap().push(fd);
+        let timed_out = selector
+            .select(&mut events, Some(Duration::from_secs(10)))
+            .unwrap();
+        assert!(!timed_out);
+        assert!(events.is_empty());
     }
 }
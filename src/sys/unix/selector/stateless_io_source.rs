@@ -3,13 +3,20 @@
 use std::io;
 use std::os::fd::RawFd;
 
+use crate::poll::UntrackOnDrop;
 use crate::{Interest, Registry, Token};
 
-pub(crate) struct IoSourceState;
+/// Only holds state with the `track-registrations` feature enabled, to stop
+/// tracking the file descriptor once it's closed.
+pub(crate) struct IoSourceState {
+    untrack: UntrackOnDrop,
+}
 
 impl IoSourceState {
     pub(crate) fn new() -> IoSourceState {
-        IoSourceState
+        IoSourceState {
+            untrack: UntrackOnDrop::default(),
+        }
     }
 
     pub(crate) fn do_io<T, F, R>(&self, f: F, io: &T) -> io::Result<R>
@@ -29,7 +36,10 @@ impl IoSourceState {
         fd: RawFd,
     ) -> io::Result<()> {
         // Pass through, we don't have any state.
-        registry.selector().register(fd, token, interests)
+        registry.selector().register(fd, token, interests)?;
+        registry.track_new_registration(fd, token, interests);
+        self.untrack = registry.untrack_on_drop(fd);
+        Ok(())
     }
 
     pub(crate) fn reregister(
@@ -40,11 +50,20 @@ impl IoSourceState {
         fd: RawFd,
     ) -> io::Result<()> {
         // Pass through, we don't have any state.
-        registry.selector().reregister(fd, token, interests)
+        registry.selector().reregister(fd, token, interests)?;
+        registry.track_registration(fd, token, interests);
+        Ok(())
     }
 
     pub(crate) fn deregister(&mut self, registry: &Registry, fd: RawFd) -> io::Result<()> {
         // Pass through, we don't have any state.
+        self.untrack.disarm();
+        registry.untrack_registration(fd);
         registry.selector().deregister(fd)
     }
+
+    /// The file descriptor outlives the state, and stays registered.
+    pub(crate) fn detach(&mut self) {
+        self.untrack.disarm();
+    }
 }
@@ -0,0 +1,1108 @@
+//! Selector using `io_uring(7)`, enabled with the `io-uring` feature.
+//!
+//! Registrations are (multishot) `IORING_OP_POLL_ADD` requests, which post a
+//! completion for every readiness change of the file descriptor, much like an
+//! edge-triggered epoll registration. The completions are converted into
+//! `epoll_event`s, so the `Event` type of the epoll selector is reused.
+//!
+//! If the kernel doesn't support `io_uring(7)`, or lacks the features needed,
+//! the epoll selector is used instead.
+//!
+//! Unlike epoll, a poll request holds a reference to the file it polls. So a
+//! file descriptor that is closed without being deregistered isn't removed
+//! from the ring, and the file stays open, until the file descriptor is reused
+//! and registered again or the `Poll` instance is dropped. To avoid this the
+//! `IoSourceState` below deregisters the file descriptor when it's dropped.
+
+use std::collections::HashMap;
+use std::os::fd::{AsRawFd, FromRawFd, OwnedFd, RawFd};
+#[cfg(debug_assertions)]
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use std::{io, mem, ptr};
+
+use crate::{Capabilities, Error, Interest, Token};
+
+use crate::sys::unix::epoll;
+
+pub use self::epoll::{event, events_from_vec, events_into_vec, Event, Events};
+
+/// Unique id for use as `SelectorId`.
+#[cfg(debug_assertions)]
+static NEXT_ID: AtomicUsize = AtomicUsize::new(1);
+
+#[derive(Debug)]
+pub struct Selector {
+    inner: Inner,
+}
+
+#[derive(Debug)]
+enum Inner {
+    Ring(Arc<Ring>),
+    Epoll(epoll::Selector),
+}
+
+impl Selector {
+    pub fn new() -> io::Result<Selector> {
+        let inner = match Ring::new()? {
+            Some(ring) => Inner::Ring(Arc::new(ring)),
+            None => Inner::Epoll(epoll::Selector::new()?),
+        };
+        Ok(Selector { inner })
+    }
+
+    pub fn reserve(&self, additional: usize) {
+        match &self.inner {
+            Inner::Ring(ring) => ring.registrations.lock().unwrap().reserve(additional),
+            Inner::Epoll(selector) => selector.reserve(additional),
+        }
+    }
+
+    pub fn capabilities(&self) -> Capabilities {
+        match &self.inner {
+            Inner::Ring(_) => Capabilities {
+                // Timeouts are passed as `timespec`.
+                precise_timeout: true,
+                exclusive: false,
+                // Poll requests without `IORING_POLL_ADD_MULTI` are one shot,
+                // and check the readiness of the file when they're submitted.
+                oneshot: true,
+                priority_interest: true,
+                level_trigger: true,
+            },
+            Inner::Epoll(selector) => selector.capabilities(),
+        }
+    }
+
+    pub fn try_clone(&self) -> io::Result<Selector> {
+        let inner = match &self.inner {
+            Inner::Ring(ring) => Inner::Ring(ring.clone()),
+            Inner::Epoll(selector) => Inner::Epoll(selector.try_clone()?),
+        };
+        Ok(Selector { inner })
+    }
+
+    pub fn select(&self, events: &mut Events, timeout: Option<Duration>) -> io::Result<bool> {
+        match &self.inner {
+            Inner::Ring(ring) => ring.select(events, timeout),
+            Inner::Epoll(selector) => selector.select(events, timeout),
+        }
+    }
+
+    pub fn register(&self, fd: RawFd, token: Token, interests: Interest) -> io::Result<()> {
+        match &self.inner {
+            Inner::Ring(ring) => ring.register(fd, token, interests),
+            Inner::Epoll(selector) => selector.register(fd, token, interests),
+        }
+    }
+
+    pub fn reregister(&self, fd: RawFd, token: Token, interests: Interest) -> io::Result<()> {
+        match &self.inner {
+            Inner::Ring(ring) => ring.reregister(fd, token, interests),
+            Inner::Epoll(selector) => selector.reregister(fd, token, interests),
+        }
+    }
+
+    pub fn deregister(&self, fd: RawFd) -> io::Result<()> {
+        match &self.inner {
+            Inner::Ring(ring) => ring.deregister(fd),
+            Inner::Epoll(selector) => selector.deregister(fd),
+        }
+    }
+}
+
+cfg_io_source! {
+    impl Selector {
+        #[cfg(debug_assertions)]
+        pub fn id(&self) -> usize {
+            match &self.inner {
+                Inner::Ring(ring) => ring.id,
+                Inner::Epoll(selector) => selector.id(),
+            }
+        }
+    }
+}
+
+impl AsRawFd for Selector {
+    fn as_raw_fd(&self) -> RawFd {
+        match &self.inner {
+            Inner::Ring(ring) => ring.fd.as_raw_fd(),
+            Inner::Epoll(selector) => selector.as_raw_fd(),
+        }
+    }
+}
+
+// Constants and types from `linux/io_uring.h`, not (yet) provided by libc.
+const IORING_SETUP_CQSIZE: u32 = 1 << 3;
+const IORING_SETUP_CLAMP: u32 = 1 << 4;
+
+const IORING_FEAT_SINGLE_MMAP: u32 = 1 << 0;
+const IORING_FEAT_NODROP: u32 = 1 << 1;
+const IORING_FEAT_EXT_ARG: u32 = 1 << 8;
+/// Added in Linux 5.13, together with multishot poll requests.
+const IORING_FEAT_RSRC_TAGS: u32 = 1 << 10;
+
+const IORING_SQ_CQ_OVERFLOW: u32 = 1 << 1;
+
+const IORING_ENTER_GETEVENTS: u32 = 1 << 0;
+const IORING_ENTER_EXT_ARG: u32 = 1 << 3;
+
+const IORING_OFF_SQ_RING: libc::off_t = 0;
+const IORING_OFF_SQES: libc::off_t = 0x10000000;
+
+const IORING_OP_POLL_ADD: u8 = 6;
+const IORING_OP_POLL_REMOVE: u8 = 7;
+const IORING_POLL_ADD_MULTI: u32 = 1 << 0;
+const IORING_CQE_F_MORE: u32 = 1 << 1;
+
+/// Number of submission queue entries.
+const SQ_ENTRIES: u32 = 128;
+/// Number of completion queue entries, as each registration can post many
+/// completions this is larger than the submission queue.
+const CQ_ENTRIES: u32 = 1024;
+
+/// `user_data` used for requests whose completion we ignore, i.e. the
+/// `IORING_OP_POLL_REMOVE` requests. Registration ids start at 1.
+const IGNORED: u64 = 0;
+
+#[repr(C)]
+#[derive(Default)]
+struct SqringOffsets {
+    head: u32,
+    tail: u32,
+    ring_mask: u32,
+    ring_entries: u32,
+    flags: u32,
+    dropped: u32,
+    array: u32,
+    resv1: u32,
+    user_addr: u64,
+}
+
+#[repr(C)]
+#[derive(Default)]
+struct CqringOffsets {
+    head: u32,
+    tail: u32,
+    ring_mask: u32,
+    ring_entries: u32,
+    overflow: u32,
+    cqes: u32,
+    flags: u32,
+    resv1: u32,
+    user_addr: u64,
+}
+
+#[repr(C)]
+#[derive(Default)]
+struct Params {
+    sq_entries: u32,
+    cq_entries: u32,
+    flags: u32,
+    sq_thread_cpu: u32,
+    sq_thread_idle: u32,
+    features: u32,
+    wq_fd: u32,
+    resv: [u32; 3],
+    sq_off: SqringOffsets,
+    cq_off: CqringOffsets,
+}
+
+/// Submission queue entry, `struct io_uring_sqe`.
+#[repr(C)]
+#[derive(Clone, Copy, Default)]
+struct Sqe {
+    opcode: u8,
+    flags: u8,
+    ioprio: u16,
+    fd: i32,
+    off: u64,
+    addr: u64,
+    len: u32,
+    op_flags: u32,
+    user_data: u64,
+    buf_index: u16,
+    personality: u16,
+    splice_fd_in: i32,
+    addr3: u64,
+    pad: u64,
+}
+
+impl Sqe {
+    /// Multishot poll request for `events` (epoll flags) on `fd`.
+    fn poll_add(fd: RawFd, events: u32, user_data: u64) -> Sqe {
+        // The kernel reads `poll32_events` as two 16 bit halves.
+        #[cfg(target_endian = "big")]
+        let events = events.rotate_left(16);
+        Sqe {
+            opcode: IORING_OP_POLL_ADD,
+            fd,
+            len: IORING_POLL_ADD_MULTI,
+            op_flags: events,
+            user_data,
+            ..Sqe::default()
+        }
+    }
+
+    /// Removes the poll request with `user_data`.
+    fn poll_remove(user_data: u64) -> Sqe {
+        Sqe {
+            opcode: IORING_OP_POLL_REMOVE,
+            fd: -1,
+            addr: user_data,
+            user_data: IGNORED,
+            ..Sqe::default()
+        }
+    }
+}
+
+/// Completion queue entry, `struct io_uring_cqe`.
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct Cqe {
+    user_data: u64,
+    res: i32,
+    flags: u32,
+}
+
+/// `struct io_uring_getevents_arg`.
+#[repr(C)]
+struct GeteventsArg {
+    sigmask: u64,
+    sigmask_sz: u32,
+    pad: u32,
+    ts: u64,
+}
+
+/// `struct __kernel_timespec`.
+#[repr(C)]
+struct Timespec {
+    tv_sec: i64,
+    tv_nsec: i64,
+}
+
+/// A `io_uring(7)` instance.
+struct Ring {
+    #[cfg(debug_assertions)]
+    id: usize,
+    sq: Mutex<SubmissionQueue>,
+    cq: Mutex<CompletionQueue>,
+    registrations: Mutex<Registrations>,
+    /// Mapping of the submission and completion queue rings.
+    _rings: Mmap,
+    /// Mapping of the submission queue entries.
+    _sqes: Mmap,
+    // NOTE: must be dropped after the mappings above.
+    fd: OwnedFd,
+}
+
+impl Ring {
+    /// Create a new ring, returns `None` if `io_uring(7)` is not available.
+    fn new() -> io::Result<Option<Ring>> {
+        let mut params = Params {
+            cq_entries: CQ_ENTRIES,
+            flags: IORING_SETUP_CQSIZE | IORING_SETUP_CLAMP,
+            ..Params::default()
+        };
+        let fd = match syscall!(syscall(
+            libc::SYS_io_uring_setup,
+            SQ_ENTRIES as libc::c_long,
+            &mut params as *mut Params,
+        )) {
+            // SAFETY: `io_uring_setup(2)` ensures the fd is valid, it always
+            // sets close-on-exec.
+            Ok(fd) => unsafe { OwnedFd::from_raw_fd(fd as RawFd) },
+            // Not supported by the kernel, disabled or blocked by a sandbox.
+            Err(ref err)
+                if matches!(
+                    err.raw_os_error(),
+                    Some(libc::ENOSYS | libc::EPERM | libc::EACCES | libc::EINVAL)
+                ) =>
+            {
+                return Ok(None)
+            }
+            Err(err) => return Err(err),
+        };
+
+        let required = IORING_FEAT_SINGLE_MMAP
+            | IORING_FEAT_NODROP
+            | IORING_FEAT_EXT_ARG
+            | IORING_FEAT_RSRC_TAGS;
+        if params.features & required != required {
+            return Ok(None);
+        }
+
+        let sq_size = params.sq_off.array as usize + params.sq_entries as usize * 4;
+        let cq_size = params.cq_off.cqes as usize + params.cq_entries as usize * 16;
+        let rings = Mmap::new(&fd, sq_size.max(cq_size), IORING_OFF_SQ_RING)?;
+        let sqes = Mmap::new(
+            &fd,
+            params.sq_entries as usize * mem::size_of::<Sqe>(),
+            IORING_OFF_SQES,
+        )?;
+
+        // SAFETY: the offsets returned by the kernel are valid for the
+        // mapping.
+        let sq = unsafe {
+            let ptr = rings.ptr;
+            // Use the submission queue entries in order.
+            let array = ptr.add(params.sq_off.array as usize).cast::<u32>();
+            for i in 0..params.sq_entries {
+                array.add(i as usize).write(i);
+            }
+            SubmissionQueue {
+                head: ptr.add(params.sq_off.head as usize).cast(),
+                tail: ptr.add(params.sq_off.tail as usize).cast(),
+                mask: *ptr.add(params.sq_off.ring_mask as usize).cast::<u32>(),
+                entries: params.sq_entries,
+                sqes: sqes.ptr.cast(),
+            }
+        };
+        // SAFETY: see above.
+        let cq = unsafe {
+            let ptr = rings.ptr;
+            CompletionQueue {
+                head: ptr.add(params.cq_off.head as usize).cast(),
+                tail: ptr.add(params.cq_off.tail as usize).cast(),
+                mask: *ptr.add(params.cq_off.ring_mask as usize).cast::<u32>(),
+                cqes: ptr.add(params.cq_off.cqes as usize).cast(),
+                sq_flags: ptr.add(params.sq_off.flags as usize).cast(),
+            }
+        };
+
+        Ok(Some(Ring {
+            #[cfg(debug_assertions)]
+            id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+            sq: Mutex::new(sq),
+            cq: Mutex::new(cq),
+            registrations: Mutex::new(Registrations::new()),
+            _rings: rings,
+            _sqes: sqes,
+            fd,
+        }))
+    }
+
+    fn select(&self, events: &mut Events, timeout: Option<Duration>) -> io::Result<bool> {
+        events.clear();
+        if events.capacity() == 0 {
+            // Same as `epoll_wait(2)`.
+            return Err(io::Error::from_raw_os_error(libc::EINVAL));
+        }
+
+        let deadline = timeout.map(|timeout| Instant::now().checked_add(timeout));
+        loop {
+            // NOTE: the lock isn't held while waiting below, so that other
+            // threads can reap completions in the meantime.
+            self.reap(&mut self.cq.lock().unwrap(), events)?;
+            if !events.is_empty() {
+                return Ok(false);
+            }
+
+            // Wait for at least one completion, which might not be an event
+            // (e.g. for a deregistered file descriptor), in which case we wait
+            // again until the deadline.
+            let timeout = match deadline {
+                // Overflowing deadline is the same as no timeout.
+                None | Some(None) => None,
+                Some(Some(deadline)) => {
+                    let timeout = deadline.saturating_duration_since(Instant::now());
+                    if timeout.is_zero() {
+                        return Ok(true);
+                    }
+                    Some(timeout)
+                }
+            };
+            let ts = timeout.map(|timeout| Timespec {
+                tv_sec: timeout.as_secs() as i64,
+                tv_nsec: i64::from(timeout.subsec_nanos()),
+            });
+            let arg = ts.as_ref().map(|ts| GeteventsArg {
+                sigmask: 0,
+                sigmask_sz: 0,
+                pad: 0,
+                ts: ts as *const Timespec as u64,
+            });
+            let (flags, arg_ptr) = match &arg {
+                Some(arg) => (
+                    IORING_ENTER_GETEVENTS | IORING_ENTER_EXT_ARG,
+                    arg as *const GeteventsArg,
+                ),
+                None => (IORING_ENTER_GETEVENTS, ptr::null()),
+            };
+            match self.enter(0, 1, flags, arg_ptr) {
+                Ok(_) => {}
+                Err(ref err) if err.raw_os_error() == Some(libc::ETIME) => {}
+                Err(err) => return Err(err),
+            }
+        }
+    }
+
+    /// Reap the completions, converting them into `events`.
+    fn reap(&self, cq: &mut CompletionQueue, events: &mut Events) -> io::Result<()> {
+        // SAFETY: the pointer is valid for the lifetime of the ring.
+        if unsafe { (*cq.sq_flags).load(Ordering::Acquire) } & IORING_SQ_CQ_OVERFLOW != 0 {
+            // Flush the overflowed completions into the queue.
+            self.enter(0, 0, IORING_ENTER_GETEVENTS, ptr::null())?;
+        }
+
+        let registrations = self.registrations.lock().unwrap();
+        let mut rearm = Vec::new();
+        // SAFETY: the pointers are valid for the lifetime of the ring, the
+        // kernel writes the completions before updating the tail.
+        unsafe {
+            let tail = (*cq.tail).load(Ordering::Acquire);
+            let mut head = (*cq.head).load(Ordering::Relaxed);
+            while head != tail && events.len() < events.capacity() {
+                let cqe = *cq.cqes.add((head & cq.mask) as usize);
+                head = head.wrapping_add(1);
+                registrations.complete(cqe, events, &mut rearm);
+            }
+            (*cq.head).store(head, Ordering::Release);
+        }
+        drop(registrations);
+
+        if rearm.is_empty() {
+            Ok(())
+        } else {
+            self.submit(&rearm)
+        }
+    }
+
+    fn register(&self, fd: RawFd, token: Token, interests: Interest) -> io::Result<()> {
+        self.register_internal(fd, token, interests, false)
+    }
+
+    /// Register the eventfd of a `Waker`.
+    fn register_waker(&self, fd: RawFd, token: Token) -> io::Result<()> {
+        self.register_internal(fd, token, Interest::READABLE, true)
+    }
+
+    fn register_internal(
+        &self,
+        fd: RawFd,
+        token: Token,
+        interests: Interest,
+        waker: bool,
+    ) -> io::Result<()> {
+        let file = pollable_file_id(fd)?;
+        let mut registrations = self.registrations.lock().unwrap();
+        if let Some(registration) = registrations.fds.get(&fd) {
+            if registration.file == file {
+                return Err(Error::AlreadyRegistered.into());
+            }
+            // The file descriptor was closed without being deregistered, and
+            // then reused.
+            let id = registration.id;
+            self.submit(&[Sqe::poll_remove(id)])?;
+            registrations.remove(fd);
+        }
+
+        let events = interests_to_poll(interests);
+        let id = registrations.next_id();
+        self.submit(&[Sqe::poll_add(fd, events, id)])?;
+        registrations.insert(
+            fd,
+            Registration {
+                id,
+                token,
+                events,
+                file,
+                waker,
+            },
+        );
+        Ok(())
+    }
+
+    fn reregister(&self, fd: RawFd, token: Token, interests: Interest) -> io::Result<()> {
+        let file = self.registered_file(fd)?;
+        let mut registrations = self.registrations.lock().unwrap();
+        let (old_id, waker) = match registrations.fds.get(&fd) {
+            Some(registration) if registration.file == file => {
+                (registration.id, registration.waker)
+            }
+            _ => return Err(Error::NotRegistered.into()),
+        };
+
+        // Replace the poll request, like `EPOLL_CTL_MOD` this reports the
+        // readiness of the file descriptor (again).
+        let events = interests_to_poll(interests);
+        let id = registrations.next_id();
+        self.submit(&[Sqe::poll_remove(old_id), Sqe::poll_add(fd, events, id)])?;
+        registrations.remove(fd);
+        registrations.insert(
+            fd,
+            Registration {
+                id,
+                token,
+                events,
+                file,
+                waker,
+            },
+        );
+        Ok(())
+    }
+
+    fn deregister(&self, fd: RawFd) -> io::Result<()> {
+        let file = self.registered_file(fd)?;
+        let mut registrations = self.registrations.lock().unwrap();
+        match registrations.fds.get(&fd) {
+            Some(registration) if registration.file == file => {
+                self.submit(&[Sqe::poll_remove(registration.id)])?;
+                registrations.remove(fd);
+                Ok(())
+            }
+            _ => Err(Error::NotRegistered.into()),
+        }
+    }
+
+    /// Returns the file id of `fd`. If `fd` is closed the stale registration
+    /// (if any) is removed, so that the file is released.
+    fn registered_file(&self, fd: RawFd) -> io::Result<(u64, u64)> {
+        file_id(fd).map_err(|err| {
+            let mut registrations = self.registrations.lock().unwrap();
+            if let Some(registration) = registrations.fds.get(&fd) {
+                if self.submit(&[Sqe::poll_remove(registration.id)]).is_ok() {
+                    registrations.remove(fd);
+                }
+            }
+            err
+        })
+    }
+
+    /// Submit `sqes` to the kernel.
+    fn submit(&self, sqes: &[Sqe]) -> io::Result<()> {
+        let mut sq = self.sq.lock().unwrap();
+        let mut sqes = sqes.iter();
+        loop {
+            let mut pending = 0;
+            for sqe in sqes.by_ref() {
+                if !sq.push(sqe) {
+                    break;
+                }
+                pending += 1;
+            }
+            if pending == 0 {
+                return Ok(());
+            }
+
+            while pending > 0 {
+                match self.enter(pending, 0, 0, ptr::null()) {
+                    Ok(0) => {
+                        sq.discard();
+                        return Err(io::Error::new(
+                            io::ErrorKind::Other,
+                            "failed to submit io_uring request",
+                        ));
+                    }
+                    Ok(n) => pending -= n,
+                    Err(ref err) if err.kind() == io::ErrorKind::Interrupted => {}
+                    Err(err) => {
+                        // Not submitted, so remove them to not submit them
+                        // unknowingly later.
+                        sq.discard();
+                        return Err(err);
+                    }
+                }
+            }
+        }
+    }
+
+    fn enter(
+        &self,
+        to_submit: u32,
+        min_complete: u32,
+        flags: u32,
+        arg: *const GeteventsArg,
+    ) -> io::Result<u32> {
+        let argsz = if arg.is_null() {
+            0
+        } else {
+            mem::size_of::<GeteventsArg>()
+        };
+        syscall!(syscall(
+            libc::SYS_io_uring_enter,
+            self.fd.as_raw_fd() as libc::c_long,
+            to_submit as libc::c_long,
+            min_complete as libc::c_long,
+            flags as libc::c_long,
+            arg,
+            argsz,
+        ))
+        .map(|n| n as u32)
+    }
+}
+
+impl std::fmt::Debug for Ring {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Ring").field("fd", &self.fd).finish()
+    }
+}
+
+struct SubmissionQueue {
+    head: *const AtomicU32,
+    tail: *const AtomicU32,
+    mask: u32,
+    entries: u32,
+    sqes: *mut Sqe,
+}
+
+impl SubmissionQueue {
+    /// Add `sqe` to the queue, returns `false` if the queue is full.
+    fn push(&mut self, sqe: &Sqe) -> bool {
+        // SAFETY: the pointers are valid for the lifetime of the ring. We're
+        // the only writer of the tail, the kernel only reads the entries
+        // between head and tail.
+        unsafe {
+            let head = (*self.head).load(Ordering::Acquire);
+            let tail = (*self.tail).load(Ordering::Relaxed);
+            if tail.wrapping_sub(head) == self.entries {
+                return false;
+            }
+            self.sqes.add((tail & self.mask) as usize).write(*sqe);
+            (*self.tail).store(tail.wrapping_add(1), Ordering::Release);
+        }
+        true
+    }
+
+    /// Remove all entries not yet consumed by the kernel.
+    fn discard(&mut self) {
+        // SAFETY: the kernel only consumes entries in `io_uring_enter(2)`,
+        // which isn't called while we hold the lock on the queue.
+        unsafe {
+            let head = (*self.head).load(Ordering::Acquire);
+            (*self.tail).store(head, Ordering::Release);
+        }
+    }
+}
+
+struct CompletionQueue {
+    head: *const AtomicU32,
+    tail: *const AtomicU32,
+    mask: u32,
+    cqes: *const Cqe,
+    /// Flags of the submission queue, indicating a completion queue overflow.
+    sq_flags: *const AtomicU32,
+}
+
+// SAFETY: the queues point into memory shared with the kernel, which is valid
+// for the lifetime of the ring. Access is synchronised using a `Mutex`.
+unsafe impl Send for SubmissionQueue {}
+unsafe impl Send for CompletionQueue {}
+
+/// Memory mapping of (part of) the ring.
+struct Mmap {
+    ptr: *mut u8,
+    len: usize,
+}
+
+impl Mmap {
+    fn new(fd: &OwnedFd, len: usize, offset: libc::off_t) -> io::Result<Mmap> {
+        let ptr = unsafe {
+            libc::mmap(
+                ptr::null_mut(),
+                len,
+                libc::PROT_READ | libc::PROT_WRITE,
+                libc::MAP_SHARED | libc::MAP_POPULATE,
+                fd.as_raw_fd(),
+                offset,
+            )
+        };
+        if ptr == libc::MAP_FAILED {
+            Err(io::Error::last_os_error())
+        } else {
+            Ok(Mmap {
+                ptr: ptr.cast(),
+                len,
+            })
+        }
+    }
+}
+
+// SAFETY: only the pointer is shared, access to the memory is done through the
+// queues.
+unsafe impl Send for Mmap {}
+unsafe impl Sync for Mmap {}
+
+impl Drop for Mmap {
+    fn drop(&mut self) {
+        let _ = unsafe { libc::munmap(self.ptr.cast(), self.len) };
+    }
+}
+
+/// Registered file descriptors.
+struct Registrations {
+    last_id: u64,
+    fds: HashMap<RawFd, Registration>,
+    /// Registration id (`user_data`) to file descriptor.
+    ids: HashMap<u64, RawFd>,
+}
+
+struct Registration {
+    id: u64,
+    token: Token,
+    /// Events (epoll flags) we're polling for.
+    events: u32,
+    /// Id of the registered file, see [`file_id`].
+    file: (u64, u64),
+    /// Registration of a `Waker`.
+    waker: bool,
+}
+
+impl Registrations {
+    fn new() -> Registrations {
+        Registrations {
+            last_id: IGNORED,
+            fds: HashMap::new(),
+            ids: HashMap::new(),
+        }
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        self.fds.reserve(additional);
+        self.ids.reserve(additional);
+    }
+
+    /// Returns a new unique registration id, used as `user_data`.
+    fn next_id(&mut self) -> u64 {
+        self.last_id += 1;
+        self.last_id
+    }
+
+    fn insert(&mut self, fd: RawFd, registration: Registration) {
+        let _ = self.ids.insert(registration.id, fd);
+        let _ = self.fds.insert(fd, registration);
+    }
+
+    fn remove(&mut self, fd: RawFd) {
+        if let Some(registration) = self.fds.remove(&fd) {
+            let _ = self.ids.remove(&registration.id);
+        }
+    }
+
+    /// Convert the completion `cqe` into an event, adding the poll request to
+    /// `rearm` if the kernel stopped it.
+    fn complete(&self, cqe: Cqe, events: &mut Events, rearm: &mut Vec<Sqe>) {
+        let Some(fd) = self.ids.get(&cqe.user_data) else {
+            // Completion of a removed registration or an ignored request.
+            return;
+        };
+        let registration = &self.fds[fd];
+        if cqe.flags & IORING_CQE_F_MORE == 0 && file_id(*fd).ok() == Some(registration.file) {
+            // The kernel stopped the multishot request, either because the
+            // completion queue overflowed or because of an error (reported as
+            // error event below), so we restart it. Like epoll this keeps
+            // reporting an error that persists.
+            rearm.push(Sqe::poll_add(*fd, registration.events, registration.id));
+        }
+        if registration.waker && !is_readable(*fd) {
+            // Waker was reset.
+            return;
+        }
+        let flags = if cqe.res < 0 {
+            libc::EPOLLERR as u32
+        } else {
+            cqe.res as u32
+        };
+        events.push(libc::epoll_event {
+            events: flags,
+            u64: usize::from(registration.token) as u64,
+        });
+    }
+}
+
+/// Returns the device and inode number of the file `fd` refers to, used to
+/// detect file descriptors that were closed and reused without being
+/// deregistered.
+fn file_id(fd: RawFd) -> io::Result<(u64, u64)> {
+    fstat(fd).map(|stat| stat_file_id(&stat))
+}
+
+/// Same as [`file_id`], but returns the same error as `epoll_ctl(2)` for files
+/// that can't be polled.
+fn pollable_file_id(fd: RawFd) -> io::Result<(u64, u64)> {
+    let stat = fstat(fd)?;
+    match stat.st_mode & libc::S_IFMT {
+        libc::S_IFREG | libc::S_IFDIR => Err(Error::NotPollable.into()),
+        _ => Ok(stat_file_id(&stat)),
+    }
+}
+
+fn fstat(fd: RawFd) -> io::Result<libc::stat> {
+    let mut stat = mem::MaybeUninit::<libc::stat>::uninit();
+    syscall!(fstat(fd, stat.as_mut_ptr()))?;
+    // SAFETY: `fstat(2)` initialised `stat`.
+    Ok(unsafe { stat.assume_init() })
+}
+
+#[allow(clippy::unnecessary_cast)] // The types differ per architecture.
+fn stat_file_id(stat: &libc::stat) -> (u64, u64) {
+    (stat.st_dev as u64, stat.st_ino as u64)
+}
+
+/// Returns `true` if `fd` is readable.
+fn is_readable(fd: RawFd) -> bool {
+    let mut pollfd = libc::pollfd {
+        fd,
+        events: libc::POLLIN,
+        revents: 0,
+    };
+    matches!(syscall!(poll(&mut pollfd, 1, 0)), Ok(1))
+}
+
+fn interests_to_poll(interests: Interest) -> u32 {
+    // Poll requests are edge triggered by default.
+    let mut kind = 0;
+
+    if interests.is_readable() {
+        kind |= libc::EPOLLIN | libc::EPOLLRDHUP;
+    }
+
+    if interests.is_writable() {
+        kind |= libc::EPOLLOUT;
+    }
+
+    if interests.is_priority() {
+        kind |= libc::EPOLLPRI;
+    }
+
+    kind as u32
+}
+
+/// Waker backed by `eventfd`.
+///
+/// The completion for a wake up is posted when the `Waker` is woken, so to
+/// ensure `reset` clears pending wake ups (as it does with epoll) wake up events
+/// are only returned if the eventfd is still readable.
+#[derive(Debug)]
+pub(crate) struct Waker {
+    inner: crate::sys::unix::waker::Waker,
+}
+
+impl Waker {
+    pub(crate) fn new(selector: &Selector, token: Token) -> io::Result<Waker> {
+        let inner = crate::sys::unix::waker::Waker::new_unregistered()?;
+        let fd = inner.as_raw_fd();
+        match &selector.inner {
+            Inner::Ring(ring) => ring.register_waker(fd, token)?,
+            Inner::Epoll(selector) => selector.register(fd, token, Interest::READABLE)?,
+        }
+        Ok(Waker { inner })
+    }
+
+    pub(crate) fn wake(&self) -> io::Result<()> {
+        self.inner.wake()
+    }
+
+    pub(crate) fn wake_from_signal(&self) -> io::Result<()> {
+        self.inner.wake_from_signal()
+    }
+
+    pub(crate) fn reset(&self) -> io::Result<()> {
+        self.inner.reset()
+    }
+}
+
+cfg_io_source! {
+    use std::sync::Weak;
+
+    use crate::poll::UntrackOnDrop;
+    use crate::Registry;
+
+    /// Deregisters the file descriptor when dropped (i.e. when the source is
+    /// closed), see the module documentation. This matches epoll, which
+    /// removes a file descriptor once it's closed, the difference being that
+    /// epoll does it in the kernel.
+    pub(crate) struct IoSourceState {
+        registration: Option<(Weak<Ring>, RawFd)>,
+        untrack: UntrackOnDrop,
+    }
+
+    impl IoSourceState {
+        pub(crate) fn new() -> IoSourceState {
+            IoSourceState {
+                registration: None,
+                untrack: UntrackOnDrop::default(),
+            }
+        }
+
+        pub(crate) fn do_io<T, F, R>(&self, f: F, io: &T) -> io::Result<R>
+        where
+            F: FnOnce(&T) -> io::Result<R>,
+        {
+            f(io)
+        }
+
+        pub(crate) fn register(
+            &mut self,
+            registry: &Registry,
+            token: Token,
+            interests: Interest,
+            fd: RawFd,
+        ) -> io::Result<()> {
+            let selector = registry.selector();
+            selector.register(fd, token, interests)?;
+            if let Inner::Ring(ring) = &selector.inner {
+                self.registration = Some((Arc::downgrade(ring), fd));
+            }
+            registry.track_new_registration(fd, token, interests);
+            self.untrack = registry.untrack_on_drop(fd);
+            Ok(())
+        }
+
+        pub(crate) fn reregister(
+            &mut self,
+            registry: &Registry,
+            token: Token,
+            interests: Interest,
+            fd: RawFd,
+        ) -> io::Result<()> {
+            registry.selector().reregister(fd, token, interests)?;
+            registry.track_registration(fd, token, interests);
+            Ok(())
+        }
+
+        pub(crate) fn deregister(&mut self, registry: &Registry, fd: RawFd) -> io::Result<()> {
+            self.registration = None;
+            self.untrack.disarm();
+            registry.untrack_registration(fd);
+            registry.selector().deregister(fd)
+        }
+
+        /// The file descriptor outlives the state and stays registered, as it
+        /// does with epoll.
+        pub(crate) fn detach(&mut self) {
+            self.registration = None;
+            self.untrack.disarm();
+        }
+    }
+
+    impl Drop for IoSourceState {
+        fn drop(&mut self) {
+            if let Some((ring, fd)) = self.registration.take() {
+                if let Some(ring) = ring.upgrade() {
+                    // The file descriptor is still open (it's closed after the
+                    // state is dropped), so this removes our registration.
+                    let _ = ring.deregister(fd);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::os::fd::{AsRawFd, FromRawFd, OwnedFd, RawFd};
+    use std::sync::atomic::Ordering;
+    use std::time::Duration;
+
+    use super::{event, Events, Ring, Sqe, CQ_ENTRIES, IORING_SQ_CQ_OVERFLOW};
+    use crate::{Interest, Token};
+
+    const ID1: Token = Token(1);
+    const ID2: Token = Token(2);
+
+    /// Returns `None` if the kernel doesn't support io_uring.
+    fn new_ring() -> Option<Ring> {
+        Ring::new().unwrap()
+    }
+
+    fn eventfd() -> OwnedFd {
+        let fd = syscall!(eventfd(0, libc::EFD_CLOEXEC | libc::EFD_NONBLOCK)).unwrap();
+        unsafe { OwnedFd::from_raw_fd(fd) }
+    }
+
+    fn write(fd: &OwnedFd) {
+        let n = 1u64.to_ne_bytes();
+        syscall!(write(fd.as_raw_fd(), n.as_ptr().cast(), n.len())).unwrap();
+    }
+
+    fn select(ring: &Ring, events: &mut Events) -> Vec<Token> {
+        let timed_out = ring
+            .select(events, Some(Duration::from_millis(100)))
+            .unwrap();
+        // Completions that aren't events don't end the wait early.
+        assert_eq!(timed_out, events.is_empty());
+        events.iter().map(event::token).collect()
+    }
+
+    fn registration_id(ring: &Ring, fd: RawFd) -> u64 {
+        ring.registrations.lock().unwrap().fds[&fd].id
+    }
+
+    #[test]
+    fn reregister() {
+        let Some(ring) = new_ring() else { return };
+        let mut events = Events::with_capacity(8);
+        let efd = eventfd();
+
+        ring.register(efd.as_raw_fd(), ID1, Interest::READABLE)
+            .unwrap();
+        write(&efd);
+        assert_eq!(select(&ring, &mut events), [ID1]);
+
+        // Reports the readiness again, but only for the new token.
+        ring.reregister(efd.as_raw_fd(), ID2, Interest::READABLE)
+            .unwrap();
+        assert_eq!(select(&ring, &mut events), [ID2]);
+        assert_eq!(select(&ring, &mut events), []);
+
+        write(&efd);
+        assert_eq!(select(&ring, &mut events), [ID2]);
+
+        ring.deregister(efd.as_raw_fd()).unwrap();
+        write(&efd);
+        assert_eq!(select(&ring, &mut events), []);
+    }
+
+    #[test]
+    fn multishot_rearm_after_overflow() {
+        let Some(ring) = new_ring() else { return };
+        let mut events = Events::with_capacity(64);
+        let efd = eventfd();
+
+        ring.register(efd.as_raw_fd(), ID1, Interest::READABLE)
+            .unwrap();
+        // Post more completions than fit in the completion queue, which makes
+        // the kernel stop the multishot request.
+        for _ in 0..2 * CQ_ENTRIES {
+            write(&efd);
+        }
+        let sq_flags = ring.cq.lock().unwrap().sq_flags;
+        assert!(unsafe { (*sq_flags).load(Ordering::Acquire) } & IORING_SQ_CQ_OVERFLOW != 0);
+
+        let mut n = 0;
+        while !select(&ring, &mut events).is_empty() {
+            assert!(events.iter().all(|e| event::token(e) == ID1));
+            n += events.len();
+        }
+        assert!(n > 0);
+
+        // The request was restarted.
+        write(&efd);
+        assert_eq!(select(&ring, &mut events), [ID1]);
+        assert_eq!(select(&ring, &mut events), []);
+    }
+
+    #[test]
+    fn multishot_rearm_after_error() {
+        let Some(ring) = new_ring() else { return };
+        let mut events = Events::with_capacity(8);
+        let efd = eventfd();
+
+        ring.register(efd.as_raw_fd(), ID1, Interest::READABLE)
+            .unwrap();
+        // Stop the request behind our back, it completes with `-ECANCELED`.
+        let id = registration_id(&ring, efd.as_raw_fd());
+        ring.submit(&[Sqe::poll_remove(id)]).unwrap();
+        assert_eq!(select(&ring, &mut events), [ID1]);
+        assert!(event::is_error(&events[0]));
+
+        // The request was restarted.
+        write(&efd);
+        assert_eq!(select(&ring, &mut events), [ID1]);
+        assert!(!event::is_error(&events[0]));
+        assert!(event::is_readable(&events[0]));
+    }
+}
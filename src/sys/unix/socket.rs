@@ -0,0 +1,727 @@
+use std::io;
+use std::mem::MaybeUninit;
+use std::net::{IpAddr, SocketAddr};
+use std::os::fd::{AsFd, AsRawFd, BorrowedFd, FromRawFd, IntoRawFd, OwnedFd, RawFd};
+
+use crate::io_source::IoSource;
+use crate::sys::unix::net::{new_socket_with_protocol, socket_addr, to_socket_addr, SocketAddrCRepr};
+use crate::{event, Interest, Registry, Token};
+
+/// A non-blocking socket of any domain, type and protocol.
+///
+/// This is an escape hatch for protocols that are not natively supported by
+/// Mio (or the standard library), for example `AF_PACKET` sockets to capture
+/// raw packets. All addresses are passed as raw bytes, which must have the
+/// memory layout of the `sockaddr` type of the `domain` of the socket, e.g.
+/// `sockaddr_ll` for `AF_PACKET` sockets.
+///
+/// # Examples
+///
+#[cfg_attr(feature = "os-poll", doc = "```")]
+#[cfg_attr(not(feature = "os-poll"), doc = "```ignore")]
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use std::mem::size_of;
+/// use std::slice;
+///
+/// use mio::unix::Socket;
+/// use mio::{Interest, Poll, Token};
+///
+/// // A UDP socket, as if Mio didn't support it.
+/// let mut socket = Socket::new(libc::AF_INET, libc::SOCK_DGRAM, 0)?;
+///
+/// let addr = libc::sockaddr_in {
+///     sin_family: libc::AF_INET as libc::sa_family_t,
+///     sin_port: 0,
+///     sin_addr: libc::in_addr { s_addr: u32::from_ne_bytes([127, 0, 0, 1]) },
+///     ..unsafe { std::mem::zeroed() }
+/// };
+/// let addr = unsafe {
+///     slice::from_raw_parts(&addr as *const _ as *const u8, size_of::<libc::sockaddr_in>())
+/// };
+/// socket.bind(addr)?;
+///
+/// let poll = Poll::new()?;
+/// poll.registry().register(&mut socket, Token(0), Interest::READABLE)?;
+/// #     Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct Socket {
+    inner: IoSource<OwnedFd>,
+}
+
+impl Socket {
+    /// Create a new socket, using `socket(2)`.
+    ///
+    /// The socket is created in non-blocking mode, with the close-on-exec flag
+    /// set.
+    pub fn new(domain: i32, ty: i32, protocol: i32) -> io::Result<Socket> {
+        let fd = new_socket_with_protocol(domain, ty, protocol)?;
+        // SAFETY: `new_socket_with_protocol` returns a valid file descriptor.
+        Ok(unsafe { Socket::from_raw_fd(fd) })
+    }
+
+    /// Bind the socket to the raw address `addr`, using `bind(2)`.
+    pub fn bind(&self, addr: &[u8]) -> io::Result<()> {
+        syscall!(bind(
+            self.inner.as_raw_fd(),
+            addr.as_ptr().cast(),
+            addr.len() as libc::socklen_t,
+        ))
+        .map(|_| ())
+    }
+
+    /// Connect the socket to the raw address `addr`, using `connect(2)`.
+    ///
+    /// For connection oriented sockets the connection may not be established
+    /// yet once this returns, in which case the socket becomes writable once
+    /// it is. Use [`take_error`] to check if the connection failed.
+    ///
+    /// [`take_error`]: Socket::take_error
+    pub fn connect(&self, addr: &[u8]) -> io::Result<()> {
+        match syscall!(connect(
+            self.inner.as_raw_fd(),
+            addr.as_ptr().cast(),
+            addr.len() as libc::socklen_t,
+        )) {
+            Err(err) if err.raw_os_error() != Some(libc::EINPROGRESS) => Err(err),
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns the raw address the socket is bound to, using
+    /// `getsockname(2)`.
+    ///
+    /// The address is written into `addr`, returns the length of the address.
+    /// If the returned length is larger than `addr` the address was truncated.
+    pub fn local_addr(&self, addr: &mut [u8]) -> io::Result<usize> {
+        let mut length = addr.len() as libc::socklen_t;
+        syscall!(getsockname(
+            self.inner.as_raw_fd(),
+            addr.as_mut_ptr().cast(),
+            &mut length,
+        ))?;
+        Ok(length as usize)
+    }
+
+    /// Send data on a connected socket, using `send(2)`.
+    ///
+    /// On success, returns the number of bytes written.
+    pub fn send(&self, buf: &[u8]) -> io::Result<usize> {
+        self.inner.do_io(|fd| {
+            syscall!(send(
+                fd.as_raw_fd(),
+                buf.as_ptr().cast(),
+                buf.len(),
+                SEND_FLAGS,
+            ))
+            .map(|n| n as usize)
+        })
+    }
+
+    /// Send data to the raw address `addr`, using `sendto(2)`.
+    ///
+    /// On success, returns the number of bytes written.
+    pub fn send_to(&self, buf: &[u8], addr: &[u8]) -> io::Result<usize> {
+        self.inner.do_io(|fd| {
+            syscall!(sendto(
+                fd.as_raw_fd(),
+                buf.as_ptr().cast(),
+                buf.len(),
+                SEND_FLAGS,
+                addr.as_ptr().cast(),
+                addr.len() as libc::socklen_t,
+            ))
+            .map(|n| n as usize)
+        })
+    }
+
+    /// Receive data from the socket, using `recv(2)`.
+    ///
+    /// On success, returns the number of bytes read.
+    pub fn recv(&self, buf: &mut [u8]) -> io::Result<usize> {
+        self.inner.do_io(|fd| {
+            syscall!(recv(fd.as_raw_fd(), buf.as_mut_ptr().cast(), buf.len(), 0))
+                .map(|n| n as usize)
+        })
+    }
+
+    /// Receive data from the socket, using `recvfrom(2)`.
+    ///
+    /// The raw source address is written into `addr`. On success, returns the
+    /// number of bytes read and the length of the address. If the returned
+    /// length is larger than `addr` the address was truncated.
+    pub fn recv_from(&self, buf: &mut [u8], addr: &mut [u8]) -> io::Result<(usize, usize)> {
+        self.inner.do_io(|fd| {
+            let mut length = addr.len() as libc::socklen_t;
+            syscall!(recvfrom(
+                fd.as_raw_fd(),
+                buf.as_mut_ptr().cast(),
+                buf.len(),
+                0,
+                addr.as_mut_ptr().cast(),
+                &mut length,
+            ))
+            .map(|n| (n as usize, length as usize))
+        })
+    }
+
+    /// Get the value of the `SO_ERROR` option on this socket.
+    ///
+    /// This will retrieve the stored error in the underlying socket, clearing
+    /// the field in the process. This can be useful for checking errors between
+    /// calls.
+    pub fn take_error(&self) -> io::Result<Option<io::Error>> {
+        let mut err: libc::c_int = 0;
+        let mut length = std::mem::size_of::<libc::c_int>() as libc::socklen_t;
+        syscall!(getsockopt(
+            self.inner.as_raw_fd(),
+            libc::SOL_SOCKET,
+            libc::SO_ERROR,
+            (&mut err as *mut libc::c_int).cast(),
+            &mut length,
+        ))?;
+        if err == 0 {
+            Ok(None)
+        } else {
+            Ok(Some(io::Error::from_raw_os_error(err)))
+        }
+    }
+
+    /// Execute an I/O operation ensuring that the socket receives more events
+    /// if it hits a [`WouldBlock`] error.
+    ///
+    /// # Notes
+    ///
+    /// This method is required to be called for **all** I/O operations to
+    /// ensure the user will receive events once the socket is ready again after
+    /// returning a [`WouldBlock`] error.
+    ///
+    /// [`WouldBlock`]: io::ErrorKind::WouldBlock
+    pub fn try_io<F, T>(&self, f: F) -> io::Result<T>
+    where
+        F: FnOnce() -> io::Result<T>,
+    {
+        self.inner.do_io(|_| f())
+    }
+}
+
+/// Don't raise `SIGPIPE` when sending on a closed connection, on platforms
+/// that don't support `SO_NOSIGPIPE` (set in `new_socket_with_protocol`).
+#[cfg(any(
+    target_os = "android",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "fuchsia",
+    target_os = "illumos",
+    target_os = "linux",
+    target_os = "netbsd",
+    target_os = "openbsd",
+))]
+const SEND_FLAGS: libc::c_int = libc::MSG_NOSIGNAL;
+#[cfg(not(any(
+    target_os = "android",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "fuchsia",
+    target_os = "illumos",
+    target_os = "linux",
+    target_os = "netbsd",
+    target_os = "openbsd",
+)))]
+const SEND_FLAGS: libc::c_int = 0;
+
+impl event::Source for Socket {
+    fn register(
+        &mut self,
+        registry: &Registry,
+        token: Token,
+        interests: Interest,
+    ) -> io::Result<()> {
+        self.inner.register(registry, token, interests)
+    }
+
+    fn reregister(
+        &mut self,
+        registry: &Registry,
+        token: Token,
+        interests: Interest,
+    ) -> io::Result<()> {
+        self.inner.reregister(registry, token, interests)
+    }
+
+    fn deregister(&mut self, registry: &Registry) -> io::Result<()> {
+        self.inner.deregister(registry)
+    }
+
+    fn poll_ready(&self, interests: Interest) -> io::Result<event::Readiness> {
+        self.inner.poll_ready(interests)
+    }
+}
+
+impl FromRawFd for Socket {
+    /// Converts a `RawFd` to a `Socket`.
+    ///
+    /// # Notes
+    ///
+    /// The caller is responsible for ensuring that the socket is in
+    /// non-blocking mode.
+    unsafe fn from_raw_fd(fd: RawFd) -> Socket {
+        Socket::from(OwnedFd::from_raw_fd(fd))
+    }
+}
+
+impl AsRawFd for Socket {
+    fn as_raw_fd(&self) -> RawFd {
+        self.inner.as_raw_fd()
+    }
+}
+
+impl IntoRawFd for Socket {
+    fn into_raw_fd(self) -> RawFd {
+        self.inner.into_inner().into_raw_fd()
+    }
+}
+
+impl From<Socket> for OwnedFd {
+    fn from(socket: Socket) -> Self {
+        socket.inner.into_inner()
+    }
+}
+
+impl AsFd for Socket {
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        self.inner.as_fd()
+    }
+}
+
+impl From<OwnedFd> for Socket {
+    /// Converts an `OwnedFd` to a `Socket`.
+    ///
+    /// # Notes
+    ///
+    /// The caller is responsible for ensuring that the socket is in
+    /// non-blocking mode.
+    fn from(fd: OwnedFd) -> Self {
+        Socket {
+            inner: IoSource::new(fd),
+        }
+    }
+}
+
+/// A non-blocking netlink socket, used for communication with the Linux kernel.
+///
+/// The socket can be used to subscribe to kernel notifications, such as
+/// changes to the network links, addresses and routes using the
+/// `NETLINK_ROUTE` protocol. Parsing (and creating) the netlink messages is
+/// left to the user.
+///
+/// # Examples
+///
+#[cfg_attr(feature = "os-poll", doc = "```")]
+#[cfg_attr(not(feature = "os-poll"), doc = "```ignore")]
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use mio::unix::NetlinkSocket;
+/// use mio::{Interest, Poll, Token};
+///
+/// // Get notified of changes to the network links.
+/// let mut socket = NetlinkSocket::new(libc::NETLINK_ROUTE)?;
+/// socket.bind(libc::RTMGRP_LINK as u32)?;
+///
+/// let poll = Poll::new()?;
+/// poll.registry().register(&mut socket, Token(0), Interest::READABLE)?;
+/// #     Ok(())
+/// # }
+/// ```
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[derive(Debug)]
+pub struct NetlinkSocket {
+    inner: Socket,
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+impl NetlinkSocket {
+    /// Create a new netlink socket for the netlink `protocol`, e.g.
+    /// `NETLINK_ROUTE`.
+    pub fn new(protocol: i32) -> io::Result<NetlinkSocket> {
+        Socket::new(libc::AF_NETLINK, libc::SOCK_RAW, protocol).map(|inner| NetlinkSocket { inner })
+    }
+
+    /// Bind the socket, subscribing to the multicast `groups`, e.g.
+    /// `RTMGRP_LINK`.
+    ///
+    /// The kernel assigns a unique port id to the socket, use `0` for
+    /// `groups` to only receive responses to requests.
+    pub fn bind(&self, groups: u32) -> io::Result<()> {
+        // SAFETY: all zeroes is valid for `sockaddr_nl`.
+        let mut addr: libc::sockaddr_nl = unsafe { std::mem::zeroed() };
+        addr.nl_family = libc::AF_NETLINK as libc::sa_family_t;
+        addr.nl_groups = groups;
+        // SAFETY: `sockaddr_nl` is plain old data.
+        let addr = unsafe {
+            std::slice::from_raw_parts(
+                (&addr as *const libc::sockaddr_nl).cast::<u8>(),
+                std::mem::size_of::<libc::sockaddr_nl>(),
+            )
+        };
+        self.inner.bind(addr)
+    }
+
+    /// Send a netlink message to the kernel.
+    ///
+    /// On success, returns the number of bytes written.
+    pub fn send(&self, buf: &[u8]) -> io::Result<usize> {
+        self.inner.send(buf)
+    }
+
+    /// Receive netlink message(s), from the kernel or a multicast group.
+    ///
+    /// On success, returns the number of bytes read. A single datagram can
+    /// contain multiple netlink messages.
+    pub fn recv(&self, buf: &mut [u8]) -> io::Result<usize> {
+        self.inner.recv(buf)
+    }
+
+    /// Get the value of the `SO_ERROR` option on this socket, see
+    /// [`Socket::take_error`].
+    pub fn take_error(&self) -> io::Result<Option<io::Error>> {
+        self.inner.take_error()
+    }
+
+    /// Execute an I/O operation ensuring that the socket receives more events
+    /// if it hits a [`WouldBlock`] error, see [`Socket::try_io`].
+    ///
+    /// [`WouldBlock`]: io::ErrorKind::WouldBlock
+    pub fn try_io<F, T>(&self, f: F) -> io::Result<T>
+    where
+        F: FnOnce() -> io::Result<T>,
+    {
+        self.inner.try_io(f)
+    }
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+impl event::Source for NetlinkSocket {
+    fn register(
+        &mut self,
+        registry: &Registry,
+        token: Token,
+        interests: Interest,
+    ) -> io::Result<()> {
+        self.inner.register(registry, token, interests)
+    }
+
+    fn reregister(
+        &mut self,
+        registry: &Registry,
+        token: Token,
+        interests: Interest,
+    ) -> io::Result<()> {
+        self.inner.reregister(registry, token, interests)
+    }
+
+    fn deregister(&mut self, registry: &Registry) -> io::Result<()> {
+        self.inner.deregister(registry)
+    }
+
+    fn poll_ready(&self, interests: Interest) -> io::Result<event::Readiness> {
+        self.inner.poll_ready(interests)
+    }
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+impl FromRawFd for NetlinkSocket {
+    /// Converts a `RawFd` to a `NetlinkSocket`.
+    ///
+    /// # Notes
+    ///
+    /// The caller is responsible for ensuring that the socket is in
+    /// non-blocking mode.
+    unsafe fn from_raw_fd(fd: RawFd) -> NetlinkSocket {
+        NetlinkSocket {
+            inner: Socket::from_raw_fd(fd),
+        }
+    }
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+impl AsRawFd for NetlinkSocket {
+    fn as_raw_fd(&self) -> RawFd {
+        self.inner.as_raw_fd()
+    }
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+impl IntoRawFd for NetlinkSocket {
+    fn into_raw_fd(self) -> RawFd {
+        self.inner.into_raw_fd()
+    }
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+impl From<NetlinkSocket> for OwnedFd {
+    fn from(socket: NetlinkSocket) -> Self {
+        socket.inner.into()
+    }
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+impl AsFd for NetlinkSocket {
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        self.inner.as_fd()
+    }
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+impl From<OwnedFd> for NetlinkSocket {
+    /// Converts an `OwnedFd` to a `NetlinkSocket`.
+    ///
+    /// # Notes
+    ///
+    /// The caller is responsible for ensuring that the socket is in
+    /// non-blocking mode.
+    fn from(fd: OwnedFd) -> Self {
+        NetlinkSocket {
+            inner: Socket::from(fd),
+        }
+    }
+}
+
+/// A non-blocking ICMP socket, e.g. for ping or traceroute like tools.
+///
+/// ICMP sockets come in two kinds:
+///
+///  * Raw sockets ([`IcmpSocket::raw_v4`] and [`IcmpSocket::raw_v6`]) receive
+///    all ICMP messages, but require elevated privileges (`CAP_NET_RAW` on
+///    Linux). The messages sent must include the complete ICMP header, including
+///    the checksum for ICMPv4. The messages received on IPv4 sockets start
+///    with the IP header.
+///  * Datagram sockets ([`IcmpSocket::dgram_v4`] and [`IcmpSocket::dgram_v6`])
+///    only support sending echo requests and receiving the replies, but don't
+///    require any privileges. On Linux the user's group must be allowed by the
+///    `net.ipv4.ping_group_range` sysctl. The kernel sets the identifier of the
+///    echo requests to the local "port" of the socket, which can be set using
+///    [`bind`], and fills in the checksum. Only messages with that identifier
+///    are received, without the IP header.
+///
+/// [`bind`]: IcmpSocket::bind
+///
+/// # Examples
+///
+#[cfg_attr(feature = "os-poll", doc = "```")]
+#[cfg_attr(not(feature = "os-poll"), doc = "```ignore")]
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use mio::unix::IcmpSocket;
+/// use mio::{Interest, Poll, Token};
+///
+/// let mut socket = match IcmpSocket::dgram_v4() {
+///     Ok(socket) => socket,
+///     // Not allowed to create ICMP datagram sockets.
+///     Err(_) => return Ok(()),
+/// };
+///
+/// // Echo request, with identifier and checksum filled in by the kernel.
+/// let request = [8, 0, 0, 0, 0, 0, 0, 1];
+/// socket.send_to(&request, "127.0.0.1".parse()?)?;
+///
+/// let poll = Poll::new()?;
+/// poll.registry().register(&mut socket, Token(0), Interest::READABLE)?;
+/// #     Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct IcmpSocket {
+    inner: Socket,
+}
+
+impl IcmpSocket {
+    /// Create a new raw ICMPv4 socket (`SOCK_RAW`, `IPPROTO_ICMP`).
+    pub fn raw_v4() -> io::Result<IcmpSocket> {
+        IcmpSocket::new(libc::AF_INET, libc::SOCK_RAW, libc::IPPROTO_ICMP)
+    }
+
+    /// Create a new raw ICMPv6 socket (`SOCK_RAW`, `IPPROTO_ICMPV6`).
+    pub fn raw_v6() -> io::Result<IcmpSocket> {
+        IcmpSocket::new(libc::AF_INET6, libc::SOCK_RAW, libc::IPPROTO_ICMPV6)
+    }
+
+    /// Create a new datagram ICMPv4 socket (`SOCK_DGRAM`, `IPPROTO_ICMP`).
+    pub fn dgram_v4() -> io::Result<IcmpSocket> {
+        IcmpSocket::new(libc::AF_INET, libc::SOCK_DGRAM, libc::IPPROTO_ICMP)
+    }
+
+    /// Create a new datagram ICMPv6 socket (`SOCK_DGRAM`, `IPPROTO_ICMPV6`).
+    pub fn dgram_v6() -> io::Result<IcmpSocket> {
+        IcmpSocket::new(libc::AF_INET6, libc::SOCK_DGRAM, libc::IPPROTO_ICMPV6)
+    }
+
+    fn new(domain: i32, ty: i32, protocol: i32) -> io::Result<IcmpSocket> {
+        Socket::new(domain, ty, protocol).map(|inner| IcmpSocket { inner })
+    }
+
+    /// Bind the socket to `addr`.
+    ///
+    /// For datagram sockets the port of `addr` is used as ICMP identifier for
+    /// echo requests, with a port of 0 the kernel picks one. Raw sockets
+    /// ignore the port.
+    pub fn bind(&self, addr: SocketAddr) -> io::Result<()> {
+        let (raw_addr, raw_addr_length) = socket_addr(&addr);
+        self.inner.bind(raw_addr_bytes(&raw_addr, raw_addr_length))
+    }
+
+    /// Returns the address the socket is bound to.
+    ///
+    /// For datagram sockets the port is the ICMP identifier used for echo
+    /// requests (once bound).
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        let mut storage = MaybeUninit::<libc::sockaddr_storage>::zeroed();
+        // SAFETY: `sockaddr_storage` is plain old data, all zeroes is valid.
+        let addr = unsafe { storage_bytes(&mut storage) };
+        let _ = self.inner.local_addr(addr)?;
+        // SAFETY: `getsockname(2)` initialised the address.
+        unsafe { to_socket_addr(storage.as_ptr()) }
+    }
+
+    /// Send the ICMP message in `buf` to `addr`.
+    ///
+    /// On success, returns the number of bytes written.
+    pub fn send_to(&self, buf: &[u8], addr: IpAddr) -> io::Result<usize> {
+        let (raw_addr, raw_addr_length) = socket_addr(&SocketAddr::new(addr, 0));
+        self.inner
+            .send_to(buf, raw_addr_bytes(&raw_addr, raw_addr_length))
+    }
+
+    /// Receive a single ICMP message, returning the number of bytes read and
+    /// the address of the sender.
+    ///
+    /// See the [type documentation] for what the message includes.
+    ///
+    /// [type documentation]: IcmpSocket
+    pub fn recv_from(&self, buf: &mut [u8]) -> io::Result<(usize, IpAddr)> {
+        let mut storage = MaybeUninit::<libc::sockaddr_storage>::zeroed();
+        // SAFETY: `sockaddr_storage` is plain old data, all zeroes is valid.
+        let addr = unsafe { storage_bytes(&mut storage) };
+        let (n, _) = self.inner.recv_from(buf, addr)?;
+        // SAFETY: `recvfrom(2)` initialised the address.
+        let addr = unsafe { to_socket_addr(storage.as_ptr())? };
+        Ok((n, addr.ip()))
+    }
+
+    /// Get the value of the `SO_ERROR` option on this socket, see
+    /// [`Socket::take_error`].
+    pub fn take_error(&self) -> io::Result<Option<io::Error>> {
+        self.inner.take_error()
+    }
+
+    /// Execute an I/O operation ensuring that the socket receives more events
+    /// if it hits a [`WouldBlock`] error, see [`Socket::try_io`].
+    ///
+    /// [`WouldBlock`]: io::ErrorKind::WouldBlock
+    pub fn try_io<F, T>(&self, f: F) -> io::Result<T>
+    where
+        F: FnOnce() -> io::Result<T>,
+    {
+        self.inner.try_io(f)
+    }
+}
+
+/// Returns the raw bytes of `addr`.
+fn raw_addr_bytes(addr: &SocketAddrCRepr, length: libc::socklen_t) -> &[u8] {
+    // SAFETY: `SocketAddrCRepr` is plain old data, at least `length` bytes.
+    unsafe { std::slice::from_raw_parts(addr.as_ptr().cast::<u8>(), length as usize) }
+}
+
+/// Returns the bytes of `storage`.
+///
+/// # Safety
+///
+/// `storage` must be initialised.
+unsafe fn storage_bytes(storage: &mut MaybeUninit<libc::sockaddr_storage>) -> &mut [u8] {
+    std::slice::from_raw_parts_mut(
+        storage.as_mut_ptr().cast::<u8>(),
+        std::mem::size_of::<libc::sockaddr_storage>(),
+    )
+}
+
+impl event::Source for IcmpSocket {
+    fn register(
+        &mut self,
+        registry: &Registry,
+        token: Token,
+        interests: Interest,
+    ) -> io::Result<()> {
+        self.inner.register(registry, token, interests)
+    }
+
+    fn reregister(
+        &mut self,
+        registry: &Registry,
+        token: Token,
+        interests: Interest,
+    ) -> io::Result<()> {
+        self.inner.reregister(registry, token, interests)
+    }
+
+    fn deregister(&mut self, registry: &Registry) -> io::Result<()> {
+        self.inner.deregister(registry)
+    }
+
+    fn poll_ready(&self, interests: Interest) -> io::Result<event::Readiness> {
+        self.inner.poll_ready(interests)
+    }
+}
+
+impl FromRawFd for IcmpSocket {
+    /// Converts a `RawFd` to an `IcmpSocket`.
+    ///
+    /// # Notes
+    ///
+    /// The caller is responsible for ensuring that the socket is in
+    /// non-blocking mode.
+    unsafe fn from_raw_fd(fd: RawFd) -> IcmpSocket {
+        IcmpSocket {
+            inner: Socket::from_raw_fd(fd),
+        }
+    }
+}
+
+impl AsRawFd for IcmpSocket {
+    fn as_raw_fd(&self) -> RawFd {
+        self.inner.as_raw_fd()
+    }
+}
+
+impl IntoRawFd for IcmpSocket {
+    fn into_raw_fd(self) -> RawFd {
+        self.inner.into_raw_fd()
+    }
+}
+
+impl From<IcmpSocket> for OwnedFd {
+    fn from(socket: IcmpSocket) -> Self {
+        socket.inner.into()
+    }
+}
+
+impl AsFd for IcmpSocket {
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        self.inner.as_fd()
+    }
+}
+
+impl From<OwnedFd> for IcmpSocket {
+    /// Converts an `OwnedFd` to an `IcmpSocket`.
+    ///
+    /// # Notes
+    ///
+    /// The caller is responsible for ensuring that the socket is in
+    /// non-blocking mode.
+    fn from(fd: OwnedFd) -> Self {
+        IcmpSocket {
+            inner: Socket::from(fd),
+        }
+    }
+}
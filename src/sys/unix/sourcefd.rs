@@ -6,6 +6,8 @@ use std::os::fd::RawFd;
 #[cfg(target_os = "hermit")]
 use std::os::hermit::io::RawFd;
 
+#[cfg(unix)]
+use crate::event::Readiness;
 use crate::{event, Interest, Registry, Token};
 
 /// Adapter for [`RawFd`] providing an [`event::Source`] implementation.
@@ -103,7 +105,9 @@ impl<'a> event::Source for SourceFd<'a> {
         token: Token,
         interests: Interest,
     ) -> io::Result<()> {
-        registry.selector().register(*self.0, token, interests)
+        registry.selector().register(*self.0, token, interests)?;
+        registry.track_new_registration(*self.0, token, interests);
+        Ok(())
     }
 
     fn reregister(
@@ -112,10 +116,18 @@ impl<'a> event::Source for SourceFd<'a> {
         token: Token,
         interests: Interest,
     ) -> io::Result<()> {
-        registry.selector().reregister(*self.0, token, interests)
+        registry.selector().reregister(*self.0, token, interests)?;
+        registry.track_registration(*self.0, token, interests);
+        Ok(())
     }
 
     fn deregister(&mut self, registry: &Registry) -> io::Result<()> {
+        registry.untrack_registration(*self.0);
         registry.selector().deregister(*self.0)
     }
+
+    #[cfg(unix)]
+    fn poll_ready(&self, interests: Interest) -> io::Result<Readiness> {
+        crate::sys::probe(*self.0, interests)
+    }
 }
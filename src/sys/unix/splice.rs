@@ -0,0 +1,169 @@
+use std::fs::File;
+use std::io;
+use std::os::fd::{AsRawFd, FromRawFd, RawFd};
+use std::ptr;
+
+use crate::sys::pipe;
+
+/// Pipe used to move bytes between two file descriptors with `splice(2)`,
+/// without copying them to user space.
+///
+/// `splice(2)` requires one of the two file descriptors to be a pipe, so
+/// moving bytes between two sockets, e.g. in a proxy, is done in two steps:
+/// from the source into this pipe and from this pipe into the destination.
+/// If the destination can't accept all bytes the remainder stays in the pipe
+/// and is written first on the next call to [`splice`], this is why the pipe
+/// must be kept around for the lifetime of the `from`-`to` pair, rather than
+/// creating one per call. Because of this a `SplicePipe` must only be used
+/// for a single destination.
+///
+/// [`splice`]: SplicePipe::splice
+///
+/// # Notes
+///
+/// `splice` operates on the raw file descriptors, when using the poll(2)
+/// based selector the sources must be used with [`try_io`] to be notified
+/// again after a `WouldBlock` error.
+///
+/// [`try_io`]: crate::net::TcpStream::try_io
+///
+/// # Examples
+///
+#[cfg_attr(all(feature = "os-poll", feature = "net"), doc = "```")]
+#[cfg_attr(not(all(feature = "os-poll", feature = "net")), doc = "```ignore")]
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use std::io::{Read, Write};
+///
+/// use mio::net::UnixStream;
+/// use mio::unix::SplicePipe;
+///
+/// let (mut client, from) = UnixStream::pair()?;
+/// let (to, mut server) = UnixStream::pair()?;
+/// client.write_all(b"hello")?;
+///
+/// let mut pipe = SplicePipe::new()?;
+/// let n = pipe.splice(&from, &to, 4096)?;
+/// assert_eq!(n, 5);
+/// assert!(pipe.is_empty());
+///
+/// let mut buf = [0; 5];
+/// server.read_exact(&mut buf)?;
+/// assert_eq!(&buf, b"hello");
+/// #     Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct SplicePipe {
+    receiver: File,
+    sender: File,
+    /// Number of bytes in the pipe that haven't been written to the
+    /// destination yet.
+    buffered: usize,
+}
+
+impl SplicePipe {
+    /// Create a new, empty, `SplicePipe`.
+    pub fn new() -> io::Result<SplicePipe> {
+        let [receiver, sender] = pipe::new_raw()?;
+        // SAFETY: `new_raw` returns two new file descriptors we own.
+        let (receiver, sender) = unsafe { (File::from_raw_fd(receiver), File::from_raw_fd(sender)) };
+        Ok(SplicePipe {
+            receiver,
+            sender,
+            buffered: 0,
+        })
+    }
+
+    /// Move up to `len` bytes from `from` to `to`, returning the number of
+    /// bytes written to `to`.
+    ///
+    /// Bytes left in the pipe by a previous call are written first. If `to`
+    /// can't accept all of them no bytes are read from `from`. Returns `Ok(0)`
+    /// once `from` reached end of file (or `len` is zero) and the pipe is
+    /// empty.
+    ///
+    /// A [`WouldBlock`] error is returned if no bytes were written to `to`. If
+    /// [`is_empty`] returns `false` the destination is not ready, wait for it
+    /// to become writable, otherwise the source is not ready, wait for it to
+    /// become readable. If both sides block at the same time, i.e. bytes
+    /// read from `from` couldn't be written to `to`, the bytes stay in the
+    /// pipe, so only the destination needs to become writable again.
+    ///
+    /// [`WouldBlock`]: io::ErrorKind::WouldBlock
+    /// [`is_empty`]: SplicePipe::is_empty
+    pub fn splice<F, T>(&mut self, from: &F, to: &T, len: usize) -> io::Result<usize>
+    where
+        F: AsRawFd + ?Sized,
+        T: AsRawFd + ?Sized,
+    {
+        let mut written = 0;
+        if self.buffered != 0 {
+            written += self.drain(to.as_raw_fd())?;
+            if self.buffered != 0 {
+                return Ok(written);
+            }
+        }
+
+        let n = match splice(from.as_raw_fd(), self.sender.as_raw_fd(), len) {
+            Ok(n) => n,
+            // Report the bytes written above, the source will return the
+            // error again on the next call.
+            Err(_) if written != 0 => return Ok(written),
+            Err(err) => return Err(err),
+        };
+        self.buffered += n;
+        match self.drain(to.as_raw_fd()) {
+            Ok(n) => Ok(written + n),
+            Err(ref err) if err.kind() == io::ErrorKind::WouldBlock && written != 0 => Ok(written),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Returns the number of bytes in the pipe that have been read from the
+    /// source, but not yet written to the destination.
+    pub fn buffered(&self) -> usize {
+        self.buffered
+    }
+
+    /// Returns `true` if there are no bytes waiting to be written to the
+    /// destination.
+    pub fn is_empty(&self) -> bool {
+        self.buffered == 0
+    }
+
+    /// Write as many buffered bytes as possible to `to`.
+    fn drain(&mut self, to: RawFd) -> io::Result<usize> {
+        let mut written = 0;
+        while self.buffered != 0 {
+            match splice(self.receiver.as_raw_fd(), to, self.buffered) {
+                Ok(n) => {
+                    self.buffered -= n;
+                    written += n;
+                }
+                Err(ref err) if err.kind() == io::ErrorKind::WouldBlock && written != 0 => break,
+                Err(err) => return Err(err),
+            }
+        }
+        Ok(written)
+    }
+}
+
+fn splice(from: RawFd, to: RawFd, len: usize) -> io::Result<usize> {
+    let flags = libc::SPLICE_F_MOVE | libc::SPLICE_F_NONBLOCK;
+    loop {
+        let res = syscall!(splice(
+            from,
+            ptr::null_mut(),
+            to,
+            ptr::null_mut(),
+            len,
+            flags
+        ));
+        match res {
+            Ok(n) => return Ok(n as usize),
+            Err(ref err) if err.kind() == io::ErrorKind::Interrupted => continue,
+            Err(err) => return Err(err),
+        }
+    }
+}
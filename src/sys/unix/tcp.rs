@@ -2,21 +2,46 @@ use std::convert::TryInto;
 use std::io;
 use std::mem::{size_of, MaybeUninit};
 use std::net::{self, SocketAddr};
+#[cfg(unix)]
+use std::time::Duration;
 #[cfg(not(target_os = "hermit"))]
 use std::os::fd::{AsRawFd, FromRawFd};
+#[cfg(any(
+    target_os = "android",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "ios",
+    target_os = "linux",
+    target_os = "macos",
+    target_os = "tvos",
+    target_os = "visionos",
+    target_os = "watchos",
+))]
+use std::os::fd::RawFd;
 // TODO: once <https://github.com/rust-lang/rust/issues/126198> is fixed this
 // can use `std::os::fd` and be merged with the above.
 #[cfg(target_os = "hermit")]
 use std::os::hermit::io::{AsRawFd, FromRawFd};
 
-use crate::sys::unix::net::{new_socket, socket_addr, to_socket_addr};
+#[cfg(unix)]
+use crate::sys::unix::net::{getsockopt, prepare_inherited_listener, setsockopt};
+#[cfg(unix)]
+pub(crate) use crate::sys::unix::net::{hop_limit, set_hop_limit};
+use crate::sys::unix::net::{new_ip_socket, socket_addr, to_socket_addr};
+#[cfg(unix)]
+use crate::net::ConnDiagnosis;
+#[cfg(target_os = "linux")]
+use crate::net::TcpRepairState;
 
 pub(crate) fn new_for_addr(address: SocketAddr) -> io::Result<libc::c_int> {
-    let domain = match address {
-        SocketAddr::V4(_) => libc::AF_INET,
-        SocketAddr::V6(_) => libc::AF_INET6,
-    };
-    new_socket(domain, libc::SOCK_STREAM)
+    new_for_addr_with_protocol(address, 0)
+}
+
+pub(crate) fn new_for_addr_with_protocol(
+    address: SocketAddr,
+    protocol: libc::c_int,
+) -> io::Result<libc::c_int> {
+    new_ip_socket(address, libc::SOCK_STREAM, protocol)
 }
 
 pub(crate) fn bind(socket: &net::TcpListener, addr: SocketAddr) -> io::Result<()> {
@@ -44,6 +69,27 @@ pub(crate) fn listen(socket: &net::TcpListener, backlog: u32) -> io::Result<()>
     Ok(())
 }
 
+#[cfg(unix)]
+pub(crate) fn prepare_inherited(socket: &net::TcpListener) -> io::Result<()> {
+    prepare_inherited_listener(socket.as_raw_fd(), &[libc::AF_INET, libc::AF_INET6])
+}
+
+#[cfg(any(target_os = "android", target_os = "freebsd", target_os = "linux"))]
+pub(crate) fn set_reuseport_lb(socket: &net::TcpListener) -> io::Result<()> {
+    // Linux load balances connections across all sockets sharing a port
+    // using `SO_REUSEPORT`, FreeBSD has a separate option for it.
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    let option = libc::SO_REUSEPORT;
+    #[cfg(target_os = "freebsd")]
+    let option = libc::SO_REUSEPORT_LB;
+    setsockopt(socket.as_raw_fd(), libc::SOL_SOCKET, option, 1 as libc::c_int)
+}
+
+#[cfg(not(any(target_os = "android", target_os = "freebsd", target_os = "linux")))]
+pub(crate) fn set_reuseport_lb(_: &net::TcpListener) -> io::Result<()> {
+    Err(io::ErrorKind::Unsupported.into())
+}
+
 pub(crate) fn set_reuseaddr(socket: &net::TcpListener, reuseaddr: bool) -> io::Result<()> {
     let val: libc::c_int = i32::from(reuseaddr);
     syscall!(setsockopt(
@@ -56,6 +102,746 @@ pub(crate) fn set_reuseaddr(socket: &net::TcpListener, reuseaddr: bool) -> io::R
     Ok(())
 }
 
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn set_defer_accept(
+    socket: &net::TcpListener,
+    timeout: Option<Duration>,
+) -> io::Result<()> {
+    // The kernel only works with whole seconds, round up so that a non-zero
+    // duration doesn't disable the option.
+    let secs = timeout.map_or(0, |timeout| {
+        let secs = timeout.as_secs() + u64::from(timeout.subsec_nanos() != 0);
+        secs.try_into().unwrap_or(libc::c_int::MAX)
+    });
+    setsockopt(
+        socket.as_raw_fd(),
+        libc::IPPROTO_TCP,
+        libc::TCP_DEFER_ACCEPT,
+        secs as libc::c_int,
+    )
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn set_ip_transparent(socket: &net::TcpListener, on: bool) -> io::Result<()> {
+    setsockopt(
+        socket.as_raw_fd(),
+        libc::SOL_IP,
+        libc::IP_TRANSPARENT,
+        libc::c_int::from(on),
+    )
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn set_nodelay(socket: &net::TcpListener, nodelay: bool) -> io::Result<()> {
+    setsockopt(
+        socket.as_raw_fd(),
+        libc::IPPROTO_TCP,
+        libc::TCP_NODELAY,
+        libc::c_int::from(nodelay),
+    )
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn nodelay(socket: &net::TcpListener) -> io::Result<bool> {
+    getsockopt::<libc::c_int>(socket.as_raw_fd(), libc::IPPROTO_TCP, libc::TCP_NODELAY)
+        .map(|nodelay| nodelay != 0)
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn set_incoming_cpu(socket: &net::TcpListener, cpu: usize) -> io::Result<()> {
+    let cpu: libc::c_int = cpu.try_into().unwrap_or(libc::c_int::MAX);
+    setsockopt(socket.as_raw_fd(), libc::SOL_SOCKET, libc::SO_INCOMING_CPU, cpu)
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn incoming_cpu(socket: &net::TcpListener) -> io::Result<Option<usize>> {
+    // The kernel uses -1 if no CPU is set.
+    getsockopt::<libc::c_int>(socket.as_raw_fd(), libc::SOL_SOCKET, libc::SO_INCOMING_CPU)
+        .map(|cpu| usize::try_from(cpu).ok())
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn set_user_timeout(
+    socket: &net::TcpStream,
+    timeout: Option<Duration>,
+) -> io::Result<()> {
+    // The kernel works with milliseconds, round up so that a non-zero duration
+    // doesn't disable the option.
+    let millis = timeout.map_or(0, |timeout| {
+        let millis = timeout.as_millis() + u128::from(timeout.subsec_nanos() % 1_000_000 != 0);
+        millis.try_into().unwrap_or(libc::c_uint::MAX)
+    });
+    setsockopt(
+        socket.as_raw_fd(),
+        libc::IPPROTO_TCP,
+        libc::TCP_USER_TIMEOUT,
+        millis as libc::c_uint,
+    )
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn user_timeout(socket: &net::TcpStream) -> io::Result<Option<Duration>> {
+    getsockopt::<libc::c_uint>(socket.as_raw_fd(), libc::IPPROTO_TCP, libc::TCP_USER_TIMEOUT)
+        .map(|millis| (millis != 0).then(|| Duration::from_millis(millis.into())))
+}
+
+#[cfg(not(any(target_os = "android", target_os = "linux")))]
+pub(crate) fn set_user_timeout(_: &net::TcpStream, _: Option<Duration>) -> io::Result<()> {
+    Err(io::ErrorKind::Unsupported.into())
+}
+
+#[cfg(not(any(target_os = "android", target_os = "linux")))]
+pub(crate) fn user_timeout(_: &net::TcpStream) -> io::Result<Option<Duration>> {
+    Err(io::ErrorKind::Unsupported.into())
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn set_priority(socket: &net::TcpStream, priority: u32) -> io::Result<()> {
+    let priority: libc::c_int = priority.try_into().unwrap_or(libc::c_int::MAX);
+    setsockopt(socket.as_raw_fd(), libc::SOL_SOCKET, libc::SO_PRIORITY, priority)
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn priority(socket: &net::TcpStream) -> io::Result<u32> {
+    getsockopt::<libc::c_int>(socket.as_raw_fd(), libc::SOL_SOCKET, libc::SO_PRIORITY)
+        .map(|priority| priority as u32)
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn set_congestion(socket: &net::TcpStream, algo: &str) -> io::Result<()> {
+    syscall!(setsockopt(
+        socket.as_raw_fd(),
+        libc::IPPROTO_TCP,
+        libc::TCP_CONGESTION,
+        algo.as_ptr().cast(),
+        algo.len() as libc::socklen_t,
+    ))
+    .map(|_| ())
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn congestion(socket: &net::TcpStream) -> io::Result<String> {
+    // `TCP_CA_NAME_MAX` in the kernel.
+    let mut buf = [0u8; 16];
+    let mut len = buf.len() as libc::socklen_t;
+    syscall!(getsockopt(
+        socket.as_raw_fd(),
+        libc::IPPROTO_TCP,
+        libc::TCP_CONGESTION,
+        buf.as_mut_ptr().cast(),
+        &mut len,
+    ))?;
+    let name = &buf[..len as usize];
+    let name = name.split(|b| *b == 0).next().unwrap_or(name);
+    String::from_utf8(name.to_vec()).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn original_dst(socket: &net::TcpStream) -> io::Result<SocketAddr> {
+    let (level, name) = match socket.local_addr()? {
+        SocketAddr::V4(..) => (libc::SOL_IP, libc::SO_ORIGINAL_DST),
+        SocketAddr::V6(..) => (libc::SOL_IPV6, libc::IP6T_SO_ORIGINAL_DST),
+    };
+    let mut storage: MaybeUninit<libc::sockaddr_storage> = MaybeUninit::zeroed();
+    let mut len = size_of::<libc::sockaddr_storage>() as libc::socklen_t;
+    syscall!(getsockopt(
+        socket.as_raw_fd(),
+        level,
+        name,
+        storage.as_mut_ptr().cast(),
+        &mut len,
+    ))?;
+    // SAFETY: the kernel initialised `storage` to a `sockaddr_in` or
+    // `sockaddr_in6`, matching the family of the socket.
+    unsafe { to_socket_addr(storage.as_ptr()) }
+}
+
+#[cfg(unix)]
+pub(crate) fn recv_queue_len(socket: &net::TcpStream) -> io::Result<usize> {
+    let mut len: libc::c_int = 0;
+    // Same as `SIOCINQ` on Linux.
+    syscall!(ioctl(socket.as_raw_fd(), libc::FIONREAD, &mut len))?;
+    Ok(len as usize)
+}
+
+#[cfg(unix)]
+pub(crate) fn send_queue_len(socket: &net::TcpStream) -> io::Result<usize> {
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    {
+        let mut len: libc::c_int = 0;
+        // Same as `SIOCOUTQ`.
+        syscall!(ioctl(socket.as_raw_fd(), libc::TIOCOUTQ, &mut len))?;
+        Ok(len as usize)
+    }
+
+    #[cfg(any(target_os = "freebsd", target_os = "netbsd"))]
+    {
+        let mut len: libc::c_int = 0;
+        syscall!(ioctl(socket.as_raw_fd(), libc::FIONWRITE, &mut len))?;
+        Ok(len as usize)
+    }
+
+    #[cfg(any(
+        target_os = "ios",
+        target_os = "macos",
+        target_os = "tvos",
+        target_os = "visionos",
+        target_os = "watchos",
+    ))]
+    {
+        getsockopt::<libc::c_int>(socket.as_raw_fd(), libc::SOL_SOCKET, libc::SO_NWRITE)
+            .map(|len| len as usize)
+    }
+
+    #[cfg(not(any(
+        target_os = "android",
+        target_os = "freebsd",
+        target_os = "ios",
+        target_os = "linux",
+        target_os = "macos",
+        target_os = "netbsd",
+        target_os = "tvos",
+        target_os = "visionos",
+        target_os = "watchos",
+    )))]
+    {
+        let _ = socket;
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "querying the send queue length is not supported on this platform",
+        ))
+    }
+}
+
+#[cfg(unix)]
+pub(crate) fn set_keepalive(socket: &net::TcpStream, keepalive: bool) -> io::Result<()> {
+    setsockopt(
+        socket.as_raw_fd(),
+        libc::SOL_SOCKET,
+        libc::SO_KEEPALIVE,
+        libc::c_int::from(keepalive),
+    )
+}
+
+#[cfg(unix)]
+pub(crate) fn keepalive(socket: &net::TcpStream) -> io::Result<bool> {
+    getsockopt::<libc::c_int>(socket.as_raw_fd(), libc::SOL_SOCKET, libc::SO_KEEPALIVE)
+        .map(|keepalive| keepalive != 0)
+}
+
+#[cfg(unix)]
+pub(crate) fn set_recv_buffer_size(socket: &net::TcpStream, size: usize) -> io::Result<()> {
+    let size: libc::c_int = size.try_into().unwrap_or(libc::c_int::MAX);
+    setsockopt(socket.as_raw_fd(), libc::SOL_SOCKET, libc::SO_RCVBUF, size)
+}
+
+#[cfg(unix)]
+pub(crate) fn recv_buffer_size(socket: &net::TcpStream) -> io::Result<usize> {
+    getsockopt::<libc::c_int>(socket.as_raw_fd(), libc::SOL_SOCKET, libc::SO_RCVBUF)
+        .map(|size| size as usize)
+}
+
+#[cfg(unix)]
+pub(crate) fn set_send_buffer_size(socket: &net::TcpStream, size: usize) -> io::Result<()> {
+    let size: libc::c_int = size.try_into().unwrap_or(libc::c_int::MAX);
+    setsockopt(socket.as_raw_fd(), libc::SOL_SOCKET, libc::SO_SNDBUF, size)
+}
+
+#[cfg(unix)]
+pub(crate) fn send_buffer_size(socket: &net::TcpStream) -> io::Result<usize> {
+    getsockopt::<libc::c_int>(socket.as_raw_fd(), libc::SOL_SOCKET, libc::SO_SNDBUF)
+        .map(|size| size as usize)
+}
+
+// On Apple platforms `SO_LINGER` uses ticks, rather than seconds.
+#[cfg(unix)]
+#[cfg(any(
+    target_os = "ios",
+    target_os = "macos",
+    target_os = "tvos",
+    target_os = "visionos",
+    target_os = "watchos",
+))]
+const SO_LINGER: libc::c_int = libc::SO_LINGER_SEC;
+#[cfg(unix)]
+#[cfg(not(any(
+    target_os = "ios",
+    target_os = "macos",
+    target_os = "tvos",
+    target_os = "visionos",
+    target_os = "watchos",
+)))]
+const SO_LINGER: libc::c_int = libc::SO_LINGER;
+
+#[cfg(unix)]
+pub(crate) fn set_linger(socket: &net::TcpStream, linger: Option<Duration>) -> io::Result<()> {
+    // The kernel only works with whole seconds, round up so that a non-zero
+    // duration doesn't turn into an abortive close.
+    let secs = linger.map_or(0, |linger| {
+        let secs = linger.as_secs() + u64::from(linger.subsec_nanos() != 0);
+        secs.try_into().unwrap_or(libc::c_int::MAX)
+    });
+    let linger = libc::linger {
+        l_onoff: libc::c_int::from(linger.is_some()),
+        l_linger: secs,
+    };
+    setsockopt(socket.as_raw_fd(), libc::SOL_SOCKET, SO_LINGER, linger)
+}
+
+#[cfg(unix)]
+pub(crate) fn linger(socket: &net::TcpStream) -> io::Result<Option<Duration>> {
+    getsockopt::<libc::linger>(socket.as_raw_fd(), libc::SOL_SOCKET, SO_LINGER).map(|linger| {
+        (linger.l_onoff != 0).then(|| Duration::from_secs(linger.l_linger as u64))
+    })
+}
+
+#[cfg(unix)]
+pub(crate) fn set_recv_low_watermark(socket: &net::TcpStream, bytes: usize) -> io::Result<()> {
+    let bytes: libc::c_int = bytes.try_into().unwrap_or(libc::c_int::MAX);
+    setsockopt(socket.as_raw_fd(), libc::SOL_SOCKET, libc::SO_RCVLOWAT, bytes)
+}
+
+#[cfg(unix)]
+pub(crate) fn recv_low_watermark(socket: &net::TcpStream) -> io::Result<usize> {
+    getsockopt::<libc::c_int>(socket.as_raw_fd(), libc::SOL_SOCKET, libc::SO_RCVLOWAT)
+        .map(|bytes| bytes as usize)
+}
+
+#[cfg(unix)]
+pub(crate) fn set_send_low_watermark(socket: &net::TcpStream, bytes: usize) -> io::Result<()> {
+    let bytes: libc::c_int = bytes.try_into().unwrap_or(libc::c_int::MAX);
+    setsockopt(socket.as_raw_fd(), libc::SOL_SOCKET, libc::SO_SNDLOWAT, bytes)
+}
+
+#[cfg(unix)]
+pub(crate) fn send_low_watermark(socket: &net::TcpStream) -> io::Result<usize> {
+    getsockopt::<libc::c_int>(socket.as_raw_fd(), libc::SOL_SOCKET, libc::SO_SNDLOWAT)
+        .map(|bytes| bytes as usize)
+}
+
+#[cfg(unix)]
+pub(crate) fn set_notsent_lowat(socket: &net::TcpStream, bytes: u32) -> io::Result<()> {
+    // The kernel stores the value as unsigned integer.
+    let bytes = bytes as libc::c_int;
+    setsockopt(socket.as_raw_fd(), libc::IPPROTO_TCP, notsent_lowat_option()?, bytes)
+}
+
+#[cfg(unix)]
+pub(crate) fn notsent_lowat(socket: &net::TcpStream) -> io::Result<u32> {
+    getsockopt::<libc::c_int>(socket.as_raw_fd(), libc::IPPROTO_TCP, notsent_lowat_option()?)
+        .map(|bytes| bytes as u32)
+}
+
+/// Returns the `TCP_NOTSENT_LOWAT` option name, if supported.
+#[cfg(unix)]
+fn notsent_lowat_option() -> io::Result<libc::c_int> {
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    {
+        Ok(libc::TCP_NOTSENT_LOWAT)
+    }
+
+    #[cfg(any(
+        target_os = "ios",
+        target_os = "macos",
+        target_os = "tvos",
+        target_os = "visionos",
+        target_os = "watchos",
+    ))]
+    {
+        // Not defined in libc, see `netinet/tcp.h`.
+        Ok(0x201)
+    }
+
+    #[cfg(not(any(
+        target_os = "android",
+        target_os = "ios",
+        target_os = "linux",
+        target_os = "macos",
+        target_os = "tvos",
+        target_os = "visionos",
+        target_os = "watchos",
+    )))]
+    {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "`TCP_NOTSENT_LOWAT` is not supported on this platform",
+        ))
+    }
+}
+
+#[cfg(unix)]
+pub(crate) fn set_mss(socket: &net::TcpStream, mss: u32) -> io::Result<()> {
+    let mss: libc::c_int = mss.try_into().unwrap_or(libc::c_int::MAX);
+    setsockopt(socket.as_raw_fd(), libc::IPPROTO_TCP, libc::TCP_MAXSEG, mss)
+}
+
+#[cfg(unix)]
+pub(crate) fn mss(socket: &net::TcpStream) -> io::Result<u32> {
+    getsockopt::<libc::c_int>(socket.as_raw_fd(), libc::IPPROTO_TCP, libc::TCP_MAXSEG)
+        .map(|mss| mss as u32)
+}
+
+#[cfg(unix)]
+pub(crate) fn diagnose(socket: &net::TcpStream) -> io::Result<ConnDiagnosis> {
+    if let Some(err) = socket.take_error()? {
+        return Ok(ConnDiagnosis::Reset(err));
+    }
+
+    // Peeking returns 0 bytes once the peer closed its write half and all data
+    // was read.
+    let mut buf = [0u8; 1];
+    let read_closed = match syscall!(recv(
+        socket.as_raw_fd(),
+        buf.as_mut_ptr().cast(),
+        buf.len(),
+        libc::MSG_PEEK | libc::MSG_DONTWAIT
+    )) {
+        Ok(n) => n == 0,
+        Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => false,
+        Err(ref err) if err.raw_os_error() == Some(libc::ENOTCONN) => {
+            return Ok(ConnDiagnosis::Closed)
+        }
+        Err(err) if err.kind() == io::ErrorKind::Interrupted => return diagnose(socket),
+        Err(err) => return Ok(ConnDiagnosis::Reset(err)),
+    };
+
+    // Our write half can't be checked without writing, except on Linux
+    // where we can look at the TCP state.
+    #[cfg(target_os = "linux")]
+    let write_closed = matches!(
+        tcp_info(socket)?.tcpi_state,
+        TCP_FIN_WAIT1 | TCP_FIN_WAIT2 | TCP_TIME_WAIT | TCP_CLOSE | TCP_LAST_ACK | TCP_CLOSING
+    );
+    #[cfg(not(target_os = "linux"))]
+    let write_closed = false;
+
+    Ok(match (read_closed, write_closed) {
+        (false, false) => ConnDiagnosis::Alive,
+        (true, false) => ConnDiagnosis::ReadClosed,
+        (false, true) => ConnDiagnosis::WriteClosed,
+        (true, true) => ConnDiagnosis::Closed,
+    })
+}
+
+// Definitions from `<linux/tcp.h>`, not (yet) provided by the libc crate.
+#[cfg(target_os = "linux")]
+const TCP_NO_QUEUE: libc::c_int = 0;
+#[cfg(target_os = "linux")]
+const TCP_RECV_QUEUE: libc::c_int = 1;
+#[cfg(target_os = "linux")]
+const TCP_SEND_QUEUE: libc::c_int = 2;
+#[cfg(target_os = "linux")]
+const TCPOPT_MAXSEG: u32 = 2;
+#[cfg(target_os = "linux")]
+const TCPOPT_WINDOW: u32 = 3;
+#[cfg(target_os = "linux")]
+const TCPOPT_SACK_PERM: u32 = 4;
+#[cfg(target_os = "linux")]
+const TCPOPT_TIMESTAMP: u32 = 8;
+#[cfg(target_os = "linux")]
+const TCPI_OPT_TIMESTAMPS: u8 = 1;
+#[cfg(target_os = "linux")]
+const TCPI_OPT_SACK: u8 = 2;
+#[cfg(target_os = "linux")]
+const TCPI_OPT_WSCALE: u8 = 4;
+// TCP states, `tcpi_state`.
+#[cfg(target_os = "linux")]
+const TCP_FIN_WAIT1: u8 = 4;
+#[cfg(target_os = "linux")]
+const TCP_FIN_WAIT2: u8 = 5;
+#[cfg(target_os = "linux")]
+const TCP_TIME_WAIT: u8 = 6;
+#[cfg(target_os = "linux")]
+const TCP_CLOSE: u8 = 7;
+#[cfg(target_os = "linux")]
+const TCP_LAST_ACK: u8 = 9;
+#[cfg(target_os = "linux")]
+const TCP_CLOSING: u8 = 11;
+
+/// Returns the `TCP_INFO` of `socket`.
+#[cfg(target_os = "linux")]
+fn tcp_info(socket: &net::TcpStream) -> io::Result<libc::tcp_info> {
+    // SAFETY: all zeroes is valid for `tcp_info`, older kernels might not
+    // fill all fields.
+    let mut info: libc::tcp_info = unsafe { std::mem::zeroed() };
+    let mut len = size_of::<libc::tcp_info>() as libc::socklen_t;
+    syscall!(getsockopt(
+        socket.as_raw_fd(),
+        libc::IPPROTO_TCP,
+        libc::TCP_INFO,
+        (&mut info as *mut libc::tcp_info).cast(),
+        &mut len,
+    ))?;
+    Ok(info)
+}
+
+/// `struct tcp_repair_opt`.
+#[cfg(target_os = "linux")]
+#[repr(C)]
+struct TcpRepairOpt {
+    opt_code: u32,
+    opt_val: u32,
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn repair_save(socket: &net::TcpStream) -> io::Result<TcpRepairState> {
+    let fd = socket.as_raw_fd();
+    setsockopt(fd, libc::IPPROTO_TCP, libc::TCP_REPAIR, 1 as libc::c_int)?;
+    let res = repair_save_state(socket);
+    if res.is_err() {
+        // Leave the socket usable if we failed.
+        let _ = setsockopt(fd, libc::IPPROTO_TCP, libc::TCP_REPAIR, 0 as libc::c_int);
+    }
+    res
+}
+
+/// Reads the state of `socket`, which must be in repair mode.
+#[cfg(target_os = "linux")]
+fn repair_save_state(socket: &net::TcpStream) -> io::Result<TcpRepairState> {
+    let fd = socket.as_raw_fd();
+
+    let mut queues = [(TCP_RECV_QUEUE, 0, Vec::new()), (TCP_SEND_QUEUE, 0, Vec::new())];
+    for (queue, seq, data) in queues.iter_mut() {
+        setsockopt(fd, libc::IPPROTO_TCP, libc::TCP_REPAIR_QUEUE, *queue)?;
+        *seq = getsockopt::<u32>(fd, libc::IPPROTO_TCP, libc::TCP_QUEUE_SEQ)?;
+        let len = if *queue == TCP_RECV_QUEUE {
+            recv_queue_len(socket)?
+        } else {
+            send_queue_len(socket)?
+        };
+        if len != 0 {
+            // In repair mode peeking returns the data of the selected queue.
+            data.resize(len, 0);
+            let n = syscall!(recv(
+                fd,
+                data.as_mut_ptr().cast(),
+                len,
+                libc::MSG_PEEK | libc::MSG_DONTWAIT
+            ))?;
+            data.truncate(n as usize);
+        }
+    }
+    setsockopt(fd, libc::IPPROTO_TCP, libc::TCP_REPAIR_QUEUE, TCP_NO_QUEUE)?;
+    let [(_, recv_seq, recv_queue), (_, send_seq, send_queue)] = queues;
+
+    let info = tcp_info(socket)?;
+    let window_scale = (info.tcpi_options & TCPI_OPT_WSCALE != 0).then(|| {
+        // `tcpi_snd_wscale : 4, tcpi_rcv_wscale : 4` bitfields.
+        let wscale = info.tcpi_snd_rcv_wscale;
+        if cfg!(target_endian = "little") {
+            (wscale & 0xf, wscale >> 4)
+        } else {
+            (wscale >> 4, wscale & 0xf)
+        }
+    });
+    let timestamp = if info.tcpi_options & TCPI_OPT_TIMESTAMPS != 0 {
+        Some(getsockopt::<u32>(
+            fd,
+            libc::IPPROTO_TCP,
+            libc::TCP_TIMESTAMP,
+        )?)
+    } else {
+        None
+    };
+
+    Ok(TcpRepairState {
+        local_addr: socket.local_addr()?,
+        peer_addr: socket.peer_addr()?,
+        send_seq,
+        recv_seq,
+        send_queue,
+        recv_queue,
+        mss: getsockopt::<libc::c_int>(fd, libc::IPPROTO_TCP, libc::TCP_MAXSEG)? as u32,
+        window_scale,
+        sack: info.tcpi_options & TCPI_OPT_SACK != 0,
+        timestamp,
+    })
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn repair_restore(state: &TcpRepairState) -> io::Result<net::TcpStream> {
+    let socket = new_for_addr(state.peer_addr)?;
+    // SAFETY: `new_for_addr` returns a valid socket.
+    let socket = unsafe { net::TcpStream::from_raw_fd(socket) };
+    let fd = socket.as_raw_fd();
+    setsockopt(fd, libc::IPPROTO_TCP, libc::TCP_REPAIR, 1 as libc::c_int)?;
+
+    // The sequence numbers point to the start of the queues, writing the
+    // queues below advances them again.
+    let queues = [
+        (TCP_RECV_QUEUE, state.recv_seq, &state.recv_queue),
+        (TCP_SEND_QUEUE, state.send_seq, &state.send_queue),
+    ];
+    for (queue, seq, data) in queues {
+        setsockopt(fd, libc::IPPROTO_TCP, libc::TCP_REPAIR_QUEUE, queue)?;
+        let seq = seq.wrapping_sub(data.len() as u32);
+        setsockopt(fd, libc::IPPROTO_TCP, libc::TCP_QUEUE_SEQ, seq)?;
+    }
+
+    // In repair mode connecting doesn't send anything, it puts the socket in
+    // the established state right away.
+    setsockopt(fd, libc::SOL_SOCKET, libc::SO_REUSEADDR, 1 as libc::c_int)?;
+    let (raw_addr, raw_addr_length) = socket_addr(&state.local_addr);
+    syscall!(bind(fd, raw_addr.as_ptr(), raw_addr_length))?;
+    connect(&socket, state.peer_addr)?;
+
+    let mut options = vec![TcpRepairOpt {
+        opt_code: TCPOPT_MAXSEG,
+        opt_val: state.mss,
+    }];
+    if let Some((send, recv)) = state.window_scale {
+        options.push(TcpRepairOpt {
+            opt_code: TCPOPT_WINDOW,
+            opt_val: u32::from(send) | (u32::from(recv) << 16),
+        });
+    }
+    if state.sack {
+        options.push(TcpRepairOpt {
+            opt_code: TCPOPT_SACK_PERM,
+            opt_val: 0,
+        });
+    }
+    if let Some(timestamp) = state.timestamp {
+        options.push(TcpRepairOpt {
+            opt_code: TCPOPT_TIMESTAMP,
+            opt_val: 0,
+        });
+        setsockopt(fd, libc::IPPROTO_TCP, libc::TCP_TIMESTAMP, timestamp)?;
+    }
+    syscall!(setsockopt(
+        fd,
+        libc::IPPROTO_TCP,
+        libc::TCP_REPAIR_OPTIONS,
+        options.as_ptr().cast(),
+        (options.len() * size_of::<TcpRepairOpt>()) as libc::socklen_t,
+    ))?;
+
+    for (queue, _, data) in queues {
+        setsockopt(fd, libc::IPPROTO_TCP, libc::TCP_REPAIR_QUEUE, queue)?;
+        let mut data = &data[..];
+        while !data.is_empty() {
+            let n = syscall!(send(
+                fd,
+                data.as_ptr().cast(),
+                data.len(),
+                libc::MSG_NOSIGNAL
+            ))?;
+            data = &data[n as usize..];
+        }
+    }
+    setsockopt(fd, libc::IPPROTO_TCP, libc::TCP_REPAIR_QUEUE, TCP_NO_QUEUE)?;
+
+    // Leaving repair mode sends a window probe, to get the current window of
+    // the peer.
+    setsockopt(fd, libc::IPPROTO_TCP, libc::TCP_REPAIR, 0 as libc::c_int)?;
+    Ok(socket)
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn send_file(
+    socket: &net::TcpStream,
+    file: RawFd,
+    offset: u64,
+    count: usize,
+) -> io::Result<usize> {
+    let mut offset = file_offset(offset)?;
+    syscall!(sendfile(socket.as_raw_fd(), file, &mut offset, count)).map(|n| n as usize)
+}
+
+#[cfg(any(target_os = "dragonfly", target_os = "freebsd"))]
+pub(crate) fn send_file(
+    socket: &net::TcpStream,
+    file: RawFd,
+    offset: u64,
+    count: usize,
+) -> io::Result<usize> {
+    // A `count` of zero means sending until the end of the file.
+    if count == 0 {
+        return Ok(0);
+    }
+    let offset = file_offset(offset)?;
+    let mut sent: libc::off_t = 0;
+    let res = syscall!(sendfile(
+        file,
+        socket.as_raw_fd(),
+        offset,
+        count,
+        std::ptr::null_mut(),
+        &mut sent,
+        0,
+    ));
+    partial_send_file(res, sent)
+}
+
+#[cfg(any(
+    target_os = "ios",
+    target_os = "macos",
+    target_os = "tvos",
+    target_os = "visionos",
+    target_os = "watchos",
+))]
+pub(crate) fn send_file(
+    socket: &net::TcpStream,
+    file: RawFd,
+    offset: u64,
+    count: usize,
+) -> io::Result<usize> {
+    // A `count` (length) of zero means sending until the end of the file.
+    if count == 0 {
+        return Ok(0);
+    }
+    let offset = file_offset(offset)?;
+    // Input is the number of bytes to send, output the number of bytes sent.
+    let mut sent: libc::off_t = count.try_into().unwrap_or(libc::off_t::MAX);
+    let res = syscall!(sendfile(
+        file,
+        socket.as_raw_fd(),
+        offset,
+        &mut sent,
+        std::ptr::null_mut(),
+        0,
+    ));
+    partial_send_file(res, sent)
+}
+
+/// Converts `offset` into an `off_t`.
+#[cfg(any(
+    target_os = "android",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "ios",
+    target_os = "linux",
+    target_os = "macos",
+    target_os = "tvos",
+    target_os = "visionos",
+    target_os = "watchos",
+))]
+fn file_offset(offset: u64) -> io::Result<libc::off_t> {
+    offset
+        .try_into()
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "file offset too large"))
+}
+
+/// On the BSDs `sendfile(2)` returns an error (e.g. `EAGAIN`) if it was
+/// interrupted after sending some data, `sent` is the amount of bytes sent in
+/// that case.
+#[cfg(any(
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "ios",
+    target_os = "macos",
+    target_os = "tvos",
+    target_os = "visionos",
+    target_os = "watchos",
+))]
+fn partial_send_file(res: io::Result<libc::c_int>, sent: libc::off_t) -> io::Result<usize> {
+    match res {
+        Ok(_) => Ok(sent as usize),
+        Err(_) if sent > 0 => Ok(sent as usize),
+        Err(err) => Err(err),
+    }
+}
+
 pub(crate) fn accept(listener: &net::TcpListener) -> io::Result<(net::TcpStream, SocketAddr)> {
     let mut addr: MaybeUninit<libc::sockaddr_storage> = MaybeUninit::uninit();
     let mut length = size_of::<libc::sockaddr_storage>() as libc::socklen_t;
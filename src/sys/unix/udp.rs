@@ -1,17 +1,53 @@
 use std::io;
 use std::mem;
-use std::net::{self, SocketAddr};
+use std::net::{self, Ipv6Addr, SocketAddr};
+#[cfg(any(target_os = "android", target_os = "linux"))]
+use std::net::Ipv4Addr;
 #[cfg(not(target_os = "hermit"))]
 use std::os::fd::{AsRawFd, FromRawFd};
 // TODO: once <https://github.com/rust-lang/rust/issues/126198> is fixed this
 // can use `std::os::fd` and be merged with the above.
 #[cfg(target_os = "hermit")]
 use std::os::hermit::io::{AsRawFd, FromRawFd};
+#[cfg(any(target_os = "android", target_os = "linux"))]
+use std::os::fd::BorrowedFd;
+#[cfg(any(target_os = "android", target_os = "linux"))]
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+#[cfg(unix)]
+use std::mem::MaybeUninit;
+#[cfg(any(target_os = "android", target_os = "linux"))]
+use std::ptr;
 
-use crate::sys::unix::net::{new_ip_socket, socket_addr};
+#[cfg(any(target_os = "android", target_os = "linux"))]
+use crate::net::{MtuDiscover, RecvInfo, SockFilter, TimestampMode};
+
+#[cfg(unix)]
+pub(crate) use crate::sys::unix::net::{hop_limit, set_hop_limit};
+use crate::sys::unix::net::{new_ip_socket, new_socket, setsockopt, socket_addr};
+#[cfg(unix)]
+use crate::net::TruncationPolicy;
+#[cfg(unix)]
+use crate::sys::unix::net::getsockopt;
+#[cfg(unix)]
+use crate::sys::unix::net::recv_with_truncation as recv_truncation;
+#[cfg(unix)]
+use crate::sys::unix::net::to_socket_addr;
 
 pub fn bind(addr: SocketAddr) -> io::Result<net::UdpSocket> {
-    let fd = new_ip_socket(addr, libc::SOCK_DGRAM)?;
+    bind_with_protocol(addr, 0)
+}
+
+pub(crate) fn new_unbound(ipv6: bool) -> io::Result<net::UdpSocket> {
+    let domain = if ipv6 { libc::AF_INET6 } else { libc::AF_INET };
+    let fd = new_socket(domain, libc::SOCK_DGRAM)?;
+    Ok(unsafe { net::UdpSocket::from_raw_fd(fd) })
+}
+
+pub(crate) fn bind_with_protocol(
+    addr: SocketAddr,
+    protocol: libc::c_int,
+) -> io::Result<net::UdpSocket> {
+    let fd = new_ip_socket(addr, libc::SOCK_DGRAM, protocol)?;
     let socket = unsafe { net::UdpSocket::from_raw_fd(fd) };
 
     let (raw_addr, raw_addr_length) = socket_addr(&addr);
@@ -20,6 +56,64 @@ pub fn bind(addr: SocketAddr) -> io::Result<net::UdpSocket> {
     Ok(socket)
 }
 
+#[cfg(unix)]
+pub(crate) fn bind_dual_stack(port: u16) -> io::Result<net::UdpSocket> {
+    let addr = SocketAddr::new(Ipv6Addr::UNSPECIFIED.into(), port);
+    let fd = new_ip_socket(addr, libc::SOCK_DGRAM, 0).map_err(dual_stack_error)?;
+    let socket = unsafe { net::UdpSocket::from_raw_fd(fd) };
+
+    // `IPV6_V6ONLY` must be set before binding. Some platforms, e.g.
+    // OpenBSD, don't allow it to be disabled at all.
+    setsockopt(fd, libc::IPPROTO_IPV6, libc::IPV6_V6ONLY, 0 as libc::c_int)
+        .map_err(dual_stack_error)?;
+
+    let (raw_addr, raw_addr_length) = socket_addr(&addr);
+    syscall!(bind(fd, raw_addr.as_ptr(), raw_addr_length))?;
+
+    Ok(socket)
+}
+
+/// Maps the errors returned when the OS doesn't support dual-stack sockets to
+/// a clearer error.
+#[cfg(unix)]
+fn dual_stack_error(err: io::Error) -> io::Error {
+    match err.raw_os_error() {
+        Some(libc::EAFNOSUPPORT | libc::EINVAL | libc::ENOPROTOOPT) => io::Error::new(
+            io::ErrorKind::Unsupported,
+            format!("dual-stack UDP sockets are not supported on this platform: {err}"),
+        ),
+        _ => err,
+    }
+}
+
+#[cfg(any(
+    target_os = "android",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "fuchsia",
+    target_os = "ios",
+    target_os = "linux",
+    target_os = "macos",
+    target_os = "netbsd",
+    target_os = "openbsd",
+    target_os = "tvos",
+    target_os = "visionos",
+    target_os = "watchos",
+))]
+pub(crate) fn bind_reuseport(addr: SocketAddr) -> io::Result<net::UdpSocket> {
+    let fd = new_ip_socket(addr, libc::SOCK_DGRAM, 0)?;
+    let socket = unsafe { net::UdpSocket::from_raw_fd(fd) };
+
+    // `SO_REUSEPORT` must be set before binding, otherwise the kernel won't
+    // add the socket to the group of sockets bound to `addr`.
+    setsockopt(fd, libc::SOL_SOCKET, libc::SO_REUSEPORT, 1 as libc::c_int)?;
+
+    let (raw_addr, raw_addr_length) = socket_addr(&addr);
+    syscall!(bind(fd, raw_addr.as_ptr(), raw_addr_length))?;
+
+    Ok(socket)
+}
+
 pub(crate) fn only_v6(socket: &net::UdpSocket) -> io::Result<bool> {
     let mut optval: libc::c_int = 0;
     let mut optlen = mem::size_of::<libc::c_int>() as libc::socklen_t;
@@ -34,3 +128,405 @@ pub(crate) fn only_v6(socket: &net::UdpSocket) -> io::Result<bool> {
 
     Ok(optval != 0)
 }
+
+#[cfg(unix)]
+pub(crate) fn set_default_scope_id(socket: &net::UdpSocket, scope_id: u32) -> io::Result<()> {
+    let fd = socket.as_raw_fd();
+    setsockopt(
+        fd,
+        libc::IPPROTO_IPV6,
+        libc::IPV6_MULTICAST_IF,
+        scope_id,
+    )?;
+    // Unlike `IPV6_MULTICAST_IF`, `IPV6_UNICAST_IF` expects the interface
+    // index in network byte order.
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    setsockopt(
+        fd,
+        libc::IPPROTO_IPV6,
+        libc::IPV6_UNICAST_IF,
+        scope_id.to_be() as libc::c_int,
+    )?;
+    Ok(())
+}
+
+#[cfg(unix)]
+pub(crate) fn default_scope_id(socket: &net::UdpSocket) -> io::Result<u32> {
+    getsockopt::<libc::c_uint>(socket.as_raw_fd(), libc::IPPROTO_IPV6, libc::IPV6_MULTICAST_IF)
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn set_ip_transparent(socket: &net::UdpSocket, on: bool) -> io::Result<()> {
+    setsockopt(
+        socket.as_raw_fd(),
+        libc::SOL_IP,
+        libc::IP_TRANSPARENT,
+        libc::c_int::from(on),
+    )
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn set_priority(socket: &net::UdpSocket, priority: u32) -> io::Result<()> {
+    let priority: libc::c_int = priority.try_into().unwrap_or(libc::c_int::MAX);
+    setsockopt(socket.as_raw_fd(), libc::SOL_SOCKET, libc::SO_PRIORITY, priority)
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn priority(socket: &net::UdpSocket) -> io::Result<u32> {
+    getsockopt::<libc::c_int>(socket.as_raw_fd(), libc::SOL_SOCKET, libc::SO_PRIORITY)
+        .map(|priority| priority as u32)
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn set_dontfrag(socket: &net::UdpSocket, dontfrag: bool) -> io::Result<()> {
+    let mode = if dontfrag {
+        MtuDiscover::Do
+    } else {
+        MtuDiscover::Want
+    };
+    set_mtu_discover(socket, mode)
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn set_mtu_discover(socket: &net::UdpSocket, mode: MtuDiscover) -> io::Result<()> {
+    let fd = socket.as_raw_fd();
+    if socket.local_addr()?.is_ipv6() {
+        let value = match mode {
+            MtuDiscover::Want => libc::IPV6_PMTUDISC_WANT,
+            MtuDiscover::Dont => libc::IPV6_PMTUDISC_DONT,
+            MtuDiscover::Do => libc::IPV6_PMTUDISC_DO,
+            MtuDiscover::Probe => libc::IPV6_PMTUDISC_PROBE,
+        };
+        setsockopt(fd, libc::IPPROTO_IPV6, libc::IPV6_MTU_DISCOVER, value)?;
+        if only_v6(socket)? {
+            return Ok(());
+        }
+    }
+    // Also used for IPv4 traffic on dual-stack IPv6 sockets.
+    let value = match mode {
+        MtuDiscover::Want => libc::IP_PMTUDISC_WANT,
+        MtuDiscover::Dont => libc::IP_PMTUDISC_DONT,
+        MtuDiscover::Do => libc::IP_PMTUDISC_DO,
+        MtuDiscover::Probe => libc::IP_PMTUDISC_PROBE,
+    };
+    setsockopt(fd, libc::IPPROTO_IP, libc::IP_MTU_DISCOVER, value)
+}
+
+#[cfg(any(
+    target_os = "freebsd",
+    target_os = "ios",
+    target_os = "macos",
+    target_os = "tvos",
+    target_os = "visionos",
+    target_os = "watchos",
+))]
+pub(crate) fn set_dontfrag(socket: &net::UdpSocket, dontfrag: bool) -> io::Result<()> {
+    let fd = socket.as_raw_fd();
+    let value = libc::c_int::from(dontfrag);
+    if socket.local_addr()?.is_ipv6() {
+        setsockopt(fd, libc::IPPROTO_IPV6, libc::IPV6_DONTFRAG, value)
+    } else {
+        setsockopt(fd, libc::IPPROTO_IP, libc::IP_DONTFRAG, value)
+    }
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn path_mtu(socket: &net::UdpSocket) -> io::Result<u32> {
+    let fd = socket.as_raw_fd();
+    let mtu: libc::c_int = if socket.local_addr()?.is_ipv6() {
+        getsockopt(fd, libc::IPPROTO_IPV6, libc::IPV6_MTU)?
+    } else {
+        getsockopt(fd, libc::IPPROTO_IP, libc::IP_MTU)?
+    };
+    Ok(mtu as u32)
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn set_timestamping(socket: &net::UdpSocket, mode: TimestampMode) -> io::Result<()> {
+    let fd = socket.as_raw_fd();
+    // Only enable one of the two options, otherwise we would receive two
+    // control messages per datagram.
+    let (nanos, timestamping) = match mode {
+        TimestampMode::Disabled => (0, 0),
+        TimestampMode::Software => (1, 0),
+        TimestampMode::Hardware => (
+            0,
+            libc::SOF_TIMESTAMPING_RX_HARDWARE
+                | libc::SOF_TIMESTAMPING_RAW_HARDWARE
+                | libc::SOF_TIMESTAMPING_RX_SOFTWARE
+                | libc::SOF_TIMESTAMPING_SOFTWARE,
+        ),
+    };
+    setsockopt(
+        fd,
+        libc::SOL_SOCKET,
+        libc::SO_TIMESTAMPING,
+        timestamping as libc::c_int,
+    )?;
+    setsockopt(
+        fd,
+        libc::SOL_SOCKET,
+        libc::SO_TIMESTAMPNS,
+        nanos as libc::c_int,
+    )
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn attach_reuseport_cbpf(
+    socket: &net::UdpSocket,
+    program: &[SockFilter],
+) -> io::Result<()> {
+    let len = libc::c_ushort::try_from(program.len()).map_err(|_| {
+        io::Error::new(io::ErrorKind::InvalidInput, "BPF program is too long")
+    })?;
+    let program = libc::sock_fprog {
+        len,
+        // The kernel doesn't modify the program. `SockFilter` has the same
+        // layout as `sock_filter` due to `repr(C)`.
+        filter: program.as_ptr().cast_mut().cast(),
+    };
+    setsockopt(
+        socket.as_raw_fd(),
+        libc::SOL_SOCKET,
+        libc::SO_ATTACH_REUSEPORT_CBPF,
+        program,
+    )
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn attach_reuseport_ebpf(
+    socket: &net::UdpSocket,
+    program: BorrowedFd<'_>,
+) -> io::Result<()> {
+    setsockopt(
+        socket.as_raw_fd(),
+        libc::SOL_SOCKET,
+        libc::SO_ATTACH_REUSEPORT_EBPF,
+        program.as_raw_fd(),
+    )
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn recv_from_with_timestamp(
+    socket: &net::UdpSocket,
+    buf: &mut [u8],
+) -> io::Result<(usize, SocketAddr, Option<SystemTime>)> {
+    let mut addr: MaybeUninit<libc::sockaddr_storage> = MaybeUninit::uninit();
+    let mut iov = libc::iovec {
+        iov_base: buf.as_mut_ptr().cast(),
+        iov_len: buf.len(),
+    };
+    // Large enough to hold a `SCM_TIMESTAMPING` message (three `timespec`s),
+    // using `u64` to get the alignment required for `cmsghdr`.
+    let mut control = [0u64; 16];
+
+    // SAFETY: all zeroes is valid for `msghdr`, it also takes care of the
+    // private padding fields in some libc implementations.
+    let mut msg: libc::msghdr = unsafe { mem::zeroed() };
+    msg.msg_name = addr.as_mut_ptr().cast();
+    msg.msg_namelen = mem::size_of::<libc::sockaddr_storage>() as libc::socklen_t;
+    msg.msg_iov = &mut iov;
+    msg.msg_iovlen = 1;
+    msg.msg_control = control.as_mut_ptr().cast();
+    msg.msg_controllen = mem::size_of_val(&control) as _;
+
+    let n = syscall!(recvmsg(socket.as_raw_fd(), &mut msg, 0))?;
+    // SAFETY: `recvmsg(2)` initialised the address for us.
+    let addr = unsafe { to_socket_addr(addr.as_ptr())? };
+
+    let mut timestamp = None;
+    // SAFETY: the kernel wrote `msg_controllen` bytes of valid control
+    // messages, which the `CMSG_*` macros stay within.
+    unsafe {
+        let mut cmsg = libc::CMSG_FIRSTHDR(&msg);
+        while !cmsg.is_null() {
+            if (*cmsg).cmsg_level == libc::SOL_SOCKET {
+                let data = libc::CMSG_DATA(cmsg);
+                match (*cmsg).cmsg_type {
+                    libc::SCM_TIMESTAMPNS => {
+                        let ts: libc::timespec = ptr::read_unaligned(data.cast());
+                        timestamp = to_system_time(ts);
+                    }
+                    libc::SCM_TIMESTAMPING => {
+                        // Software, deprecated and raw hardware timestamp, in
+                        // that order. Prefer the hardware timestamp if the
+                        // NIC provided one.
+                        let ts: [libc::timespec; 3] = ptr::read_unaligned(data.cast());
+                        timestamp = to_system_time(ts[2]).or_else(|| to_system_time(ts[0]));
+                    }
+                    _ => {}
+                }
+            }
+            cmsg = libc::CMSG_NXTHDR(&msg, cmsg);
+        }
+    }
+
+    Ok((n as usize, addr, timestamp))
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn set_recv_pktinfo(socket: &net::UdpSocket, enable: bool) -> io::Result<()> {
+    let fd = socket.as_raw_fd();
+    let value = libc::c_int::from(enable);
+    if socket.local_addr()?.is_ipv6() {
+        setsockopt(fd, libc::IPPROTO_IPV6, libc::IPV6_RECVPKTINFO, value)?;
+        if only_v6(socket)? {
+            return Ok(());
+        }
+    }
+    // Also used for IPv4 traffic on dual-stack IPv6 sockets.
+    setsockopt(fd, libc::IPPROTO_IP, libc::IP_PKTINFO, value)
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn recv_pktinfo(socket: &net::UdpSocket) -> io::Result<bool> {
+    let (level, name) = if socket.local_addr()?.is_ipv6() {
+        (libc::IPPROTO_IPV6, libc::IPV6_RECVPKTINFO)
+    } else {
+        (libc::IPPROTO_IP, libc::IP_PKTINFO)
+    };
+    getsockopt::<libc::c_int>(socket.as_raw_fd(), level, name).map(|value| value != 0)
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn recv_from_with_info(
+    socket: &net::UdpSocket,
+    buf: &mut [u8],
+) -> io::Result<(usize, SocketAddr, RecvInfo)> {
+    let mut addr: MaybeUninit<libc::sockaddr_storage> = MaybeUninit::uninit();
+    let mut iov = libc::iovec {
+        iov_base: buf.as_mut_ptr().cast(),
+        iov_len: buf.len(),
+    };
+    // Large enough to hold a `IPV6_PKTINFO` message, and other messages such
+    // as timestamps that might be enabled, using `u64` to get the alignment
+    // required for `cmsghdr`.
+    let mut control = [0u64; 16];
+
+    // SAFETY: all zeroes is valid for `msghdr`, it also takes care of the
+    // private padding fields in some libc implementations.
+    let mut msg: libc::msghdr = unsafe { mem::zeroed() };
+    msg.msg_name = addr.as_mut_ptr().cast();
+    msg.msg_namelen = mem::size_of::<libc::sockaddr_storage>() as libc::socklen_t;
+    msg.msg_iov = &mut iov;
+    msg.msg_iovlen = 1;
+    msg.msg_control = control.as_mut_ptr().cast();
+    msg.msg_controllen = mem::size_of_val(&control) as _;
+
+    let n = syscall!(recvmsg(socket.as_raw_fd(), &mut msg, 0))?;
+    // SAFETY: `recvmsg(2)` initialised the address for us.
+    let addr = unsafe { to_socket_addr(addr.as_ptr())? };
+
+    let mut info = RecvInfo {
+        interface_index: None,
+        destination: None,
+    };
+    // SAFETY: the kernel wrote `msg_controllen` bytes of valid control
+    // messages, which the `CMSG_*` macros stay within.
+    #[allow(clippy::unnecessary_cast)] // The interface index is a `c_int` on Android.
+    unsafe {
+        let mut cmsg = libc::CMSG_FIRSTHDR(&msg);
+        while !cmsg.is_null() {
+            let data = libc::CMSG_DATA(cmsg);
+            match ((*cmsg).cmsg_level, (*cmsg).cmsg_type) {
+                (libc::IPPROTO_IP, libc::IP_PKTINFO) => {
+                    let pktinfo: libc::in_pktinfo = ptr::read_unaligned(data.cast());
+                    info.interface_index = Some(pktinfo.ipi_ifindex as u32);
+                    let ip = u32::from_be(pktinfo.ipi_addr.s_addr);
+                    info.destination = Some(Ipv4Addr::from(ip).into());
+                }
+                (libc::IPPROTO_IPV6, libc::IPV6_PKTINFO) => {
+                    let pktinfo: libc::in6_pktinfo = ptr::read_unaligned(data.cast());
+                    info.interface_index = Some(pktinfo.ipi6_ifindex as u32);
+                    info.destination = Some(Ipv6Addr::from(pktinfo.ipi6_addr.s6_addr).into());
+                }
+                _ => {}
+            }
+            cmsg = libc::CMSG_NXTHDR(&msg, cmsg);
+        }
+    }
+
+    Ok((n as usize, addr, info))
+}
+
+/// Returns `None` for a zeroed `timespec`, which the kernel uses to indicate
+/// a missing timestamp.
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn to_system_time(ts: libc::timespec) -> Option<SystemTime> {
+    if ts.tv_sec == 0 && ts.tv_nsec == 0 {
+        None
+    } else {
+        Some(UNIX_EPOCH + Duration::new(ts.tv_sec as u64, ts.tv_nsec as u32))
+    }
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn recv_batch(
+    socket: &net::UdpSocket,
+    buffers: &mut [Vec<u8>],
+) -> io::Result<Vec<(usize, SocketAddr)>> {
+    let mut addrs: Vec<MaybeUninit<libc::sockaddr_storage>> =
+        buffers.iter().map(|_| MaybeUninit::uninit()).collect();
+    let mut iovs: Vec<libc::iovec> = buffers
+        .iter_mut()
+        .map(|buf| libc::iovec {
+            iov_base: buf.as_mut_ptr().cast(),
+            iov_len: buf.len(),
+        })
+        .collect();
+    let mut msgs: Vec<libc::mmsghdr> = iovs
+        .iter_mut()
+        .zip(addrs.iter_mut())
+        .map(|(iov, addr)| {
+            // SAFETY: all zeroes is valid for `mmsghdr`.
+            let mut msg: libc::mmsghdr = unsafe { mem::zeroed() };
+            msg.msg_hdr.msg_name = addr.as_mut_ptr().cast();
+            msg.msg_hdr.msg_namelen = mem::size_of::<libc::sockaddr_storage>() as libc::socklen_t;
+            msg.msg_hdr.msg_iov = iov;
+            msg.msg_hdr.msg_iovlen = 1;
+            msg
+        })
+        .collect();
+
+    let vlen = msgs.len().min(libc::c_uint::MAX as usize);
+    // The socket is non-blocking, so this never blocks for the first datagram,
+    // but `MSG_WAITFORONE` makes explicit that we only want the datagrams that
+    // are immediately available after it.
+    let n = syscall!(recvmmsg(
+        socket.as_raw_fd(),
+        msgs.as_mut_ptr(),
+        vlen as _,
+        libc::MSG_WAITFORONE as _,
+        ptr::null_mut(),
+    ))?;
+
+    msgs[..n as usize]
+        .iter()
+        .zip(addrs.iter())
+        // SAFETY: `recvmmsg(2)` initialised the addresses of the received
+        // messages for us.
+        .map(|(msg, addr)| Ok((msg.msg_len as usize, unsafe { to_socket_addr(addr.as_ptr())? })))
+        .collect()
+}
+
+#[cfg(unix)]
+pub(crate) fn recv_from_with_truncation(
+    socket: &net::UdpSocket,
+    buf: &mut [u8],
+    policy: TruncationPolicy,
+) -> io::Result<(usize, SocketAddr)> {
+    let mut addr: MaybeUninit<libc::sockaddr_storage> = MaybeUninit::uninit();
+    let n = recv_truncation(socket.as_raw_fd(), buf, Some(&mut addr), policy)?;
+    // SAFETY: `recvmsg(2)` initialised the address for us.
+    let addr = unsafe { to_socket_addr(addr.as_ptr())? };
+    Ok((n, addr))
+}
+
+#[cfg(unix)]
+pub(crate) fn recv_with_truncation(
+    socket: &net::UdpSocket,
+    buf: &mut [u8],
+    policy: TruncationPolicy,
+) -> io::Result<usize> {
+    recv_truncation(socket.as_raw_fd(), buf, None, policy)
+}
@@ -2,7 +2,8 @@ use std::io;
 use std::os::fd::{AsRawFd, FromRawFd};
 use std::os::unix::net::{self, SocketAddr};
 
-use crate::sys::unix::net::new_socket;
+use crate::net::TruncationPolicy;
+use crate::sys::unix::net::{new_socket, recv_with_truncation as recv_truncation};
 use crate::sys::unix::uds::unix_addr;
 
 pub(crate) fn bind_addr(address: &SocketAddr) -> io::Result<net::UnixDatagram> {
@@ -23,3 +24,11 @@ pub(crate) fn unbound() -> io::Result<net::UnixDatagram> {
 pub(crate) fn pair() -> io::Result<(net::UnixDatagram, net::UnixDatagram)> {
     super::pair(libc::SOCK_DGRAM)
 }
+
+pub(crate) fn recv_with_truncation(
+    socket: &net::UnixDatagram,
+    buf: &mut [u8],
+    policy: TruncationPolicy,
+) -> io::Result<usize> {
+    recv_truncation(socket.as_raw_fd(), buf, None, policy)
+}
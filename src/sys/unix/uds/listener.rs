@@ -1,12 +1,12 @@
 use std::ffi::OsStr;
-use std::os::fd::{AsRawFd, FromRawFd};
+use std::os::fd::{AsRawFd, FromRawFd, RawFd};
 use std::os::unix::ffi::OsStrExt;
 use std::os::unix::net::{self, SocketAddr};
 use std::path::Path;
 use std::{io, mem};
 
 use crate::net::UnixStream;
-use crate::sys::unix::net::new_socket;
+use crate::sys::unix::net::{new_socket, prepare_inherited_listener, socket_family};
 use crate::sys::unix::uds::{path_offset, unix_addr};
 
 pub(crate) fn bind_addr(address: &SocketAddr) -> io::Result<net::UnixListener> {
@@ -119,3 +119,11 @@ pub(crate) fn accept(listener: &net::UnixListener) -> io::Result<(UnixStream, So
     let address = SocketAddr::from_pathname(Path::new(OsStr::from_bytes(path)))?;
     Ok((socket, address))
 }
+
+pub(crate) fn prepare_inherited(listener: &net::UnixListener) -> io::Result<()> {
+    prepare_inherited_listener(listener.as_raw_fd(), &[libc::AF_UNIX])
+}
+
+pub(crate) fn is_unix_socket(fd: RawFd) -> io::Result<bool> {
+    socket_family(fd).map(|family| family == libc::AF_UNIX)
+}
@@ -22,7 +22,6 @@ pub(crate) struct Waker {
 }
 
 impl Waker {
-    #[allow(dead_code)] // Not used by the `poll(2)` implementation.
     pub(crate) fn new(selector: &Selector, token: Token) -> io::Result<Waker> {
         let waker = Waker::new_unregistered()?;
         selector.register(waker.fd.as_raw_fd(), token, Interest::READABLE)?;
@@ -62,14 +61,44 @@ impl Waker {
         }
     }
 
+    /// Same as `wake`, but async-signal-safe.
+    ///
+    /// This must only do a single `write(2)` of 8 bytes, which is
+    /// async-signal-safe. It doesn't allocate, lock or retry. On illumos the
+    /// eventfd is read once before, see `wake`.
+    #[cfg(unix)]
+    pub(crate) fn wake_from_signal(&self) -> io::Result<()> {
+        // See `wake`, `read(2)` is also async-signal-safe.
+        #[cfg(target_os = "illumos")]
+        self.reset()?;
+
+        let buf: [u8; 8] = 1u64.to_ne_bytes();
+        match syscall!(write(self.fd.as_raw_fd(), buf.as_ptr().cast(), buf.len())) {
+            Ok(_) => Ok(()),
+            // The counter is about to overflow, which means the eventfd is
+            // already readable, so there is no need to reset it.
+            Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => Ok(()),
+            Err(err) => Err(err),
+        }
+    }
+
     #[allow(dead_code)] // Only used by the `poll(2)` implementation.
     pub(crate) fn ack_and_reset(&self) {
         let _ = self.reset();
     }
 
-    /// Reset the eventfd object, only need to call this if `wake` fails.
+    /// Reset the waker and register it again, used by `Poll::poll_cancellable`.
+    ///
+    /// The `poll(2)` implementation removes the interest in the file descriptor
+    /// once an event is returned, so it has to be registered again.
+    pub(crate) fn rearm(&self, selector: &Selector, token: Token) -> io::Result<()> {
+        self.reset()?;
+        selector.reregister(self.fd.as_raw_fd(), token, Interest::READABLE)
+    }
+
+    /// Reset the eventfd object, setting the counter to zero.
     #[allow(clippy::unused_io_amount)] // Don't care about partial reads.
-    fn reset(&self) -> io::Result<()> {
+    pub(crate) fn reset(&self) -> io::Result<()> {
         let mut buf: [u8; 8] = 0u64.to_ne_bytes();
         match (&self.fd).read(&mut buf) {
             Ok(_) => Ok(()),
@@ -1,28 +1,60 @@
 use std::io;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 use crate::sys::Selector;
 use crate::Token;
 
+/// Unique identifier for the user space notification of each `Waker`.
+static NEXT_IDENT: AtomicUsize = AtomicUsize::new(0);
+
 /// Waker backed by kqueue user space notifications (`EVFILT_USER`).
 ///
 /// The implementation is fairly simple, first the kqueue must be setup to
 /// receive waker events this done by calling `Selector.setup_waker`. Next
 /// we need access to kqueue, thus we need to duplicate the file descriptor.
 /// Now waking is as simple as adding an event to the kqueue.
+///
+/// Each waker uses its own identifier for the notification, so that multiple
+/// wakers, e.g. a `Waker` and the waker of `Poll::cancel_notifier`, can be
+/// used with the same kqueue without overwriting each other's token.
 #[derive(Debug)]
 pub(crate) struct Waker {
     selector: Selector,
+    ident: usize,
     token: Token,
 }
 
 impl Waker {
     pub(crate) fn new(selector: &Selector, token: Token) -> io::Result<Waker> {
         let selector = selector.try_clone()?;
-        selector.setup_waker(token)?;
-        Ok(Waker { selector, token })
+        let ident = NEXT_IDENT.fetch_add(1, Ordering::Relaxed);
+        selector.setup_waker(ident, token)?;
+        Ok(Waker {
+            selector,
+            ident,
+            token,
+        })
     }
 
     pub(crate) fn wake(&self) -> io::Result<()> {
-        self.selector.wake(self.token)
+        self.selector.wake(self.ident, self.token)
+    }
+
+    /// Same as `wake`, but async-signal-safe.
+    ///
+    /// `wake` does a single `kevent(2)` call using only stack memory, which is
+    /// safe to call from a signal handler.
+    pub(crate) fn wake_from_signal(&self) -> io::Result<()> {
+        self.wake()
+    }
+
+    pub(crate) fn reset(&self) -> io::Result<()> {
+        self.selector.reset_waker(self.ident, self.token)
+    }
+
+    /// Same as `reset`, the user space notification doesn't have to be
+    /// registered again.
+    pub(crate) fn rearm(&self, _: &Selector, _: Token) -> io::Result<()> {
+        self.reset()
     }
 }
@@ -22,7 +22,6 @@ pub(crate) struct Waker {
 }
 
 impl Waker {
-    #[allow(dead_code)] // Not used by the `poll(2)` implementation.
     pub(crate) fn new(selector: &Selector, token: Token) -> io::Result<Waker> {
         let waker = Waker::new_unregistered()?;
         selector.register(waker.receiver.as_raw_fd(), token, Interest::READABLE)?;
@@ -57,11 +56,47 @@ impl Waker {
         }
     }
 
+    /// Same as `wake`, but async-signal-safe.
+    ///
+    /// This must only do a single `write(2)` of 1 byte, which is
+    /// async-signal-safe (and can't be a partial write). It doesn't allocate,
+    /// lock or retry. On illumos the pipe is emptied using `read(2)` before,
+    /// see `wake`.
+    #[cfg(unix)]
+    pub(crate) fn wake_from_signal(&self) -> io::Result<()> {
+        // See `wake`, `read(2)` is also async-signal-safe.
+        #[cfg(target_os = "illumos")]
+        self.empty();
+
+        match syscall!(write(self.sender.as_raw_fd(), [1u8].as_ptr().cast(), 1)) {
+            Ok(_) => Ok(()),
+            // The pipe is full, which means the reading end is already
+            // readable, so there is no need to empty it.
+            Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => Ok(()),
+            Err(err) => Err(err),
+        }
+    }
+
     #[allow(dead_code)] // Only used by the `poll(2)` implementation.
     pub(crate) fn ack_and_reset(&self) {
         self.empty();
     }
 
+    #[allow(dead_code)] // Not used by the `poll(2)` implementation.
+    pub(crate) fn reset(&self) -> io::Result<()> {
+        self.empty();
+        Ok(())
+    }
+
+    /// Reset the waker and register it again, used by `Poll::poll_cancellable`.
+    ///
+    /// The `poll(2)` implementation removes the interest in the file descriptor
+    /// once an event is returned, so it has to be registered again.
+    pub(crate) fn rearm(&self, selector: &Selector, token: Token) -> io::Result<()> {
+        self.empty();
+        selector.reregister(self.receiver.as_raw_fd(), token, Interest::READABLE)
+    }
+
     /// Empty the pipe's buffer, only need to call this if `wake` fails.
     /// This ignores any errors.
     fn empty(&self) {
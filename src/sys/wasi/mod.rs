@@ -21,7 +21,7 @@ use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
 #[cfg(feature = "net")]
-use crate::{Interest, Token};
+use crate::{Capabilities, Interest, Token};
 
 cfg_net! {
     pub(crate) mod tcp {
@@ -56,12 +56,27 @@ impl Selector {
         })
     }
 
+    pub(crate) fn reserve(&self, additional: usize) {
+        self.subscriptions.lock().unwrap().reserve(additional);
+    }
+
+    pub(crate) fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            // Timeouts are passed in nanoseconds.
+            precise_timeout: true,
+            exclusive: false,
+            oneshot: false,
+            priority_interest: false,
+            level_trigger: true,
+        }
+    }
+
     #[cfg(all(debug_assertions, feature = "net"))]
     pub(crate) fn id(&self) -> usize {
         self.id
     }
 
-    pub(crate) fn select(&self, events: &mut Events, timeout: Option<Duration>) -> io::Result<()> {
+    pub(crate) fn select(&self, events: &mut Events, timeout: Option<Duration>) -> io::Result<bool> {
         events.clear();
 
         let mut subscriptions = self.subscriptions.lock().unwrap();
@@ -104,13 +119,15 @@ impl Selector {
                 unsafe { events.set_len(n_events) };
 
                 // Remove the timeout event.
+                let mut timed_out = false;
                 if timeout.is_some() {
                     if let Some(index) = events.iter().position(is_timeout_event) {
                         events.swap_remove(index);
+                        timed_out = events.is_empty();
                     }
                 }
 
-                check_errors(&events)
+                check_errors(&events).map(|()| timed_out)
             }
             Err(err) => Err(io_err(err)),
         }
@@ -255,6 +272,16 @@ fn io_err(errno: wasi::Errno) -> io::Error {
 
 pub(crate) type Events = Vec<Event>;
 
+/// Converts an allocation of events into `Events`, see `Events::from_vec`.
+pub(crate) fn events_from_vec(events: Vec<Event>) -> Events {
+    events
+}
+
+/// Converts `Events` into its allocation, see `Events::into_vec`.
+pub(crate) fn events_into_vec(events: Events) -> Vec<Event> {
+    events
+}
+
 pub(crate) type Event = wasi::Event;
 
 pub(crate) mod event {
@@ -357,6 +384,9 @@ cfg_os_poll! {
                 IoSourceState
             }
 
+            /// The I/O source outlives the state, nothing to do.
+            pub(crate) fn detach(&mut self) {}
+
             pub(crate) fn do_io<T, F, R>(&self, f: F, io: &T) -> io::Result<R>
             where
                 F: FnOnce(&T) -> io::Result<R>,
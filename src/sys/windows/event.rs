@@ -160,6 +160,14 @@ impl Events {
         self.events.get(idx)
     }
 
+    pub fn as_mut_slice(&mut self) -> &mut [Event] {
+        &mut self.events
+    }
+
+    pub fn retain<F: FnMut(&Event) -> bool>(&mut self, f: F) {
+        self.events.retain(f);
+    }
+
     pub fn clear(&mut self) {
         self.events.clear();
         for status in self.statuses.iter_mut() {
@@ -167,3 +175,18 @@ impl Events {
         }
     }
 }
+
+/// Converts an allocation of events into `Events`, see `Events::from_vec`.
+///
+/// This allocates the buffer of completion statuses.
+pub fn events_from_vec(events: Vec<Event>) -> Events {
+    Events {
+        statuses: vec![CompletionStatus::zero(); events.capacity()].into_boxed_slice(),
+        events,
+    }
+}
+
+/// Converts `Events` into its allocation, see `Events::into_vec`.
+pub fn events_into_vec(events: Events) -> Vec<Event> {
+    events.events
+}
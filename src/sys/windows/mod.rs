@@ -1,7 +1,7 @@
 mod afd;
 
 pub mod event;
-pub use event::{Event, Events};
+pub use event::{events_from_vec, events_into_vec, Event, Events};
 
 mod handle;
 use handle::Handle;
@@ -41,10 +41,33 @@ cfg_net! {
 
 cfg_os_ext! {
     pub(crate) mod named_pipe;
+
+    /// Sets the socket in non-blocking or blocking mode.
+    ///
+    /// Mio requires all sources to be in non-blocking mode. This can be used
+    /// to prepare sockets not created by Mio, without having to use
+    /// `windows-sys` directly.
+    ///
+    /// Windows doesn't provide a way to retrieve the current mode of a socket,
+    /// so unlike on Unix there is no `is_nonblocking` function.
+    pub fn set_nonblocking(
+        socket: std::os::windows::io::RawSocket,
+        nonblocking: bool,
+    ) -> std::io::Result<()> {
+        use windows_sys::Win32::Networking::WinSock::{ioctlsocket, FIONBIO, SOCKET};
+
+        let mut value = u32::from(nonblocking);
+        if unsafe { ioctlsocket(socket as SOCKET, FIONBIO, &mut value) } != 0 {
+            Err(std::io::Error::last_os_error())
+        } else {
+            Ok(())
+        }
+    }
 }
 
 mod waker;
 pub(crate) use waker::Waker;
+pub(crate) use waker::Waker as CancelWaker;
 
 cfg_io_source! {
     use std::io;
@@ -52,7 +75,7 @@ cfg_io_source! {
     use std::pin::Pin;
     use std::sync::{Arc, Mutex};
 
-    use crate::{Interest, Registry, Token};
+    use crate::{Error, Interest, Registry, Token};
 
     struct InternalState {
         selector: Arc<SelectorInner>,
@@ -81,6 +104,9 @@ cfg_io_source! {
             IoSourceState { inner: None }
         }
 
+        /// The I/O source outlives the state, nothing to do.
+        pub fn detach(&mut self) {}
+
         pub fn do_io<T, F, R>(&self, f: F, io: &T) -> io::Result<R>
         where
             F: FnOnce(&T) -> io::Result<R>,
@@ -106,7 +132,7 @@ cfg_io_source! {
             socket: RawSocket,
         ) -> io::Result<()> {
             if self.inner.is_some() {
-                Err(io::ErrorKind::AlreadyExists.into())
+                Err(Error::AlreadyRegistered.into())
             } else {
                 registry
                     .selector()
@@ -133,7 +159,7 @@ cfg_io_source! {
                             state.interests = interests;
                         })
                 }
-                None => Err(io::ErrorKind::NotFound.into()),
+                None => Err(Error::NotRegistered.into()),
             }
         }
 
@@ -147,7 +173,7 @@ cfg_io_source! {
                     self.inner = None;
                     Ok(())
                 }
-                None => Err(io::ErrorKind::NotFound.into()),
+                None => Err(Error::NotRegistered.into()),
             }
         }
     }
@@ -25,7 +25,7 @@ use crate::event::Source;
 use crate::sys::windows::iocp::{CompletionPort, CompletionStatus};
 use crate::sys::windows::{Event, Handle, Overlapped};
 use crate::Registry;
-use crate::{Interest, Token};
+use crate::{Error, Interest, Token};
 
 /// Non-blocking windows named pipe.
 ///
@@ -611,10 +611,7 @@ impl Source for NamedPipe {
         io.check_association(registry, false)?;
 
         if io.token.is_some() {
-            return Err(io::Error::new(
-                io::ErrorKind::AlreadyExists,
-                "I/O source already registered with a `Registry`",
-            ));
+            return Err(Error::AlreadyRegistered.into());
         }
 
         if io.cp.is_none() {
@@ -653,10 +650,7 @@ impl Source for NamedPipe {
         io.check_association(registry, true)?;
 
         if io.token.is_none() {
-            return Err(io::Error::new(
-                io::ErrorKind::NotFound,
-                "I/O source not registered with `Registry`",
-            ));
+            return Err(Error::NotRegistered.into());
         }
 
         io.token = None;
@@ -680,6 +674,13 @@ impl Drop for NamedPipe {
     fn drop(&mut self) {
         // Cancel pending reads/connects, but don't cancel writes to ensure that
         // everything is flushed out.
+        //
+        // This doesn't wait for the cancellation to complete, it doesn't have
+        // to: every pending operation owns a reference to `Inner` (see the
+        // `mem::forget` calls when scheduling them), which contains the
+        // `OVERLAPPED` structures and buffers. That reference is only released
+        // once the completion (or cancellation) is dequeued from the
+        // completion port, so the kernel never writes into freed memory.
         unsafe {
             if self.inner.connecting.load(SeqCst) {
                 drop(cancel(&self.inner.handle, &self.inner.connect));
@@ -960,14 +961,10 @@ fn event_done(status: &OVERLAPPED_ENTRY, events: Option<&mut Vec<Event>>) {
 impl Io {
     fn check_association(&self, registry: &Registry, required: bool) -> io::Result<()> {
         match self.cp {
-            Some(ref cp) if !registry.selector().same_port(cp) => Err(io::Error::new(
-                io::ErrorKind::AlreadyExists,
-                "I/O source already registered with a different `Registry`",
-            )),
-            None if required => Err(io::Error::new(
-                io::ErrorKind::NotFound,
-                "I/O source not registered with `Registry`",
-            )),
+            Some(ref cp) if !registry.selector().same_port(cp) => {
+                Err(Error::WrongRegistry.into())
+            }
+            None if required => Err(Error::NotRegistered.into()),
             _ => Ok(()),
         }
     }
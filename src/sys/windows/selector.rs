@@ -2,6 +2,7 @@ use super::afd::{self, Afd, AfdPollInfo};
 use super::io_status_block::IoStatusBlock;
 use super::Event;
 use crate::sys::Events;
+use crate::Capabilities;
 
 cfg_net! {
     use crate::sys::event::{
@@ -343,6 +344,22 @@ impl Selector {
         })
     }
 
+    /// Reserve capacity for at least `additional` more registrations, the
+    /// completion port doesn't need this so it is a no-op.
+    pub fn reserve(&self, _: usize) {}
+
+    pub fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            // `GetQueuedCompletionStatusEx` only supports milliseconds.
+            precise_timeout: false,
+            exclusive: false,
+            // AFD poll operations complete once.
+            oneshot: true,
+            priority_interest: false,
+            level_trigger: false,
+        }
+    }
+
     pub fn try_clone(&self) -> io::Result<Selector> {
         Ok(Selector {
             #[cfg(debug_assertions)]
@@ -355,7 +372,7 @@ impl Selector {
     ///
     /// This requires a mutable reference to self because only a single thread
     /// can poll IOCP at a time.
-    pub fn select(&mut self, events: &mut Events, timeout: Option<Duration>) -> io::Result<()> {
+    pub fn select(&mut self, events: &mut Events, timeout: Option<Duration>) -> io::Result<bool> {
         self.inner.select(events, timeout)
     }
 
@@ -428,29 +445,31 @@ impl SelectorInner {
     /// # Safety
     ///
     /// May only be calling via `Selector::select`.
-    pub fn select(&self, events: &mut Events, timeout: Option<Duration>) -> io::Result<()> {
+    pub fn select(&self, events: &mut Events, timeout: Option<Duration>) -> io::Result<bool> {
         events.clear();
 
         if timeout.is_none() {
             loop {
                 let len = self.select2(&mut events.statuses, &mut events.events, None)?;
-                if len == 0 {
+                if len == Some(0) {
                     continue;
                 }
-                break Ok(());
+                break Ok(false);
             }
         } else {
-            self.select2(&mut events.statuses, &mut events.events, timeout)?;
-            Ok(())
+            self.select2(&mut events.statuses, &mut events.events, timeout)
+                .map(|len| len.is_none())
         }
     }
 
+    /// Returns the number of processed completion statuses, or `None` if the
+    /// wait timed out (`WAIT_TIMEOUT`).
     pub fn select2(
         &self,
         statuses: &mut [CompletionStatus],
         events: &mut Vec<Event>,
         timeout: Option<Duration>,
-    ) -> io::Result<usize> {
+    ) -> io::Result<Option<usize>> {
         assert!(!self.is_polling.swap(true, Ordering::AcqRel));
 
         unsafe { self.update_sockets_events() }?;
@@ -460,8 +479,8 @@ impl SelectorInner {
         self.is_polling.store(false, Ordering::Relaxed);
 
         match result {
-            Ok(iocp_events) => Ok(unsafe { self.feed_events(events, iocp_events) }),
-            Err(ref e) if e.raw_os_error() == Some(WAIT_TIMEOUT as i32) => Ok(0),
+            Ok(iocp_events) => Ok(Some(unsafe { self.feed_events(events, iocp_events) })),
+            Err(ref e) if e.raw_os_error() == Some(WAIT_TIMEOUT as i32) => Ok(None),
             Err(e) => Err(e),
         }
     }
@@ -59,6 +59,10 @@ pub(crate) fn listen(socket: &net::TcpListener, backlog: u32) -> io::Result<()>
     Ok(())
 }
 
+pub(crate) fn set_reuseport_lb(_: &net::TcpListener) -> io::Result<()> {
+    Err(io::ErrorKind::Unsupported.into())
+}
+
 pub(crate) fn accept(listener: &net::TcpListener) -> io::Result<(net::TcpStream, SocketAddr)> {
     // The non-blocking state of `listener` is inherited. See
     // https://docs.microsoft.com/en-us/windows/win32/api/winsock2/nf-winsock2-accept#remarks.
@@ -4,9 +4,9 @@ use std::net::{self, SocketAddr};
 use std::os::windows::io::{AsRawSocket, FromRawSocket};
 use std::os::windows::raw::SOCKET as StdSocket; // windows-sys uses usize, stdlib uses u32/u64.
 
-use crate::sys::windows::net::{new_ip_socket, socket_addr};
+use crate::sys::windows::net::{new_ip_socket, new_socket, socket_addr};
 use windows_sys::Win32::Networking::WinSock::{
-    bind as win_bind, getsockopt, IPPROTO_IPV6, IPV6_V6ONLY, SOCKET_ERROR, SOCK_DGRAM,
+    bind as win_bind, getsockopt, AF_INET, AF_INET6, IPPROTO_IPV6, IPV6_V6ONLY, SOCKET_ERROR, SOCK_DGRAM,
 };
 
 pub fn bind(addr: SocketAddr) -> io::Result<net::UdpSocket> {
@@ -23,6 +23,12 @@ pub fn bind(addr: SocketAddr) -> io::Result<net::UdpSocket> {
     Ok(socket)
 }
 
+pub(crate) fn new_unbound(ipv6: bool) -> io::Result<net::UdpSocket> {
+    let domain = if ipv6 { AF_INET6 } else { AF_INET };
+    let raw_socket = new_socket(domain.into(), SOCK_DGRAM)?;
+    Ok(unsafe { net::UdpSocket::from_raw_socket(raw_socket as StdSocket) })
+}
+
 pub(crate) fn only_v6(socket: &net::UdpSocket) -> io::Result<bool> {
     let mut optval: MaybeUninit<i32> = MaybeUninit::uninit();
     let mut optlen = mem::size_of::<i32>() as i32;
@@ -26,4 +26,14 @@ impl Waker {
 
         self.port.post(ev.to_completion_status())
     }
+
+    pub fn reset(&self) -> io::Result<()> {
+        // Completion packets that were already posted can't be removed from
+        // the completion port.
+        Ok(())
+    }
+
+    pub fn rearm(&self, _: &Selector, _: Token) -> io::Result<()> {
+        self.reset()
+    }
 }
@@ -10,6 +10,85 @@
 /// [`Registry::register`]: struct.Registry.html#method.register
 /// [`Registry::reregister`]: struct.Registry.html#method.reregister
 ///
+/// # Storing context
+///
+/// The value of the token is returned unchanged in the [`Event`]. It's stored
+/// in the pointer-sized user data field of the selector: the `data` field for
+/// epoll and `udata` for kqueue. On Windows the completion key and overlapped
+/// pointer are used by Mio itself, the token is stored in Mio's per-socket
+/// state instead. Because the token occupies the only field available to users
+/// Mio doesn't offer a separate per-registration context, but nothing stops a
+/// token from *being* the context, for example a pointer created by
+/// [`Box::into_raw`]. In that case the user is responsible for keeping the
+/// pointee alive until the source is deregistered.
+///
+/// [`Event`]: ./event/struct.Event.html
+///
+#[cfg_attr(all(feature = "os-poll", feature = "net"), doc = "```")]
+#[cfg_attr(not(all(feature = "os-poll", feature = "net")), doc = "```ignore")]
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use std::time::Duration;
+///
+/// use mio::{Events, Interest, Poll, Token};
+/// use mio::net::UdpSocket;
+///
+/// struct Connection {
+///     socket: UdpSocket,
+///     name: &'static str,
+/// }
+///
+/// let mut poll = Poll::new()?;
+/// let mut events = Events::with_capacity(8);
+///
+/// let conn = Box::new(Connection {
+///     socket: UdpSocket::bind("127.0.0.1:0".parse()?)?,
+///     name: "my connection",
+/// });
+///
+/// // Use the pointer to the connection as token.
+/// let ptr = Box::into_raw(conn);
+/// let token = Token(ptr as usize);
+/// # let sender = UdpSocket::bind("127.0.0.1:0".parse()?)?;
+/// # sender.send_to(b"hello", unsafe { (*ptr).socket.local_addr()? })?;
+/// poll.registry().register(unsafe { &mut (*ptr).socket }, token, Interest::READABLE)?;
+///
+/// poll.poll(&mut events, Some(Duration::from_millis(100)))?;
+/// for event in events.iter() {
+///     // Safety: the connection is kept alive until it's deregistered below.
+///     let conn = unsafe { &*(event.token().0 as *const Connection) };
+///     assert_eq!(conn.name, "my connection");
+/// }
+///
+/// poll.registry().deregister(unsafe { &mut (*ptr).socket })?;
+/// // Safety: the source is deregistered, so the token is no longer used.
+/// drop(unsafe { Box::from_raw(ptr) });
+/// #     Ok(())
+/// # }
+/// ```
+///
+/// # Generations
+///
+/// Tokens are often indices into a slab of connections, which are reused once
+/// a connection is closed. An event for the old connection (e.g. one returned
+/// by the same call to [`Poll::poll`] in which the connection was closed) can
+/// then be mistaken for an event for the new connection. To detect such stale
+/// tokens a generation counter can be stored alongside the index, see
+/// [`Token::from_parts`]. This is only available on 64-bit platforms, where
+/// both fit in the token.
+///
+/// [`Poll::poll`]: crate::Poll::poll
+///
+/// # Reserved tokens
+///
+/// Once [`Poll::cancel_notifier`] is called `Token(usize::MAX)` is reserved
+/// for the notifications, registering an event source or creating a [`Waker`]
+/// with it returns an [`Error::ReservedToken`] error.
+///
+/// [`Poll::cancel_notifier`]: crate::Poll::cancel_notifier
+/// [`Waker`]: crate::Waker
+/// [`Error::ReservedToken`]: crate::Error::ReservedToken
+///
 /// # Example
 ///
 /// Using `Token` to track which socket generated the event. In this example,
@@ -131,6 +210,38 @@
 #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Token(pub usize);
 
+#[cfg(target_pointer_width = "64")]
+impl Token {
+    /// Create a token from an `index` and a `generation`.
+    ///
+    /// The index is stored in the lower 32 bits of the token and the generation
+    /// in the upper 32 bits. The parts can be retrieved using [`Token::index`]
+    /// and [`Token::generation`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mio::Token;
+    ///
+    /// let token = Token::from_parts(7, 2);
+    /// assert_eq!(token.index(), 7);
+    /// assert_eq!(token.generation(), 2);
+    /// ```
+    pub const fn from_parts(index: u32, generation: u32) -> Token {
+        Token(((generation as usize) << 32) | index as usize)
+    }
+
+    /// Returns the index part of the token, see [`Token::from_parts`].
+    pub const fn index(self) -> u32 {
+        self.0 as u32
+    }
+
+    /// Returns the generation part of the token, see [`Token::from_parts`].
+    pub const fn generation(self) -> u32 {
+        (self.0 >> 32) as u32
+    }
+}
+
 impl From<Token> for usize {
     fn from(val: Token) -> usize {
         val.0
@@ -1,6 +1,8 @@
 use crate::{sys, Registry, Token};
 
 use std::io;
+use std::sync::mpsc::{self, TrySendError};
+use std::sync::Arc;
 
 /// Waker allows cross-thread waking of [`Poll`].
 ///
@@ -16,6 +18,25 @@ use std::io;
 /// `Waker` events are only guaranteed to be delivered while the `Waker` value
 /// is alive.
 ///
+/// A call to [`wake`] is never lost: it's guaranteed to cause a future (or
+/// the current) call to [`Poll::poll`] to return an event for the `Waker`,
+/// even if the events of previous calls to `wake` weren't processed yet and
+/// even though the `Waker` is registered edge-triggered. However multiple
+/// calls to `wake` can be coalesced into a single event, events should not be
+/// used to count how many times `wake` was called.
+///
+/// More precisely: all calls to `wake` that happen before a call to
+/// [`Poll::poll`] returns an event for the `Waker` may be coalesced into that
+/// event. A call to `wake` that happens after the event is returned causes a
+/// new event. Because the event can be returned while another thread calls
+/// `wake`, a single event doesn't mean a single call to `wake` was made, nor
+/// that all calls to `wake` that happened before processing the event were
+/// handled by it: a call can also cause a spurious event after its work was
+/// already processed. Use [`reset`] to avoid this, see it for an example.
+///
+/// [`Poll::poll`]: struct.Poll.html#method.poll
+/// [`reset`]: Waker::reset
+///
 /// Only a single `Waker` can be active per [`Poll`], if multiple threads need
 /// access to the `Waker` it can be shared via for example an `Arc`. What
 /// happens if multiple `Waker`s are registered with the same `Poll` is
@@ -77,14 +98,21 @@ use std::io;
 #[derive(Debug)]
 pub struct Waker {
     inner: sys::Waker,
+    #[cfg(all(unix, feature = "track-registrations"))]
+    _counted: crate::poll::CountedWaker,
 }
 
 impl Waker {
     /// Create a new `Waker`.
     pub fn new(registry: &Registry, token: Token) -> io::Result<Waker> {
+        registry.check_token(token)?;
         #[cfg(debug_assertions)]
         registry.register_waker();
-        sys::Waker::new(registry.selector(), token).map(|inner| Waker { inner })
+        sys::Waker::new(registry.selector(), token).map(|inner| Waker {
+            inner,
+            #[cfg(all(unix, feature = "track-registrations"))]
+            _counted: registry.count_waker(),
+        })
     }
 
     /// Wake up the [`Poll`] associated with this `Waker`.
@@ -93,4 +121,362 @@ impl Waker {
     pub fn wake(&self) -> io::Result<()> {
         self.inner.wake()
     }
+
+    /// Wake up the [`Poll`] associated with this `Waker` from a signal
+    /// handler.
+    ///
+    /// Unlike [`wake`], this function is guaranteed to be async-signal-safe,
+    /// i.e. it can be called from a signal handler (installed using
+    /// `sigaction(2)`), e.g. to wake up the event loop on `SIGTERM`. It
+    /// doesn't allocate or take any locks and does a single system call: an
+    /// 8 byte `write(2)` to an eventfd, a 1 byte `write(2)` to a pipe, or a
+    /// `kevent(2)` call, depending on the platform. The exception is illumos,
+    /// where the eventfd or pipe is first drained using `read(2)` (which is
+    /// also async-signal-safe) to work around a bug in its epoll emulation,
+    /// see [illumos#16700] and [illumos#13436].
+    ///
+    /// If the `Waker` was already woken up, but the [`Poll`] hasn't processed
+    /// the event yet, this does nothing and returns `Ok(())`.
+    ///
+    /// [`Poll`]: struct.Poll.html
+    /// [`wake`]: Waker::wake
+    /// [illumos#16700]: https://www.illumos.org/issues/16700
+    /// [illumos#13436]: https://www.illumos.org/issues/13436
+    ///
+    /// # Notes
+    ///
+    /// Like any function making a system call, this can overwrite `errno`.
+    /// Signal handlers should save and restore `errno` if the interrupted code
+    /// might depend on it.
+    ///
+    /// The `Waker` must be accessible from the signal handler without locking
+    /// or allocating, e.g. stored in a `static` [`OnceLock`] initialised before
+    /// the signal handler is installed.
+    ///
+    /// [`OnceLock`]: std::sync::OnceLock
+    ///
+    /// This is only available on Unix.
+    #[cfg(unix)]
+    pub fn wake_from_signal(&self) -> io::Result<()> {
+        self.inner.wake_from_signal()
+    }
+
+    /// Clear any pending wake up, i.e. calls to [`wake`] that haven't been
+    /// returned as event by [`Poll::poll`] yet.
+    ///
+    /// This can be used to establish a clean edge before processing a work
+    /// queue: by calling `reset` first and then checking the queue, all work
+    /// that was added before the reset is seen by the check, and work added
+    /// after it (and followed by a call to [`wake`]) causes a new event. So
+    /// no work is missed, and the next event is not a leftover for work that
+    /// was already processed.
+    ///
+    /// [`Poll::poll`]: struct.Poll.html#method.poll
+    /// [`wake`]: Waker::wake
+    ///
+    /// # Notes
+    ///
+    /// On Windows the pending event can't be cleared and this does nothing,
+    /// so a spurious event can still be returned.
+    ///
+    /// # Examples
+    ///
+    #[cfg_attr(feature = "os-poll", doc = "```")]
+    #[cfg_attr(not(feature = "os-poll"), doc = "```ignore")]
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use std::sync::{Arc, Mutex};
+    /// use std::thread;
+    ///
+    /// use mio::{Events, Poll, Token, Waker};
+    ///
+    /// const WAKE_TOKEN: Token = Token(10);
+    ///
+    /// let mut poll = Poll::new()?;
+    /// let mut events = Events::with_capacity(8);
+    ///
+    /// let waker = Arc::new(Waker::new(poll.registry(), WAKE_TOKEN)?);
+    /// let queue = Arc::new(Mutex::new(Vec::new()));
+    ///
+    /// let (waker1, queue1) = (waker.clone(), queue.clone());
+    /// let handle = thread::spawn(move || {
+    ///     for n in 0..10 {
+    ///         queue1.lock().unwrap().push(n);
+    ///         waker1.wake().expect("unable to wake");
+    ///     }
+    /// });
+    ///
+    /// let mut received = Vec::new();
+    /// while received.len() < 10 {
+    ///     // Reset first, then check the queue.
+    ///     waker.reset()?;
+    ///     received.extend(queue.lock().unwrap().drain(..));
+    ///     if received.len() < 10 {
+    ///         // Wait for more work.
+    ///         poll.poll(&mut events, None)?;
+    ///     }
+    /// }
+    /// # handle.join().unwrap();
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn reset(&self) -> io::Result<()> {
+        self.inner.reset()
+    }
+}
+
+/// A [`Waker`] combined with a multi-producer, single-consumer queue.
+///
+/// Messages can be send from any thread using a [`WakerSender`], which will
+/// enqueue the message and wake up the [`Poll`] associated with the channel.
+/// Once an event with the channel's token is returned the thread calling
+/// [`Poll::poll`] should drain the queue using [`try_recv`] or [`try_iter`].
+///
+/// The queue is either unbounded, see [`WakerChannel::unbounded`], or bounded
+/// see [`WakerChannel::bounded`]. Both are based on [`std::sync::mpsc`].
+///
+/// [`Poll`]: struct.Poll.html
+/// [`Poll::poll`]: crate::Poll::poll
+/// [`try_recv`]: WakerChannel::try_recv
+/// [`try_iter`]: WakerChannel::try_iter
+///
+/// # Notes
+///
+/// Because the channel uses a [`Waker`] the same restriction apply: only a
+/// single `WakerChannel` (or `Waker`) can be active per [`Poll`].
+///
+/// Wake ups are coalesced, a single event can mean that multiple messages are
+/// ready to be received. Always drain the entire queue when handling an event.
+///
+/// The channel keeps a sender (to create new ones in [`sender`]), so the queue
+/// is never disconnected while the `WakerChannel` is alive, even if all
+/// [`WakerSender`]s are dropped. Send an explicit message (like `Shutdown` in
+/// the example below) if the receiving side needs to know when to stop.
+///
+/// [`sender`]: WakerChannel::sender
+///
+/// # Examples
+///
+#[cfg_attr(feature = "os-poll", doc = "```")]
+#[cfg_attr(not(feature = "os-poll"), doc = "```ignore")]
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use std::thread;
+///
+/// use mio::{Events, Token, Poll, WakerChannel};
+///
+/// const CHANNEL: Token = Token(10);
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Command {
+///     Ping,
+///     Shutdown,
+/// }
+///
+/// let mut poll = Poll::new()?;
+/// let mut events = Events::with_capacity(2);
+///
+/// let channel = WakerChannel::unbounded(poll.registry(), CHANNEL)?;
+///
+/// let sender = channel.sender();
+/// let handle = thread::spawn(move || {
+///     sender.send(Command::Ping).expect("unable to send");
+///     sender.send(Command::Shutdown).expect("unable to send");
+/// });
+///
+/// let mut received = Vec::new();
+/// while received.last() != Some(&Command::Shutdown) {
+///     poll.poll(&mut events, None)?;
+///     for event in events.iter() {
+///         if event.token() == CHANNEL {
+///             received.extend(channel.try_iter());
+///         }
+///     }
+/// }
+/// assert_eq!(received, [Command::Ping, Command::Shutdown]);
+/// # handle.join().unwrap();
+/// #     Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct WakerChannel<T> {
+    waker: Arc<Waker>,
+    sender: SenderKind<T>,
+    receiver: mpsc::Receiver<T>,
+}
+
+/// Sending half of a [`WakerChannel`].
+///
+/// Created by [`WakerChannel::sender`], can be cloned to send from multiple
+/// threads.
+#[derive(Debug)]
+pub struct WakerSender<T> {
+    waker: Arc<Waker>,
+    sender: SenderKind<T>,
+}
+
+#[derive(Debug)]
+enum SenderKind<T> {
+    Unbounded(mpsc::Sender<T>),
+    Bounded(mpsc::SyncSender<T>),
+}
+
+impl<T> WakerChannel<T> {
+    /// Create a new `WakerChannel` with an unbounded queue.
+    pub fn unbounded(registry: &Registry, token: Token) -> io::Result<WakerChannel<T>> {
+        let (sender, receiver) = mpsc::channel();
+        WakerChannel::new(registry, token, SenderKind::Unbounded(sender), receiver)
+    }
+
+    /// Create a new `WakerChannel` with a queue that holds at most `capacity`
+    /// messages.
+    ///
+    /// [`WakerSender::send`] will block if the queue is full,
+    /// [`WakerSender::try_send`] returns a [`WouldBlock`] error instead.
+    ///
+    /// A `capacity` of zero returns an [`InvalidInput`] error. Such a channel
+    /// would only accept a message while the receiver is blocked waiting for
+    /// one, which a `WakerChannel` never does, so sending would block forever.
+    ///
+    /// [`WouldBlock`]: io::ErrorKind::WouldBlock
+    /// [`InvalidInput`]: io::ErrorKind::InvalidInput
+    pub fn bounded(
+        registry: &Registry,
+        token: Token,
+        capacity: usize,
+    ) -> io::Result<WakerChannel<T>> {
+        if capacity == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "WakerChannel with a capacity of zero",
+            ));
+        }
+        let (sender, receiver) = mpsc::sync_channel(capacity);
+        WakerChannel::new(registry, token, SenderKind::Bounded(sender), receiver)
+    }
+
+    fn new(
+        registry: &Registry,
+        token: Token,
+        sender: SenderKind<T>,
+        receiver: mpsc::Receiver<T>,
+    ) -> io::Result<WakerChannel<T>> {
+        let waker = Arc::new(Waker::new(registry, token)?);
+        Ok(WakerChannel {
+            waker,
+            sender,
+            receiver,
+        })
+    }
+
+    /// Returns a new sender for this channel.
+    pub fn sender(&self) -> WakerSender<T> {
+        WakerSender {
+            waker: self.waker.clone(),
+            sender: self.sender.clone(),
+        }
+    }
+
+    /// Attempt to receive a message from the queue, returning `None` if the
+    /// queue is empty.
+    pub fn try_recv(&self) -> Option<T> {
+        self.receiver.try_recv().ok()
+    }
+
+    /// Returns an iterator that receives all messages currently in the queue,
+    /// without blocking.
+    pub fn try_iter(&self) -> mpsc::TryIter<'_, T> {
+        self.receiver.try_iter()
+    }
+}
+
+impl<T> WakerSender<T> {
+    /// Send `msg` and wake up the [`Poll`] associated with the channel.
+    ///
+    /// If the channel is bounded and full this will block until space is
+    /// available. Returns a [`BrokenPipe`] error if the [`WakerChannel`] was
+    /// dropped.
+    ///
+    /// [`Poll`]: struct.Poll.html
+    /// [`BrokenPipe`]: io::ErrorKind::BrokenPipe
+    pub fn send(&self, msg: T) -> io::Result<()> {
+        let res = match &self.sender {
+            SenderKind::Unbounded(sender) => sender.send(msg),
+            SenderKind::Bounded(sender) => sender.send(msg),
+        };
+        match res {
+            Ok(()) => self.waker.wake(),
+            Err(_) => Err(disconnected()),
+        }
+    }
+
+    /// Send `msg` and wake up the [`Poll`] associated with the channel,
+    /// without blocking.
+    ///
+    /// If the channel is bounded and full this returns a [`WouldBlock`] error.
+    /// Returns a [`BrokenPipe`] error if the [`WakerChannel`] was dropped.
+    ///
+    /// [`Poll`]: struct.Poll.html
+    /// [`WouldBlock`]: io::ErrorKind::WouldBlock
+    /// [`BrokenPipe`]: io::ErrorKind::BrokenPipe
+    pub fn try_send(&self, msg: T) -> io::Result<()> {
+        let res = match &self.sender {
+            SenderKind::Unbounded(sender) => sender.send(msg).map_err(|_| disconnected()),
+            SenderKind::Bounded(sender) => sender.try_send(msg).map_err(|err| match err {
+                TrySendError::Full(_) => io::ErrorKind::WouldBlock.into(),
+                TrySendError::Disconnected(_) => disconnected(),
+            }),
+        };
+        res.and_then(|()| self.waker.wake())
+    }
+}
+
+impl<T> Clone for WakerSender<T> {
+    fn clone(&self) -> WakerSender<T> {
+        WakerSender {
+            waker: self.waker.clone(),
+            sender: self.sender.clone(),
+        }
+    }
+}
+
+impl<T> Clone for SenderKind<T> {
+    fn clone(&self) -> SenderKind<T> {
+        match self {
+            SenderKind::Unbounded(sender) => SenderKind::Unbounded(sender.clone()),
+            SenderKind::Bounded(sender) => SenderKind::Bounded(sender.clone()),
+        }
+    }
+}
+
+fn disconnected() -> io::Error {
+    io::Error::new(io::ErrorKind::BrokenPipe, "WakerChannel was dropped")
+}
+
+/// Wakes up a call to [`Poll::poll_cancellable`].
+///
+/// Created by [`Poll::cancel_notifier`], can be cloned to notify from multiple
+/// threads.
+///
+/// [`Poll::poll_cancellable`]: crate::Poll::poll_cancellable
+/// [`Poll::cancel_notifier`]: crate::Poll::cancel_notifier
+#[derive(Clone, Debug)]
+pub struct CancelNotifier {
+    waker: Arc<sys::CancelWaker>,
+}
+
+impl CancelNotifier {
+    pub(crate) fn new(waker: Arc<sys::CancelWaker>) -> CancelNotifier {
+        CancelNotifier { waker }
+    }
+
+    /// Wake up the [`Poll`] associated with this notifier.
+    ///
+    /// The cancellation flag passed to [`Poll::poll_cancellable`] must be set
+    /// before calling this, otherwise the call returns `false`, as for a
+    /// spurious wake up.
+    ///
+    /// [`Poll`]: crate::Poll
+    /// [`Poll::poll_cancellable`]: crate::Poll::poll_cancellable
+    pub fn notify(&self) -> io::Result<()> {
+        self.waker.wake()
+    }
 }
@@ -0,0 +1,111 @@
+#![cfg(not(target_os = "wasi"))]
+#![cfg(all(feature = "os-poll", feature = "net"))]
+
+use std::io::{IoSliceMut, Read, Write};
+use std::net;
+
+use mio::buf::BufPool;
+use mio::net::{TcpStream, UdpSocket};
+use mio::{Interest, Token};
+
+mod util;
+use util::{
+    any_local_address, assert_send, assert_sync, assert_would_block, expect_events, init_with_poll,
+    ExpectEvent,
+};
+
+const ID1: Token = Token(0);
+
+const POOL_SIZE: usize = 4;
+const BUF_SIZE: usize = 64;
+
+#[test]
+fn is_send_and_sync() {
+    assert_send::<BufPool>();
+    assert_sync::<BufPool>();
+}
+
+#[test]
+fn buf_pool_recv_cycles() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let mut socket = UdpSocket::bind(any_local_address()).unwrap();
+    let address = socket.local_addr().unwrap();
+    let sender = net::UdpSocket::bind(any_local_address()).unwrap();
+    poll.registry()
+        .register(&mut socket, ID1, Interest::READABLE)
+        .unwrap();
+
+    let pool = BufPool::new(POOL_SIZE, BUF_SIZE);
+    assert_eq!(pool.capacity(), POOL_SIZE);
+    assert_eq!(pool.buf_size(), BUF_SIZE);
+    assert_eq!(pool.allocated(), 0);
+
+    for cycle in 0..100u8 {
+        for i in 0..POOL_SIZE as u8 {
+            sender.send_to(&[cycle, i], address).unwrap();
+        }
+        expect_events(
+            &mut poll,
+            &mut events,
+            vec![ExpectEvent::new(ID1, Interest::READABLE)],
+        );
+
+        let mut received = Vec::with_capacity(POOL_SIZE);
+        for i in 0..POOL_SIZE as u8 {
+            let mut buf = pool.get().unwrap();
+            assert_eq!(buf.len(), BUF_SIZE);
+            let (n, _) = socket.recv_from(&mut buf).unwrap();
+            assert_eq!(&buf[..n], &[cycle, i]);
+            received.push(buf);
+        }
+        // All buffers are in use.
+        assert!(pool.get().is_none());
+        assert_eq!(pool.available(), 0);
+
+        // Returns the buffer to the pool, even on `WouldBlock`.
+        let _ = received.pop();
+        let mut buf = pool.get().unwrap();
+        assert_would_block(socket.recv_from(&mut buf));
+        drop(buf);
+        drop(received);
+
+        assert_eq!(pool.allocated(), POOL_SIZE);
+        assert_eq!(pool.available(), POOL_SIZE);
+    }
+}
+
+#[test]
+fn buf_pool_read_vectored() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let mut stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    let (mut peer, _) = listener.accept().unwrap();
+    poll.registry()
+        .register(&mut stream, ID1, Interest::READABLE)
+        .unwrap();
+
+    let pool = BufPool::new(2, 4);
+    let mut buf1 = pool.get().unwrap();
+    let mut buf2 = pool.get().unwrap();
+
+    peer.write_all(b"Hello world!").unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+
+    let mut bufs = [buf1.as_io_slice_mut(), IoSliceMut::new(&mut buf2)];
+    let n = stream.read_vectored(&mut bufs).unwrap();
+    assert_eq!(n, 8);
+    assert_eq!(&*buf1, b"Hell");
+    assert_eq!(&*buf2, b"o wo");
+
+    drop(buf1);
+    let mut buf = pool.get().unwrap();
+    let n = stream.read(&mut buf).unwrap();
+    assert_eq!(&buf[..n], b"rld!");
+    assert_eq!(pool.allocated(), 2);
+}
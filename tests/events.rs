@@ -1,15 +1,18 @@
 #![cfg(not(target_os = "wasi"))]
 #![cfg(all(feature = "os-poll", feature = "net"))]
 
+use std::io;
 use std::time::Duration;
 
-use mio::net::TcpStream;
-use mio::{event, Token, Waker};
+use mio::event::{Readiness, ReadinessCache};
+use mio::net::{TcpStream, UdpSocket};
+use mio::{event, Events, Interest, PriorityEvents, Token, Waker};
 
 mod util;
 use util::init_with_poll;
 
 const WAKE_TOKEN: Token = Token(10);
+const ID1: Token = Token(0);
 
 #[test]
 fn assert_event_source_implemented_for() {
@@ -41,3 +44,269 @@ fn events_all() {
     events.clear();
     assert!(events.is_empty());
 }
+
+#[test]
+fn events_from_into_vec() {
+    let (mut poll, _) = init_with_poll();
+    let mut events = Events::from_vec(Vec::with_capacity(8));
+    assert_eq!(events.capacity(), 8);
+    assert!(events.is_empty());
+
+    let waker = Waker::new(poll.registry(), WAKE_TOKEN).unwrap();
+    waker.wake().unwrap();
+    poll.poll(&mut events, Some(Duration::from_millis(100)))
+        .unwrap();
+
+    let vec = events.into_vec();
+    assert_eq!(vec.capacity(), 8);
+    assert_eq!(vec.len(), 1);
+    assert_eq!(vec[0].token(), WAKE_TOKEN);
+    let ptr = vec.as_ptr();
+
+    // Reuses the allocation, but not the events.
+    let events = Events::from_vec(vec);
+    assert_eq!(events.capacity(), 8);
+    assert!(events.is_empty());
+    let vec = events.into_vec();
+    assert_eq!(vec.as_ptr(), ptr);
+}
+
+#[test]
+fn events_get() {
+    let (mut poll, mut events) = init_with_poll();
+    assert!(events.first().is_none());
+
+    let waker = Waker::new(poll.registry(), WAKE_TOKEN).unwrap();
+    let mut socket = UdpSocket::bind("127.0.0.1:0".parse().unwrap()).unwrap();
+    poll.registry()
+        .register(&mut socket, ID1, Interest::WRITABLE)
+        .unwrap();
+
+    waker.wake().expect("unable to wake");
+    poll.poll(&mut events, Some(Duration::from_millis(100)))
+        .unwrap();
+
+    let count = events.iter().count();
+    assert_eq!(count, 2);
+    let first = events.first().expect("missing event");
+    assert_eq!(events.get(0).unwrap().token(), first.token());
+    let second = events.get(1).expect("missing event");
+    assert_ne!(first.token(), second.token());
+    assert!(events.get(count).is_none());
+
+    // Peeking doesn't consume the events.
+    let mut iter = events.iter();
+    assert_eq!(iter.next().unwrap().token(), first.token());
+    assert_eq!(iter.next().unwrap().token(), second.token());
+    assert!(iter.next().is_none());
+}
+
+#[test]
+// The `poll(2)` based selector grows `Events` as needed.
+#[cfg_attr(mio_unsupported_force_poll_poll, ignore)]
+fn events_is_full() {
+    let (mut poll, _) = init_with_poll();
+    let mut events = Events::with_capacity(2);
+    assert!(!events.is_full());
+
+    let mut sockets = (0..4)
+        .map(|_| UdpSocket::bind("127.0.0.1:0".parse().unwrap()).unwrap())
+        .collect::<Vec<_>>();
+    for (i, socket) in sockets.iter_mut().enumerate() {
+        poll.registry()
+            .register(socket, Token(i), Interest::WRITABLE)
+            .unwrap();
+    }
+
+    poll.poll(&mut events, Some(Duration::from_millis(100)))
+        .unwrap();
+    assert_eq!(events.iter().count(), 2);
+    assert!(events.is_full());
+
+    events.clear();
+    assert!(!events.is_full());
+}
+
+#[test]
+fn readiness_cache() {
+    let (mut poll, mut events) = init_with_poll();
+    let mut cache = ReadinessCache::new();
+
+    let mut socket = UdpSocket::bind("127.0.0.1:0".parse().unwrap()).unwrap();
+    let sender = UdpSocket::bind("127.0.0.1:0".parse().unwrap()).unwrap();
+    poll.registry()
+        .register(&mut socket, ID1, Interest::READABLE | Interest::WRITABLE)
+        .unwrap();
+    assert!(cache.readiness(ID1).is_empty());
+
+    poll.poll(&mut events, Some(Duration::from_millis(100)))
+        .unwrap();
+    cache.update(&events);
+    assert!(cache.readiness(ID1).is_writable());
+    assert!(!cache.readiness(ID1).is_readable());
+
+    // Readiness is kept between polls.
+    poll.poll(&mut events, Some(Duration::from_millis(10)))
+        .unwrap();
+    cache.update(&events);
+    assert!(cache.readiness(ID1).is_writable());
+
+    sender
+        .send_to(b"hello", socket.local_addr().unwrap())
+        .unwrap();
+    poll.poll(&mut events, Some(Duration::from_millis(100)))
+        .unwrap();
+    cache.update(&events);
+    assert!(cache
+        .readiness(ID1)
+        .contains(Readiness::READABLE | Readiness::WRITABLE));
+
+    let mut buf = [0; 16];
+    assert_eq!(socket.recv(&mut buf).unwrap(), 5);
+    let err = socket.recv(&mut buf).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::WouldBlock);
+    cache.clear(ID1, Readiness::READABLE);
+    assert_eq!(cache.readiness(ID1), Readiness::WRITABLE);
+
+    // Readable again after a new event.
+    sender
+        .send_to(b"hello", socket.local_addr().unwrap())
+        .unwrap();
+    poll.poll(&mut events, Some(Duration::from_millis(100)))
+        .unwrap();
+    cache.update(&events);
+    assert!(cache.readiness(ID1).is_readable());
+
+    poll.registry().deregister(&mut socket).unwrap();
+    cache.remove(ID1);
+    assert!(cache.readiness(ID1).is_empty());
+}
+
+#[test]
+fn priority_events() {
+    const HIGH: Token = Token(100);
+
+    let (mut poll, _) = init_with_poll();
+    let mut events = PriorityEvents::with_capacity(16, |token| u8::from(token == HIGH));
+    assert_eq!(events.capacity(), 16);
+
+    // Register the low priority sources first, so that the OS is likely to
+    // return their events first.
+    let mut low: Vec<_> = (1..=4)
+        .map(|i| {
+            let mut socket = UdpSocket::bind("127.0.0.1:0".parse().unwrap()).unwrap();
+            poll.registry()
+                .register(&mut socket, Token(i), Interest::WRITABLE)
+                .unwrap();
+            socket
+        })
+        .collect();
+    let mut high = UdpSocket::bind("127.0.0.1:0".parse().unwrap()).unwrap();
+    poll.registry()
+        .register(&mut high, HIGH, Interest::WRITABLE)
+        .unwrap();
+
+    events
+        .poll(&mut poll, Some(Duration::from_millis(100)))
+        .unwrap();
+    assert_eq!(events.events().iter().count(), low.len() + 1);
+    let tokens: Vec<Token> = events.iter().map(|event| event.token()).collect();
+    assert_eq!(tokens.len(), low.len() + 1);
+    assert_eq!(tokens[0], HIGH);
+    // Same priority events keep the order of the OS.
+    let os_order: Vec<Token> = events
+        .events()
+        .iter()
+        .map(|event| event.token())
+        .filter(|token| *token != HIGH)
+        .collect();
+    assert_eq!(&tokens[1..], &*os_order);
+
+    for socket in &mut low {
+        poll.registry().deregister(socket).unwrap();
+    }
+    poll.registry().deregister(&mut high).unwrap();
+    events.clear();
+    assert!(events.is_empty());
+    assert_eq!(events.iter().count(), 0);
+}
+
+#[test]
+fn events_iter_mut_and_retain() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let waker1 = Waker::new(poll.registry(), Token(1)).unwrap();
+    let mut socket = UdpSocket::bind("127.0.0.1:0".parse().unwrap()).unwrap();
+    poll.registry()
+        .register(&mut socket, Token(2), Interest::WRITABLE)
+        .unwrap();
+    waker1.wake().unwrap();
+
+    // Both sources are ready, so both events should be returned at once.
+    poll.poll(&mut events, Some(Duration::from_millis(100)))
+        .unwrap();
+    assert_eq!(events.iter_mut().count(), 2);
+
+    // Reorder in place.
+    let mut iter = events.iter_mut();
+    let first = iter.next().unwrap();
+    let second = iter.next().unwrap();
+    assert!(iter.next().is_none());
+    let (t1, t2) = (first.token(), second.token());
+    std::mem::swap(first, second);
+    let reordered: Vec<Token> = (&mut events).into_iter().map(|e| e.token()).collect();
+    assert_eq!(reordered, [t2, t1]);
+
+    // Overwrite in place.
+    let mut iter = events.iter_mut();
+    let first = iter.next().unwrap();
+    let second = iter.next().unwrap();
+    *first = second.clone();
+    let overwritten: Vec<Token> = events.iter().map(|e| e.token()).collect();
+    assert_eq!(overwritten, [t1, t1]);
+
+    events.retain(|event| event.token() == t1);
+    assert_eq!(events.iter().count(), 2);
+    events.retain(|event| event.token() != t1);
+    assert!(events.is_empty());
+}
+
+#[test]
+#[cfg(unix)]
+fn event_raw_fd() {
+    use std::os::fd::AsRawFd;
+
+    let (mut poll, mut events) = init_with_poll();
+
+    let mut socket = UdpSocket::bind("127.0.0.1:0".parse().unwrap()).unwrap();
+    poll.registry()
+        .register(&mut socket, ID1, Interest::WRITABLE)
+        .unwrap();
+    let waker = Waker::new(poll.registry(), WAKE_TOKEN).unwrap();
+    waker.wake().unwrap();
+
+    let mut found = Vec::new();
+    while found.len() < 2 {
+        poll.poll(&mut events, Some(Duration::from_millis(500)))
+            .unwrap();
+        assert!(!events.is_empty(), "missing events");
+        found.extend(events.iter().map(|event| (event.token(), event.raw_fd())));
+    }
+    found.sort();
+
+    // epoll only stores the token.
+    let socket_fd = if cfg!(all(
+        any(
+            target_os = "android",
+            target_os = "illumos",
+            target_os = "linux",
+            target_os = "redox",
+        ),
+        not(mio_unsupported_force_poll_poll)
+    )) {
+        None
+    } else {
+        Some(socket.as_raw_fd())
+    };
+    assert_eq!(found, [(ID1, socket_fd), (WAKE_TOKEN, None)]);
+}
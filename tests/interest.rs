@@ -8,6 +8,19 @@ fn is_tests() {
     assert!(Interest::WRITABLE.is_writable());
     assert!(!Interest::WRITABLE.is_aio());
     assert!(!Interest::WRITABLE.is_lio());
+    assert!(!Interest::WRITABLE.is_error_only());
+    #[cfg(any(
+        target_os = "android",
+        target_os = "illumos",
+        target_os = "linux",
+        target_os = "redox",
+    ))]
+    {
+        assert!(Interest::error_only().is_error_only());
+        assert!(!Interest::error_only().is_readable());
+        assert!(!Interest::error_only().is_writable());
+        assert!(!(Interest::READABLE | Interest::error_only()).is_error_only());
+    }
 }
 
 #[test]
@@ -41,6 +54,15 @@ fn fmt_debug() {
     {
         assert_eq!(format!("{:?}", Interest::LIO), "LIO");
     }
+    #[cfg(any(
+        target_os = "android",
+        target_os = "illumos",
+        target_os = "linux",
+        target_os = "redox",
+    ))]
+    {
+        assert_eq!(format!("{:?}", Interest::error_only()), "ERROR");
+    }
 }
 
 #[test]
@@ -0,0 +1,122 @@
+#![cfg(all(unix, feature = "os-poll", feature = "net"))]
+
+use std::env;
+use std::net;
+use std::os::fd::AsRawFd;
+use std::os::unix::net as unix_net;
+use std::os::unix::process::CommandExt;
+use std::process::Command;
+
+use mio::net::{listen_fds, ListenFd};
+use mio::{Events, Interest, Poll, Token};
+
+mod util;
+use util::{
+    any_local_address, assert_socket_close_on_exec, assert_socket_non_blocking, expect_events,
+    init, temp_file, ExpectEvent,
+};
+
+const CHILD: &str = "MIO_TEST_LISTEN_FDS_CHILD";
+const TCP_ADDR: &str = "MIO_TEST_LISTEN_FDS_TCP_ADDR";
+const UNIX_PATH: &str = "MIO_TEST_LISTEN_FDS_UNIX_PATH";
+
+#[test]
+fn listen_fds_not_activated() {
+    // The test harness isn't started by systemd.
+    assert!(listen_fds().unwrap().is_empty());
+}
+
+/// Runs `listen_fds_child` in a new process, mimicking systemd: the listeners
+/// are passed as fd 3 and 4, with `LISTEN_PID` set to the pid of the new
+/// process.
+#[test]
+fn listen_fds_activated() {
+    init();
+    let tcp_listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let path = temp_file("listen_fds_activated");
+    let unix_listener = unix_net::UnixListener::bind(&path).unwrap();
+
+    // Move the listeners out of the way of fd 3 and 4.
+    let tcp_fd = unsafe { libc::fcntl(tcp_listener.as_raw_fd(), libc::F_DUPFD_CLOEXEC, 10) };
+    let unix_fd = unsafe { libc::fcntl(unix_listener.as_raw_fd(), libc::F_DUPFD_CLOEXEC, 10) };
+    assert!(tcp_fd != -1 && unix_fd != -1);
+
+    let mut cmd = Command::new("sh");
+    // `exec` keeps the pid of the shell.
+    cmd.args(["-c", "LISTEN_PID=$$ exec \"$0\" \"$@\""])
+        .arg(env::current_exe().unwrap())
+        .args(["listen_fds_child", "--exact", "--nocapture"])
+        .env(CHILD, "1")
+        .env(TCP_ADDR, tcp_listener.local_addr().unwrap().to_string())
+        .env(UNIX_PATH, &path)
+        .env("LISTEN_FDS", "2");
+    unsafe {
+        // `dup2` clears `FD_CLOEXEC` on the new file descriptor.
+        cmd.pre_exec(move || {
+            if libc::dup2(tcp_fd, 3) == -1 || libc::dup2(unix_fd, 4) == -1 {
+                return Err(std::io::Error::last_os_error());
+            }
+            Ok(())
+        });
+    }
+    let output = cmd.output().unwrap();
+    unsafe {
+        libc::close(tcp_fd);
+        libc::close(unix_fd);
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        output.status.success(),
+        "child failed: {stdout}{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(stdout.contains("1 passed"), "child didn't run: {stdout}");
+}
+
+#[test]
+fn listen_fds_child() {
+    if env::var_os(CHILD).is_none() {
+        return;
+    }
+    // NOTE: not using `init` as that removes the temporary files, including
+    // the socket of the Unix listener.
+    let mut poll = Poll::new().unwrap();
+    let mut events = Events::with_capacity(16);
+
+    let mut listeners = listen_fds().unwrap();
+    assert_eq!(listeners.len(), 2);
+    assert!(env::var_os("LISTEN_PID").is_none());
+    assert!(env::var_os("LISTEN_FDS").is_none());
+    // The sockets are only returned once.
+    assert!(listen_fds().unwrap().is_empty());
+
+    for (i, listener) in listeners.iter_mut().enumerate() {
+        assert_eq!(listener.as_raw_fd(), 3 + i as i32);
+        assert_socket_non_blocking(listener);
+        assert_socket_close_on_exec(listener);
+        poll.registry()
+            .register(listener, Token(i), Interest::READABLE)
+            .unwrap();
+    }
+
+    let tcp_addr: net::SocketAddr = env::var(TCP_ADDR).unwrap().parse().unwrap();
+    let _tcp_stream = net::TcpStream::connect(tcp_addr).unwrap();
+    let _unix_stream = unix_net::UnixStream::connect(env::var_os(UNIX_PATH).unwrap()).unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![
+            ExpectEvent::new(Token(0), Interest::READABLE),
+            ExpectEvent::new(Token(1), Interest::READABLE),
+        ],
+    );
+
+    match &listeners[0] {
+        ListenFd::Tcp(listener) => assert_eq!(listener.local_addr().unwrap(), tcp_addr),
+        listener => panic!("unexpected listener: {listener:?}"),
+    }
+    match &listeners[1] {
+        ListenFd::Unix(listener) => drop(listener.accept().unwrap()),
+        listener => panic!("unexpected listener: {listener:?}"),
+    }
+}
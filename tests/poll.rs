@@ -4,16 +4,20 @@
 use std::net;
 use std::sync::{Arc, Barrier};
 use std::thread::{self, sleep};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use std::{fmt, io};
 
 use mio::event::Source;
 use mio::net::{TcpListener, TcpStream, UdpSocket};
-use mio::{event, Events, Interest, Poll, Registry, Token};
+use mio::{
+    event, suggest_event_capacity, Events, Interest, Poll, PollStatus, Registry, SharedPoll, Token,
+    Waker,
+};
 
 mod util;
 use util::{
-    any_local_address, assert_send, assert_sync, expect_events, init, init_with_poll, ExpectEvent,
+    any_local_address, assert_send, assert_sync, expect_events, expect_no_events, init,
+    init_with_poll, ExpectEvent,
 };
 
 const ID1: Token = Token(1);
@@ -30,6 +34,9 @@ fn is_send_and_sync() {
 
     assert_sync::<Registry>();
     assert_send::<Registry>();
+
+    assert_sync::<SharedPoll>();
+    assert_send::<SharedPoll>();
 }
 
 #[test]
@@ -42,6 +49,172 @@ fn run_once_with_nothing() {
         .unwrap();
 }
 
+#[test]
+fn poll_now() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let start = Instant::now();
+    poll.poll_now(&mut events).unwrap();
+    assert!(events.is_empty());
+    assert!(start.elapsed() < Duration::from_millis(500));
+
+    let mut socket = UdpSocket::bind(any_local_address()).unwrap();
+    poll.registry()
+        .register(&mut socket, ID1, Interest::WRITABLE)
+        .unwrap();
+    // Make sure the event is ready before calling `poll_now`.
+    thread::sleep(Duration::from_millis(10));
+    poll.poll_now(&mut events).unwrap();
+    let event = events.iter().next().expect("no event");
+    assert_eq!(event.token(), ID1);
+    assert!(event.is_writable());
+}
+
+#[test]
+fn poll_ext_status() {
+    init();
+
+    let mut events = Events::with_capacity(16);
+    let mut poll = Poll::new().unwrap();
+
+    let status = poll
+        .poll_ext(&mut events, Some(Duration::from_millis(10)))
+        .unwrap();
+    assert_eq!(status, PollStatus::TimedOut);
+    let status = poll.poll_ext(&mut events, Some(Duration::ZERO)).unwrap();
+    assert_eq!(status, PollStatus::TimedOut);
+
+    let waker = Waker::new(poll.registry(), ID1).unwrap();
+    waker.wake().unwrap();
+    let status = poll
+        .poll_ext(&mut events, Some(Duration::from_millis(100)))
+        .unwrap();
+    assert_eq!(status, PollStatus::Events(1));
+}
+
+#[test]
+fn poll_deadline() {
+    init();
+
+    let mut events = Events::with_capacity(16);
+    let mut poll = Poll::new().unwrap();
+
+    let start = Instant::now();
+    let deadline = start + Duration::from_millis(20);
+    let now = poll.poll_deadline(&mut events, deadline).unwrap();
+    assert!(events.is_empty());
+    assert!(now >= deadline, "returned before the deadline");
+    let elapsed = start.elapsed();
+    assert!(now - start <= elapsed);
+    assert!(elapsed >= Duration::from_millis(20));
+
+    // Deadline in the past doesn't block, but still returns events.
+    let waker = Waker::new(poll.registry(), ID1).unwrap();
+    waker.wake().unwrap();
+    let start = Instant::now();
+    let now = poll.poll_deadline(&mut events, start).unwrap();
+    assert!(now >= start);
+    assert!(start.elapsed() < Duration::from_millis(10));
+    assert_eq!(events.iter().count(), 1);
+
+    // Event before the deadline, consistent remaining time.
+    let deadline = Instant::now() + Duration::from_secs(10);
+    waker.wake().unwrap();
+    let now = poll.poll_deadline(&mut events, deadline).unwrap();
+    assert_eq!(events.iter().count(), 1);
+    let remaining = deadline.saturating_duration_since(now);
+    assert!(remaining > Duration::from_secs(9));
+    assert!(remaining >= deadline.saturating_duration_since(Instant::now()));
+}
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn poll_ext_interrupted() {
+    use std::mem;
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    extern "C" fn noop_handler(_: libc::c_int) {}
+
+    init();
+
+    let mut events = Events::with_capacity(16);
+    let mut poll = Poll::new().unwrap();
+
+    // Install a signal handler so the signal doesn't terminate the process.
+    unsafe {
+        let mut action: libc::sigaction = mem::zeroed();
+        action.sa_sigaction = noop_handler as extern "C" fn(libc::c_int) as libc::sighandler_t;
+        assert_eq!(
+            libc::sigaction(libc::SIGUSR1, &action, std::ptr::null_mut()),
+            0
+        );
+    }
+
+    // Keep sending the signal until the poll call is interrupted, a signal
+    // that is delivered before the call starts doesn't interrupt it.
+    let poll_thread = unsafe { libc::pthread_self() } as usize;
+    let done = Arc::new(AtomicBool::new(false));
+    let done2 = done.clone();
+    let handle = thread::spawn(move || {
+        while !done2.load(Ordering::Acquire) {
+            let res = unsafe { libc::pthread_kill(poll_thread as libc::pthread_t, libc::SIGUSR1) };
+            assert_eq!(res, 0);
+            sleep(Duration::from_millis(10));
+        }
+    });
+
+    let status = poll
+        .poll_ext(&mut events, Some(Duration::from_secs(10)))
+        .unwrap();
+    done.store(true, Ordering::Release);
+    handle.join().unwrap();
+    assert_eq!(status, PollStatus::Interrupted);
+}
+
+#[test]
+fn poll_with_capacity() {
+    init();
+
+    const N: usize = 256;
+
+    // Register more sources than the capacity hint.
+    let mut poll = Poll::with_capacity(N / 2).unwrap();
+    let mut events = Events::with_capacity(N);
+
+    let mut sockets: Vec<UdpSocket> = (0..N)
+        .map(|_| UdpSocket::bind(any_local_address()).unwrap())
+        .collect();
+    for (i, socket) in sockets.iter_mut().enumerate() {
+        poll.registry()
+            .register(socket, Token(i), Interest::READABLE)
+            .unwrap();
+    }
+    #[cfg(all(unix, feature = "track-registrations"))]
+    assert_eq!(poll.registry().registered_count(), N);
+
+    let sender = UdpSocket::bind(any_local_address()).unwrap();
+    for socket in &sockets {
+        sender
+            .send_to(b"hello", socket.local_addr().unwrap())
+            .unwrap();
+    }
+
+    let mut seen = vec![false; N];
+    while seen.iter().any(|seen| !seen) {
+        poll.poll(&mut events, Some(Duration::from_secs(1)))
+            .unwrap();
+        assert!(!events.is_empty(), "missing events");
+        for event in events.iter() {
+            assert!(event.is_readable());
+            seen[event.token().0] = true;
+        }
+    }
+
+    for socket in sockets.iter_mut() {
+        poll.registry().deregister(socket).unwrap();
+    }
+}
+
 #[test]
 fn add_then_drop() {
     init();
@@ -530,6 +703,601 @@ fn deregister_without_register() {
     assert_error(poll.registry().deregister(&mut listener), "not registered");
 }
 
+#[test]
+#[cfg(all(unix, feature = "track-registrations"))]
+fn registered_count() {
+    init();
+    let poll = Poll::new().unwrap();
+    let registry = poll.registry();
+    let cloned_registry = registry.try_clone().unwrap();
+    assert_eq!(registry.registered_count(), 0);
+
+    // Wakers are counted while they're alive.
+    let waker = Waker::new(registry, ID3).unwrap();
+    assert_eq!(registry.registered_count(), 1);
+
+    let mut listener = TcpListener::bind(any_local_address()).unwrap();
+    let mut socket = UdpSocket::bind(any_local_address()).unwrap();
+    registry
+        .register(&mut listener, ID1, Interest::READABLE)
+        .unwrap();
+    cloned_registry
+        .register(&mut socket, ID2, Interest::READABLE)
+        .unwrap();
+    assert_eq!(registry.registered_count(), 3);
+    assert_eq!(cloned_registry.registered_count(), 3);
+
+    // Reregistering doesn't change the count.
+    registry
+        .reregister(&mut listener, ID1, Interest::WRITABLE)
+        .unwrap();
+    assert_eq!(registry.registered_count(), 3);
+
+    // Failed registrations are not counted.
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    {
+        assert!(registry
+            .register(&mut listener, ID1, Interest::READABLE)
+            .is_err());
+        assert_eq!(registry.registered_count(), 3);
+    }
+
+    cloned_registry.deregister(&mut listener).unwrap();
+    assert_eq!(registry.registered_count(), 2);
+    drop(waker);
+    assert_eq!(registry.registered_count(), 1);
+
+    // Dropping a source without deregistering it isn't counted either.
+    drop(socket);
+    assert_eq!(registry.registered_count(), 0);
+    assert_eq!(cloned_registry.registered_count(), 0);
+
+    // Clearing removes all sources.
+    let mut listener = TcpListener::bind(any_local_address()).unwrap();
+    registry
+        .register(&mut listener, ID1, Interest::READABLE)
+        .unwrap();
+    assert_eq!(registry.registered_count(), 1);
+    registry.clear();
+    assert_eq!(registry.registered_count(), 0);
+}
+
+#[test]
+fn registry_swap() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let mut old = UdpSocket::bind(any_local_address()).unwrap();
+    let mut new = UdpSocket::bind(any_local_address()).unwrap();
+    let sender = UdpSocket::bind(any_local_address()).unwrap();
+
+    poll.registry()
+        .register(&mut old, ID1, Interest::READABLE)
+        .unwrap();
+
+    // Readiness that occurs before the swap must not be lost.
+    sender.send_to(b"hello", new.local_addr().unwrap()).unwrap();
+
+    poll.registry()
+        .swap(&mut old, &mut new, ID1, Interest::READABLE)
+        .unwrap();
+    #[cfg(all(unix, feature = "track-registrations"))]
+    assert_eq!(poll.registry().registered_count(), 1);
+
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+    let mut buf = [0; 10];
+    assert_eq!(new.recv(&mut buf).unwrap(), 5);
+
+    // No more events for the old socket.
+    sender.send_to(b"hello", old.local_addr().unwrap()).unwrap();
+    poll.poll(&mut events, Some(Duration::from_millis(100)))
+        .unwrap();
+    assert!(events.is_empty());
+
+    poll.registry().deregister(&mut new).unwrap();
+}
+
+#[test]
+#[cfg(all(unix, feature = "track-registrations"))]
+fn registry_clear() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let waker = Waker::new(poll.registry(), ID3).unwrap();
+
+    let mut listener = TcpListener::bind(any_local_address()).unwrap();
+    let mut socket = UdpSocket::bind(any_local_address()).unwrap();
+    poll.registry()
+        .register(&mut listener, ID1, Interest::READABLE)
+        .unwrap();
+    poll.registry()
+        .try_clone()
+        .unwrap()
+        .register(&mut socket, ID2, Interest::READABLE)
+        .unwrap();
+
+    poll.registry().clear();
+    // The waker isn't cleared.
+    assert_eq!(poll.registry().registered_count(), 1);
+
+    // No events for the cleared sources.
+    let _stream = net::TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    let sender = UdpSocket::bind(any_local_address()).unwrap();
+    sender
+        .send_to(b"hello", socket.local_addr().unwrap())
+        .unwrap();
+    poll.poll(&mut events, Some(Duration::from_millis(100)))
+        .unwrap();
+    assert!(events.is_empty());
+
+    // The waker still works.
+    waker.wake().unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID3, Interest::READABLE)],
+    );
+
+    // The sources can be registered again, after deregistering them.
+    let _ = poll.registry().deregister(&mut socket);
+    poll.registry()
+        .register(&mut socket, ID2, Interest::READABLE)
+        .unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID2, Interest::READABLE)],
+    );
+}
+
+#[test]
+#[cfg(all(unix, feature = "track-registrations"))]
+fn registry_clear_reused_fd() {
+    use mio::unix::SourceFd;
+    use std::os::fd::AsRawFd;
+
+    let (mut poll, mut events) = init_with_poll();
+
+    // Close the file descriptor without deregistering it, the waker likely
+    // reuses it (on platforms where it uses a file descriptor).
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    poll.registry()
+        .register(
+            &mut SourceFd(&listener.as_raw_fd()),
+            ID1,
+            Interest::READABLE,
+        )
+        .unwrap();
+    drop(listener);
+    let waker = Waker::new(poll.registry(), ID2).unwrap();
+
+    // Must not deregister the waker.
+    poll.registry().clear();
+    waker.wake().unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID2, Interest::READABLE)],
+    );
+}
+
+#[test]
+#[cfg(all(unix, feature = "track-registrations"))]
+fn poll_shutdown() {
+    use mio::unix::SourceFd;
+    use std::os::fd::AsRawFd;
+
+    let (poll, _) = init_with_poll();
+
+    let mut listener = TcpListener::bind(any_local_address()).unwrap();
+    let socket = net::UdpSocket::bind(any_local_address()).unwrap();
+    let mut dropped = UdpSocket::bind(any_local_address()).unwrap();
+    poll.registry()
+        .register(&mut listener, ID1, Interest::READABLE)
+        .unwrap();
+    poll.registry()
+        .register(&mut SourceFd(&socket.as_raw_fd()), ID2, Interest::READABLE)
+        .unwrap();
+    poll.registry()
+        .register(&mut dropped, ID3, Interest::READABLE)
+        .unwrap();
+    // Closing the file descriptor without deregistering it removes it from
+    // the kernel's selector, so deregistering it fails. Dropping a Mio socket
+    // removes it from the tracked registrations.
+    drop(socket);
+    drop(dropped);
+
+    let registry = poll.registry().try_clone().unwrap();
+    let errors = poll.shutdown();
+    assert_eq!(registry.registered_count(), 0);
+    assert!(registry.registered_tokens().is_empty());
+    #[cfg(not(mio_unsupported_force_poll_poll))]
+    {
+        assert_eq!(errors.len(), 1, "unexpected errors: {errors:?}");
+        assert_eq!(errors[0].0, ID2);
+    }
+    #[cfg(mio_unsupported_force_poll_poll)]
+    assert!(errors.iter().all(|(token, _)| *token == ID2));
+}
+
+#[test]
+#[cfg(all(unix, feature = "track-registrations"))]
+fn registry_set_writable_interest() {
+    let (mut poll, mut events) = init_with_poll();
+    let registry = poll.registry().try_clone().unwrap();
+
+    let mut socket = UdpSocket::bind(any_local_address()).unwrap();
+    let sender = UdpSocket::bind(any_local_address()).unwrap();
+
+    // Not registered.
+    for writable in [false, true] {
+        let err = registry
+            .set_writable_interest(&mut socket, ID1, writable)
+            .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::NotFound);
+    }
+    assert_eq!(registry.registered_count(), 0);
+
+    registry
+        .register(&mut socket, ID1, Interest::WRITABLE)
+        .unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::WRITABLE)],
+    );
+
+    // Removing the only interest keeps the socket registered.
+    #[cfg(any(
+        target_os = "android",
+        target_os = "illumos",
+        target_os = "linux",
+        target_os = "redox",
+    ))]
+    {
+        registry
+            .set_writable_interest(&mut socket, ID1, false)
+            .unwrap();
+        assert_eq!(registry.registered_count(), 1);
+        expect_no_events(&mut poll, &mut events);
+        registry
+            .set_writable_interest(&mut socket, ID1, true)
+            .unwrap();
+        expect_events(
+            &mut poll,
+            &mut events,
+            vec![ExpectEvent::new(ID1, Interest::WRITABLE)],
+        );
+    }
+    #[cfg(not(any(
+        target_os = "android",
+        target_os = "illumos",
+        target_os = "linux",
+        target_os = "redox",
+    )))]
+    {
+        let err = registry
+            .set_writable_interest(&mut socket, ID1, false)
+            .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::Unsupported);
+        assert_eq!(registry.registered_count(), 1);
+    }
+    registry.deregister(&mut socket).unwrap();
+
+    // Keeps readable interest.
+    registry
+        .register(&mut socket, ID1, Interest::READABLE)
+        .unwrap();
+    registry
+        .set_writable_interest(&mut socket, ID1, true)
+        .unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::WRITABLE)],
+    );
+    registry
+        .set_writable_interest(&mut socket, ID1, false)
+        .unwrap();
+    assert_eq!(registry.registered_count(), 1);
+    expect_no_events(&mut poll, &mut events);
+
+    sender
+        .send_to(b"hello", socket.local_addr().unwrap())
+        .unwrap();
+    poll.poll(&mut events, Some(Duration::from_millis(500)))
+        .unwrap();
+    let mut iter = events.iter();
+    let event = iter.next().expect("no event");
+    assert_eq!(event.token(), ID1);
+    assert!(event.is_readable());
+    assert!(!event.is_writable());
+    assert!(iter.next().is_none());
+}
+
+#[test]
+#[cfg(all(unix, feature = "track-registrations"))]
+fn registry_ensure_interest() {
+    use std::os::fd::{AsRawFd, RawFd};
+
+    /// Counts the calls to the selector.
+    struct Counting {
+        socket: UdpSocket,
+        registers: usize,
+        reregisters: usize,
+    }
+
+    impl event::Source for Counting {
+        fn register(
+            &mut self,
+            registry: &Registry,
+            token: Token,
+            interests: Interest,
+        ) -> io::Result<()> {
+            self.registers += 1;
+            self.socket.register(registry, token, interests)
+        }
+
+        fn reregister(
+            &mut self,
+            registry: &Registry,
+            token: Token,
+            interests: Interest,
+        ) -> io::Result<()> {
+            self.reregisters += 1;
+            self.socket.reregister(registry, token, interests)
+        }
+
+        fn deregister(&mut self, registry: &Registry) -> io::Result<()> {
+            self.socket.deregister(registry)
+        }
+    }
+
+    impl AsRawFd for Counting {
+        fn as_raw_fd(&self) -> RawFd {
+            self.socket.as_raw_fd()
+        }
+    }
+
+    let (mut poll, mut events) = init_with_poll();
+    let registry = poll.registry().try_clone().unwrap();
+
+    let mut source = Counting {
+        socket: UdpSocket::bind(any_local_address()).unwrap(),
+        registers: 0,
+        reregisters: 0,
+    };
+
+    for _ in 0..3 {
+        registry
+            .ensure_interest(&mut source, ID1, Interest::READABLE)
+            .unwrap();
+    }
+    assert_eq!((source.registers, source.reregisters), (1, 0));
+    assert_eq!(registry.registered_count(), 1);
+    expect_no_events(&mut poll, &mut events);
+
+    let interests = Interest::READABLE | Interest::WRITABLE;
+    for _ in 0..3 {
+        registry
+            .ensure_interest(&mut source, ID1, interests)
+            .unwrap();
+    }
+    assert_eq!((source.registers, source.reregisters), (1, 1));
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::WRITABLE)],
+    );
+
+    // A different token also reregisters.
+    for _ in 0..3 {
+        registry
+            .ensure_interest(&mut source, ID2, interests)
+            .unwrap();
+    }
+    assert_eq!((source.registers, source.reregisters), (1, 2));
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID2, Interest::WRITABLE)],
+    );
+
+    registry.deregister(&mut source).unwrap();
+    registry
+        .ensure_interest(&mut source, ID1, Interest::READABLE)
+        .unwrap();
+    assert_eq!((source.registers, source.reregisters), (2, 2));
+}
+
+#[test]
+#[cfg(unix)]
+fn registry_register_split() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let mut socket = UdpSocket::bind(any_local_address()).unwrap();
+    let sender = UdpSocket::bind(any_local_address()).unwrap();
+
+    // Needs both read and write interests.
+    let err = poll
+        .registry()
+        .register_split(&mut socket, ID1, ID2, Interest::READABLE)
+        .unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    let err = poll
+        .registry()
+        .register_split(&mut socket, ID1, ID2, Interest::WRITABLE)
+        .unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+
+    let mut split = poll
+        .registry()
+        .register_split(
+            &mut socket,
+            ID1,
+            ID2,
+            Interest::READABLE | Interest::WRITABLE,
+        )
+        .unwrap();
+    #[cfg(feature = "track-registrations")]
+    assert_eq!(poll.registry().registered_count(), 2);
+
+    // Writable events should only arrive under the write token.
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID2, Interest::WRITABLE)],
+    );
+    for event in events.iter() {
+        assert_eq!(event.token(), ID2, "unexpected event: {event:?}");
+        assert!(!event.is_readable());
+    }
+
+    sender
+        .send_to(b"hello", socket.local_addr().unwrap())
+        .unwrap();
+    // Readable events should only arrive under the read token.
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+    for event in events.iter() {
+        assert_eq!(event.token(), ID1, "unexpected event: {event:?}");
+        assert!(!event.is_writable());
+    }
+    let mut buf = [0; 16];
+    assert_eq!(socket.recv(&mut buf).unwrap(), 5);
+
+    // The write token can be changed independently.
+    poll.registry()
+        .reregister(&mut split, ID3, Interest::WRITABLE)
+        .unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID3, Interest::WRITABLE)],
+    );
+
+    poll.registry().deregister(&mut split).unwrap();
+    poll.registry().deregister(&mut socket).unwrap();
+    #[cfg(feature = "track-registrations")]
+    assert_eq!(poll.registry().registered_count(), 0);
+    sender
+        .send_to(b"hello", socket.local_addr().unwrap())
+        .unwrap();
+    expect_no_events(&mut poll, &mut events);
+}
+
+#[test]
+#[cfg(unix)]
+fn registry_register_and_probe() {
+    let (mut poll, mut events) = init_with_poll();
+    let registry = poll.registry().try_clone().unwrap();
+
+    let mut socket1 = UdpSocket::bind(any_local_address()).unwrap();
+    let mut socket2 = UdpSocket::bind(any_local_address()).unwrap();
+    let mut socket3 = UdpSocket::bind(any_local_address()).unwrap();
+
+    let readiness = registry
+        .register_and_probe(&mut socket1, ID1, Interest::WRITABLE)
+        .unwrap()
+        .expect("writable socket not ready");
+    assert!(readiness.is_writable());
+    assert!(!readiness.is_readable());
+
+    // No data, so not ready.
+    let readiness = registry
+        .register_and_probe(&mut socket2, ID2, Interest::READABLE)
+        .unwrap();
+    assert_eq!(readiness, None);
+
+    socket1
+        .send_to(b"hello", socket3.local_addr().unwrap())
+        .unwrap();
+    // Wait for the data to arrive.
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::WRITABLE)],
+    );
+    thread::sleep(Duration::from_millis(10));
+
+    let readiness = registry
+        .register_and_probe(&mut socket3, ID3, Interest::READABLE)
+        .unwrap()
+        .expect("socket with data not ready");
+    assert!(readiness.is_readable());
+    assert!(!readiness.is_writable());
+
+    // The event is still delivered.
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID3, Interest::READABLE)],
+    );
+    #[cfg(feature = "track-registrations")]
+    assert_eq!(registry.registered_count(), 3);
+}
+
+#[test]
+#[cfg(unix)]
+fn source_poll_ready() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let mut listener = TcpListener::bind(any_local_address()).unwrap();
+    let address = listener.local_addr().unwrap();
+    // Doesn't require the source to be registered.
+    let readiness = listener.poll_ready(Interest::READABLE).unwrap();
+    assert!(readiness.is_empty());
+
+    poll.registry()
+        .register(&mut listener, ID1, Interest::READABLE)
+        .unwrap();
+    let peer = net::TcpStream::connect(address).unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+    let readiness = listener.poll_ready(Interest::READABLE).unwrap();
+    assert!(readiness.is_readable());
+
+    let (stream, _) = listener.accept().unwrap();
+    let readiness = stream
+        .poll_ready(Interest::READABLE | Interest::WRITABLE)
+        .unwrap();
+    assert!(readiness.is_writable());
+    assert!(!readiness.is_readable());
+    assert!(!readiness.is_read_closed());
+
+    drop(peer);
+    // Wait for the shutdown to arrive.
+    thread::sleep(Duration::from_millis(10));
+    let readiness = stream.poll_ready(Interest::READABLE).unwrap();
+    assert!(readiness.is_readable());
+    assert!(readiness.is_read_closed());
+    // Boxed sources forward the call.
+    let stream = Box::new(stream);
+    assert!(stream.poll_ready(Interest::READABLE).unwrap().is_readable());
+
+    // Not supported by the default implementation.
+    let source = TestEventSource::new();
+    let err = source.poll_ready(Interest::READABLE).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::Unsupported);
+}
+
+#[test]
+fn suggested_event_capacity() {
+    assert_eq!(suggest_event_capacity(0), 64);
+    assert_eq!(suggest_event_capacity(64), 64);
+    assert_eq!(suggest_event_capacity(100), 100);
+    assert_eq!(suggest_event_capacity(1024), 1024);
+    assert_eq!(suggest_event_capacity(100_000), 1024);
+}
+
 struct TestEventSource {
     registrations: Vec<(Token, Interest)>,
     reregistrations: Vec<(Token, Interest)>,
@@ -663,3 +1431,439 @@ pub fn assert_error<T, E: fmt::Display>(result: Result<T, E>, expected_msg: &str
         ),
     }
 }
+
+#[test]
+#[cfg(target_pointer_width = "64")]
+fn token_with_generation() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let token = Token::from_parts(u32::MAX - 1, 3);
+    let mut socket = UdpSocket::bind(any_local_address()).unwrap();
+    poll.registry()
+        .register(&mut socket, token, Interest::WRITABLE)
+        .unwrap();
+
+    poll.poll(&mut events, Some(Duration::from_millis(100)))
+        .unwrap();
+    let event = events.iter().next().expect("no event");
+    assert_eq!(event.token(), token);
+    assert_eq!(event.token().index(), u32::MAX - 1);
+    assert_eq!(event.token().generation(), 3);
+}
+
+#[test]
+#[cfg(all(unix, feature = "track-registrations"))]
+fn registry_registered_tokens() {
+    let (poll, _) = init_with_poll();
+    let registry = poll.registry();
+    assert!(registry.registered_tokens().is_empty());
+
+    let _waker = Waker::new(registry, Token(100)).unwrap();
+    let mut socket1 = UdpSocket::bind(any_local_address()).unwrap();
+    let mut socket2 = UdpSocket::bind(any_local_address()).unwrap();
+    registry
+        .register(&mut socket1, ID2, Interest::READABLE)
+        .unwrap();
+    registry
+        .register(&mut socket2, ID1, Interest::READABLE)
+        .unwrap();
+    assert_eq!(registry.registered_tokens(), vec![ID1, ID2]);
+
+    registry
+        .reregister(&mut socket1, ID3, Interest::WRITABLE)
+        .unwrap();
+    assert_eq!(registry.registered_tokens(), vec![ID1, ID3]);
+    // Clones share the registrations.
+    let clone = registry.try_clone().unwrap();
+    assert_eq!(clone.registered_tokens(), vec![ID1, ID3]);
+
+    registry.deregister(&mut socket2).unwrap();
+    assert_eq!(registry.registered_tokens(), vec![ID3]);
+    registry.deregister(&mut socket1).unwrap();
+    assert!(registry.registered_tokens().is_empty());
+}
+
+#[test]
+fn mio_error() {
+    let (poll, _) = init_with_poll();
+
+    let mut listener = TcpListener::bind(any_local_address()).unwrap();
+    poll.registry()
+        .register(&mut listener, ID1, Interest::READABLE)
+        .unwrap();
+    #[cfg(debug_assertions)] // Check is only present when debug assertions are enabled.
+    {
+        let err = poll
+            .registry()
+            .register(&mut listener, ID2, Interest::READABLE)
+            .unwrap_err();
+        assert!(matches!(
+            mio::Error::from_io(&err),
+            Some(mio::Error::AlreadyRegistered)
+        ));
+        assert!(matches!(
+            mio::Error::from(err),
+            mio::Error::AlreadyRegistered
+        ));
+    }
+
+    // OS errors.
+    let err = io::Error::from(io::ErrorKind::ConnectionRefused);
+    assert!(mio::Error::from_io(&err).is_none());
+    let err = mio::Error::from(err);
+    assert!(matches!(err, mio::Error::Os(_)));
+    assert_eq!(err.kind(), io::ErrorKind::ConnectionRefused);
+
+    let err = io::Error::from(mio::Error::NotRegistered);
+    assert_eq!(err.kind(), io::ErrorKind::NotFound);
+    assert_eq!(err.to_string(), "I/O source not registered with `Registry`");
+}
+
+#[test]
+fn registration_errors() {
+    let (poll, _) = init_with_poll();
+    let registry = poll.registry();
+
+    let mut socket = UdpSocket::bind(any_local_address()).unwrap();
+    registry
+        .register(&mut socket, ID1, Interest::READABLE)
+        .unwrap();
+
+    // kqueue allows registering a file descriptor twice and ignores unknown
+    // file descriptors, so this is only checked with debug assertions enabled.
+    #[cfg(any(debug_assertions, target_os = "android", target_os = "linux", windows))]
+    {
+        let err = registry
+            .register(&mut socket, ID2, Interest::READABLE)
+            .unwrap_err();
+        assert_registration_error(err, io::ErrorKind::AlreadyExists, |err| {
+            matches!(err, mio::Error::AlreadyRegistered)
+        });
+        registry.deregister(&mut socket).unwrap();
+
+        let err = registry
+            .reregister(&mut socket, ID1, Interest::READABLE)
+            .unwrap_err();
+        assert_registration_error(err, io::ErrorKind::NotFound, |err| {
+            matches!(err, mio::Error::NotRegistered)
+        });
+        let err = registry.deregister(&mut socket).unwrap_err();
+        assert_registration_error(err, io::ErrorKind::NotFound, |err| {
+            matches!(err, mio::Error::NotRegistered)
+        });
+    }
+}
+
+/// Asserts `err` is of `kind`, and is the Mio error matched by `is_mio_error`
+/// if it's not returned by the OS. Whether Mio checks it itself depends on the
+/// platform and debug assertions, otherwise the OS error code is kept.
+fn assert_registration_error(
+    err: io::Error,
+    kind: io::ErrorKind,
+    is_mio_error: fn(&mio::Error) -> bool,
+) {
+    assert_eq!(err.kind(), kind, "{err}");
+    if err.raw_os_error().is_none() {
+        let err = mio::Error::from(err);
+        assert!(is_mio_error(&err), "{err}");
+    }
+}
+
+#[test]
+#[cfg(all(
+    any(target_os = "android", target_os = "linux"),
+    feature = "os-ext",
+    not(mio_unsupported_force_poll_poll)
+))]
+fn register_not_pollable() {
+    use std::os::fd::AsRawFd;
+
+    use mio::unix::SourceFd;
+
+    let (poll, _) = init_with_poll();
+    // epoll doesn't support regular files.
+    let file = std::fs::File::open(std::env::current_exe().unwrap()).unwrap();
+    let err = poll
+        .registry()
+        .register(&mut SourceFd(&file.as_raw_fd()), ID1, Interest::READABLE)
+        .unwrap_err();
+    // The io_uring selector checks this itself.
+    assert!(
+        matches!(err.raw_os_error(), None | Some(libc::EPERM)),
+        "{err}"
+    );
+    assert_registration_error(err, io::ErrorKind::PermissionDenied, |err| {
+        matches!(err, mio::Error::NotPollable)
+    });
+}
+
+#[test]
+fn poll_with_deadlines() {
+    let (mut poll, mut events) = init_with_poll();
+    let mut expired = Vec::new();
+
+    let mut socket1 = UdpSocket::bind(any_local_address()).unwrap();
+    let mut socket2 = UdpSocket::bind(any_local_address()).unwrap();
+    let start = Instant::now();
+    poll.registry()
+        .register_with_deadline(
+            &mut socket1,
+            ID1,
+            Interest::READABLE,
+            start + Duration::from_millis(50),
+        )
+        .unwrap();
+    poll.registry()
+        .register_with_deadline(
+            &mut socket2,
+            ID2,
+            Interest::READABLE,
+            start + Duration::from_millis(20),
+        )
+        .unwrap();
+    poll.registry()
+        .set_deadline(ID3, start + Duration::from_millis(10));
+    poll.registry().clear_deadline(ID3);
+
+    // Timeout shorter than the nearest deadline.
+    poll.poll_with_deadlines(&mut events, &mut expired, Some(Duration::ZERO))
+        .unwrap();
+    assert!(events.is_empty());
+    assert!(expired.is_empty());
+
+    // The poll timeout should be armed to the nearest deadline.
+    poll.poll_with_deadlines(&mut events, &mut expired, None)
+        .unwrap();
+    assert!(start.elapsed() >= Duration::from_millis(20));
+    assert_eq!(expired, [ID2]);
+
+    // Moving a deadline forward replaces the old one.
+    poll.registry()
+        .set_deadline(ID1, start + Duration::from_millis(80));
+    poll.poll_with_deadlines(&mut events, &mut expired, None)
+        .unwrap();
+    assert!(start.elapsed() >= Duration::from_millis(80));
+    assert_eq!(expired, [ID1]);
+
+    // Deadlines are one-shot.
+    poll.poll_with_deadlines(&mut events, &mut expired, Some(Duration::from_millis(10)))
+        .unwrap();
+    assert!(expired.is_empty());
+
+    // Sources are not deregistered.
+    socket2
+        .send_to(b"hello", socket1.local_addr().unwrap())
+        .unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+}
+
+#[test]
+#[cfg(all(unix, feature = "os-ext", not(mio_unsupported_force_poll_poll)))]
+fn register_reused_fd() {
+    use std::os::fd::AsRawFd;
+
+    use mio::unix::SourceFd;
+
+    let (mut poll, mut events) = init_with_poll();
+
+    // Register a socket, but never deregister it.
+    let socket1 = net::UdpSocket::bind(any_local_address()).unwrap();
+    let fd = socket1.as_raw_fd();
+    poll.registry()
+        .register(&mut SourceFd(&fd), ID1, Interest::READABLE)
+        .unwrap();
+
+    // Replace the socket with another one, using the same file descriptor.
+    // This closes the first socket, `socket1` now refers to the second socket.
+    let socket2 = net::UdpSocket::bind(any_local_address()).unwrap();
+    let addr = socket2.local_addr().unwrap();
+    assert_ne!(unsafe { libc::dup2(socket2.as_raw_fd(), fd) }, -1);
+    drop(socket2);
+
+    // The closed socket is no longer registered.
+    poll.registry()
+        .register(&mut SourceFd(&fd), ID2, Interest::READABLE)
+        .unwrap();
+    let sender = net::UdpSocket::bind(any_local_address()).unwrap();
+    sender.send_to(b"hello", addr).unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID2, Interest::READABLE)],
+    );
+    poll.registry().deregister(&mut SourceFd(&fd)).unwrap();
+    drop(socket1);
+}
+
+#[test]
+#[cfg(all(
+    target_os = "linux",
+    feature = "io-uring",
+    not(mio_unsupported_force_poll_poll)
+))]
+fn io_uring_selector() {
+    use std::fs;
+    use std::os::fd::AsRawFd;
+
+    let poll = Poll::new().unwrap();
+
+    // Check if the kernel supports `io_uring(7)` with multishot poll requests
+    // (`IORING_FEAT_RSRC_TAGS`), `struct io_uring_params` is 120 bytes.
+    let mut params = [0u32; 30];
+    let fd = unsafe { libc::syscall(libc::SYS_io_uring_setup, 1, params.as_mut_ptr()) };
+    if fd >= 0 {
+        unsafe { libc::close(fd as i32) };
+    }
+    let expected = if fd >= 0 && params[5] & (1 << 10) != 0 {
+        "anon_inode:[io_uring]"
+    } else {
+        // Falls back to epoll.
+        "anon_inode:[eventpoll]"
+    };
+
+    let path = format!("/proc/self/fd/{}", poll.as_raw_fd());
+    assert_eq!(fs::read_link(path).unwrap().to_str(), Some(expected));
+    assert_eq!(
+        poll.capabilities().precise_timeout(),
+        expected == "anon_inode:[io_uring]"
+    );
+}
+
+#[test]
+fn capabilities() {
+    init();
+    let poll = Poll::new().unwrap();
+    let capabilities = poll.capabilities();
+    assert_eq!(Poll::new().unwrap().capabilities(), capabilities);
+
+    assert_eq!(
+        capabilities.priority_interest(),
+        cfg!(any(target_os = "android", target_os = "linux"))
+    );
+    // All selectors, except for IOCP, support level-triggered notifications.
+    assert_eq!(capabilities.level_trigger(), !cfg!(windows));
+
+    if cfg!(mio_unsupported_force_poll_poll) {
+        assert!(!capabilities.precise_timeout());
+        assert!(!capabilities.exclusive());
+        assert!(!capabilities.oneshot());
+    } else if cfg!(any(
+        target_os = "freebsd",
+        target_os = "ios",
+        target_os = "macos",
+        target_os = "netbsd",
+        target_os = "openbsd",
+    )) {
+        // kqueue.
+        assert!(capabilities.precise_timeout());
+        assert!(!capabilities.exclusive());
+        assert!(capabilities.oneshot());
+    } else if cfg!(all(target_os = "linux", not(feature = "io-uring"))) {
+        // epoll.
+        assert!(!capabilities.precise_timeout());
+        assert!(capabilities.oneshot());
+    } else if cfg!(windows) {
+        assert!(!capabilities.precise_timeout());
+        assert!(!capabilities.exclusive());
+        assert!(capabilities.oneshot());
+    }
+}
+
+#[test]
+fn poll_into_shared() {
+    let (poll, _) = init_with_poll();
+
+    let mut socket = UdpSocket::bind(any_local_address()).unwrap();
+    let sender = UdpSocket::bind(any_local_address()).unwrap();
+    poll.registry()
+        .register(&mut socket, ID1, Interest::READABLE)
+        .unwrap();
+    #[cfg(all(target_os = "linux", not(mio_unsupported_force_poll_poll)))]
+    let fd = std::os::fd::AsRawFd::as_raw_fd(&poll);
+
+    // Registrations are kept and the same selector is used.
+    let poll = poll.into_shared();
+    #[cfg(all(target_os = "linux", not(mio_unsupported_force_poll_poll)))]
+    assert_eq!(
+        std::os::fd::AsRawFd::as_raw_fd(poll.registry()),
+        fd,
+        "selector not moved"
+    );
+    sender
+        .send_to(b"hello", socket.local_addr().unwrap())
+        .unwrap();
+    let mut events = Events::with_capacity(8);
+    poll.poll(&mut events, Some(Duration::from_secs(1)))
+        .unwrap();
+    let tokens: Vec<Token> = events.iter().map(|event| event.token()).collect();
+    assert_eq!(tokens, [ID1]);
+}
+
+#[test]
+fn shared_poll() {
+    use std::collections::HashMap;
+    use std::io::Write;
+    use std::sync::Mutex;
+
+    const THREADS: usize = 4;
+    const STREAMS: usize = 32;
+
+    init();
+    let poll = Arc::new(SharedPoll::new().unwrap());
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let mut streams = Vec::with_capacity(STREAMS);
+    let mut peers = Vec::with_capacity(STREAMS);
+    for i in 0..STREAMS {
+        let mut stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        poll.registry()
+            .register(&mut stream, Token(i), Interest::READABLE)
+            .unwrap();
+        streams.push(stream);
+        peers.push(listener.accept().unwrap().0);
+    }
+
+    // Number of times an event is received per token.
+    let received = Arc::new(Mutex::new(HashMap::new()));
+    let barrier = Arc::new(Barrier::new(THREADS + 1));
+    let handles: Vec<_> = (0..THREADS)
+        .map(|_| {
+            let poll = poll.clone();
+            let received = received.clone();
+            let barrier = barrier.clone();
+            thread::spawn(move || {
+                let mut events = Events::with_capacity(8);
+                barrier.wait();
+                let start = Instant::now();
+                // Keep polling for a while to catch any duplicate events.
+                while start.elapsed() < Duration::from_millis(500) {
+                    poll.poll(&mut events, Some(Duration::from_millis(10)))
+                        .unwrap();
+                    let mut received = received.lock().unwrap();
+                    for event in events.iter() {
+                        assert!(event.is_readable());
+                        *received.entry(event.token()).or_insert(0) += 1;
+                    }
+                }
+            })
+        })
+        .collect();
+
+    barrier.wait();
+    // A single write, without reading, causes a single event.
+    for peer in &mut peers {
+        peer.write_all(b"Hello world!").unwrap();
+    }
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    let received = received.lock().unwrap();
+    for i in 0..STREAMS {
+        assert_eq!(received.get(&Token(i)), Some(&1), "token {i}");
+    }
+}
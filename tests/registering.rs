@@ -244,3 +244,112 @@ fn registering_after_deregistering() {
         .unwrap();
     assert!(events.is_empty());
 }
+
+#[test]
+#[cfg(all(unix, feature = "track-registrations"))]
+// The `poll(2)` based selector doesn't notice the file descriptor is closed.
+#[cfg_attr(mio_unsupported_force_poll_poll, ignore)]
+fn registering_reused_fd() {
+    use std::net;
+    use std::os::fd::AsRawFd;
+
+    use mio::unix::SourceFd;
+
+    init();
+
+    let mut poll = Poll::new().unwrap();
+    let mut events = Events::with_capacity(8);
+
+    let old = net::TcpListener::bind(any_local_address()).unwrap();
+    let new = net::TcpListener::bind(any_local_address()).unwrap();
+    let fd = old.as_raw_fd();
+    poll.registry()
+        .register(&mut SourceFd(&fd), SERVER, Interest::READABLE)
+        .unwrap();
+
+    // Closes the old listener without deregistering it and reuses its file
+    // descriptor for the new listener. Mio logs a warning (with debug
+    // assertions enabled), but the registration succeeds.
+    assert_ne!(unsafe { libc::dup2(new.as_raw_fd(), fd) }, -1);
+    poll.registry()
+        .register(&mut SourceFd(&fd), CLIENT, Interest::READABLE)
+        .unwrap();
+    assert_eq!(poll.registry().registered_tokens(), vec![CLIENT]);
+
+    let _stream = net::TcpStream::connect(new.local_addr().unwrap()).unwrap();
+    poll.poll(&mut events, Some(Duration::from_secs(1)))
+        .unwrap();
+    let tokens: Vec<Token> = events.iter().map(|event| event.token()).collect();
+    assert_eq!(tokens, vec![CLIENT]);
+}
+
+#[test]
+#[cfg(all(unix, feature = "os-ext"))]
+fn deregistering_before_fd_reuse() {
+    use std::net;
+    use std::os::fd::{AsRawFd, FromRawFd, IntoRawFd, OwnedFd};
+
+    use mio::unix::SourceFd;
+
+    init();
+
+    let mut poll = Poll::new().unwrap();
+    let mut events = Events::with_capacity(8);
+
+    let mut old = TcpListener::bind(any_local_address()).unwrap();
+    let new = net::TcpListener::bind(any_local_address()).unwrap();
+    poll.registry()
+        .register(&mut old, SERVER, Interest::READABLE)
+        .unwrap();
+
+    // Make the old listener ready, but don't poll for it.
+    let _stream1 = net::TcpStream::connect(old.local_addr().unwrap()).unwrap();
+    sleep(Duration::from_millis(10));
+    poll.registry().deregister(&mut old).unwrap();
+
+    // Close the old listener and reuse its file descriptor.
+    let fd = old.into_raw_fd();
+    assert_ne!(unsafe { libc::dup2(new.as_raw_fd(), fd) }, -1);
+    let fd = unsafe { OwnedFd::from_raw_fd(fd) };
+    poll.registry()
+        .register(&mut SourceFd(&fd.as_raw_fd()), CLIENT, Interest::READABLE)
+        .unwrap();
+    #[cfg(feature = "track-registrations")]
+    assert_eq!(poll.registry().registered_tokens(), vec![CLIENT]);
+
+    let _stream2 = net::TcpStream::connect(new.local_addr().unwrap()).unwrap();
+    poll.poll(&mut events, Some(Duration::from_secs(1)))
+        .unwrap();
+    let tokens: Vec<Token> = events.iter().map(|event| event.token()).collect();
+    assert_eq!(tokens, vec![CLIENT]);
+}
+
+#[test]
+#[cfg(all(unix, feature = "os-ext"))]
+// The `poll(2)` based selector doesn't check the fd when registering.
+#[cfg_attr(mio_unsupported_force_poll_poll, ignore)]
+fn registering_invalid_fd() {
+    use mio::unix::SourceFd;
+
+    init();
+
+    let poll = Poll::new().unwrap();
+    let fd = -1;
+
+    let err = poll
+        .registry()
+        .register(&mut SourceFd(&fd), SERVER, Interest::READABLE)
+        .unwrap_err();
+    assert_eq!(err.raw_os_error(), Some(libc::EBADF));
+    #[cfg(feature = "track-registrations")]
+    assert_eq!(poll.registry().registered_count(), 0);
+
+    let err = poll
+        .registry()
+        .reregister(&mut SourceFd(&fd), SERVER, Interest::WRITABLE)
+        .unwrap_err();
+    assert_eq!(err.raw_os_error(), Some(libc::EBADF));
+
+    let err = poll.registry().deregister(&mut SourceFd(&fd)).unwrap_err();
+    assert_eq!(err.raw_os_error(), Some(libc::EBADF));
+}
@@ -0,0 +1,106 @@
+#![cfg(all(target_os = "linux", feature = "os-poll", feature = "net"))]
+
+use std::io;
+use std::net::SocketAddr;
+
+use mio::net::{SctpInfo, SctpSocket};
+use mio::{Interest, Token};
+
+mod util;
+use util::{
+    any_local_address, assert_send, assert_socket_close_on_exec, assert_socket_non_blocking,
+    assert_sync, assert_would_block, expect_events, init_with_poll, ExpectEvent,
+};
+
+const DATA1: &[u8] = b"Hello world!";
+const DATA2: &[u8] = b"Hello mars!";
+
+const ID1: Token = Token(0);
+const ID2: Token = Token(1);
+const ID3: Token = Token(2);
+
+#[test]
+fn is_send_and_sync() {
+    assert_send::<SctpSocket>();
+    assert_sync::<SctpSocket>();
+}
+
+/// Returns `None` if the kernel doesn't support SCTP.
+fn bind(addr: SocketAddr) -> Option<SctpSocket> {
+    match SctpSocket::bind(addr) {
+        Ok(socket) => Some(socket),
+        Err(ref err) if err.kind() == io::ErrorKind::Unsupported => None,
+        Err(err) => panic!("unexpected error: {err}"),
+    }
+}
+
+#[test]
+fn sctp_socket() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let Some(mut socket1) = bind(any_local_address()) else {
+        return;
+    };
+    let mut socket2 = bind(any_local_address()).unwrap();
+    let address1 = socket1.local_addr().unwrap();
+    let address2 = socket2.local_addr().unwrap();
+    assert_socket_non_blocking(&socket1);
+    assert_socket_close_on_exec(&socket1);
+
+    poll.registry()
+        .register(&mut socket1, ID1, Interest::READABLE)
+        .unwrap();
+    poll.registry()
+        .register(&mut socket2, ID2, Interest::READABLE)
+        .unwrap();
+
+    let mut buf = [0; 64];
+    assert_would_block(socket2.recv_from(&mut buf));
+
+    // Sets up an association.
+    let n = socket1
+        .send_to(DATA1, address2, SctpInfo::new(1, 42))
+        .unwrap();
+    assert_eq!(n, DATA1.len());
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID2, Interest::READABLE)],
+    );
+    let (n, peer_address, info) = socket2.recv_from(&mut buf).unwrap();
+    assert_eq!(&buf[..n], DATA1);
+    assert_eq!(peer_address.port(), address1.port());
+    assert_eq!(info.stream(), 1);
+    assert_eq!(info.ppid(), 42);
+    assert_ne!(info.assoc_id(), 0);
+
+    // Reply on the same association and stream.
+    let n = socket2.send(DATA2, info).unwrap();
+    assert_eq!(n, DATA2.len());
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+    let (n, _, reply_info) = socket1.recv_from(&mut buf).unwrap();
+    assert_eq!(&buf[..n], DATA2);
+    assert_eq!(reply_info.stream(), 1);
+    assert_eq!(reply_info.ppid(), 42);
+
+    // Messages on the association are now received by the peeled off socket.
+    let mut peeled = socket2.peel_off(info.assoc_id()).unwrap();
+    assert_socket_non_blocking(&peeled);
+    assert_socket_close_on_exec(&peeled);
+    poll.registry()
+        .register(&mut peeled, ID3, Interest::READABLE)
+        .unwrap();
+    socket1.send(DATA1, reply_info).unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID3, Interest::READABLE)],
+    );
+    let (n, _, _) = peeled.recv_from(&mut buf).unwrap();
+    assert_eq!(&buf[..n], DATA1);
+    assert_would_block(socket2.recv_from(&mut buf));
+}
@@ -21,7 +21,15 @@ const CLIENT: Token = Token(1);
 const SERVER: Token = Token(2);
 
 #[test]
-#[cfg(all(unix, not(mio_unsupported_force_poll_poll), not(debug_assertions)))]
+#[cfg(all(
+    unix,
+    not(mio_unsupported_force_poll_poll),
+    // The io_uring selector keeps per source state, see below.
+    not(all(target_os = "linux", feature = "io-uring")),
+    // Tracking the registrations adds state as well.
+    not(feature = "track-registrations"),
+    not(debug_assertions)
+))]
 fn assert_size() {
     use mio::net::*;
     use std::mem::size_of;
@@ -33,6 +41,34 @@ fn assert_size() {
     assert_eq!(size_of::<TcpStream>(), size_of::<std::net::TcpStream>());
 }
 
+#[test]
+#[cfg(all(
+    target_os = "linux",
+    feature = "io-uring",
+    not(mio_unsupported_force_poll_poll),
+    not(feature = "track-registrations"),
+    not(debug_assertions)
+))]
+fn assert_size_io_uring() {
+    use mio::net::*;
+    use std::mem::size_of;
+    use std::os::fd::RawFd;
+    use std::sync::Weak;
+
+    // The io_uring selector keeps a (weak) reference to the ring and the file
+    // descriptor, to deregister the socket when it's dropped.
+    #[allow(dead_code)]
+    struct WithState<S>(Option<(Weak<()>, RawFd)>, S);
+    assert_eq!(
+        size_of::<TcpListener>(),
+        size_of::<WithState<std::net::TcpListener>>()
+    );
+    assert_eq!(
+        size_of::<TcpStream>(),
+        size_of::<WithState<std::net::TcpStream>>()
+    );
+}
+
 #[test]
 fn is_send_and_sync() {
     assert_send::<TcpListener>();
@@ -573,6 +609,106 @@ fn connect_error() {
     assert!(stream.take_error().unwrap().is_some());
 }
 
+#[test]
+fn connect_refused_error_event() {
+    let (mut poll, mut events) = init_with_poll();
+
+    // Get an address that (most likely) refuses connections.
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let addr = listener.local_addr().unwrap();
+    drop(listener);
+
+    let mut stream = match TcpStream::connect(addr) {
+        Ok(stream) => stream,
+        Err(ref e) if e.kind() == io::ErrorKind::ConnectionRefused => {
+            // Connection failed synchronously, nothing to test.
+            return;
+        }
+        Err(e) => panic!("TcpStream::connect unexpected error {:?}", e),
+    };
+
+    poll.registry()
+        .register(&mut stream, CLIENT, Interest::WRITABLE)
+        .unwrap();
+
+    poll.poll(&mut events, Some(Duration::from_secs(5)))
+        .unwrap();
+    let event = events
+        .iter()
+        .find(|event| event.token() == CLIENT)
+        .expect("no event for failed connect");
+    assert!(event.is_error(), "event not an error: {:?}", event);
+
+    let err = stream.take_error().unwrap().expect("no socket error");
+    assert_eq!(err.kind(), io::ErrorKind::ConnectionRefused);
+}
+
+#[test]
+fn take_connect_result_refused() {
+    let (mut poll, mut events) = init_with_poll();
+
+    // Get an address that (most likely) refuses connections.
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let addr = listener.local_addr().unwrap();
+    drop(listener);
+
+    let mut stream = match TcpStream::connect(addr) {
+        Ok(stream) => stream,
+        Err(ref e) if e.kind() == io::ErrorKind::ConnectionRefused => {
+            // Connection failed synchronously, nothing to test.
+            return;
+        }
+        Err(e) => panic!("TcpStream::connect unexpected error {:?}", e),
+    };
+
+    poll.registry()
+        .register(&mut stream, CLIENT, Interest::WRITABLE)
+        .unwrap();
+    poll.poll(&mut events, Some(Duration::from_secs(5)))
+        .unwrap();
+    assert!(
+        events.iter().any(|event| event.token() == CLIENT),
+        "no event for failed connect"
+    );
+
+    let err = stream.take_connect_result().unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::ConnectionRefused);
+    // The error was taken, but the stream is still not connected.
+    #[cfg(unix)]
+    {
+        let err = stream.take_connect_result().unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::NotConnected);
+    }
+}
+
+#[test]
+fn take_connect_result_connected() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let mut stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    poll.registry()
+        .register(&mut stream, CLIENT, Interest::WRITABLE)
+        .unwrap();
+
+    let start = std::time::Instant::now();
+    loop {
+        poll.poll(&mut events, Some(Duration::from_secs(5)))
+            .unwrap();
+        match stream.take_connect_result().unwrap() {
+            Some(()) => break,
+            None => assert!(start.elapsed() < Duration::from_secs(5), "not connected"),
+        }
+    }
+    // Stays connected.
+    assert_eq!(stream.take_connect_result().unwrap(), Some(()));
+    let (mut peer, _) = listener.accept().unwrap();
+    checked_write!(stream.write(b"hello"));
+    let mut buf = [0; 5];
+    peer.read_exact(&mut buf).unwrap();
+    assert_eq!(&buf, b"hello");
+}
+
 #[test]
 fn write_error() {
     init();
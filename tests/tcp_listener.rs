@@ -1,9 +1,10 @@
 #![cfg(not(target_os = "wasi"))]
 #![cfg(all(feature = "os-poll", feature = "net"))]
 
-use mio::net::TcpListener;
+use mio::net::{TcpListener, TcpStream};
 use mio::{Interest, Token};
 use std::io::{self, Read};
+use std::mem::MaybeUninit;
 use std::net::{self, SocketAddr};
 #[cfg(unix)]
 use std::os::fd::{AsRawFd, FromRawFd, IntoRawFd};
@@ -47,6 +48,64 @@ fn tcp_listener_std() {
     });
 }
 
+#[test]
+fn tcp_listener_with_backlog() {
+    smoke_test_tcp_listener(any_local_address(), |addr| {
+        TcpListener::bind_with_backlog(addr, 16)
+    });
+    smoke_test_tcp_listener(any_local_address(), |addr| {
+        TcpListener::bind_with_backlog(addr, -1)
+    });
+}
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn bind_with_backlog() {
+    // For listening sockets `tcpi_sacked` is the backlog.
+    fn backlog(listener: &TcpListener) -> u32 {
+        let mut info: libc::tcp_info = unsafe { std::mem::zeroed() };
+        let mut len = std::mem::size_of::<libc::tcp_info>() as libc::socklen_t;
+        let res = unsafe {
+            libc::getsockopt(
+                listener.as_raw_fd(),
+                libc::IPPROTO_TCP,
+                libc::TCP_INFO,
+                (&mut info as *mut libc::tcp_info).cast(),
+                &mut len,
+            )
+        };
+        assert_eq!(res, 0, "{}", io::Error::last_os_error());
+        info.tcpi_sacked
+    }
+
+    let somaxconn: u32 = std::fs::read_to_string("/proc/sys/net/core/somaxconn")
+        .unwrap()
+        .trim()
+        .parse()
+        .unwrap();
+
+    let listener = TcpListener::bind(any_local_address()).unwrap();
+    assert_eq!(backlog(&listener), somaxconn.min(1024));
+    let listener = TcpListener::bind_with_backlog(any_local_address(), 5).unwrap();
+    assert_eq!(backlog(&listener), 5.min(somaxconn));
+    let listener = TcpListener::bind_with_backlog(any_local_address(), -1).unwrap();
+    assert_eq!(backlog(&listener), somaxconn);
+}
+
+#[test]
+fn tcp_listener_port_zero() {
+    for addr in [any_local_address(), any_local_ipv6_address()] {
+        assert_eq!(addr.port(), 0);
+        let listener = TcpListener::bind(addr).unwrap();
+        let address = listener.local_addr().unwrap();
+        assert_eq!(address.ip(), addr.ip());
+        assert_ne!(address.port(), 0);
+
+        // The port is usable right away.
+        net::TcpStream::connect(address).unwrap();
+    }
+}
+
 fn smoke_test_tcp_listener<F>(addr: SocketAddr, make_listener: F)
 where
     F: FnOnce(SocketAddr) -> io::Result<TcpListener>,
@@ -104,6 +163,19 @@ fn set_get_ttl() {
     assert!(listener.take_error().unwrap().is_none());
 }
 
+#[test]
+#[cfg(unix)]
+fn set_get_hop_limit() {
+    init();
+
+    let socket = TcpListener::bind(any_local_ipv6_address()).unwrap();
+
+    const HOPS: u32 = 10;
+    socket.set_hop_limit(HOPS).unwrap();
+    assert_eq!(socket.hop_limit().unwrap(), HOPS);
+    assert!(socket.take_error().unwrap().is_none());
+}
+
 #[test]
 fn get_ttl_without_previous_set() {
     init();
@@ -215,6 +287,82 @@ fn no_events_after_deregister() {
     thread_handle.join().expect("unable to join thread");
 }
 
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn set_defer_accept() {
+    use std::io::Write;
+    use std::time::Duration;
+
+    let (mut poll, mut events) = init_with_poll();
+
+    let mut listener = TcpListener::bind(any_local_address()).unwrap();
+    listener
+        .set_defer_accept(Some(Duration::from_secs(10)))
+        .unwrap();
+    let address = listener.local_addr().unwrap();
+
+    poll.registry()
+        .register(&mut listener, ID1, Interest::READABLE)
+        .unwrap();
+
+    let mut stream = net::TcpStream::connect(address).unwrap();
+
+    // Connection is established, but no data is send yet.
+    expect_no_events(&mut poll, &mut events);
+    assert_would_block(listener.accept());
+
+    stream.write_all(b"Hello world!").unwrap();
+
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+
+    let (mut stream, _) = listener.accept().unwrap();
+    let mut buf = [0; 12];
+    stream.read_exact(&mut buf).unwrap();
+    assert_eq!(&buf, b"Hello world!");
+
+    listener.set_defer_accept(None).unwrap();
+}
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn set_nodelay_on_accept() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let mut listener = TcpListener::bind(any_local_address()).unwrap();
+    assert!(!listener.nodelay_on_accept().unwrap());
+    listener.set_nodelay_on_accept(true).unwrap();
+    assert!(listener.nodelay_on_accept().unwrap());
+    let address = listener.local_addr().unwrap();
+
+    poll.registry()
+        .register(&mut listener, ID1, Interest::READABLE)
+        .unwrap();
+
+    let _stream1 = net::TcpStream::connect(address).unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+    let (stream, _) = listener.accept().unwrap();
+    assert!(stream.nodelay().unwrap());
+    assert_would_block(listener.accept());
+
+    listener.set_nodelay_on_accept(false).unwrap();
+    let _stream2 = net::TcpStream::connect(address).unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+    let (stream, _) = listener.accept().unwrap();
+    assert!(!stream.nodelay().unwrap());
+}
+
 /// This tests reregister on successful accept works
 #[test]
 fn tcp_listener_two_streams() {
@@ -268,6 +416,119 @@ fn tcp_listener_two_streams() {
     thread_handle2.join().expect("unable to join thread");
 }
 
+#[test]
+fn accept_many() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let mut listener = TcpListener::bind(any_local_address()).unwrap();
+    let address = listener.local_addr().unwrap();
+    poll.registry()
+        .register(&mut listener, ID1, Interest::READABLE)
+        .unwrap();
+
+    let streams: Vec<_> = (0..3)
+        .map(|_| net::TcpStream::connect(address).unwrap())
+        .collect();
+
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+
+    // Empty buffer shouldn't accept anything.
+    assert_eq!(listener.accept_many(&mut []).unwrap(), 0);
+
+    let mut out: [MaybeUninit<(TcpStream, SocketAddr)>; 2] =
+        [MaybeUninit::uninit(), MaybeUninit::uninit()];
+    // Accepts until the buffer is full.
+    let n = listener.accept_many(&mut out).unwrap();
+    assert_eq!(n, 2);
+    let mut accepted: Vec<_> = out[..n]
+        .iter_mut()
+        .map(|a| unsafe { a.assume_init_read() })
+        .collect();
+    // Accepts until `WouldBlock`.
+    let n = listener.accept_many(&mut out).unwrap();
+    assert_eq!(n, 1);
+    accepted.push(unsafe { out[0].assume_init_read() });
+    assert_would_block(listener.accept_many(&mut out));
+
+    let mut peer_addresses: Vec<_> = streams.iter().map(|s| s.local_addr().unwrap()).collect();
+    let mut accepted_addresses: Vec<_> = accepted.iter().map(|(_, addr)| *addr).collect();
+    peer_addresses.sort();
+    accepted_addresses.sort();
+    assert_eq!(peer_addresses, accepted_addresses);
+    for (stream, peer_address) in &accepted {
+        assert_eq!(stream.peer_addr().unwrap(), *peer_address);
+        assert_eq!(stream.local_addr().unwrap(), address);
+    }
+}
+
+#[test]
+#[cfg(unix)]
+fn accept_with_opts() {
+    use mio::net::AcceptOptions;
+
+    let (mut poll, mut events) = init_with_poll();
+
+    let mut listener = TcpListener::bind(any_local_address()).unwrap();
+    let address = listener.local_addr().unwrap();
+    poll.registry()
+        .register(&mut listener, ID1, Interest::READABLE)
+        .unwrap();
+
+    let peer = net::TcpStream::connect(address).unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+
+    const RECV_BUF_SIZE: usize = 64 * 1024;
+    let opts = AcceptOptions::new()
+        .nodelay(true)
+        .keepalive(true)
+        .recv_buffer_size(RECV_BUF_SIZE);
+    let (stream, peer_address) = listener.accept_with_opts(&opts).unwrap();
+    assert_eq!(peer_address, peer.local_addr().unwrap());
+    assert_socket_non_blocking(&stream);
+    assert_socket_close_on_exec(&stream);
+    assert!(stream.nodelay().unwrap());
+    assert_ne!(getsockopt(&stream, libc::SO_KEEPALIVE), 0);
+    // Linux doubles the size.
+    assert!(getsockopt(&stream, libc::SO_RCVBUF) as usize >= RECV_BUF_SIZE);
+
+    assert_would_block(listener.accept_with_opts(&opts));
+
+    // Unset options aren't changed.
+    let _peer = net::TcpStream::connect(address).unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+    let (stream, _) = listener.accept_with_opts(&AcceptOptions::new()).unwrap();
+    assert!(!stream.nodelay().unwrap());
+    assert_eq!(getsockopt(&stream, libc::SO_KEEPALIVE), 0);
+
+    fn getsockopt(stream: &TcpStream, name: libc::c_int) -> libc::c_int {
+        let mut value: libc::c_int = 0;
+        let mut len = std::mem::size_of::<libc::c_int>() as libc::socklen_t;
+        let res = unsafe {
+            libc::getsockopt(
+                stream.as_raw_fd(),
+                libc::SOL_SOCKET,
+                name,
+                (&mut value as *mut libc::c_int).cast(),
+                &mut len,
+            )
+        };
+        assert_eq!(res, 0, "{}", io::Error::last_os_error());
+        value
+    }
+}
+
 /// Start `n_connections` connections to `address`. If a `barrier` is provided
 /// it will wait on it after each connection is made before it is dropped.
 fn start_connections(
@@ -283,3 +544,275 @@ fn start_connections(
         }
     })
 }
+
+#[test]
+#[cfg(unix)]
+fn bind_with_protocol() {
+    use mio::net::TcpStream;
+
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    let protocols = [libc::IPPROTO_TCP, libc::IPPROTO_MPTCP];
+    #[cfg(not(any(target_os = "android", target_os = "linux")))]
+    let protocols = [libc::IPPROTO_TCP];
+
+    for protocol in protocols {
+        let (mut poll, mut events) = init_with_poll();
+
+        let mut listener = match TcpListener::bind_with_protocol(any_local_address(), protocol) {
+            Ok(listener) => listener,
+            // Not all kernels support Multipath TCP.
+            Err(ref err)
+                if matches!(
+                    err.raw_os_error(),
+                    Some(libc::EPROTONOSUPPORT | libc::ENOPROTOOPT | libc::EINVAL)
+                ) =>
+            {
+                continue
+            }
+            Err(err) => panic!("unexpected error: {}", err),
+        };
+        let address = listener.local_addr().unwrap();
+        assert_socket_non_blocking(&listener);
+        assert_socket_close_on_exec(&listener);
+
+        poll.registry()
+            .register(&mut listener, ID1, Interest::READABLE)
+            .unwrap();
+
+        let mut stream = TcpStream::connect_with_protocol(address, protocol).unwrap();
+        poll.registry()
+            .register(&mut stream, ID2, Interest::WRITABLE)
+            .unwrap();
+
+        expect_events(
+            &mut poll,
+            &mut events,
+            vec![
+                ExpectEvent::new(ID1, Interest::READABLE),
+                ExpectEvent::new(ID2, Interest::WRITABLE),
+            ],
+        );
+
+        let (_, peer_address) = listener.accept().unwrap();
+        assert_eq!(peer_address, stream.local_addr().unwrap());
+    }
+}
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn set_ip_transparent() {
+    let listener = TcpListener::bind(any_local_address()).unwrap();
+    match listener.set_ip_transparent(true) {
+        Ok(()) => listener.set_ip_transparent(false).unwrap(),
+        // Requires `CAP_NET_ADMIN`.
+        Err(err) => assert_eq!(err.kind(), io::ErrorKind::PermissionDenied),
+    }
+}
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "freebsd", target_os = "linux"))]
+fn bind_reuseport_lb() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let mut listener1 = TcpListener::bind_reuseport_lb(any_local_address()).unwrap();
+    let address = listener1.local_addr().unwrap();
+    let mut listener2 = TcpListener::bind_reuseport_lb(address).unwrap();
+    assert_eq!(listener2.local_addr().unwrap(), address);
+    // A listener not in the group can't be bound to the same address.
+    assert_eq!(
+        TcpListener::bind(address).unwrap_err().kind(),
+        io::ErrorKind::AddrInUse
+    );
+
+    poll.registry()
+        .register(&mut listener1, ID1, Interest::READABLE)
+        .unwrap();
+    poll.registry()
+        .register(&mut listener2, ID2, Interest::READABLE)
+        .unwrap();
+
+    // With enough connections both listeners must accept at least one.
+    const N: usize = 32;
+    let streams: Vec<_> = (0..N)
+        .map(|_| net::TcpStream::connect(address).unwrap())
+        .collect();
+    let mut accepted = [0; 2];
+    while accepted[0] + accepted[1] < N {
+        poll.poll(&mut events, Some(std::time::Duration::from_secs(1)))
+            .unwrap();
+        assert!(!events.is_empty(), "missing accept events");
+        for event in events.iter() {
+            let (index, listener) = match event.token() {
+                ID1 => (0, &listener1),
+                ID2 => (1, &listener2),
+                token => panic!("unexpected token: {token:?}"),
+            };
+            loop {
+                match listener.accept() {
+                    Ok(_) => accepted[index] += 1,
+                    Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => break,
+                    Err(err) => panic!("unexpected error accepting: {err}"),
+                }
+            }
+        }
+    }
+    assert!(accepted[0] > 0 && accepted[1] > 0, "{accepted:?}");
+    drop(streams);
+}
+
+#[test]
+#[cfg(not(any(target_os = "android", target_os = "freebsd", target_os = "linux")))]
+fn bind_reuseport_lb_unsupported() {
+    let err = TcpListener::bind_reuseport_lb(any_local_address()).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::Unsupported);
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn set_incoming_cpu() {
+    let listener = TcpListener::bind(any_local_address()).unwrap();
+    assert_eq!(listener.incoming_cpu().unwrap(), None);
+    listener.set_incoming_cpu(0).unwrap();
+    assert_eq!(listener.incoming_cpu().unwrap(), Some(0));
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn reuseport_group() {
+    use mio::net::ReuseportGroup;
+
+    let (mut poll, mut events) = init_with_poll();
+
+    let err = ReuseportGroup::bind(any_local_address(), &[]).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+
+    // All listeners use the same CPU, so that the connections are distributed
+    // across the listeners regardless of the CPU running the test.
+    let group = ReuseportGroup::bind(any_local_address(), &[0, 0]).unwrap();
+    let address = group.local_addr().unwrap();
+    for listener in group.listeners() {
+        assert_eq!(listener.local_addr().unwrap(), address);
+        assert_eq!(listener.incoming_cpu().unwrap(), Some(0));
+    }
+    let mut listeners = group.into_listeners();
+    assert_eq!(listeners.len(), 2);
+    for (i, listener) in listeners.iter_mut().enumerate() {
+        poll.registry()
+            .register(listener, Token(i), Interest::READABLE)
+            .unwrap();
+    }
+
+    // With enough connections each listener must accept at least one.
+    const N: usize = 32;
+    let streams: Vec<_> = (0..N)
+        .map(|_| net::TcpStream::connect(address).unwrap())
+        .collect();
+    let mut accepted = [0; 2];
+    while accepted.iter().sum::<usize>() < N {
+        poll.poll(&mut events, Some(std::time::Duration::from_secs(1)))
+            .unwrap();
+        assert!(!events.is_empty(), "missing accept events");
+        for event in events.iter() {
+            let index = event.token().0;
+            loop {
+                match listeners[index].accept() {
+                    Ok(_) => accepted[index] += 1,
+                    Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => break,
+                    Err(err) => panic!("unexpected error accepting: {err}"),
+                }
+            }
+        }
+    }
+    assert!(accepted.iter().all(|n| *n > 0), "{accepted:?}");
+    drop(streams);
+}
+
+#[test]
+#[cfg(unix)]
+fn from_inherited_fd() {
+    use std::os::fd::OwnedFd;
+
+    let (mut poll, mut events) = init_with_poll();
+
+    // Simulate a socket inherited from a parent process: a blocking listener
+    // without `FD_CLOEXEC`.
+    let std_listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let fd = std_listener.as_raw_fd();
+    assert_ne!(unsafe { libc::fcntl(fd, libc::F_SETFD, 0) }, -1);
+    let address = std_listener.local_addr().unwrap();
+
+    let mut listener = TcpListener::from_inherited_fd(OwnedFd::from(std_listener)).unwrap();
+    assert_socket_non_blocking(&listener);
+    assert_socket_close_on_exec(&listener);
+    assert_eq!(listener.local_addr().unwrap(), address);
+
+    poll.registry()
+        .register(&mut listener, ID1, Interest::READABLE)
+        .unwrap();
+    let stream = net::TcpStream::connect(address).unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+    let (_, peer_address) = listener.accept().unwrap();
+    assert_eq!(peer_address, stream.local_addr().unwrap());
+    assert_would_block(listener.accept());
+}
+
+#[test]
+#[cfg(unix)]
+fn from_inherited_fd_invalid() {
+    use std::os::fd::OwnedFd;
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let address = listener.local_addr().unwrap();
+
+    // Not listening.
+    let stream = net::TcpStream::connect(address).unwrap();
+    let err = TcpListener::from_inherited_fd(OwnedFd::from(stream)).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+
+    // Not a stream socket.
+    let socket = net::UdpSocket::bind(any_local_address()).unwrap();
+    let err = TcpListener::from_inherited_fd(OwnedFd::from(socket)).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+
+    // Not an IP socket.
+    let path = util::temp_file("tcp_listener_from_inherited_fd_invalid");
+    let unix_listener = std::os::unix::net::UnixListener::bind(path).unwrap();
+    assert!(TcpListener::from_inherited_fd(OwnedFd::from(unix_listener)).is_err());
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn set_cloexec() {
+    use std::process::Command;
+
+    init();
+
+    let listener = TcpListener::bind(any_local_address()).unwrap();
+    let fd = listener.as_raw_fd();
+    // Returns true if a child process inherits the listener.
+    let inherited = || {
+        Command::new("sh")
+            .arg("-c")
+            .arg(format!("[ -e /proc/self/fd/{fd} ]"))
+            .status()
+            .unwrap()
+            .success()
+    };
+
+    assert!(listener.cloexec().unwrap());
+    assert!(!inherited());
+
+    listener.set_cloexec(false).unwrap();
+    assert!(!listener.cloexec().unwrap());
+    #[cfg(feature = "os-ext")]
+    assert!(!mio::unix::is_cloexec(fd).unwrap());
+    assert!(inherited());
+
+    listener.set_cloexec(true).unwrap();
+    assert!(listener.cloexec().unwrap());
+    assert!(!inherited());
+}
@@ -166,6 +166,18 @@ fn set_get_ttl() {
     thread_handle.join().expect("unable to join thread");
 }
 
+#[test]
+#[cfg(unix)]
+fn set_get_hop_limit() {
+    let listener = net::TcpListener::bind(any_local_ipv6_address()).unwrap();
+    let stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+
+    const HOPS: u32 = 10;
+    stream.set_hop_limit(HOPS).unwrap();
+    assert_eq!(stream.hop_limit().unwrap(), HOPS);
+    assert!(stream.take_error().unwrap().is_none());
+}
+
 #[test]
 fn get_ttl_without_previous_set() {
     let (mut poll, mut events) = init_with_poll();
@@ -228,6 +240,612 @@ fn set_get_nodelay() {
     thread_handle.join().expect("unable to join thread");
 }
 
+#[test]
+fn connect_nodelay() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let barrier = Arc::new(Barrier::new(2));
+    let (thread_handle, address) = start_listener(1, Some(barrier.clone()), false);
+
+    let mut stream = TcpStream::connect_nodelay(address).unwrap();
+    // Should be set before the connection is established.
+    assert!(stream.nodelay().unwrap());
+
+    poll.registry()
+        .register(&mut stream, ID1, Interest::WRITABLE)
+        .expect("unable to register TCP stream");
+
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::WRITABLE)],
+    );
+
+    assert!(stream.nodelay().unwrap());
+    assert!(stream.take_error().unwrap().is_none());
+
+    barrier.wait();
+    thread_handle.join().expect("unable to join thread");
+}
+
+#[test]
+#[cfg(unix)]
+fn set_get_user_timeout() {
+    use std::time::Duration;
+
+    let (thread_handle, address) = start_listener(1, None, false);
+    let stream = TcpStream::connect(address).unwrap();
+
+    #[cfg(not(any(target_os = "android", target_os = "linux")))]
+    {
+        let err = stream.set_user_timeout(None).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::Unsupported);
+        let err = stream.user_timeout().unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::Unsupported);
+    }
+
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    {
+        assert_eq!(stream.user_timeout().unwrap(), None);
+        stream
+            .set_user_timeout(Some(Duration::from_millis(1500)))
+            .unwrap();
+        assert_eq!(
+            stream.user_timeout().unwrap(),
+            Some(Duration::from_millis(1500))
+        );
+        // Rounded up to whole milliseconds.
+        stream
+            .set_user_timeout(Some(Duration::from_micros(10)))
+            .unwrap();
+        assert_eq!(
+            stream.user_timeout().unwrap(),
+            Some(Duration::from_millis(1))
+        );
+        stream.set_user_timeout(None).unwrap();
+        assert_eq!(stream.user_timeout().unwrap(), None);
+    }
+
+    drop(stream);
+    thread_handle.join().expect("unable to join thread");
+}
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn set_get_priority() {
+    let (thread_handle, address) = start_listener(1, None, false);
+    let stream = TcpStream::connect(address).unwrap();
+
+    assert_eq!(stream.priority().unwrap(), 0);
+    stream.set_priority(5).unwrap();
+    assert_eq!(stream.priority().unwrap(), 5);
+
+    drop(stream);
+    thread_handle.join().expect("unable to join thread");
+}
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn set_get_congestion() {
+    let (thread_handle, address) = start_listener(1, None, false);
+    let stream = TcpStream::connect(address).unwrap();
+
+    let algo = stream.congestion().unwrap();
+    assert!(!algo.is_empty());
+    // The default algorithm is always allowed.
+    stream.set_congestion(&algo).unwrap();
+    assert_eq!(stream.congestion().unwrap(), algo);
+
+    // Cubic is the default on most systems, but it might not be loaded or
+    // allowed.
+    match stream.set_congestion("cubic") {
+        Ok(()) => assert_eq!(stream.congestion().unwrap(), "cubic"),
+        Err(err) if matches!(err.raw_os_error(), Some(libc::ENOENT | libc::EPERM)) => {}
+        Err(err) => panic!("unexpected error: {err}"),
+    }
+
+    let err = stream.set_congestion("no-such-algorithm").unwrap_err();
+    assert_eq!(err.raw_os_error(), Some(libc::ENOENT));
+
+    drop(stream);
+    thread_handle.join().expect("unable to join thread");
+}
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn original_dst() {
+    for address in [any_local_address(), any_local_ipv6_address()] {
+        let listener = net::TcpListener::bind(address).unwrap();
+        let address = listener.local_addr().unwrap();
+        let _stream = net::TcpStream::connect(address).unwrap();
+        let (accepted, _) = listener.accept().unwrap();
+        let accepted = TcpStream::from_std(accepted);
+
+        // Without a redirect the original destination is either the local
+        // address (if the connection is tracked by netfilter) or the kernel
+        // returns an error.
+        match accepted.original_dst() {
+            Ok(original_dst) => assert_eq!(original_dst, address),
+            Err(err) => assert!(
+                matches!(err.raw_os_error(), Some(libc::ENOENT | libc::ENOPROTOOPT)),
+                "unexpected error: {err}"
+            ),
+        }
+    }
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn repair_save_restore() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    let (mut peer, _) = listener.accept().unwrap();
+
+    // Leave some data in the receive queue.
+    peer.write_all(DATA1).unwrap();
+    thread::sleep(Duration::from_millis(10));
+
+    let state = match stream.repair_save() {
+        Ok(state) => state,
+        // Requires `CAP_NET_ADMIN`.
+        Err(ref err) if err.kind() == io::ErrorKind::PermissionDenied => return,
+        Err(err) => panic!("unexpected error: {err}"),
+    };
+    assert_eq!(state.local_addr, stream.local_addr().unwrap());
+    assert_eq!(state.peer_addr, peer.local_addr().unwrap());
+    assert_eq!(state.recv_queue, DATA1);
+    assert!(state.send_queue.is_empty());
+    // In repair mode the peer isn't notified.
+    drop(stream);
+
+    let mut stream = TcpStream::repair_restore(&state).unwrap();
+    assert_socket_non_blocking(&stream);
+    assert_socket_close_on_exec(&stream);
+    assert_eq!(stream.local_addr().unwrap(), state.local_addr);
+    assert_eq!(stream.peer_addr().unwrap(), state.peer_addr);
+    poll.registry()
+        .register(&mut stream, ID1, Interest::READABLE)
+        .unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+
+    let mut buf = [0; 16];
+    let n = stream.read(&mut buf).unwrap();
+    assert_eq!(&buf[..n], DATA1);
+
+    // The connection continues as normal.
+    stream.write_all(DATA2).unwrap();
+    peer.set_read_timeout(Some(Duration::from_secs(1))).unwrap();
+    let n = peer.read(&mut buf).unwrap();
+    assert_eq!(&buf[..n], DATA2);
+}
+
+#[test]
+#[cfg(unix)]
+fn configure_options() {
+    use mio::net::TcpOptions;
+
+    let (thread_handle, address) = start_listener(1, None, false);
+    let stream = TcpStream::connect(address).unwrap();
+
+    let opts = TcpOptions {
+        nodelay: Some(true),
+        keepalive: Some(true),
+        linger: Some(Some(Duration::from_secs(5))),
+        recv_buffer_size: Some(32 * 1024),
+        send_buffer_size: None,
+    };
+    stream.configure(&opts).unwrap();
+    let got = stream.options().unwrap();
+    assert_eq!(got.nodelay, Some(true));
+    assert_eq!(got.keepalive, Some(true));
+    assert_eq!(got.linger, Some(Some(Duration::from_secs(5))));
+    // The OS may increase the size.
+    assert!(got.recv_buffer_size.unwrap() >= 32 * 1024);
+    assert!(got.send_buffer_size.is_some());
+
+    // Options that are not set are left alone.
+    stream
+        .configure(&TcpOptions {
+            linger: Some(None),
+            ..TcpOptions::default()
+        })
+        .unwrap();
+    stream.configure(&TcpOptions::default()).unwrap();
+    let got2 = stream.options().unwrap();
+    assert_eq!(got2.linger, Some(None));
+    assert_eq!(
+        TcpOptions {
+            linger: None,
+            ..got2
+        },
+        TcpOptions {
+            linger: None,
+            ..got
+        }
+    );
+
+    drop(stream);
+    thread_handle.join().expect("unable to join thread");
+}
+
+#[test]
+#[cfg(unix)]
+fn diagnose() {
+    use mio::net::ConnDiagnosis;
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let address = listener.local_addr().unwrap();
+    let stream = net::TcpStream::connect(address).unwrap();
+    stream.set_nonblocking(true).unwrap();
+    let mut stream = TcpStream::from_std(stream);
+    let (mut peer, _) = listener.accept().unwrap();
+    assert!(matches!(stream.diagnose().unwrap(), ConnDiagnosis::Alive));
+
+    // Peeking doesn't remove any data.
+    peer.write_all(DATA1).unwrap();
+    peer.shutdown(Shutdown::Write).unwrap();
+    thread::sleep(Duration::from_millis(10));
+    assert!(matches!(stream.diagnose().unwrap(), ConnDiagnosis::Alive));
+    let mut buf = Vec::new();
+    stream.read_to_end(&mut buf).unwrap();
+    assert_eq!(buf, DATA1);
+    assert!(matches!(
+        stream.diagnose().unwrap(),
+        ConnDiagnosis::ReadClosed
+    ));
+
+    stream.shutdown(Shutdown::Write).unwrap();
+    #[cfg(target_os = "linux")]
+    assert!(matches!(stream.diagnose().unwrap(), ConnDiagnosis::Closed));
+    #[cfg(not(target_os = "linux"))]
+    assert!(matches!(
+        stream.diagnose().unwrap(),
+        ConnDiagnosis::ReadClosed
+    ));
+
+    let stream = TcpStream::from_std(net::TcpStream::connect(address).unwrap());
+    let (peer, _) = listener.accept().unwrap();
+    stream.shutdown(Shutdown::Write).unwrap();
+    #[cfg(target_os = "linux")]
+    assert!(matches!(
+        stream.diagnose().unwrap(),
+        ConnDiagnosis::WriteClosed
+    ));
+
+    // Resetting the connection.
+    let peer = TcpStream::from_std(peer);
+    set_linger_zero(&peer);
+    drop(peer);
+    thread::sleep(Duration::from_millis(10));
+    match stream.diagnose().unwrap() {
+        ConnDiagnosis::Reset(err) => assert_eq!(err.kind(), io::ErrorKind::ConnectionReset),
+        diagnosis => panic!("unexpected diagnosis: {diagnosis:?}"),
+    }
+    // The error is taken.
+    assert!(matches!(stream.diagnose().unwrap(), ConnDiagnosis::Closed));
+}
+
+#[test]
+#[cfg(unix)]
+fn set_recv_low_watermark() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let mut stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    let (mut peer, _) = listener.accept().unwrap();
+
+    stream
+        .set_recv_low_watermark(DATA1_LEN + DATA2_LEN)
+        .unwrap();
+    assert_eq!(stream.recv_low_watermark().unwrap(), DATA1_LEN + DATA2_LEN);
+
+    poll.registry()
+        .register(&mut stream, ID1, Interest::READABLE)
+        .unwrap();
+
+    // Not enough bytes buffered.
+    peer.write_all(DATA1).unwrap();
+    expect_no_events(&mut poll, &mut events);
+
+    peer.write_all(DATA2).unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+
+    let mut buf = [0; 32];
+    let n = stream.read(&mut buf).unwrap();
+    assert_eq!(n, DATA1_LEN + DATA2_LEN);
+}
+
+#[test]
+#[cfg(unix)]
+fn mss() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let mut stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    poll.registry()
+        .register(&mut stream, ID1, Interest::WRITABLE)
+        .unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::WRITABLE)],
+    );
+
+    let mss = stream.mss().unwrap();
+    assert!(
+        mss > 0 && mss <= u32::from(u16::MAX),
+        "unexpected mss: {mss}"
+    );
+
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    {
+        stream.set_mss(1000).unwrap();
+        // Below the minimum allowed MSS, the OS error is returned.
+        let err = stream.set_mss(1).unwrap_err();
+        assert_eq!(err.raw_os_error(), Some(libc::EINVAL));
+    }
+}
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn notsent_lowat() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let mut stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    let (mut peer, _) = listener.accept().unwrap();
+    peer.set_nonblocking(true).unwrap();
+    poll.registry()
+        .register(&mut stream, ID1, Interest::WRITABLE)
+        .unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::WRITABLE)],
+    );
+
+    assert_eq!(stream.notsent_lowat().unwrap(), 0);
+    stream.set_notsent_lowat(1024).unwrap();
+    assert_eq!(stream.notsent_lowat().unwrap(), 1024);
+
+    // The peer doesn't read, so once its receive window is full all written
+    // data stays unsent and the stream is no longer writable.
+    let buf = [0; 1024];
+    loop {
+        match stream.write(&buf) {
+            Ok(_) => {}
+            Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => break,
+            Err(err) => panic!("unexpected error: {err}"),
+        }
+    }
+    // Events triggered by the writes above may still be pending, these are
+    // spurious events.
+    poll.poll(&mut events, Some(Duration::ZERO)).unwrap();
+    expect_no_events(&mut poll, &mut events);
+
+    // Only once the amount of unsent data drops below the watermark is the
+    // stream writable again.
+    let mut buf = [0; 64 * 1024];
+    for _ in 0..100 {
+        loop {
+            match peer.read(&mut buf) {
+                Ok(0) => panic!("unexpected end of stream"),
+                Ok(_) => {}
+                Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => break,
+                Err(err) => panic!("unexpected error: {err}"),
+            }
+        }
+        poll.poll(&mut events, Some(Duration::from_millis(10)))
+            .unwrap();
+        if events
+            .iter()
+            .any(|event| event.token() == ID1 && event.is_writable())
+        {
+            return;
+        }
+    }
+    panic!("no writable event");
+}
+
+#[test]
+#[cfg(any(
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "ios",
+    target_os = "macos",
+    target_os = "netbsd",
+    target_os = "openbsd",
+))]
+fn set_send_low_watermark() {
+    use std::os::fd::AsRawFd;
+
+    let (mut poll, mut events) = init_with_poll();
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let mut stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    let (mut peer, _) = listener.accept().unwrap();
+    peer.set_nonblocking(true).unwrap();
+    poll.registry()
+        .register(&mut stream, ID1, Interest::WRITABLE)
+        .unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::WRITABLE)],
+    );
+
+    // Use a small send buffer so the watermark is a significant part of it.
+    let size: libc::c_int = 16 * 1024;
+    let res = unsafe {
+        libc::setsockopt(
+            stream.as_raw_fd(),
+            libc::SOL_SOCKET,
+            libc::SO_SNDBUF,
+            (&size as *const libc::c_int).cast(),
+            std::mem::size_of::<libc::c_int>() as libc::socklen_t,
+        )
+    };
+    assert_eq!(res, 0, "{}", io::Error::last_os_error());
+
+    stream.set_send_low_watermark(8 * 1024).unwrap();
+    let watermark = stream.send_low_watermark().unwrap();
+    assert_eq!(watermark, 8 * 1024);
+
+    // The peer doesn't read, so eventually the send buffer is full.
+    let buf = [0; 1024];
+    loop {
+        match stream.write(&buf) {
+            Ok(_) => {}
+            Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => break,
+            Err(err) => panic!("unexpected error: {err}"),
+        }
+    }
+    poll.poll(&mut events, Some(Duration::ZERO)).unwrap();
+    expect_no_events(&mut poll, &mut events);
+
+    // Once the stream is writable again at least `watermark` bytes can be
+    // written at once.
+    let mut read_buf = [0; 64 * 1024];
+    for _ in 0..100 {
+        loop {
+            match peer.read(&mut read_buf) {
+                Ok(0) => panic!("unexpected end of stream"),
+                Ok(_) => {}
+                Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => break,
+                Err(err) => panic!("unexpected error: {err}"),
+            }
+        }
+        poll.poll(&mut events, Some(Duration::from_millis(10)))
+            .unwrap();
+        if events
+            .iter()
+            .any(|event| event.token() == ID1 && event.is_writable())
+        {
+            let buf = vec![0; watermark];
+            assert_eq!(stream.write(&buf).unwrap(), watermark);
+            return;
+        }
+    }
+    panic!("no writable event");
+}
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn set_send_low_watermark_unsupported() {
+    init();
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+
+    // Linux doesn't allow changing the send low watermark.
+    let err = stream.set_send_low_watermark(8 * 1024).unwrap_err();
+    assert_eq!(err.raw_os_error(), Some(libc::ENOPROTOOPT));
+    assert_eq!(stream.send_low_watermark().unwrap(), 1);
+}
+
+#[test]
+fn recv_ring() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let mut stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    let (mut peer, _) = listener.accept().unwrap();
+    poll.registry()
+        .register(&mut stream, ID1, Interest::READABLE)
+        .unwrap();
+
+    // Wraps around, filling both slices.
+    peer.write_all(DATA1).unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+    let mut buf = [0; 16];
+    let (second, first) = buf.split_at_mut(10);
+    let n = stream.recv_ring(first, second).unwrap();
+    assert_eq!(n, DATA1_LEN);
+    assert_eq!(&buf[10..], &DATA1[..6]);
+    assert_eq!(&buf[..6], &DATA1[6..]);
+    assert_would_block(stream.recv_ring(&mut buf[..8], &mut []));
+
+    // Either slice can be empty.
+    peer.write_all(DATA1).unwrap();
+    peer.write_all(DATA2).unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+    let mut buf = [0; DATA1_LEN];
+    assert_eq!(stream.recv_ring(&mut [], &mut buf).unwrap(), DATA1_LEN);
+    assert_eq!(buf, DATA1);
+    let mut buf = [0; DATA2_LEN];
+    assert_eq!(stream.recv_ring(&mut buf, &mut []).unwrap(), DATA2_LEN);
+    assert_eq!(buf, DATA2);
+    assert_would_block(stream.recv_ring(&mut [0; 8], &mut [0; 8]));
+}
+
+#[test]
+#[cfg(unix)]
+fn queue_len() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let mut stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    let (mut peer, _) = listener.accept().unwrap();
+    poll.registry()
+        .register(&mut stream, ID1, Interest::READABLE)
+        .unwrap();
+
+    assert_eq!(stream.recv_queue_len().unwrap(), 0);
+    peer.write_all(DATA1).unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+    assert_eq!(stream.recv_queue_len().unwrap(), DATA1_LEN);
+    let mut buf = [0; 4];
+    stream.read_exact(&mut buf).unwrap();
+    assert_eq!(stream.recv_queue_len().unwrap(), DATA1_LEN - buf.len());
+
+    // Fill the send buffer, as `peer` isn't reading the data stays in the
+    // send buffer of `stream` once the receive buffer of `peer` is full.
+    #[cfg(any(
+        target_os = "android",
+        target_os = "freebsd",
+        target_os = "ios",
+        target_os = "linux",
+        target_os = "macos",
+        target_os = "netbsd",
+        target_os = "tvos",
+        target_os = "visionos",
+        target_os = "watchos",
+    ))]
+    {
+        let data = [0; 4096];
+        loop {
+            match stream.write(&data) {
+                Ok(_) => continue,
+                Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => break,
+                Err(err) => panic!("unexpected error: {err}"),
+            }
+        }
+        assert!(stream.send_queue_len().unwrap() > 0);
+    }
+}
+
 #[test]
 fn get_nodelay_without_previous_set() {
     let (mut poll, mut events) = init_with_poll();
@@ -671,6 +1289,47 @@ fn tcp_reset_close_event() {
     }
 }
 
+#[test]
+#[cfg(any(
+    target_os = "android",
+    target_os = "illumos",
+    target_os = "linux",
+    target_os = "redox",
+))]
+fn tcp_error_only_interest() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let sockaddr = listener.local_addr().unwrap();
+    let mut stream = TcpStream::connect(sockaddr).unwrap();
+
+    poll.registry()
+        .register(&mut stream, ID1, Interest::error_only())
+        .unwrap();
+
+    let (server_stream, _) = listener.accept().unwrap();
+    let mut server_stream = TcpStream::from_std(server_stream);
+
+    // Neither the connection becoming writable, nor incoming data should
+    // trigger an event.
+    checked_write!(server_stream.write(DATA1));
+    expect_no_events(&mut poll, &mut events);
+
+    // Server resets the connection.
+    set_linger_zero(&server_stream);
+    drop(server_stream);
+
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Readiness::ERROR)],
+    );
+    for event in events.iter() {
+        assert!(!event.is_readable());
+        assert!(!event.is_writable());
+    }
+}
+
 #[test]
 #[cfg_attr(
     windows,
@@ -864,3 +1523,80 @@ fn send_oob_data<S: AsRawFd>(stream: &S, data: &[u8]) -> io::Result<usize> {
         }
     }
 }
+
+#[test]
+#[cfg(any(
+    target_os = "android",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "ios",
+    target_os = "linux",
+    target_os = "macos",
+    target_os = "tvos",
+    target_os = "visionos",
+    target_os = "watchos",
+))]
+fn send_file() {
+    use std::fs::{self, File};
+
+    let (mut poll, mut events) = init_with_poll();
+
+    // Large enough to fill the send buffer, making `send_file` return partial
+    // sends and `WouldBlock` errors.
+    const LEN: usize = 8 * 1024 * 1024;
+    const OFFSET: usize = 1000;
+    let data: Vec<u8> = (0..LEN).map(|i| (i % 251) as u8).collect();
+    let path = util::temp_file("tcp_stream::send_file");
+    fs::write(&path, &data).unwrap();
+    let file = File::open(&path).unwrap();
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let address = listener.local_addr().unwrap();
+    let handle = thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut received = Vec::new();
+        stream.read_to_end(&mut received).unwrap();
+        received
+    });
+
+    let mut stream = TcpStream::connect(address).unwrap();
+    poll.registry()
+        .register(&mut stream, ID1, Interest::WRITABLE)
+        .unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::WRITABLE)],
+    );
+
+    assert_eq!(stream.send_file(&file, OFFSET as u64, 0).unwrap(), 0);
+    assert_eq!(stream.send_file(&file, LEN as u64, 10).unwrap(), 0);
+
+    let mut offset = OFFSET;
+    while offset < LEN {
+        match stream.send_file(&file, offset as u64, LEN - offset) {
+            Ok(n) => {
+                assert!(n > 0);
+                offset += n;
+            }
+            Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => {
+                expect_events(
+                    &mut poll,
+                    &mut events,
+                    vec![ExpectEvent::new(ID1, Interest::WRITABLE)],
+                );
+            }
+            Err(err) => panic!("unexpected error sending file: {err}"),
+        }
+    }
+    assert_eq!(offset, LEN);
+    drop(stream);
+
+    let received = handle.join().unwrap();
+    assert_eq!(received.len(), LEN - OFFSET);
+    assert!(
+        received == data[OFFSET..],
+        "received data doesn't match file"
+    );
+    let _ = fs::remove_file(path);
+}
@@ -4,7 +4,8 @@
 use log::{debug, info};
 use mio::net::UdpSocket;
 use mio::{Events, Interest, Poll, Registry, Token};
-use std::net::{self, IpAddr, SocketAddr};
+use std::io;
+use std::net::{self, IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV6};
 #[cfg(unix)]
 use std::os::fd::{AsRawFd, FromRawFd, IntoRawFd};
 use std::str;
@@ -30,7 +31,15 @@ const ID2: Token = Token(3);
 const ID3: Token = Token(4);
 
 #[test]
-#[cfg(all(unix, not(mio_unsupported_force_poll_poll), not(debug_assertions)))]
+#[cfg(all(
+    unix,
+    not(mio_unsupported_force_poll_poll),
+    // The io_uring selector keeps per source state, see below.
+    not(all(target_os = "linux", feature = "io-uring")),
+    // Tracking the registrations adds state as well.
+    not(feature = "track-registrations"),
+    not(debug_assertions)
+))]
 fn assert_size() {
     use mio::net::*;
     use std::mem::size_of;
@@ -41,6 +50,30 @@ fn assert_size() {
     assert_eq!(size_of::<UdpSocket>(), size_of::<std::net::UdpSocket>());
 }
 
+#[test]
+#[cfg(all(
+    target_os = "linux",
+    feature = "io-uring",
+    not(mio_unsupported_force_poll_poll),
+    not(feature = "track-registrations"),
+    not(debug_assertions)
+))]
+fn assert_size_io_uring() {
+    use mio::net::*;
+    use std::mem::size_of;
+    use std::os::fd::RawFd;
+    use std::sync::Weak;
+
+    // The io_uring selector keeps a (weak) reference to the ring and the file
+    // descriptor, to deregister the socket when it's dropped.
+    #[allow(dead_code)]
+    struct WithState<S>(Option<(Weak<()>, RawFd)>, S);
+    assert_eq!(
+        size_of::<UdpSocket>(),
+        size_of::<WithState<std::net::UdpSocket>>()
+    );
+}
+
 #[test]
 fn empty_datagram() {
     const EMPTY: &[u8] = b"";
@@ -181,6 +214,19 @@ fn set_get_ttl() {
     assert!(socket1.take_error().unwrap().is_none());
 }
 
+#[test]
+#[cfg(unix)]
+fn set_get_hop_limit() {
+    init();
+
+    let socket = UdpSocket::bind(any_local_ipv6_address()).unwrap();
+
+    const HOPS: u32 = 10;
+    socket.set_hop_limit(HOPS).unwrap();
+    assert_eq!(socket.hop_limit().unwrap(), HOPS);
+    assert!(socket.take_error().unwrap().is_none());
+}
+
 #[test]
 fn get_ttl_without_previous_set() {
     let socket1 = UdpSocket::bind(any_local_address()).unwrap();
@@ -311,6 +357,37 @@ fn connected_udp_socket_ipv6() {
     smoke_test_connected_udp_socket(socket1, socket2);
 }
 
+#[test]
+fn connected_udp_socket_new_v4() {
+    let socket1 = UdpSocket::new_v4().unwrap();
+    let socket2 = UdpSocket::bind(any_local_address()).unwrap();
+    let address2 = socket2.local_addr().unwrap();
+
+    // Connecting binds the socket.
+    socket1.connect(address2).unwrap();
+    let address1 = socket1.local_addr().unwrap();
+    assert!(address1.is_ipv4());
+    assert_ne!(address1.port(), 0);
+    socket2.connect(address1).unwrap();
+
+    smoke_test_connected_udp_socket(socket1, socket2);
+}
+
+#[test]
+fn connected_udp_socket_new_v6() {
+    let socket1 = UdpSocket::new_v6().unwrap();
+    let socket2 = UdpSocket::bind(any_local_ipv6_address()).unwrap();
+    let address2 = socket2.local_addr().unwrap();
+
+    socket1.connect(address2).unwrap();
+    let address1 = socket1.local_addr().unwrap();
+    assert!(address1.is_ipv6());
+    assert_ne!(address1.port(), 0);
+    socket2.connect(address1).unwrap();
+
+    smoke_test_connected_udp_socket(socket1, socket2);
+}
+
 #[test]
 fn connected_udp_socket_std() {
     let socket1 = net::UdpSocket::bind(any_local_address()).unwrap();
@@ -1149,3 +1226,629 @@ fn et_behavior_recv_from() {
     assert!(socket1.take_error().unwrap().is_none());
     assert!(socket2.take_error().unwrap().is_none());
 }
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn bind_reuseport() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let mut socket1 = UdpSocket::bind_reuseport(any_local_address()).unwrap();
+    let address = socket1.local_addr().unwrap();
+    let mut socket2 = UdpSocket::bind_reuseport(address).unwrap();
+    assert_eq!(socket2.local_addr().unwrap(), address);
+
+    // Binding without `SO_REUSEPORT` to the same address must fail.
+    assert!(UdpSocket::bind(address).is_err());
+
+    poll.registry()
+        .register(&mut socket1, ID1, Interest::READABLE)
+        .unwrap();
+    poll.registry()
+        .register(&mut socket2, ID2, Interest::READABLE)
+        .unwrap();
+
+    // The kernel hashes on the four-tuple, so we send from many different
+    // source ports to make it (all but) certain both sockets receive some.
+    let senders: Vec<net::UdpSocket> = (0..64)
+        .map(|_| net::UdpSocket::bind(any_local_address()).unwrap())
+        .collect();
+    for sender in &senders {
+        checked_write!(sender.send_to(DATA1, address));
+    }
+
+    let mut received = [0, 0];
+    let mut buf = [0; 20];
+    while received[0] + received[1] < senders.len() {
+        poll.poll(&mut events, Some(Duration::from_millis(500)))
+            .unwrap();
+        assert!(!events.is_empty(), "missing datagrams: {:?}", received);
+        for event in events.iter() {
+            let (socket, count) = match event.token() {
+                ID1 => (&socket1, &mut received[0]),
+                ID2 => (&socket2, &mut received[1]),
+                token => panic!("unexpected token: {:?}", token),
+            };
+            loop {
+                match socket.recv_from(&mut buf) {
+                    Ok((n, _)) => {
+                        assert_eq!(&buf[..n], DATA1);
+                        *count += 1;
+                    }
+                    Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => break,
+                    Err(err) => panic!("unexpected error: {}", err),
+                }
+            }
+        }
+    }
+
+    assert!(received[0] > 0, "first socket received no datagrams");
+    assert!(received[1] > 0, "second socket received no datagrams");
+}
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn attach_reuseport_cbpf() {
+    use mio::net::SockFilter;
+
+    let (mut poll, mut events) = init_with_poll();
+
+    let mut socket1 = UdpSocket::bind_reuseport(any_local_address()).unwrap();
+    let address = socket1.local_addr().unwrap();
+    let mut socket2 = UdpSocket::bind_reuseport(address).unwrap();
+
+    // `BPF_RET | BPF_K`, always steering to the second socket in the group.
+    const BPF_RET_K: u16 = 0x06;
+    socket1
+        .attach_reuseport_cbpf(&[SockFilter::new(BPF_RET_K, 0, 0, 1)])
+        .unwrap();
+
+    poll.registry()
+        .register(&mut socket1, ID1, Interest::READABLE)
+        .unwrap();
+    poll.registry()
+        .register(&mut socket2, ID2, Interest::READABLE)
+        .unwrap();
+
+    let senders: Vec<net::UdpSocket> = (0..16)
+        .map(|_| net::UdpSocket::bind(any_local_address()).unwrap())
+        .collect();
+    for sender in &senders {
+        checked_write!(sender.send_to(DATA1, address));
+    }
+
+    let mut received = 0;
+    let mut buf = [0; 20];
+    while received < senders.len() {
+        poll.poll(&mut events, Some(Duration::from_millis(500)))
+            .unwrap();
+        assert!(!events.is_empty(), "missing datagrams: {}", received);
+        for event in events.iter() {
+            assert_eq!(event.token(), ID2);
+            loop {
+                match socket2.recv_from(&mut buf) {
+                    Ok((n, _)) => {
+                        assert_eq!(&buf[..n], DATA1);
+                        received += 1;
+                    }
+                    Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => break,
+                    Err(err) => panic!("unexpected error: {}", err),
+                }
+            }
+        }
+    }
+    assert_would_block(socket1.recv_from(&mut buf));
+
+    // An empty program is rejected by the kernel.
+    assert!(socket1.attach_reuseport_cbpf(&[]).is_err());
+}
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn send_to_link_local_scope_id() {
+    let (address, scope_id) = match link_local_address() {
+        Some(link_local) => link_local,
+        None => {
+            info!("no interface with a link-local address, skipping test");
+            return;
+        }
+    };
+
+    let (mut poll, mut events) = init_with_poll();
+    let mut receiver =
+        UdpSocket::bind(SocketAddr::V6(SocketAddrV6::new(address, 0, 0, scope_id))).unwrap();
+    let receiver_address = receiver.local_addr().unwrap();
+    let sender = UdpSocket::bind("[::]:0".parse().unwrap()).unwrap();
+    poll.registry()
+        .register(&mut receiver, ID1, Interest::READABLE)
+        .unwrap();
+    let mut buf = [0; 20];
+
+    // Explicit scope id in the address.
+    checked_write!(sender.send_to(DATA1, receiver_address));
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+    expect_read!(receiver.recv(&mut buf), DATA1);
+    assert_would_block(receiver.recv(&mut buf));
+
+    // Using the default scope id.
+    assert_eq!(sender.default_scope_id().unwrap(), 0);
+    sender.set_default_scope_id(scope_id).unwrap();
+    assert_eq!(sender.default_scope_id().unwrap(), scope_id);
+    let unscoped = SocketAddr::new(address.into(), receiver_address.port());
+    checked_write!(sender.send_to(DATA2, unscoped));
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+    expect_read!(receiver.recv(&mut buf), DATA2);
+
+    // Link-local multicast, with and without explicit scope id.
+    let all_nodes = Ipv6Addr::new(0xff02, 0, 0, 0, 0, 0, 0, 1);
+    let scoped = SocketAddrV6::new(all_nodes, receiver_address.port(), 0, scope_id);
+    checked_write!(sender.send_to(DATA1, scoped.into()));
+    checked_write!(sender.send_to(DATA1, (all_nodes, receiver_address.port()).into()));
+}
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn set_default_scope_id_ipv4() {
+    let socket = UdpSocket::bind(any_local_address()).unwrap();
+    let err = socket.set_default_scope_id(1).unwrap_err();
+    assert_eq!(err.raw_os_error(), Some(libc::ENOPROTOOPT));
+}
+
+/// Returns a link-local address and the index of its interface, if any.
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn link_local_address() -> Option<(Ipv6Addr, u32)> {
+    // Format: address (hex), interface index (hex), prefix length, scope,
+    // flags and name.
+    let addresses = std::fs::read_to_string("/proc/net/if_inet6").ok()?;
+    addresses.lines().find_map(|line| {
+        let mut fields = line.split_whitespace();
+        let address = u128::from_str_radix(fields.next()?, 16).ok()?;
+        let index = u32::from_str_radix(fields.next()?, 16).ok()?;
+        // Link scope.
+        if fields.nth(1)? != "20" {
+            return None;
+        }
+        Some((Ipv6Addr::from(address), index))
+    })
+}
+
+#[test]
+#[cfg(unix)]
+fn bind_dual_stack() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let mut socket = UdpSocket::bind_dual_stack(0).unwrap();
+    let address = socket.local_addr().unwrap();
+    assert!(address.is_ipv6());
+    assert!(!socket.only_v6().unwrap());
+    assert_socket_non_blocking(&socket);
+    assert_socket_close_on_exec(&socket);
+    poll.registry()
+        .register(&mut socket, ID1, Interest::READABLE)
+        .unwrap();
+
+    let sender_v4 = net::UdpSocket::bind(any_local_address()).unwrap();
+    let sender_v6 = net::UdpSocket::bind(any_local_ipv6_address()).unwrap();
+
+    sender_v4
+        .send_to(DATA1, (Ipv4Addr::LOCALHOST, address.port()))
+        .unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+    let sender_address = sender_v4.local_addr().unwrap();
+    let mut buf = [0; 20];
+    let (n, got_address) = socket.recv_from(&mut buf).unwrap();
+    assert_eq!(&buf[..n], DATA1);
+    assert_would_block(socket.recv_from(&mut buf));
+    // IPv4 peers use a mapped address.
+    match got_address {
+        SocketAddr::V6(got) => {
+            assert_eq!(got.ip().to_ipv4_mapped(), Some(Ipv4Addr::LOCALHOST));
+            assert_eq!(got.port(), sender_address.port());
+        }
+        SocketAddr::V4(_) => panic!("unexpected IPv4 address: {got_address}"),
+    }
+    // Replying to the mapped address should reach the IPv4 peer.
+    checked_write!(socket.send_to(DATA2, got_address));
+    let n = sender_v4.recv(&mut buf).unwrap();
+    assert_eq!(&buf[..n], DATA2);
+
+    sender_v6
+        .send_to(DATA2, (Ipv6Addr::LOCALHOST, address.port()))
+        .unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+    expect_read!(
+        socket.recv_from(&mut buf),
+        DATA2,
+        sender_v6.local_addr().unwrap()
+    );
+}
+
+#[test]
+#[cfg(unix)]
+fn bind_with_protocol() {
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    let protocols = [libc::IPPROTO_UDP, libc::IPPROTO_UDPLITE];
+    #[cfg(not(any(target_os = "android", target_os = "linux")))]
+    let protocols = [libc::IPPROTO_UDP];
+
+    for protocol in protocols {
+        let (mut poll, mut events) = init_with_poll();
+
+        let mut socket = match UdpSocket::bind_with_protocol(any_local_address(), protocol) {
+            Ok(socket) => socket,
+            // Not all kernels are compiled with UDP-Lite support.
+            Err(ref err) if err.raw_os_error() == Some(libc::EPROTONOSUPPORT) => continue,
+            Err(err) => panic!("unexpected error: {}", err),
+        };
+        let address = socket.local_addr().unwrap();
+        assert_socket_non_blocking(&socket);
+        assert_socket_close_on_exec(&socket);
+
+        let sender = UdpSocket::bind_with_protocol(any_local_address(), protocol).unwrap();
+        poll.registry()
+            .register(&mut socket, ID1, Interest::READABLE)
+            .unwrap();
+
+        checked_write!(sender.send_to(DATA1, address));
+        expect_events(
+            &mut poll,
+            &mut events,
+            vec![ExpectEvent::new(ID1, Interest::READABLE)],
+        );
+        let mut buf = [0; 20];
+        expect_read!(
+            socket.recv_from(&mut buf),
+            DATA1,
+            sender.local_addr().unwrap()
+        );
+    }
+}
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn set_ip_transparent() {
+    let socket = UdpSocket::bind(any_local_address()).unwrap();
+    match socket.set_ip_transparent(true) {
+        Ok(()) => socket.set_ip_transparent(false).unwrap(),
+        // Requires `CAP_NET_ADMIN`.
+        Err(err) => assert_eq!(err.kind(), io::ErrorKind::PermissionDenied),
+    }
+}
+
+#[test]
+fn peer_addr() {
+    let socket = UdpSocket::bind(any_local_address()).unwrap();
+    let peer = UdpSocket::bind(any_local_address()).unwrap();
+    let peer_address = peer.local_addr().unwrap();
+
+    let err = socket.peer_addr().unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::NotConnected);
+
+    socket.connect(peer_address).unwrap();
+    assert_eq!(socket.peer_addr().unwrap(), peer_address);
+}
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn set_get_priority() {
+    let socket = UdpSocket::bind(any_local_address()).unwrap();
+
+    assert_eq!(socket.priority().unwrap(), 0);
+    socket.set_priority(5).unwrap();
+    assert_eq!(socket.priority().unwrap(), 5);
+}
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn set_dontfrag() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let mut socket = UdpSocket::bind(any_local_address()).unwrap();
+    let receiver = UdpSocket::bind(any_local_address()).unwrap();
+    socket.connect(receiver.local_addr().unwrap()).unwrap();
+    socket.set_dontfrag(true).unwrap();
+
+    poll.registry()
+        .register(&mut socket, ID1, Interest::WRITABLE)
+        .unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::WRITABLE)],
+    );
+
+    let mtu = socket.path_mtu().unwrap() as usize;
+    assert!(mtu > 0);
+    // Larger than the path MTU, including the IP and UDP headers.
+    let buf = vec![0; mtu];
+    let err = socket.send(&buf).unwrap_err();
+    assert_eq!(err.raw_os_error(), Some(libc::EMSGSIZE));
+
+    // Datagrams smaller than the MTU can still be send.
+    socket.send(DATA1).unwrap();
+    socket.set_dontfrag(false).unwrap();
+}
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn set_mtu_discover() {
+    use mio::net::MtuDiscover;
+
+    for addr in [any_local_address(), any_local_ipv6_address()] {
+        let socket = UdpSocket::bind(addr).unwrap();
+        let receiver = UdpSocket::bind(addr).unwrap();
+        socket.connect(receiver.local_addr().unwrap()).unwrap();
+
+        for mode in [
+            MtuDiscover::Want,
+            MtuDiscover::Dont,
+            MtuDiscover::Probe,
+            MtuDiscover::Do,
+        ] {
+            socket.set_mtu_discover(mode).unwrap();
+        }
+
+        let buf = vec![0; socket.path_mtu().unwrap() as usize];
+        let err = socket.send(&buf).unwrap_err();
+        assert_eq!(err.raw_os_error(), Some(libc::EMSGSIZE));
+    }
+}
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn recv_from_with_timestamp() {
+    use mio::net::TimestampMode;
+    use std::time::SystemTime;
+
+    let (mut poll, mut events) = init_with_poll();
+
+    let mut socket = UdpSocket::bind(any_local_address()).unwrap();
+    let address = socket.local_addr().unwrap();
+    let sender = UdpSocket::bind(any_local_address()).unwrap();
+    let sender_address = sender.local_addr().unwrap();
+
+    poll.registry()
+        .register(&mut socket, ID1, Interest::READABLE)
+        .unwrap();
+
+    let mut buf = [0; 20];
+    for mode in [
+        TimestampMode::Software,
+        TimestampMode::Hardware,
+        TimestampMode::Disabled,
+    ] {
+        socket.set_timestamping(mode).unwrap();
+
+        // The kernel enables timestamping of received packets lazily (using a
+        // work queue), so the first packets after enabling it can be missing
+        // a timestamp when using `SO_TIMESTAMPING`.
+        let mut attempts = 0;
+        let (timestamp, before, after) = loop {
+            let before = SystemTime::now();
+            checked_write!(sender.send_to(DATA1, address));
+            expect_events(
+                &mut poll,
+                &mut events,
+                vec![ExpectEvent::new(ID1, Interest::READABLE)],
+            );
+            let (n, from, timestamp) = socket.recv_from_with_timestamp(&mut buf).unwrap();
+            let after = SystemTime::now();
+            assert_eq!(&buf[..n], DATA1);
+            assert_eq!(from, sender_address);
+            assert_would_block(socket.recv_from_with_timestamp(&mut buf));
+
+            if timestamp.is_none() && mode != TimestampMode::Disabled && attempts < 10 {
+                attempts += 1;
+                thread::sleep(Duration::from_millis(10));
+                continue;
+            }
+            break (timestamp, before, after);
+        };
+
+        if mode == TimestampMode::Disabled {
+            assert_eq!(timestamp, None);
+        } else {
+            // Loopback doesn't support hardware timestamps, so we expect a
+            // software timestamp for both modes.
+            let timestamp = timestamp.expect("missing timestamp");
+            let slack = Duration::from_millis(100);
+            assert!(
+                timestamp + slack >= before,
+                "{:?} < {:?}",
+                timestamp,
+                before
+            );
+            assert!(timestamp <= after + slack, "{:?} > {:?}", timestamp, after);
+        }
+    }
+}
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn recv_from_with_info() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let loopback = unsafe { libc::if_nametoindex(b"lo\0".as_ptr().cast()) };
+    assert_ne!(loopback, 0);
+
+    let mut buf = [0; 20];
+    for (addr, unspecified) in [
+        (any_local_address(), IpAddr::V4(Ipv4Addr::UNSPECIFIED)),
+        (any_local_ipv6_address(), IpAddr::V6(Ipv6Addr::UNSPECIFIED)),
+    ] {
+        // Bound to the unspecified address to receive datagrams for any local
+        // address, in which case the destination isn't the local address.
+        let mut socket = UdpSocket::bind(SocketAddr::new(unspecified, 0)).unwrap();
+        let address = SocketAddr::new(addr.ip(), socket.local_addr().unwrap().port());
+        let sender = UdpSocket::bind(addr).unwrap();
+        let sender_address = sender.local_addr().unwrap();
+        poll.registry()
+            .register(&mut socket, ID1, Interest::READABLE)
+            .unwrap();
+
+        assert!(!socket.recv_pktinfo().unwrap());
+        for enable in [false, true] {
+            socket.set_recv_pktinfo(enable).unwrap();
+            assert_eq!(socket.recv_pktinfo().unwrap(), enable);
+
+            checked_write!(sender.send_to(DATA1, address));
+            expect_events(
+                &mut poll,
+                &mut events,
+                vec![ExpectEvent::new(ID1, Interest::READABLE)],
+            );
+            let (n, from, info) = socket.recv_from_with_info(&mut buf).unwrap();
+            assert_eq!(&buf[..n], DATA1);
+            assert_eq!(from, sender_address);
+            if enable {
+                assert_eq!(info.interface_index(), Some(loopback));
+                assert_eq!(info.destination(), Some(addr.ip()));
+            } else {
+                assert_eq!(info.interface_index(), None);
+                assert_eq!(info.destination(), None);
+            }
+            assert_would_block(socket.recv_from_with_info(&mut buf));
+        }
+    }
+}
+
+#[test]
+fn recv_batch() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let mut socket = UdpSocket::bind(any_local_address()).unwrap();
+    let address = socket.local_addr().unwrap();
+    let sender = UdpSocket::bind(any_local_address()).unwrap();
+    let sender_address = sender.local_addr().unwrap();
+
+    poll.registry()
+        .register(&mut socket, ID1, Interest::READABLE)
+        .unwrap();
+
+    assert!(socket.recv_batch(&mut []).unwrap().is_empty());
+    let mut buffers = vec![vec![0; 20]; 5];
+    assert_would_block(socket.recv_batch(&mut buffers));
+
+    checked_write!(sender.send_to(DATA1, address));
+    checked_write!(sender.send_to(DATA2, address));
+    checked_write!(sender.send_to(DATA1, address));
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+
+    let received = socket.recv_batch(&mut buffers).unwrap();
+    assert_eq!(
+        received,
+        vec![
+            (DATA1.len(), sender_address),
+            (DATA2.len(), sender_address),
+            (DATA1.len(), sender_address),
+        ]
+    );
+    assert_eq!(&buffers[0][..DATA1.len()], DATA1);
+    assert_eq!(&buffers[1][..DATA2.len()], DATA2);
+    assert_eq!(&buffers[2][..DATA1.len()], DATA1);
+
+    assert_would_block(socket.recv_batch(&mut buffers));
+
+    // Fewer buffers than datagrams.
+    checked_write!(sender.send_to(DATA1, address));
+    checked_write!(sender.send_to(DATA2, address));
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+    let received = socket.recv_batch(&mut buffers[..1]).unwrap();
+    assert_eq!(received, vec![(DATA1.len(), sender_address)]);
+    let received = socket.recv_batch(&mut buffers[..1]).unwrap();
+    assert_eq!(received, vec![(DATA2.len(), sender_address)]);
+    assert_eq!(&buffers[0][..DATA2.len()], DATA2);
+}
+
+#[test]
+#[cfg(unix)]
+fn recv_from_with_truncation() {
+    use mio::net::TruncationPolicy;
+
+    let (mut poll, mut events) = init_with_poll();
+
+    let mut socket = UdpSocket::bind(any_local_address()).unwrap();
+    let address = socket.local_addr().unwrap();
+    let sender = UdpSocket::bind(any_local_address()).unwrap();
+    let sender_address = sender.local_addr().unwrap();
+    poll.registry()
+        .register(&mut socket, ID1, Interest::READABLE)
+        .unwrap();
+
+    let mut buf = [0; 5];
+    assert_would_block(socket.recv_from_with_truncation(&mut buf, TruncationPolicy::Truncate));
+
+    let mut send_oversized = || {
+        checked_write!(sender.send_to(DATA1, address));
+        expect_events(
+            &mut poll,
+            &mut events,
+            vec![ExpectEvent::new(ID1, Interest::READABLE)],
+        );
+    };
+
+    send_oversized();
+    let res = socket.recv_from_with_truncation(&mut buf, TruncationPolicy::Truncate);
+    assert_eq!(res.unwrap(), (buf.len(), sender_address));
+    assert_eq!(buf, DATA1[..buf.len()]);
+
+    // Rearm the readiness for the poll(2) based selector.
+    assert_would_block(socket.recv_from(&mut buf));
+    send_oversized();
+    let err = socket
+        .recv_from_with_truncation(&mut buf, TruncationPolicy::Error)
+        .unwrap_err();
+    assert_eq!(err.raw_os_error(), Some(libc::EMSGSIZE));
+
+    assert_would_block(socket.recv_from(&mut buf));
+    send_oversized();
+    let res = socket.recv_from_with_truncation(&mut buf, TruncationPolicy::ReportLength);
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    {
+        assert_eq!(res.unwrap(), (DATA1.len(), sender_address));
+        assert_eq!(buf, DATA1[..buf.len()]);
+    }
+    #[cfg(not(any(target_os = "android", target_os = "linux")))]
+    {
+        assert_eq!(res.unwrap_err().kind(), io::ErrorKind::Unsupported);
+        let _ = socket.recv_from(&mut buf);
+    }
+
+    assert_would_block(socket.recv_from(&mut buf));
+
+    // Datagrams that fit are not affected by the policy.
+    checked_write!(sender.send_to(DATA2, address));
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+    let mut buf = [0; 20];
+    let res = socket.recv_from_with_truncation(&mut buf, TruncationPolicy::Error);
+    assert_eq!(res.unwrap(), (DATA2.len(), sender_address));
+    assert_eq!(&buf[..DATA2.len()], DATA2);
+    assert_would_block(socket.recv_from_with_truncation(&mut buf, TruncationPolicy::Error));
+}
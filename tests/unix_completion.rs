@@ -0,0 +1,78 @@
+#![cfg(all(unix, feature = "os-poll", feature = "os-ext", feature = "net"))]
+
+use std::thread;
+use std::time::Duration;
+
+use mio::unix::Completion;
+use mio::{Events, Interest, Poll, Token};
+
+mod util;
+use util::{assert_send, assert_sync, expect_events, expect_no_events, ExpectEvent, Readiness};
+
+const ID1: Token = Token(0);
+const ID2: Token = Token(1);
+
+#[test]
+fn is_send_and_sync() {
+    assert_send::<Completion<String>>();
+    assert_sync::<Completion<String>>();
+    assert_send::<mio::unix::Completer<String>>();
+    assert_sync::<mio::unix::Completer<String>>();
+}
+
+#[test]
+fn complete_from_thread() {
+    let mut poll = Poll::new().unwrap();
+    let mut events = Events::with_capacity(8);
+
+    let (mut completion1, completer1) = Completion::new().unwrap();
+    let (mut completion2, completer2) = Completion::<u64>::new().unwrap();
+    poll.registry()
+        .register(&mut completion1, ID1, Interest::READABLE)
+        .unwrap();
+    poll.registry()
+        .register(&mut completion2, ID2, Interest::READABLE)
+        .unwrap();
+    expect_no_events(&mut poll, &mut events);
+    assert_eq!(completion1.take(), None);
+
+    let handle = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(10));
+        completer1.complete(String::from("done"));
+    });
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+    assert_eq!(completion1.take().as_deref(), Some("done"));
+    assert_eq!(completion1.take(), None);
+    handle.join().unwrap();
+
+    // Dropping the completer also results in an event, but without a value.
+    drop(completer2);
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID2, Readiness::READ_CLOSED)],
+    );
+    assert_eq!(completion2.take(), None);
+}
+
+#[test]
+fn register_after_complete() {
+    let mut poll = Poll::new().unwrap();
+    let mut events = Events::with_capacity(8);
+
+    let (mut completion, completer) = Completion::new().unwrap();
+    completer.complete(123);
+    poll.registry()
+        .register(&mut completion, ID1, Interest::READABLE)
+        .unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+    assert_eq!(completion.take(), Some(123));
+}
@@ -127,6 +127,49 @@ fn unix_datagram_connect() {
         .unwrap();
 }
 
+#[test]
+fn unix_datagram_pair_flags() {
+    let (s1, s2) = UnixDatagram::pair().unwrap();
+    assert_socket_non_blocking(&s1);
+    assert_socket_close_on_exec(&s1);
+    assert_socket_non_blocking(&s2);
+    assert_socket_close_on_exec(&s2);
+}
+
+#[test]
+fn unix_datagram_recv_with_truncation() {
+    use mio::net::TruncationPolicy;
+
+    init();
+    let (datagram1, datagram2) = UnixDatagram::pair().unwrap();
+    let mut buf = [0; 5];
+
+    checked_write!(datagram1.send(DATA1));
+    let n = datagram2
+        .recv_with_truncation(&mut buf, TruncationPolicy::Truncate)
+        .unwrap();
+    assert_eq!(n, buf.len());
+    assert_eq!(buf, DATA1[..buf.len()]);
+
+    checked_write!(datagram1.send(DATA1));
+    let err = datagram2
+        .recv_with_truncation(&mut buf, TruncationPolicy::Error)
+        .unwrap_err();
+    assert_eq!(err.raw_os_error(), Some(libc::EMSGSIZE));
+
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    {
+        checked_write!(datagram1.send(DATA1));
+        let n = datagram2
+            .recv_with_truncation(&mut buf, TruncationPolicy::ReportLength)
+            .unwrap();
+        assert_eq!(n, DATA1.len());
+        assert_eq!(buf, DATA1[..buf.len()]);
+    }
+
+    assert_would_block(datagram2.recv_with_truncation(&mut buf, TruncationPolicy::Truncate));
+}
+
 #[test]
 fn unix_datagram_pair() {
     let (mut poll, mut events) = init_with_poll();
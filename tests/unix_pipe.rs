@@ -225,3 +225,90 @@ pub fn expect_one_closed_event(poll: &mut Poll, events: &mut Events, token: Toke
     }
     assert!(iter.next().is_none());
 }
+
+#[test]
+fn set_nonblocking_raw_fd() {
+    use std::fs::File;
+    use std::os::fd::{AsRawFd, FromRawFd};
+
+    let mut fds = [-1; 2];
+    assert_eq!(unsafe { libc::pipe(fds.as_mut_ptr()) }, 0);
+    let mut receiver = unsafe { File::from_raw_fd(fds[0]) };
+    let mut sender = unsafe { File::from_raw_fd(fds[1]) };
+
+    // Pipes start in blocking mode.
+    assert!(!mio::unix::is_nonblocking(receiver.as_raw_fd()).unwrap());
+
+    mio::unix::set_nonblocking(receiver.as_raw_fd(), true).unwrap();
+    assert!(mio::unix::is_nonblocking(receiver.as_raw_fd()).unwrap());
+    let mut buf = [0; 20];
+    assert_would_block(receiver.read(&mut buf));
+
+    // In blocking mode the read should wait for the write.
+    mio::unix::set_nonblocking(receiver.as_raw_fd(), false).unwrap();
+    assert!(!mio::unix::is_nonblocking(receiver.as_raw_fd()).unwrap());
+    let handle = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(50));
+        sender.write_all(DATA1).unwrap();
+    });
+    let n = receiver.read(&mut buf).unwrap();
+    assert_eq!(&buf[..n], DATA1);
+    handle.join().unwrap();
+}
+
+#[test]
+fn from_child_pipes() {
+    let mut child = Command::new("sh")
+        .args(["-c", "cat; echo error >&2"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("failed to start `sh` command");
+
+    let mut sender = Sender::from_child_stdin(&mut child).unwrap();
+    let mut stdout = Receiver::from_child_stdout(&mut child).unwrap();
+    let mut stderr = Receiver::from_child_stderr(&mut child).unwrap();
+    assert!(child.stdin.is_none() && child.stdout.is_none() && child.stderr.is_none());
+
+    // Pipes are already taken.
+    let err = Receiver::from_child_stdout(&mut child).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::NotFound);
+    let err = Sender::from_child_stdin(&mut child).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::NotFound);
+
+    // Non-blocking.
+    let mut buf = [0; 20];
+    assert_would_block(stdout.read(&mut buf));
+    assert_would_block(stderr.read(&mut buf));
+
+    let mut poll = Poll::new().unwrap();
+    let mut events = Events::with_capacity(8);
+    poll.registry()
+        .register(&mut stdout, RECEIVER, Interest::READABLE)
+        .unwrap();
+    poll.registry()
+        .register(&mut stderr, Token(2), Interest::READABLE)
+        .unwrap();
+
+    assert_eq!(sender.write(DATA1).unwrap(), DATA1.len());
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(RECEIVER, Interest::READABLE)],
+    );
+    let n = stdout.read(&mut buf).unwrap();
+    assert_eq!(&buf[..n], DATA1);
+
+    // Closing stdin makes `cat` exit.
+    drop(sender);
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(Token(2), Interest::READABLE)],
+    );
+    let n = stderr.read(&mut buf).unwrap();
+    assert_eq!(&buf[..n], b"error\n");
+
+    child.wait().unwrap();
+}
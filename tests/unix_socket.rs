@@ -0,0 +1,289 @@
+#![cfg(all(unix, feature = "os-poll", feature = "os-ext", feature = "net"))]
+
+use std::io;
+use std::mem::size_of;
+use std::net::{self, Ipv4Addr, SocketAddr, SocketAddrV4};
+use std::slice;
+
+use log::info;
+use mio::unix::Socket;
+use mio::{Interest, Token};
+
+mod util;
+use util::{
+    assert_socket_close_on_exec, assert_socket_non_blocking, assert_would_block, expect_events,
+    init_with_poll, ExpectEvent,
+};
+
+const ID1: Token = Token(0);
+
+const DATA1: &[u8] = b"Hello world!";
+const DATA2: &[u8] = b"Hello mars!";
+
+fn raw_addr(addr: SocketAddrV4) -> libc::sockaddr_in {
+    libc::sockaddr_in {
+        sin_family: libc::AF_INET as libc::sa_family_t,
+        sin_port: addr.port().to_be(),
+        sin_addr: libc::in_addr {
+            s_addr: u32::from_ne_bytes(addr.ip().octets()),
+        },
+        ..unsafe { std::mem::zeroed() }
+    }
+}
+
+fn as_bytes(addr: &libc::sockaddr_in) -> &[u8] {
+    unsafe {
+        slice::from_raw_parts(
+            addr as *const _ as *const u8,
+            size_of::<libc::sockaddr_in>(),
+        )
+    }
+}
+
+fn from_bytes(addr: &[u8]) -> SocketAddrV4 {
+    assert_eq!(addr.len(), size_of::<libc::sockaddr_in>());
+    let addr: libc::sockaddr_in = unsafe { std::ptr::read_unaligned(addr.as_ptr().cast()) };
+    assert_eq!(addr.sin_family, libc::AF_INET as libc::sa_family_t);
+    SocketAddrV4::new(
+        Ipv4Addr::from(addr.sin_addr.s_addr.to_ne_bytes()),
+        u16::from_be(addr.sin_port),
+    )
+}
+
+#[test]
+fn socket_udp() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let mut socket = Socket::new(libc::AF_INET, libc::SOCK_DGRAM, 0).unwrap();
+    assert_socket_non_blocking(&socket);
+    assert_socket_close_on_exec(&socket);
+
+    socket
+        .bind(as_bytes(&raw_addr(SocketAddrV4::new(
+            Ipv4Addr::LOCALHOST,
+            0,
+        ))))
+        .unwrap();
+    let mut addr = [0; size_of::<libc::sockaddr_in>()];
+    let n = socket.local_addr(&mut addr).unwrap();
+    let address = from_bytes(&addr[..n]);
+    assert_ne!(address.port(), 0);
+
+    poll.registry()
+        .register(&mut socket, ID1, Interest::READABLE)
+        .unwrap();
+
+    let mut buf = [0; 20];
+    assert_would_block(socket.recv(&mut buf));
+
+    let peer = net::UdpSocket::bind("127.0.0.1:0").unwrap();
+    let peer_address = match peer.local_addr().unwrap() {
+        SocketAddr::V4(addr) => addr,
+        SocketAddr::V6(_) => unreachable!(),
+    };
+    peer.send_to(DATA1, address).unwrap();
+
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+    let mut addr = [0; size_of::<libc::sockaddr_in>()];
+    let (n, addr_len) = socket.recv_from(&mut buf, &mut addr).unwrap();
+    assert_eq!(&buf[..n], DATA1);
+    assert_eq!(from_bytes(&addr[..addr_len]), peer_address);
+    assert_would_block(socket.recv_from(&mut buf, &mut addr));
+
+    let n = socket
+        .send_to(DATA2, as_bytes(&raw_addr(peer_address)))
+        .unwrap();
+    assert_eq!(n, DATA2.len());
+    let (n, from) = peer.recv_from(&mut buf).unwrap();
+    assert_eq!(&buf[..n], DATA2);
+    assert_eq!(from, SocketAddr::V4(address));
+
+    // Connected.
+    socket.connect(as_bytes(&raw_addr(peer_address))).unwrap();
+    assert_eq!(socket.send(DATA1).unwrap(), DATA1.len());
+    let (n, _) = peer.recv_from(&mut buf).unwrap();
+    assert_eq!(&buf[..n], DATA1);
+
+    peer.send_to(DATA2, address).unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+    let n = socket.recv(&mut buf).unwrap();
+    assert_eq!(&buf[..n], DATA2);
+
+    assert!(socket.take_error().unwrap().is_none());
+}
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn netlink_socket() {
+    use mio::unix::NetlinkSocket;
+
+    let (mut poll, mut events) = init_with_poll();
+
+    let mut socket = NetlinkSocket::new(libc::NETLINK_ROUTE).unwrap();
+    assert_socket_non_blocking(&socket);
+    assert_socket_close_on_exec(&socket);
+    socket.bind(0).unwrap();
+
+    poll.registry()
+        .register(&mut socket, ID1, Interest::READABLE)
+        .unwrap();
+
+    let mut buf = [0; 8192];
+    assert_would_block(socket.recv(&mut buf));
+
+    // Request a dump of all network links: `nlmsghdr` followed by an
+    // (all zero) `ifinfomsg`.
+    const HEADER_LEN: usize = size_of::<libc::nlmsghdr>();
+    const REQUEST_LEN: usize = HEADER_LEN + 16;
+    let header = libc::nlmsghdr {
+        nlmsg_len: REQUEST_LEN as u32,
+        nlmsg_type: libc::RTM_GETLINK,
+        nlmsg_flags: (libc::NLM_F_REQUEST | libc::NLM_F_DUMP) as u16,
+        nlmsg_seq: 1,
+        nlmsg_pid: 0,
+    };
+    let mut request = [0; REQUEST_LEN];
+    request[..HEADER_LEN].copy_from_slice(unsafe {
+        slice::from_raw_parts(&header as *const _ as *const u8, HEADER_LEN)
+    });
+    assert_eq!(socket.send(&request).unwrap(), REQUEST_LEN);
+
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+    let n = socket.recv(&mut buf).unwrap();
+    assert!(n >= HEADER_LEN);
+    let header: libc::nlmsghdr = unsafe { std::ptr::read_unaligned(buf.as_ptr().cast()) };
+    // At least the loopback link should exist.
+    assert_eq!(header.nlmsg_type, libc::RTM_NEWLINK);
+    assert_eq!(header.nlmsg_seq, 1);
+    assert!(socket.take_error().unwrap().is_none());
+}
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn icmp_socket_dgram() {
+    use mio::unix::IcmpSocket;
+
+    let mut socket = match IcmpSocket::dgram_v4() {
+        Ok(socket) => socket,
+        // Not allowed by `net.ipv4.ping_group_range`.
+        Err(ref err) if err.kind() == io::ErrorKind::PermissionDenied => {
+            info!("not allowed to create ICMP datagram sockets, skipping test");
+            return;
+        }
+        Err(err) => panic!("unexpected error creating ICMP socket: {err}"),
+    };
+    assert_socket_non_blocking(&socket);
+    assert_socket_close_on_exec(&socket);
+    socket
+        .bind(SocketAddr::new(Ipv4Addr::LOCALHOST.into(), 0))
+        .unwrap();
+    let identifier = socket.local_addr().unwrap().port();
+
+    // Identifier and checksum are filled in by the kernel.
+    let request = echo_request(0, 1);
+    let reply = icmp_echo(&mut socket, &request);
+    assert_eq!(reply[0], ICMP_ECHO_REPLY);
+    assert_eq!(u16::from_be_bytes([reply[4], reply[5]]), identifier);
+    assert_eq!(&reply[6..], &request[6..]);
+}
+
+#[test]
+fn icmp_socket_raw() {
+    use mio::unix::IcmpSocket;
+
+    let mut socket = match IcmpSocket::raw_v4() {
+        Ok(socket) => socket,
+        // Requires `CAP_NET_RAW`.
+        Err(ref err) if err.kind() == io::ErrorKind::PermissionDenied => {
+            info!("not allowed to create raw ICMP sockets, skipping test");
+            return;
+        }
+        Err(err) => panic!("unexpected error creating ICMP socket: {err}"),
+    };
+    assert_socket_non_blocking(&socket);
+    assert_socket_close_on_exec(&socket);
+
+    let request = echo_request(0x1234, 2);
+    let reply = icmp_echo(&mut socket, &request);
+    // Raw IPv4 sockets include the IP header.
+    let header_len = usize::from(reply[0] & 0x0f) * 4;
+    let reply = &reply[header_len..];
+    assert_eq!(reply[0], ICMP_ECHO_REPLY);
+    assert_eq!(&reply[4..], &request[4..]);
+}
+
+const ICMP_ECHO_REQUEST: u8 = 8;
+const ICMP_ECHO_REPLY: u8 = 0;
+
+/// Returns an ICMP echo request with payload `DATA1`.
+fn echo_request(identifier: u16, sequence: u16) -> Vec<u8> {
+    let mut request = vec![ICMP_ECHO_REQUEST, 0, 0, 0];
+    request.extend_from_slice(&identifier.to_be_bytes());
+    request.extend_from_slice(&sequence.to_be_bytes());
+    request.extend_from_slice(DATA1);
+    // Internet checksum, see RFC 1071.
+    let mut sum: u32 = request
+        .chunks(2)
+        .map(|c| u32::from(u16::from_be_bytes([c[0], *c.get(1).unwrap_or(&0)])))
+        .sum();
+    while sum > 0xffff {
+        sum = (sum & 0xffff) + (sum >> 16);
+    }
+    request[2..4].copy_from_slice(&(!(sum as u16)).to_be_bytes());
+    request
+}
+
+/// Sends `request` to localhost and returns the reply.
+fn icmp_echo(socket: &mut mio::unix::IcmpSocket, request: &[u8]) -> Vec<u8> {
+    let (mut poll, mut events) = init_with_poll();
+    poll.registry()
+        .register(socket, ID1, Interest::READABLE)
+        .unwrap();
+
+    let mut buf = [0; 512];
+    assert_would_block(socket.recv_from(&mut buf));
+
+    let localhost = Ipv4Addr::LOCALHOST.into();
+    assert_eq!(socket.send_to(request, localhost).unwrap(), request.len());
+
+    // Raw sockets also receive our own request.
+    loop {
+        expect_events(
+            &mut poll,
+            &mut events,
+            vec![ExpectEvent::new(ID1, Interest::READABLE)],
+        );
+        loop {
+            match socket.recv_from(&mut buf) {
+                Ok((n, addr)) => {
+                    assert_eq!(addr, localhost);
+                    let message = &buf[..n];
+                    // Skip the IP header, if any.
+                    let offset = if message[0] >> 4 == 4 {
+                        usize::from(message[0] & 0x0f) * 4
+                    } else {
+                        0
+                    };
+                    if message[offset] == ICMP_ECHO_REPLY {
+                        assert!(socket.take_error().unwrap().is_none());
+                        return message.to_vec();
+                    }
+                }
+                Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => break,
+                Err(err) => panic!("unexpected error receiving ICMP reply: {err}"),
+            }
+        }
+    }
+}
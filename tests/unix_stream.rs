@@ -145,6 +145,15 @@ fn unix_stream_from_std() {
     )
 }
 
+#[test]
+fn unix_stream_pair_flags() {
+    let (s1, s2) = UnixStream::pair().unwrap();
+    assert_socket_non_blocking(&s1);
+    assert_socket_close_on_exec(&s1);
+    assert_socket_non_blocking(&s2);
+    assert_socket_close_on_exec(&s2);
+}
+
 #[test]
 fn unix_stream_pair() {
     let (mut poll, mut events) = init_with_poll();
@@ -428,6 +437,47 @@ fn unix_stream_shutdown_listener_write() {
     handle.join().unwrap();
 }
 
+#[test]
+#[cfg_attr(target_os = "hurd", ignore = "POLLRDHUP isn't supported on GNU/Hurd")]
+#[cfg_attr(target_os = "solaris", ignore = "POLLRDHUP isn't supported on Solaris")]
+#[cfg_attr(target_os = "nto", ignore = "POLLRDHUP isn't supported on NTO")]
+fn unix_stream_pair_shutdown_write() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let (mut s1, mut s2) = UnixStream::pair().unwrap();
+    poll.registry()
+        .register(&mut s2, TOKEN_2, Interest::READABLE | Interest::WRITABLE)
+        .unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(TOKEN_2, Interest::WRITABLE)],
+    );
+
+    checked_write!(s1.write(DATA1));
+    s1.shutdown(Shutdown::Write).unwrap();
+    let err = s1.write(DATA1).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::BrokenPipe);
+
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(
+            TOKEN_2,
+            Readiness::READABLE | Readiness::READ_CLOSED,
+        )],
+    );
+
+    // Data written before the shutdown is still received, followed by EOF.
+    let mut buf = [0; DEFAULT_BUF_SIZE];
+    expect_read!(s2.read(&mut buf), DATA1);
+    assert_eq!(s2.read(&mut buf).unwrap(), 0);
+
+    // The reverse direction still works.
+    checked_write!(s2.write(DATA2));
+    expect_read!(s1.read(&mut buf), DATA2);
+}
+
 #[test]
 #[cfg_attr(
     target_os = "hurd",
@@ -499,6 +549,91 @@ fn unix_stream_deregister() {
     handle.join().unwrap();
 }
 
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn unix_stream_splice() {
+    use mio::unix::SplicePipe;
+
+    init();
+    let (mut client, from) = UnixStream::pair().unwrap();
+    let (mut to, mut server) = UnixStream::pair().unwrap();
+    let mut pipe = SplicePipe::new().unwrap();
+
+    // Nothing to read from the source.
+    assert_would_block(pipe.splice(&from, &to, 4096));
+    assert!(pipe.is_empty());
+
+    let data: Vec<u8> = (0..1024 * 1024).map(|i| (i % 251) as u8).collect();
+    let mut sent = 0;
+    let mut received = Vec::with_capacity(data.len());
+    let mut buf = vec![0; 64 * 1024];
+    while received.len() < data.len() {
+        while sent < data.len() {
+            match client.write(&data[sent..]) {
+                Ok(n) => sent += n,
+                Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => break,
+                Err(err) => panic!("unexpected error: {err}"),
+            }
+        }
+        drain(&mut pipe, &from, &to);
+        read_all(&mut server, &mut buf, &mut received);
+    }
+    assert!(pipe.is_empty());
+    assert!(received == data, "proxied bytes differ");
+
+    // Fill the destination, the spliced bytes must be kept in the pipe until
+    // it's writable again.
+    let mut filled = 0;
+    loop {
+        match to.write(&buf) {
+            Ok(n) => filled += n,
+            Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => break,
+            Err(err) => panic!("unexpected error: {err}"),
+        }
+    }
+    client.write_all(DATA1).unwrap();
+    assert_would_block(pipe.splice(&from, &to, 4096));
+    assert_eq!(pipe.buffered(), DATA1_LEN);
+    // Nothing is read from the source until the pipe is empty.
+    client.write_all(DATA2).unwrap();
+    assert_would_block(pipe.splice(&from, &to, 4096));
+    assert_eq!(pipe.buffered(), DATA1_LEN);
+
+    received.clear();
+    while received.len() < filled + DATA1_LEN + DATA2_LEN {
+        read_all(&mut server, &mut buf, &mut received);
+        drain(&mut pipe, &from, &to);
+    }
+    assert!(pipe.is_empty());
+    assert_eq!(&received[filled..filled + DATA1_LEN], DATA1);
+    assert_eq!(&received[filled + DATA1_LEN..], DATA2);
+
+    // End of file on the source.
+    drop(client);
+    assert_eq!(pipe.splice(&from, &to, 4096).unwrap(), 0);
+
+    fn drain(pipe: &mut SplicePipe, from: &UnixStream, to: &UnixStream) {
+        loop {
+            match pipe.splice(from, to, 64 * 1024) {
+                Ok(0) => panic!("unexpected end of file"),
+                Ok(_) => {}
+                Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => break,
+                Err(err) => panic!("unexpected error: {err}"),
+            }
+        }
+    }
+
+    fn read_all(stream: &mut UnixStream, buf: &mut [u8], received: &mut Vec<u8>) {
+        loop {
+            match stream.read(buf) {
+                Ok(n) => received.extend_from_slice(&buf[..n]),
+                Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => break,
+                Err(err) => panic!("unexpected error: {err}"),
+            }
+        }
+    }
+}
+
 fn smoke_test<F>(connect_stream: F, test_name: &'static str)
 where
     F: FnOnce(&Path) -> io::Result<UnixStream>,
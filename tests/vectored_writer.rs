@@ -0,0 +1,110 @@
+#![cfg(all(unix, feature = "os-poll", feature = "net"))]
+
+use std::io::{self, Read};
+use std::os::fd::AsRawFd;
+use std::time::Duration;
+
+use mio::io::VectoredWriter;
+use mio::net::UnixStream;
+use mio::{Interest, Token};
+
+mod util;
+use util::{assert_send, assert_sync, init_with_poll};
+
+const SENDER: Token = Token(0);
+const RECEIVER: Token = Token(1);
+
+#[test]
+fn is_send_and_sync() {
+    assert_send::<VectoredWriter<Vec<u8>>>();
+    assert_sync::<VectoredWriter<Vec<u8>>>();
+}
+
+#[test]
+fn vectored_writer_partial_writes() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let (mut sender, mut receiver) = UnixStream::pair().unwrap();
+    // Small send buffer, to force partial writes.
+    let size: libc::c_int = 4096;
+    assert_ne!(
+        unsafe {
+            libc::setsockopt(
+                sender.as_raw_fd(),
+                libc::SOL_SOCKET,
+                libc::SO_SNDBUF,
+                (&size as *const libc::c_int).cast(),
+                std::mem::size_of::<libc::c_int>() as libc::socklen_t,
+            )
+        },
+        -1
+    );
+    poll.registry()
+        .register(&mut sender, SENDER, Interest::WRITABLE)
+        .unwrap();
+    poll.registry()
+        .register(&mut receiver, RECEIVER, Interest::READABLE)
+        .unwrap();
+
+    // Buffers of different sizes, including an empty one.
+    let bufs: Vec<Vec<u8>> = (0..100usize)
+        .map(|i| (0..(i * 97) % 4000).map(|j| (i + j) as u8).collect())
+        .collect();
+    let expected: Vec<u8> = bufs.concat();
+    let mut writer = VectoredWriter::new(bufs);
+    assert_eq!(writer.remaining(), expected.len());
+
+    let mut received = Vec::new();
+    let mut partial_writes = 0;
+    while received.len() < expected.len() {
+        poll.poll(&mut events, Some(Duration::from_secs(1)))
+            .unwrap();
+        assert!(!events.is_empty(), "missing events");
+        for event in events.iter() {
+            match event.token() {
+                SENDER => match writer.write_to(&mut &sender) {
+                    Ok(()) => assert_eq!(writer.remaining(), 0),
+                    Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => {
+                        assert!(writer.remaining() > 0);
+                        partial_writes += 1;
+                    }
+                    Err(err) => panic!("unexpected error: {err}"),
+                },
+                RECEIVER => {
+                    let mut buf = [0; 1024];
+                    loop {
+                        match receiver.read(&mut buf) {
+                            Ok(n) => received.extend_from_slice(&buf[..n]),
+                            Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => break,
+                            Err(err) => panic!("unexpected error: {err}"),
+                        }
+                    }
+                }
+                token => panic!("unexpected token: {token:?}"),
+            }
+        }
+    }
+
+    assert!(writer.is_done());
+    assert!(partial_writes > 1, "expected multiple partial writes");
+    assert!(received == expected, "received unexpected data");
+    // Writing again is a no-op.
+    writer.write_to(&mut &sender).unwrap();
+    assert_eq!(writer.into_inner().len(), 100);
+}
+
+#[test]
+fn vectored_writer_zero_write() {
+    let mut writer = VectoredWriter::new(vec![&b"hello"[..], b"", b" world"]);
+    let mut buf = [0; 4];
+    let err = writer.write_to(&mut &mut buf[..]).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::WriteZero);
+    assert_eq!(&buf, b"hell");
+    assert_eq!(writer.remaining(), 7);
+
+    let mut output = Vec::new();
+    writer.push(b"!");
+    writer.write_to(&mut output).unwrap();
+    assert_eq!(output, b"o world!");
+    assert!(writer.is_done());
+}
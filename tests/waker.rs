@@ -1,8 +1,11 @@
 #![cfg(not(target_os = "wasi"))]
 #![cfg(all(feature = "os-poll", feature = "net"))]
 
-use mio::{Events, Poll, Token, Waker};
-use std::sync::{Arc, Barrier};
+use mio::net::UdpSocket;
+use mio::{CancelNotifier, Events, Interest, Poll, Token, Waker, WakerChannel, WakerSender};
+use std::io;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Barrier, Mutex};
 use std::thread;
 use std::time::Duration;
 
@@ -13,6 +16,11 @@ use util::{assert_send, assert_sync, expect_no_events, init};
 fn is_send_and_sync() {
     assert_send::<Waker>();
     assert_sync::<Waker>();
+    assert_send::<WakerChannel<u64>>();
+    assert_send::<WakerSender<u64>>();
+    assert_sync::<WakerSender<u64>>();
+    assert_send::<CancelNotifier>();
+    assert_sync::<CancelNotifier>();
 }
 
 #[test]
@@ -107,6 +115,132 @@ fn waker_multiple_wakeups_different_thread() {
     handle2.join().unwrap();
 }
 
+#[test]
+fn waker_wakeups_never_lost() {
+    init();
+
+    let mut poll = Poll::new().expect("unable to create new Poll instance");
+    let mut events = Events::with_capacity(10);
+
+    let token = Token(10);
+    let waker = Arc::new(Waker::new(poll.registry(), token).expect("unable to create waker"));
+
+    // Ping-pong: every wake must cause the poll to return, even though the
+    // previous wakes were never drained by the user.
+    const ROUNDS: usize = 1000;
+    let barrier = Arc::new(Barrier::new(2));
+    let handle = thread::spawn({
+        let waker = waker.clone();
+        let barrier = barrier.clone();
+        move || {
+            for _ in 0..ROUNDS {
+                waker.wake().expect("unable to wake");
+                barrier.wait();
+            }
+        }
+    });
+    for _ in 0..ROUNDS {
+        poll.poll(&mut events, Some(Duration::from_secs(5)))
+            .unwrap();
+        assert!(!events.is_empty(), "lost wake up");
+        for event in events.iter() {
+            assert_eq!(event.token(), token);
+            assert!(event.is_readable());
+        }
+        barrier.wait();
+    }
+    handle.join().unwrap();
+
+    // Free running: wakes and polls interleave arbitrarily. As long as not all
+    // wakes have started the poll must not time out.
+    let woken = Arc::new(AtomicUsize::new(0));
+    let handle = thread::spawn({
+        let waker = waker.clone();
+        let woken = woken.clone();
+        move || {
+            for _ in 0..ROUNDS {
+                // Increment before waking, so that if the count is read
+                // after the poll returned the wake must cause another return.
+                let n = woken.fetch_add(1, Ordering::AcqRel) + 1;
+                waker.wake().expect("unable to wake");
+                if n % 16 == 0 {
+                    thread::yield_now();
+                }
+            }
+        }
+    });
+    loop {
+        poll.poll(&mut events, Some(Duration::from_secs(5)))
+            .unwrap();
+        assert!(!events.is_empty(), "lost wake up");
+        if woken.load(Ordering::Acquire) == ROUNDS {
+            break;
+        }
+    }
+    handle.join().unwrap();
+}
+
+#[test]
+fn waker_reset() {
+    init();
+
+    let mut poll = Poll::new().expect("unable to create new Poll instance");
+    let mut events = Events::with_capacity(10);
+
+    let token = Token(10);
+    let waker = Arc::new(Waker::new(poll.registry(), token).expect("unable to create waker"));
+
+    // Resetting clears pending wake ups. Not possible on Windows.
+    #[cfg(not(windows))]
+    {
+        waker.wake().expect("unable to wake");
+        waker.wake().expect("unable to wake");
+        waker.reset().expect("unable to reset");
+        expect_no_events(&mut poll, &mut events);
+    }
+    // Resetting without a pending wake up is fine, and doesn't prevent
+    // future wake ups.
+    waker.reset().expect("unable to reset");
+    waker.wake().expect("unable to wake");
+    expect_waker_event(&mut poll, &mut events, token);
+
+    // Reset, then check the queue, then poll: no work is lost, and no event
+    // is returned for work that was already processed.
+    const ITEMS: usize = 1000;
+    let queue = Arc::new(Mutex::new(Vec::new()));
+    let handle = thread::spawn({
+        let waker = waker.clone();
+        let queue = queue.clone();
+        move || {
+            for n in 0..ITEMS {
+                queue.lock().unwrap().push(n);
+                waker.wake().expect("unable to wake");
+                if n % 16 == 0 {
+                    thread::yield_now();
+                }
+            }
+        }
+    });
+    let mut received = Vec::new();
+    loop {
+        waker.reset().expect("unable to reset");
+        received.extend(queue.lock().unwrap().drain(..));
+        if received.len() == ITEMS {
+            break;
+        }
+        poll.poll(&mut events, Some(Duration::from_secs(5)))
+            .unwrap();
+        assert!(!events.is_empty(), "lost wake up");
+    }
+    handle.join().unwrap();
+    assert_eq!(received, (0..ITEMS).collect::<Vec<_>>());
+
+    // All work was processed after the last reset, the last wake up must not
+    // cause a spurious event.
+    #[cfg(not(windows))]
+    expect_no_events(&mut poll, &mut events);
+}
+
 #[test]
 #[cfg_attr(
     not(debug_assertions),
@@ -152,6 +286,262 @@ fn using_multiple_wakers_panics_different_cloned_registries() {
     drop(waker2);
 }
 
+#[test]
+fn waker_channel_multiple_threads() {
+    init();
+
+    const THREADS: usize = 4;
+    const MESSAGES: usize = 100;
+
+    let mut poll = Poll::new().expect("unable to create new Poll instance");
+    let mut events = Events::with_capacity(10);
+
+    let token = Token(10);
+    let channel = WakerChannel::unbounded(poll.registry(), token).unwrap();
+
+    let handles: Vec<_> = (0..THREADS)
+        .map(|id| {
+            let sender = channel.sender();
+            thread::spawn(move || {
+                for n in 0..MESSAGES {
+                    sender.send((id, n)).expect("unable to send");
+                }
+            })
+        })
+        .collect();
+
+    let mut next = [0; THREADS];
+    while next.iter().any(|n| *n != MESSAGES) {
+        poll.poll(&mut events, Some(Duration::from_secs(1)))
+            .unwrap();
+        assert!(!events.is_empty(), "missing waker event");
+        for event in events.iter() {
+            assert_eq!(event.token(), token);
+            assert!(event.is_readable());
+            for (id, n) in channel.try_iter() {
+                // Messages must be received in the order they were send.
+                assert_eq!(next[id], n);
+                next[id] += 1;
+            }
+        }
+    }
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    assert!(channel.try_recv().is_none());
+}
+
+#[test]
+fn waker_channel_bounded() {
+    init();
+
+    let mut poll = Poll::new().expect("unable to create new Poll instance");
+    let mut events = Events::with_capacity(10);
+
+    let token = Token(10);
+    // Would never accept a message.
+    let err = WakerChannel::<u64>::bounded(poll.registry(), token, 0).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+
+    let channel = WakerChannel::bounded(poll.registry(), token, 2).unwrap();
+    let sender = channel.sender();
+
+    sender.try_send(1).unwrap();
+    sender.send(2).unwrap();
+    let err = sender.try_send(3).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::WouldBlock);
+
+    expect_waker_event(&mut poll, &mut events, token);
+    assert_eq!(channel.try_iter().collect::<Vec<_>>(), [1, 2]);
+    assert!(channel.try_recv().is_none());
+
+    drop(channel);
+    let err = sender.send(4).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::BrokenPipe);
+}
+
+#[test]
+#[cfg(unix)]
+fn waker_wake_from_signal() {
+    use std::sync::OnceLock;
+
+    static WAKER: OnceLock<Waker> = OnceLock::new();
+
+    extern "C" fn handle_signal(_: libc::c_int) {
+        if let Some(waker) = WAKER.get() {
+            waker
+                .wake_from_signal()
+                .expect("unable to wake from signal");
+        }
+    }
+
+    init();
+
+    let mut poll = Poll::new().expect("unable to create new Poll instance");
+    let mut events = Events::with_capacity(10);
+
+    let token = Token(10);
+    let waker = Waker::new(poll.registry(), token).expect("unable to create waker");
+    assert!(WAKER.set(waker).is_ok());
+
+    unsafe {
+        let mut action: libc::sigaction = std::mem::zeroed();
+        action.sa_sigaction = handle_signal as extern "C" fn(libc::c_int) as libc::sighandler_t;
+        assert_eq!(libc::sigemptyset(&mut action.sa_mask), 0);
+        assert_eq!(
+            libc::sigaction(libc::SIGUSR1, &action, std::ptr::null_mut()),
+            0
+        );
+    }
+
+    // Multiple signals should result in a single event. `raise` delivers the
+    // signal to the current thread before returning, `kill` could deliver it
+    // to any thread, possibly after we polled.
+    for _ in 0..3 {
+        assert_eq!(unsafe { libc::raise(libc::SIGUSR1) }, 0);
+    }
+    expect_waker_event(&mut poll, &mut events, token);
+    expect_no_events(&mut poll, &mut events);
+
+    assert_eq!(unsafe { libc::raise(libc::SIGUSR1) }, 0);
+    expect_waker_event(&mut poll, &mut events, token);
+}
+
+#[test]
+fn poll_cancellable() {
+    init();
+
+    let mut poll = Poll::new().expect("unable to create new Poll instance");
+    let mut events = Events::with_capacity(10);
+    let cancel = Arc::new(AtomicBool::new(false));
+
+    // Works besides a user's `Waker`.
+    let token = Token(10);
+    let waker = Waker::new(poll.registry(), token).expect("unable to create waker");
+    let notifier = poll.cancel_notifier().unwrap();
+
+    let timeout = Some(Duration::from_millis(10));
+    assert!(!poll
+        .poll_cancellable(&mut events, timeout, &cancel)
+        .unwrap());
+    assert!(events.is_empty());
+
+    // Notifications without setting the flag are removed from the events, the
+    // notifier can be used multiple times.
+    for _ in 0..3 {
+        notifier.notify().unwrap();
+        assert!(!poll
+            .poll_cancellable(&mut events, timeout, &cancel)
+            .unwrap());
+        assert!(events.is_empty());
+        expect_no_events(&mut poll, &mut events);
+    }
+
+    waker.wake().unwrap();
+    assert!(!poll
+        .poll_cancellable(&mut events, timeout, &cancel)
+        .unwrap());
+    assert_eq!(
+        events.iter().map(|event| event.token()).collect::<Vec<_>>(),
+        [token]
+    );
+
+    let handle = thread::spawn({
+        let cancel = cancel.clone();
+        let notifier = poll.cancel_notifier().unwrap();
+        move || {
+            thread::sleep(Duration::from_millis(50));
+            cancel.store(true, Ordering::Release);
+            notifier.notify().unwrap();
+        }
+    });
+    assert!(poll.poll_cancellable(&mut events, None, &cancel).unwrap());
+    assert!(events.is_empty());
+    handle.join().unwrap();
+
+    // Once set it returns immediately.
+    assert!(poll.poll_cancellable(&mut events, None, &cancel).unwrap());
+}
+
+#[test]
+fn poll_cancellable_pending_waker() {
+    init();
+
+    // With kqueue both wakers use user space notifications, make sure they
+    // don't override each other's token or reset each other's events.
+    let mut poll = Poll::new().expect("unable to create new Poll instance");
+    let mut events = Events::with_capacity(10);
+    let cancel = AtomicBool::new(false);
+
+    let token = Token(10);
+    let waker = Waker::new(poll.registry(), token).expect("unable to create waker");
+    let notifier = poll.cancel_notifier().unwrap();
+
+    let timeout = Some(Duration::from_millis(100));
+    for wake_first in [true, false] {
+        if wake_first {
+            waker.wake().unwrap();
+            notifier.notify().unwrap();
+        } else {
+            notifier.notify().unwrap();
+            waker.wake().unwrap();
+        }
+        assert!(!poll
+            .poll_cancellable(&mut events, timeout, &cancel)
+            .unwrap());
+        assert_eq!(
+            events.iter().map(|event| event.token()).collect::<Vec<_>>(),
+            [token]
+        );
+        expect_no_events(&mut poll, &mut events);
+    }
+
+    // The waker still works after the cancel waker is rearmed.
+    notifier.notify().unwrap();
+    assert!(!poll
+        .poll_cancellable(&mut events, timeout, &cancel)
+        .unwrap());
+    assert!(events.is_empty());
+    waker.wake().unwrap();
+    expect_waker_event(&mut poll, &mut events, token);
+}
+
+#[test]
+fn cancel_notifier_reserves_token() {
+    init();
+
+    let poll = Poll::new().expect("unable to create new Poll instance");
+    let registry = poll.registry().try_clone().unwrap();
+    let mut socket = UdpSocket::bind("127.0.0.1:0".parse().unwrap()).unwrap();
+    let reserved = Token(usize::MAX);
+
+    // Before the notifier is created the token can be used.
+    registry
+        .register(&mut socket, reserved, Interest::READABLE)
+        .unwrap();
+
+    let _notifier = poll.cancel_notifier().unwrap();
+    let err = registry
+        .reregister(&mut socket, reserved, Interest::READABLE)
+        .unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    assert_eq!(err.to_string(), mio::Error::ReservedToken.to_string());
+    registry.deregister(&mut socket).unwrap();
+    let err = poll
+        .registry()
+        .register(&mut socket, reserved, Interest::READABLE)
+        .unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    let err = Waker::new(poll.registry(), reserved).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+
+    // Other tokens are unaffected.
+    registry
+        .register(&mut socket, Token(usize::MAX - 1), Interest::READABLE)
+        .unwrap();
+}
+
 fn expect_waker_event(poll: &mut Poll, events: &mut Events, token: Token) {
     poll.poll(events, Some(Duration::from_millis(100))).unwrap();
     assert!(!events.is_empty());